@@ -1,8 +1,9 @@
 //! Affine transforms.
 
+use std::f64::consts::PI;
 use std::ops::{Mul, MulAssign};
 
-use crate::{Point, Rect, Vec2};
+use crate::{PathEl, Point, Rect, Transformed, Vec2};
 
 /// A 2D affine transform.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -10,6 +11,9 @@ use crate::{Point, Rect, Vec2};
 pub struct Affine([f64; 6]);
 
 impl Affine {
+    /// The identity transform.
+    pub const IDENTITY: Affine = Affine::scale(1.0);
+
     /// A transform that is flipped on the y-axis. Useful for converting between
     /// y-up and y-down spaces.
     pub const FLIP_Y: Affine = Affine::new([1.0, 0., 0., -1.0, 0., 0.]);
@@ -74,6 +78,47 @@ impl Affine {
         Affine([1.0, 0.0, 0.0, 1.0, p.x, p.y])
     }
 
+    /// The transform mapping one rectangle onto another.
+    ///
+    /// This scales each axis separately and translates, taking the corner
+    /// `(src.x0, src.y0)` to `(dst.x0, dst.y0)` and `(src.x1, src.y1)` to
+    /// `(dst.x1, dst.y1)`. There is no implicit flip: the y axis keeps its
+    /// sense exactly when `src` and `dst` are ordered the same way in y,
+    /// which can be used to map between y-up and y-down coordinates by
+    /// swapping `y0` and `y1` in one of them. The result can also be
+    /// represented as a [`TranslateScale`] when the aspect ratios agree, so
+    /// that the scale is uniform.
+    ///
+    /// If `src` has zero width, the x scale is zero and every point maps to
+    /// the middle of `dst` in x, and likewise for the height, so the
+    /// transform is degenerate and has no inverse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Affine, Point, Rect};
+    /// let src = Rect::new(0.0, 0.0, 100.0, 50.0);
+    /// let dst = Rect::new(10.0, 10.0, 30.0, 20.0);
+    /// let affine = Affine::rect_to_rect(src, dst);
+    /// assert_eq!(affine * Point::new(100.0, 50.0), Point::new(30.0, 20.0));
+    /// assert_eq!(affine * Point::new(50.0, 0.0), Point::new(20.0, 10.0));
+    /// ```
+    ///
+    /// [`TranslateScale`]: struct.TranslateScale.html
+    pub fn rect_to_rect(src: Rect, dst: Rect) -> Affine {
+        let axis = |s0: f64, s1: f64, d0: f64, d1: f64| {
+            if s1 == s0 {
+                (0.0, 0.5 * (d0 + d1))
+            } else {
+                let scale = (d1 - d0) / (s1 - s0);
+                (scale, d0 - scale * s0)
+            }
+        };
+        let (sx, tx) = axis(src.x0, src.x1, dst.x0, dst.x1);
+        let (sy, ty) = axis(src.y0, src.y1, dst.y0, dst.y1);
+        Affine([sx, 0.0, 0.0, sy, tx, ty])
+    }
+
     /// Get the coefficients of the transform.
     #[inline]
     pub fn as_coeffs(self) -> [f64; 6] {
@@ -100,6 +145,79 @@ impl Affine {
         ])
     }
 
+    /// Interpolate between two transforms.
+    ///
+    /// Rather than blending the coefficients, which would shrink a shape
+    /// halfway between two rotations, each transform is decomposed into a
+    /// translation, a rotation, and a scale and shear (in that order, as
+    /// applied to the shape in reverse), and the parts are interpolated
+    /// separately: the rotation angle along the shorter way around, and the
+    /// others linearly. For pure translations and scales this is the same as
+    /// blending the coefficients. A reflection is carried by the scale, so
+    /// interpolating between a transform and its mirror image passes through
+    /// a degenerate transform.
+    ///
+    /// The result is `self` at `t = 0` and `other` at `t = 1`, up to
+    /// rounding; values of `t` outside `[0, 1]` extrapolate. If either
+    /// transform maps the x axis to a point, it has no rotation to
+    /// interpolate, and the coefficients are blended instead.
+    pub fn lerp(self, other: Affine, t: f64) -> Affine {
+        let (a, b) = match (self.decompose(), other.decompose()) {
+            (Some(a), Some(b)) => (a, b),
+            _ => {
+                let mut c = [0.0; 6];
+                for (i, c) in c.iter_mut().enumerate() {
+                    *c = (1.0 - t) * self.0[i] + t * other.0[i];
+                }
+                return Affine(c);
+            }
+        };
+        let lerp = |x: f64, y: f64| (1.0 - t) * x + t * y;
+        let mut dth = b.1 - a.1;
+        if dth > PI {
+            dth -= 2.0 * PI;
+        } else if dth < -PI {
+            dth += 2.0 * PI;
+        }
+        let th = a.1 + t * dth;
+        let (sx, shear, sy) = (lerp(a.2, b.2), lerp(a.3, b.3), lerp(a.4, b.4));
+        Affine::translate(a.0.lerp(b.0, t))
+            * Affine::rotate(th)
+            * Affine([sx, 0.0, shear, sy, 0.0, 0.0])
+    }
+
+    /// Decompose into a translation, a rotation angle, and the x scale,
+    /// shear and y scale of an upper triangular matrix, so that the
+    /// transform is `translate * rotate * [sx shear; 0 sy]`.
+    ///
+    /// Returns `None` if the x axis is mapped to a point.
+    fn decompose(self) -> Option<(Vec2, f64, f64, f64, f64)> {
+        let [a, b, c, d, _, _] = self.0;
+        let sx = a.hypot(b);
+        if !(sx > 0.0 && sx.is_finite()) {
+            return None;
+        }
+        let th = b.atan2(a);
+        let shear = (a * c + b * d) / sx;
+        let sy = self.determinant() / sx;
+        Some((self.get_translation(), th, sx, shear, sy))
+    }
+
+    /// Lazily transform an iterator over path elements.
+    ///
+    /// This composes with [`Shape::path_elements`], applying the transform to
+    /// each element without collecting into an intermediate [`BezPath`].
+    ///
+    /// [`Shape::path_elements`]: trait.Shape.html#tymethod.path_elements
+    /// [`BezPath`]: struct.BezPath.html
+    #[inline]
+    pub fn transform_elements<I>(self, elements: I) -> Transformed<I::IntoIter>
+    where
+        I: IntoIterator<Item = PathEl>,
+    {
+        Transformed::new(self, elements.into_iter())
+    }
+
     /// Compute the bounding box of a transformed rectangle.
     ///
     /// Returns the minimal `Rect` that encloses the given `Rect` after affine transformation.
@@ -115,6 +233,30 @@ impl Affine {
         Rect::from_points(p00, p01).union(Rect::from_points(p10, p11))
     }
 
+    /// Transform the four corners of a rectangle.
+    ///
+    /// This is the exact image of the rectangle, a parallelogram, where
+    /// [`transform_rect_bbox`] gives its bounding box. The first corner is
+    /// the image of the corner with the smallest x and y, and the others
+    /// follow in counterclockwise order (in a y-up coordinate system): for a
+    /// transform that preserves orientation, the images of `(x1, y0)`,
+    /// `(x1, y1)` and `(x0, y1)`, and the reverse of that if the transform
+    /// is a reflection.
+    ///
+    /// [`transform_rect_bbox`]: #method.transform_rect_bbox
+    pub fn transform_rect(self, rect: Rect) -> [Point; 4] {
+        let r = rect.abs();
+        let p00 = self * Point::new(r.x0, r.y0);
+        let p10 = self * Point::new(r.x1, r.y0);
+        let p11 = self * Point::new(r.x1, r.y1);
+        let p01 = self * Point::new(r.x0, r.y1);
+        if self.determinant() < 0.0 {
+            [p00, p01, p11, p10]
+        } else {
+            [p00, p10, p11, p01]
+        }
+    }
+
     /// Compute the singular value decomposition of the linear transformation (ignoring the
     /// translation).
     ///
@@ -182,7 +324,7 @@ impl Affine {
 impl Default for Affine {
     #[inline]
     fn default() -> Affine {
-        Affine::scale(1.0)
+        Affine::IDENTITY
     }
 }
 
@@ -269,7 +411,7 @@ impl From<mint::ColumnMatrix2x3<f64>> for Affine {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Affine, Point};
+    use crate::{Affine, Point, Rect};
     use std::f64::consts::PI;
 
     fn assert_near(p0: Point, p1: Point) {
@@ -287,6 +429,31 @@ mod tests {
         assert_near(Affine::translate((5.0, 6.0)) * p, Point::new(8.0, 10.0));
     }
 
+    #[test]
+    fn affine_rect_to_rect() {
+        let src = Rect::new(1.0, 2.0, 5.0, 4.0);
+        let dst = Rect::new(-1.0, 10.0, 7.0, 0.0);
+        let affine = Affine::rect_to_rect(src, dst);
+        assert_near(affine * Point::new(1.0, 2.0), Point::new(-1.0, 10.0));
+        assert_near(affine * Point::new(5.0, 4.0), Point::new(7.0, 0.0));
+        assert_near(affine * Point::new(3.0, 3.0), Point::new(3.0, 5.0));
+        // The rects are ordered differently in y, so it is flipped.
+        assert!(affine.determinant() < 0.0);
+        assert!(Affine::rect_to_rect(src, src.abs()).determinant() > 0.0);
+        let back = Affine::rect_to_rect(dst, src);
+        assert_near(
+            (back * affine) * Point::new(4.0, -7.0),
+            Point::new(4.0, -7.0),
+        );
+
+        // A zero-height source collapses onto the middle of the target.
+        let flat = Rect::new(0.0, 1.0, 2.0, 1.0);
+        let affine = Affine::rect_to_rect(flat, Rect::new(0.0, 0.0, 4.0, 6.0));
+        assert_eq!(affine.determinant(), 0.0);
+        assert_near(affine * Point::new(1.0, 1.0), Point::new(2.0, 3.0));
+        assert_near(affine * Point::new(2.0, 5.0), Point::new(4.0, 3.0));
+    }
+
     #[test]
     fn affine_mul() {
         let a1 = Affine::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
@@ -315,4 +482,62 @@ mod tests {
         assert_near(a_inv * (a * py), py);
         assert_near(a_inv * (a * pxy), pxy);
     }
+
+    #[test]
+    fn affine_lerp() {
+        let a = Affine::translate((1.0, 2.0)) * Affine::rotate(3.0) * Affine::scale(2.0);
+        let b = Affine::translate((5.0, -2.0))
+            * Affine::rotate(-3.0)
+            * Affine::new([1.0, 0.0, 0.5, 3.0, 0.0, 0.0]);
+        let p = Point::new(0.7, -1.3);
+        assert_near(a.lerp(b, 0.0) * p, a * p);
+        assert_near(a.lerp(b, 1.0) * p, b * p);
+        // The rotation goes the short way, through pi, and the scale of the
+        // x axis is interpolated linearly.
+        let mid = a.lerp(b, 0.5);
+        assert_near(mid * Point::ORIGIN, Point::new(3.0, 0.0));
+        assert_near(mid * Point::new(1.0, 0.0), Point::new(1.5, 0.0));
+
+        // Pure translations and scales are blended linearly.
+        let t0 = Affine::translate((1.0, 1.0)) * Affine::scale(2.0);
+        let t1 = Affine::translate((3.0, -1.0)) * Affine::scale(4.0);
+        let blend = |t: f64| {
+            let (c0, c1) = (t0.as_coeffs(), t1.as_coeffs());
+            let mut c = [0.0; 6];
+            for i in 0..6 {
+                c[i] = (1.0 - t) * c0[i] + t * c1[i];
+            }
+            Affine::new(c)
+        };
+        assert_near(t0.lerp(t1, 0.25) * p, blend(0.25) * p);
+        assert_near(t0.lerp(t1, 1.5) * p, blend(1.5) * p);
+
+        // A collapsed transform falls back to blending.
+        let z = Affine::new([0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+        assert_near(z.lerp(Affine::IDENTITY, 0.5) * p, Point::new(-0.3, -1.3));
+    }
+
+    #[test]
+    fn affine_transform_rect() {
+        let rect = Rect::new(3.0, 2.0, 1.0, 0.0);
+        let ring_area = |c: [Point; 4]| {
+            (0..4)
+                .map(|i| c[i].to_vec2().cross(c[(i + 1) % 4].to_vec2()))
+                .sum::<f64>()
+                * 0.5
+        };
+        let a = Affine::translate((1.0, 1.0))
+            * Affine::rotate(0.3)
+            * Affine::new([1.0, 0.0, 0.5, 2.0, 0.0, 0.0]);
+        for &t in &[a, Affine::FLIP_Y * a, Affine::FLIP_X] {
+            let corners = t.transform_rect(rect);
+            assert_near(corners[0], t * Point::new(1.0, 0.0));
+            let area = ring_area(corners);
+            assert!((area - 4.0 * t.determinant().abs()).abs() < 1e-9);
+            let bbox = t.transform_rect_bbox(rect);
+            for &c in &corners {
+                assert!(bbox.inflate(1e-12, 1e-12).contains(c));
+            }
+        }
+    }
 }
@@ -1,6 +1,6 @@
 //! A circle arc.
 
-use crate::{PathEl, Point, Rect, Shape, Vec2};
+use crate::{Affine, BezPath, PathEl, Point, Rect, Shape, Vec2};
 use std::{
     f64::consts::{FRAC_PI_2, PI},
     iter,
@@ -55,6 +55,114 @@ impl Arc {
         }
     }
 
+    /// The signed area of the sector between the arc and its center.
+    ///
+    /// This is `radii.x * radii.y * sweep_angle / 2`, exactly. The sign
+    /// follows the sweep: positive for a positive sweep angle, which is
+    /// counterclockwise in a y-up coordinate system, consistent with
+    /// [`Shape::area`]. A sweep of more than a full turn counts the area it
+    /// covers repeatedly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Arc, Vec2};
+    /// use std::f64::consts::PI;
+    /// let arc = Arc {
+    ///     center: (0.0, 0.0).into(),
+    ///     radii: Vec2::new(2.0, 2.0),
+    ///     start_angle: 0.3,
+    ///     sweep_angle: -PI / 2.0,
+    ///     x_rotation: 0.0,
+    /// };
+    /// assert!((arc.sector_area() + PI).abs() < 1e-12);
+    /// // The quarter circle minus the triangle under the chord.
+    /// assert!((arc.segment_area() + PI - 2.0).abs() < 1e-12);
+    /// ```
+    ///
+    /// [`Shape::area`]: trait.Shape.html#method.area
+    #[inline]
+    pub fn sector_area(&self) -> f64 {
+        0.5 * self.radii.x * self.radii.y * self.sweep_angle
+    }
+
+    /// The signed area of the circular segment between the arc and its
+    /// chord.
+    ///
+    /// This is `radii.x * radii.y * (sweep_angle - sin(sweep_angle)) / 2`:
+    /// the sector minus the triangle formed by the center and the ends of the
+    /// arc. It has the sign of the sweep angle, like [`sector_area`]. For a
+    /// reflex arc, whose sweep is more than π in magnitude, the center is
+    /// inside the segment and the triangle is added instead.
+    ///
+    /// [`sector_area`]: #method.sector_area
+    #[inline]
+    pub fn segment_area(&self) -> f64 {
+        0.5 * self.radii.x * self.radii.y * (self.sweep_angle - self.sweep_angle.sin())
+    }
+
+    /// The same arc, traversed in the opposite direction.
+    ///
+    /// The arc starts where this one ends and sweeps back by the same angle,
+    /// so it covers the same points, and its [`sector_area`] and
+    /// [`segment_area`] are negated.
+    ///
+    /// [`sector_area`]: #method.sector_area
+    /// [`segment_area`]: #method.segment_area
+    #[inline]
+    pub fn reversed(&self) -> Arc {
+        Arc {
+            start_angle: self.start_angle + self.sweep_angle,
+            sweep_angle: -self.sweep_angle,
+            ..*self
+        }
+    }
+
+    /// An equivalent arc with its angles in canonical ranges.
+    ///
+    /// The rotation is brought into [0, π), and the start angle, adjusted
+    /// for the change in rotation, into [0, 2π). A sweep of more than a full
+    /// turn either way is clamped to one, after which the arc only retraces
+    /// itself. The arc draws the same points, starting and ending at the
+    /// same places and going the same way, unless the sweep is clamped, in
+    /// which case it ends at its start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Arc, Point, Vec2};
+    /// use std::f64::consts::PI;
+    /// let arc = Arc {
+    ///     center: Point::ORIGIN,
+    ///     radii: Vec2::new(2.0, 1.0),
+    ///     start_angle: -7.0 * PI,
+    ///     sweep_angle: 0.5,
+    ///     x_rotation: 2.5 * PI,
+    /// };
+    /// let normalized = arc.normalized();
+    /// assert!((normalized.x_rotation - 0.5 * PI).abs() < 1e-12);
+    /// assert!((normalized.start_angle - PI).abs() < 1e-12);
+    /// assert_eq!(normalized.sweep_angle, 0.5);
+    /// ```
+    pub fn normalized(&self) -> Arc {
+        // Rotating the ellipse by a half turn is the same as moving along
+        // it by a half turn.
+        let half_turns = (self.x_rotation / PI).floor();
+        let x_rotation = (self.x_rotation - half_turns * PI).rem_euclid(PI);
+        let start_angle = (self.start_angle + half_turns * PI).rem_euclid(2.0 * PI);
+        Arc {
+            center: self.center,
+            radii: self.radii,
+            start_angle: if start_angle < 2.0 * PI {
+                start_angle
+            } else {
+                0.0
+            },
+            sweep_angle: self.sweep_angle.clamp(-2.0 * PI, 2.0 * PI),
+            x_rotation: if x_rotation < PI { x_rotation } else { 0.0 },
+        }
+    }
+
     /// Converts an Arc into a series of cubic bezier segments.
     ///
     /// Closure will be invoked for each segment.
@@ -136,11 +244,19 @@ impl Shape for Arc {
         iter::once(PathEl::MoveTo(self.center + p0)).chain(self.append_iter(tolerance))
     }
 
-    /// Note: shape isn't closed so area is not well defined.
+    /// The signed area of the arc closed by its chord.
+    ///
+    /// This is the [`segment_area`], consistent with [`winding`] and with
+    /// filling the path of the arc, which is closed implicitly by a line
+    /// back to its start. The area of the pie closed through the center is
+    /// the [`sector_area`].
+    ///
+    /// [`segment_area`]: #method.segment_area
+    /// [`sector_area`]: #method.sector_area
+    /// [`winding`]: #method.winding
     #[inline]
     fn area(&self) -> f64 {
-        let Vec2 { x, y } = self.radii;
-        PI * x * y
+        self.segment_area()
     }
 
     /// Note: Finding the perimiter of an ellipse is fairly involved, so for now just approximate
@@ -151,14 +267,310 @@ impl Shape for Arc {
         self.path_segments(0.1).perimeter(accuracy)
     }
 
-    /// Note: shape isn't closed so a point's winding number is not well defined.
-    #[inline]
+    /// The winding number of the arc closed by its chord, as when filling it.
+    ///
+    /// This is computed analytically, by mapping the point into the frame
+    /// where the ellipse is the unit circle, so it has no flattening error.
+    /// The sign follows the sweep angle, and sweeps of more than a full turn
+    /// wind around points more than once.
     fn winding(&self, pt: Point) -> i32 {
-        self.path_segments(0.1).winding(pt)
+        let det = self.radii.x * self.radii.y;
+        if det == 0.0 || !det.is_finite() {
+            return 0;
+        }
+        let frame = Affine::translate(self.center.to_vec2())
+            * Affine::rotate(self.x_rotation)
+            * Affine::scale_non_uniform(self.radii.x, self.radii.y);
+        let mut q = (frame.inverse() * pt).to_vec2();
+        if q.hypot2() >= 1.0 {
+            return 0;
+        }
+        // Mirror a negative sweep so that the arc runs counterclockwise.
+        let mut start = self.start_angle;
+        if self.sweep_angle < 0.0 {
+            start = -start;
+            q.y = -q.y;
+        }
+        let sweep = self.sweep_angle.abs();
+        let turns = (sweep / (2.0 * PI)).floor();
+        let rest = sweep - turns * 2.0 * PI;
+        // Inside the disk, the remainder of the arc and its chord enclose the
+        // points to the right of the chord.
+        let u0 = Vec2::from_angle(start);
+        let u1 = Vec2::from_angle(start + rest);
+        let in_segment = (u1 - u0).cross(q - u0) < 0.0;
+        let winding = turns as i32 + in_segment as i32;
+        if (self.sweep_angle < 0.0) != (det < 0.0) {
+            -winding
+        } else {
+            winding
+        }
     }
 
-    #[inline]
+    /// The bounding box of the arc itself, which is also that of the arc
+    /// closed by its chord.
+    ///
+    /// This is exact: besides the ends of the arc, it includes those points
+    /// where the ellipse is extreme in x or y which lie on the sweep.
     fn bounding_box(&self) -> Rect {
-        self.path_segments(0.1).bounding_box()
+        let (s, c) = self.x_rotation.sin_cos();
+        let Vec2 { x: rx, y: ry } = self.radii;
+        let end_angle = self.start_angle + self.sweep_angle;
+        let p0 = self.center + sample_ellipse(self.radii, self.x_rotation, self.start_angle);
+        let p1 = self.center + sample_ellipse(self.radii, self.x_rotation, end_angle);
+        let mut bbox = Rect::from_points(p0, p1);
+        // The angles where the derivative of x and of y vanishes.
+        let x_extreme = (-ry * s).atan2(rx * c);
+        let y_extreme = (ry * c).atan2(rx * s);
+        for &base in &[x_extreme, y_extreme] {
+            for &angle in &[base, base + PI] {
+                if self.sweeps_through(angle) {
+                    let p = self.center + sample_ellipse(self.radii, self.x_rotation, angle);
+                    bbox = bbox.union_pt(p);
+                }
+            }
+        }
+        bbox
+    }
+}
+
+impl Arc {
+    /// Whether the sweep of the arc passes through `angle`, modulo a full
+    /// turn.
+    fn sweeps_through(&self, angle: f64) -> bool {
+        let sweep = self.sweep_angle.abs();
+        if sweep >= 2.0 * PI {
+            return true;
+        }
+        let offset = (angle - self.start_angle) * self.sweep_angle.signum();
+        offset.rem_euclid(2.0 * PI) <= sweep
+    }
+}
+
+/// A filled circular sector: a closed path from `center` out to the circle
+/// of radius `radius` at `start_angle`, along the circle to `end_angle`,
+/// and back to the center.
+///
+/// The arc sweeps from `start_angle` to `end_angle` as given, without
+/// reducing either modulo a full turn: it goes in the direction of
+/// increasing angle if `end_angle` is the larger, so a sector crossing the
+/// positive x axis can be written as from `-0.5` to `0.5`, or from
+/// `2.0 * PI - 0.5` to `2.0 * PI + 0.5`. A sweep of more than a full turn is
+/// clamped to one, giving the whole disc. The arc is converted to Béziers
+/// within `tolerance` as by [`Arc::append_iter`].
+///
+/// The area of the path is [`sector_area`], up to the tolerance.
+///
+/// # Examples
+///
+/// ```
+/// use kurbo::{sector_area, sector_path, Point, Shape};
+/// use std::f64::consts::PI;
+/// let path = sector_path(Point::ORIGIN, 2.0, 1.5 * PI, 2.5 * PI, 1e-9);
+/// assert!((path.area() - 2.0 * PI).abs() < 1e-8);
+/// assert!((sector_area(2.0, 1.5 * PI, 2.5 * PI) - 2.0 * PI).abs() < 1e-12);
+/// ```
+///
+/// [`Arc::append_iter`]: struct.Arc.html#method.append_iter
+/// [`sector_area`]: fn.sector_area.html
+pub fn sector_path(
+    center: Point,
+    radius: f64,
+    start_angle: f64,
+    end_angle: f64,
+    tolerance: f64,
+) -> BezPath {
+    let arc = Arc {
+        center,
+        radii: Vec2::new(radius, radius),
+        start_angle,
+        sweep_angle: clamp_sweep(end_angle - start_angle),
+        x_rotation: 0.0,
+    };
+    let mut path = BezPath::new();
+    path.move_to(center);
+    path.line_to(center + Vec2::from_angle(start_angle) * radius);
+    path.extend(arc.append_iter(tolerance));
+    path.close_path();
+    path
+}
+
+/// The signed area of the sector drawn by [`sector_path`] with the same
+/// arguments.
+///
+/// This is half the square of the radius times the sweep from `start_angle`
+/// to `end_angle`, clamped to at most a full turn: positive when the sector
+/// goes in the direction of increasing angle.
+///
+/// [`sector_path`]: fn.sector_path.html
+#[inline]
+pub fn sector_area(radius: f64, start_angle: f64, end_angle: f64) -> f64 {
+    0.5 * radius * radius * clamp_sweep(end_angle - start_angle)
+}
+
+fn clamp_sweep(sweep: f64) -> f64 {
+    sweep.clamp(-2.0 * PI, 2.0 * PI)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sample_ellipse;
+    use crate::{sector_area, sector_path, Arc, Point, Rect, Shape, Vec2};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn arc_area_bbox() {
+        let arc = Arc {
+            center: Point::new(1.0, 2.0),
+            radii: Vec2::new(3.0, 1.5),
+            start_angle: 0.4,
+            sweep_angle: 4.0,
+            x_rotation: 0.7,
+        };
+        // The area is that of the arc closed by its chord.
+        let path = arc.to_path(1e-9);
+        assert!((arc.area() - path.area()).abs() < 1e-8);
+        assert_eq!(arc.area(), arc.segment_area());
+
+        for &(start, sweep, rot) in &[(0.4, 4.0, 0.7), (0.1, 0.2, 0.0), (2.0, -5.0, -1.2)] {
+            let arc = Arc {
+                start_angle: start,
+                sweep_angle: sweep,
+                x_rotation: rot,
+                ..arc
+            };
+            let bbox = arc.bounding_box();
+            let mut expected = Rect::from_points(arc.center, arc.center);
+            let n = 100_000;
+            for i in 0..=n {
+                let angle = start + sweep * i as f64 / n as f64;
+                let p = arc.center + sample_ellipse(arc.radii, rot, angle);
+                expected = if i == 0 {
+                    Rect::from_points(p, p)
+                } else {
+                    expected.union_pt(p)
+                };
+            }
+            for &(a, b) in &[
+                (bbox.x0, expected.x0),
+                (bbox.y0, expected.y0),
+                (bbox.x1, expected.x1),
+                (bbox.y1, expected.y1),
+            ] {
+                assert!((a - b).abs() < 1e-8, "{:?} != {:?}", bbox, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn arc_reversed_normalized() {
+        let arc = Arc {
+            center: Point::new(1.0, 2.0),
+            radii: Vec2::new(3.0, 1.5),
+            start_angle: -20.0,
+            sweep_angle: 4.0,
+            x_rotation: 11.0,
+        };
+        let at = |arc: &Arc, t: f64| {
+            arc.center
+                + sample_ellipse(
+                    arc.radii,
+                    arc.x_rotation,
+                    arc.start_angle + t * arc.sweep_angle,
+                )
+        };
+        let reversed = arc.reversed();
+        let normalized = arc.normalized();
+        assert!(normalized.start_angle >= 0.0 && normalized.start_angle < 2.0 * PI);
+        assert!(normalized.x_rotation >= 0.0 && normalized.x_rotation < PI);
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert!((at(&reversed, 1.0 - t) - at(&arc, t)).hypot() < 1e-12);
+            assert!((at(&normalized, t) - at(&arc, t)).hypot() < 1e-12);
+        }
+        assert_eq!(reversed.reversed(), arc);
+        assert!((reversed.segment_area() + arc.segment_area()).abs() < 1e-12);
+        assert!((reversed.area() + arc.area()).abs() < 1e-12);
+        let again = normalized.normalized();
+        assert!((again.start_angle - normalized.start_angle).abs() < 1e-12);
+        assert!((again.x_rotation - normalized.x_rotation).abs() < 1e-12);
+
+        // A sweep of more than a full turn is clamped.
+        let spiral = Arc {
+            sweep_angle: -9.0,
+            ..arc
+        };
+        let normalized = spiral.normalized();
+        assert_eq!(normalized.sweep_angle, -2.0 * PI);
+        assert!((at(&normalized, 0.0) - at(&spiral, 0.0)).hypot() < 1e-12);
+        assert!((at(&normalized, 1.0) - at(&spiral, 0.0)).hypot() < 1e-12);
+    }
+
+    #[test]
+    fn arc_winding() {
+        let arc = Arc {
+            center: Point::new(1.0, 2.0),
+            radii: Vec2::new(3.0, 1.5),
+            start_angle: 0.4,
+            sweep_angle: 4.0,
+            x_rotation: 0.7,
+        };
+        let mut path = arc.to_path(1e-9);
+        path.close_path();
+        let mut flipped = arc;
+        flipped.radii.x = -flipped.radii.x;
+        let mut flipped_path = flipped.to_path(1e-9);
+        flipped_path.close_path();
+        for i in 0..20 {
+            for j in 0..20 {
+                let pt = Point::new(-2.0 + 0.3 * i as f64, -1.0 + 0.3 * j as f64);
+                assert_eq!(arc.winding(pt), path.winding(pt), "{:?}", pt);
+                assert_eq!(flipped.winding(pt), flipped_path.winding(pt), "{:?}", pt);
+                let mut reversed = arc;
+                reversed.start_angle += reversed.sweep_angle;
+                reversed.sweep_angle = -reversed.sweep_angle;
+                assert_eq!(reversed.winding(pt), -arc.winding(pt));
+            }
+        }
+        // More than a full turn.
+        let mut spiral = arc;
+        spiral.sweep_angle = 2.0 * PI + 1.0;
+        assert_eq!(spiral.winding(arc.center), 1);
+        spiral.sweep_angle = -4.0 * PI;
+        assert_eq!(spiral.winding(arc.center), -2);
+    }
+
+    #[test]
+    fn sectors() {
+        let center = Point::new(1.0, -2.0);
+        let tolerance = 1e-9;
+        for &(start, end) in &[
+            (0.3, 1.2),
+            (-0.5, 0.5),
+            (6.0, 6.6),
+            (2.0, -1.0),
+            (1.0, 1.0 + 2.0 * PI),
+            (0.0, 10.0),
+            (0.0, -10.0),
+        ] {
+            let path = sector_path(center, 3.0, start, end, tolerance);
+            let area = sector_area(3.0, start, end);
+            assert!((path.area() - area).abs() < 1e-7, "{} {}", start, end);
+            assert_eq!(path.elements().last(), Some(&crate::PathEl::ClosePath));
+            let mid = 0.5 * (start + end);
+            let inside = center + Vec2::from_angle(mid) * 1.5;
+            if (end - start).abs() < 2.0 * PI {
+                assert_eq!(path.winding(inside), area.signum() as i32);
+            }
+        }
+        // Crossing the x axis, the sector contains points on both sides.
+        let path = sector_path(center, 3.0, 6.0, 6.6, tolerance);
+        assert_eq!(path.winding(center + Vec2::new(2.0, 0.2)), 1);
+        assert_eq!(path.winding(center + Vec2::new(2.0, -0.2)), 1);
+        assert_eq!(path.winding(center + Vec2::new(-2.0, 0.0)), 0);
+        // More than a full turn is clamped to the whole disc.
+        assert_eq!(sector_area(3.0, 0.0, 10.0), 9.0 * PI);
+        let disc = sector_path(center, 3.0, 0.0, 10.0, tolerance);
+        assert_eq!(disc.winding(center + Vec2::new(-2.0, 0.1)), 1);
     }
 }
@@ -8,22 +8,31 @@ use std::ops::{Mul, Range};
 
 use arrayvec::ArrayVec;
 
-use crate::common::{solve_cubic, solve_quadratic};
+use crate::common::{orient2d, solve_cubic, solve_quadratic};
+use crate::cubicbez::{fit_cubic_handles, flatten_quad_vertices};
 use crate::MAX_EXTREMA;
 use crate::{
-    Affine, CubicBez, Line, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveExtrema,
-    ParamCurveNearest, Point, QuadBez, Rect, Shape, TranslateScale,
+    Affine, Circle, CubicBez, FillRule, Line, ParamCurve, ParamCurveArclen, ParamCurveArea,
+    ParamCurveCurvature, ParamCurveDeriv, ParamCurveExtrema, ParamCurveNearest, Point, Polygon,
+    QuadBez, Rect, Shape, TranslateScale, Vec2,
 };
 
+// The tags of the flat path encoding; see `BezPath::to_flat`.
+const FLAT_MOVE_TO: u8 = 0;
+const FLAT_LINE_TO: u8 = 1;
+const FLAT_QUAD_TO: u8 = 2;
+const FLAT_CURVE_TO: u8 = 3;
+const FLAT_CLOSE_PATH: u8 = 4;
+
 /// A path that can Bézier segments up to cubic, possibly with multiple subpaths.
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BezPath(Vec<PathEl>);
 
 /// The element of a Bézier path.
 ///
 /// A valid path has `Moveto` at the beginning of each subpath.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PathEl {
     /// Move directly to the point without drawing anything, starting a new
@@ -35,10 +44,85 @@ pub enum PathEl {
     QuadTo(Point, Point),
     /// Draw a cubic bezier using the current location and the three points.
     CurveTo(Point, Point, Point),
-    /// Close off the path.
+    /// Close off the current subpath with a line back to its start.
+    ///
+    /// The current point returns to the start of the subpath, so a segment
+    /// that follows without a `MoveTo` begins a new subpath there, as in SVG
+    /// and PostScript.
+    ClosePath,
+}
+
+/// The kind of a [`PathEl`], without its points.
+///
+/// This is returned by [`PathEl::verb`].
+///
+/// [`PathEl`]: enum.PathEl.html
+/// [`PathEl::verb`]: enum.PathEl.html#method.verb
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PathVerb {
+    /// A `MoveTo` element.
+    MoveTo,
+    /// A `LineTo` element.
+    LineTo,
+    /// A `QuadTo` element.
+    QuadTo,
+    /// A `CurveTo` element.
+    CurveTo,
+    /// A `ClosePath` element.
     ClosePath,
 }
 
+/// One step of an edit script turning one path into another.
+///
+/// This is produced by [`BezPath::diff`] and consumed by
+/// [`BezPath::apply_edits`]. The index of each edit is the position of the
+/// element in the path as left by the edits before it.
+///
+/// [`BezPath::diff`]: struct.BezPath.html#method.diff
+/// [`BezPath::apply_edits`]: struct.BezPath.html#method.apply_edits
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PathEdit {
+    /// Insert the element at the index, shifting the following ones.
+    Insert(usize, PathEl),
+    /// Remove the element at the index, shifting the following ones.
+    Delete(usize),
+    /// Overwrite the element at the index.
+    Replace(usize, PathEl),
+}
+
+/// How the sign of a signed distance to a path is determined, in
+/// [`BezPath::signed_distance_with`].
+///
+/// Both methods agree for a path without self-intersections whose outer
+/// boundaries all have one orientation and holes the other, at points
+/// whose nearest point on the path is unique; they may differ for points
+/// on the medial axis, where two parts of the path are equally near, and
+/// for paths not oriented that way.
+///
+/// [`BezPath::signed_distance_with`]: struct.BezPath.html#method.signed_distance_with
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignedDistanceMethod {
+    /// Negative where the winding number is nonzero.
+    ///
+    /// This is the nonzero fill rule, so it is correct for any path,
+    /// including overlapping and self-intersecting ones, at the cost of a
+    /// separate winding number query.
+    Winding,
+    /// Negative where the point is on the inner side of the path at its
+    /// nearest point.
+    ///
+    /// The side is given by the normal of the path there, or at a vertex
+    /// by the angle-weighted pseudo-normal, the sum of the unit normals of
+    /// the two segments meeting there, which is what makes the sign
+    /// correct outside a reflex corner. The inner side is the left of the
+    /// direction of travel if the total area of the path is positive, and
+    /// the right otherwise.
+    PseudoNormal,
+}
+
 /// A segment of a Bézier path.
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -72,12 +156,44 @@ pub struct LineIntersection {
     pub segment_t: f64,
 }
 
+/// The smoothness of a join between two curves.
+///
+/// This is used by [`BezPath::join_smooth`].
+///
+/// [`BezPath::join_smooth`]: struct.BezPath.html#method.join_smooth
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Continuity {
+    /// The curves meet at a point, possibly at a corner.
+    C0,
+    /// The curves meet with the same tangent direction.
+    G1,
+    /// The curves meet with the same tangent direction and curvature.
+    G2,
+}
+
 impl BezPath {
     /// Create a new path.
     pub fn new() -> BezPath {
         Default::default()
     }
 
+    /// Create a new path with capacity for at least `capacity` elements.
+    ///
+    /// ```
+    /// use kurbo::BezPath;
+    ///
+    /// let mut path = BezPath::with_capacity(4);
+    /// path.move_to((0.0, 0.0))
+    ///     .line_to((10.0, 0.0))
+    ///     .line_to((10.0, 10.0))
+    ///     .close_path();
+    /// assert_eq!(path.elements().len(), 4);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> BezPath {
+        BezPath(Vec::with_capacity(capacity))
+    }
+
     /// Create a path from a vector of path elements.
     ///
     /// `BezPath` also implements `FromIterator<PathEl>`, so it works with `collect`:
@@ -94,34 +210,300 @@ impl BezPath {
         BezPath(v)
     }
 
+    /// Create a path from a TrueType contour.
+    ///
+    /// Each point is flagged `true` if it is on the curve, and `false` if it
+    /// is the control point of a quadratic segment. Between two consecutive
+    /// off-curve points there is an implied on-curve point at their midpoint,
+    /// as in the `glyf` table.
+    ///
+    /// A `closed` contour wraps around from its last point to its first, and
+    /// ends with a `ClosePath`. It starts at its first on-curve point, so a
+    /// contour starting with off-curve points still has those as the controls
+    /// of its final segments; if no point is on the curve, it starts at the
+    /// implied point between the last and the first. In an open contour, the
+    /// first and last points are taken as on the curve whatever their flags.
+    ///
+    /// ```
+    /// use kurbo::{BezPath, ParamCurve, Point};
+    ///
+    /// let pts = [
+    ///     (Point::new(1.0, 1.0), false),
+    ///     (Point::new(-1.0, 1.0), false),
+    ///     (Point::new(-1.0, -1.0), false),
+    ///     (Point::new(1.0, -1.0), false),
+    /// ];
+    /// let path = BezPath::from_tt_points(&pts, true);
+    /// assert_eq!(path.segments().count(), 4);
+    /// assert_eq!(path.segments().next().unwrap().start(), Point::new(1.0, 0.0));
+    /// ```
+    pub fn from_tt_points(points: &[(Point, bool)], closed: bool) -> BezPath {
+        let mut path = BezPath::new();
+        let n = points.len();
+        if n == 0 {
+            return path;
+        }
+        let (start, first) = if !closed {
+            (points[0].0, 1)
+        } else {
+            match points.iter().position(|&(_, on)| on) {
+                Some(k) => (points[k].0, k + 1),
+                None => (points[n - 1].0.midpoint(points[0].0), 0),
+            }
+        };
+        path.move_to(start);
+        let rest = if closed { n } else { n - 1 };
+        let mut control: Option<Point> = None;
+        for i in first..first + rest {
+            let (p, on) = points[i % n];
+            let on = on || (!closed && i == n - 1);
+            match (control, on) {
+                (Some(c), true) => {
+                    path.quad_to(c, p);
+                    control = None;
+                }
+                (None, true) => {
+                    path.line_to(p);
+                }
+                (Some(c), false) => {
+                    path.quad_to(c, c.midpoint(p));
+                    control = Some(p);
+                }
+                (None, false) => control = Some(p),
+            }
+        }
+        if closed {
+            // The closing segment is implied, unless it is a curve.
+            if let Some(c) = control {
+                path.quad_to(c, start);
+            }
+            if let Some(&PathEl::LineTo(p)) = path.elements().last() {
+                if p == start && path.elements().len() > 1 {
+                    path.0.pop();
+                }
+            }
+            path.close_path();
+        }
+        path
+    }
+
+    /// Stitch a sequence of segments back into a path.
+    ///
+    /// Consecutive segments are joined where the end of one is within a
+    /// small epsilon of the start of the next; the point where they meet is
+    /// then the end of the first. Otherwise a `MoveTo` starts a new subpath at
+    /// the start of the next segment. A subpath whose last segment ends
+    /// within the same epsilon of its start is closed, with a final line
+    /// back to the start left to the `ClosePath`. The epsilon is `1e-9`
+    /// times the largest coordinate magnitude on the segments, or `1e-9` if
+    /// all coordinates are smaller than 1.
+    ///
+    /// This is the inverse of [`explode`], in that the result has the same
+    /// segments as the exploded path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{BezPath, Rect, Shape};
+    /// let path = Rect::new(0.0, 0.0, 2.0, 1.0).to_path(1e-9);
+    /// let segs = path.explode();
+    /// assert_eq!(segs.len(), 4);
+    /// assert_eq!(BezPath::from_segments(&segs).explode(), segs);
+    /// ```
+    ///
+    /// [`explode`]: #method.explode
+    pub fn from_segments(segs: &[PathSeg]) -> BezPath {
+        let scale = segs.iter().fold(1.0f64, |m, seg| {
+            let bbox = seg.bounding_box();
+            m.max(bbox.x0.abs())
+                .max(bbox.x1.abs())
+                .max(bbox.y0.abs())
+                .max(bbox.y1.abs())
+        });
+        let eps = 1e-9 * scale;
+        let mut path = BezPath::new();
+        // The start and current end of the subpath being built.
+        let mut chain: Option<(Point, Point)> = None;
+        for &seg in segs {
+            let joined = matches!(chain, Some((_, end)) if (seg.start() - end).hypot() <= eps);
+            if !joined {
+                if let Some((start, end)) = chain {
+                    path.close_chain(start, end, eps);
+                }
+                path.move_to(seg.start());
+                chain = Some((seg.start(), seg.start()));
+            }
+            path.push(match seg {
+                PathSeg::Line(l) => PathEl::LineTo(l.p1),
+                PathSeg::Quad(q) => PathEl::QuadTo(q.p1, q.p2),
+                PathSeg::Cubic(c) => PathEl::CurveTo(c.p1, c.p2, c.p3),
+            });
+            if let Some((_, end)) = chain.as_mut() {
+                *end = seg.end();
+            }
+        }
+        if let Some((start, end)) = chain {
+            path.close_chain(start, end, eps);
+        }
+        path
+    }
+
+    /// Close the last subpath, started at `start` and now at `end`, if it
+    /// has come back within `eps` of its start.
+    fn close_chain(&mut self, start: Point, end: Point, eps: f64) {
+        if (end - start).hypot() > eps {
+            return;
+        }
+        // The line back to the start is implied by the `ClosePath`, unless
+        // it is the only segment.
+        let n = self.0.len();
+        let implied = matches!(self.0[n - 1], PathEl::LineTo(_))
+            && !matches!(self.0[n - 2], PathEl::MoveTo(_));
+        if implied {
+            self.0.pop();
+        }
+        self.close_path();
+    }
+
     /// Push a generic path element onto the path.
     pub fn push(&mut self, el: PathEl) {
         self.0.push(el)
     }
 
-    /// Push a "move to" element onto the path.
-    pub fn move_to<P: Into<Point>>(&mut self, p: P) {
+    /// A shortest edit script turning this path into `other`.
+    ///
+    /// The script has as few edits as possible, each inserting, deleting or
+    /// replacing one element, found by dynamic programming over the element
+    /// sequences (the edit distance, a generalization of their longest
+    /// common subsequence). Elements are compared exactly. The common prefix
+    /// and suffix are skipped first, so a small change to a long path is
+    /// cheap; for the part that changed, time is proportional to the product
+    /// of the lengths but memory only to their sum, by Hirschberg's
+    /// divide and conquer. Applying the result with [`apply_edits`] turns a
+    /// copy of this path into `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{BezPath, PathEdit, PathEl, Point};
+    /// let mut a = BezPath::new();
+    /// a.move_to((0.0, 0.0));
+    /// a.line_to((1.0, 0.0));
+    /// a.line_to((1.0, 1.0));
+    /// let mut b = a.clone();
+    /// b.close_path();
+    /// let edits = a.diff(&b);
+    /// assert_eq!(edits, vec![PathEdit::Insert(3, PathEl::ClosePath)]);
+    /// a.apply_edits(&edits);
+    /// assert_eq!(a, b);
+    /// ```
+    ///
+    /// [`apply_edits`]: #method.apply_edits
+    pub fn diff(&self, other: &BezPath) -> Vec<PathEdit> {
+        let (a, b) = (self.elements(), other.elements());
+        let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+        let (a, b) = (&a[prefix..], &b[prefix..]);
+        let suffix = a
+            .iter()
+            .rev()
+            .zip(b.iter().rev())
+            .take_while(|(x, y)| x == y)
+            .count();
+        let (a, b) = (&a[..a.len() - suffix], &b[..b.len() - suffix]);
+        let mut edits = Vec::new();
+        diff_into(a, b, prefix, &mut edits);
+        edits
+    }
+
+    /// Apply an edit script, as produced by [`diff`], in order.
+    ///
+    /// Panics if an index is out of range for the path at that point of
+    /// the script.
+    ///
+    /// [`diff`]: #method.diff
+    pub fn apply_edits(&mut self, edits: &[PathEdit]) {
+        for edit in edits {
+            match *edit {
+                PathEdit::Insert(index, el) => self.0.insert(index, el),
+                PathEdit::Delete(index) => {
+                    self.0.remove(index);
+                }
+                PathEdit::Replace(index, el) => self.0[index] = el,
+            }
+        }
+    }
+
+    /// Push a "move to" element onto the path, starting a new subpath.
+    ///
+    /// Returns `&mut self` so that calls can be chained.
+    pub fn move_to<P: Into<Point>>(&mut self, p: P) -> &mut BezPath {
         self.push(PathEl::MoveTo(p.into()));
+        self
     }
 
     /// Push a "line to" element onto the path.
-    pub fn line_to<P: Into<Point>>(&mut self, p: P) {
+    ///
+    /// Returns `&mut self` so that calls can be chained.
+    ///
+    /// Each subpath should start with [`move_to`]. No validation is done here;
+    /// if the path is empty, [`segments`] treats the end point of the first
+    /// element as an implicit "move to", and [`flatten`] skips it.
+    ///
+    /// [`move_to`]: #method.move_to
+    /// [`segments`]: #method.segments
+    /// [`flatten`]: #method.flatten
+    pub fn line_to<P: Into<Point>>(&mut self, p: P) -> &mut BezPath {
         self.push(PathEl::LineTo(p.into()));
+        self
     }
 
     /// Push a "quad to" element onto the path.
-    pub fn quad_to<P: Into<Point>>(&mut self, p1: P, p2: P) {
+    ///
+    /// Returns `&mut self` so that calls can be chained. See [`line_to`] for
+    /// the behavior when there is no current subpath.
+    ///
+    /// [`line_to`]: #method.line_to
+    pub fn quad_to<P: Into<Point>>(&mut self, p1: P, p2: P) -> &mut BezPath {
         self.push(PathEl::QuadTo(p1.into(), p2.into()));
+        self
     }
 
     /// Push a "curve to" element onto the path.
-    pub fn curve_to<P: Into<Point>>(&mut self, p1: P, p2: P, p3: P) {
+    ///
+    /// Returns `&mut self` so that calls can be chained. See [`line_to`] for
+    /// the behavior when there is no current subpath.
+    ///
+    /// [`line_to`]: #method.line_to
+    pub fn curve_to<P: Into<Point>>(&mut self, p1: P, p2: P, p3: P) -> &mut BezPath {
         self.push(PathEl::CurveTo(p1.into(), p2.into(), p3.into()));
+        self
     }
 
     /// Push a "close path" element onto the path.
-    pub fn close_path(&mut self) {
+    ///
+    /// Returns `&mut self` so that calls can be chained.
+    pub fn close_path(&mut self) -> &mut BezPath {
         self.push(PathEl::ClosePath);
+        self
+    }
+
+    /// Append the elements of another path, applying a transform.
+    ///
+    /// Subpath boundaries are preserved: each `MoveTo` in `other` starts a
+    /// fresh subpath. No intermediate path is allocated. For appending
+    /// elements without a transform, `BezPath` also implements
+    /// `Extend<PathEl>`.
+    pub fn extend_path(&mut self, other: &BezPath, transform: Affine) {
+        if transform == Affine::IDENTITY {
+            self.extend_from_slice(other.elements());
+        } else {
+            self.0.extend(other.iter().map(|el| transform * el));
+        }
+    }
+
+    /// Append a slice of path elements onto the path.
+    pub fn extend_from_slice(&mut self, elements: &[PathEl]) {
+        self.0.extend_from_slice(elements);
     }
 
     /// Get the path elements.
@@ -139,6 +521,101 @@ impl BezPath {
         segments(self.iter())
     }
 
+    /// The segments of the path, collected into a vector.
+    ///
+    /// This is the same as `segments().collect()`, and the segments can be
+    /// stitched back into a path with [`from_segments`].
+    ///
+    /// [`from_segments`]: #method.from_segments
+    pub fn explode(&self) -> Vec<PathSeg> {
+        self.segments().collect()
+    }
+
+    /// Iterate over the on-curve points of the path, in order.
+    ///
+    /// These are the points of the `MoveTo` elements and the end points of
+    /// the segments, without the control points of curves. The last segment
+    /// of a closed subpath is often drawn back to its start before the
+    /// `ClosePath`; that end point is skipped, so every vertex of a closed
+    /// subpath appears once. A path starting with a segment rather than a
+    /// `MoveTo` starts at that segment's end point, as in [`segments`].
+    ///
+    /// [`segments`]: fn.segments.html
+    pub fn vertices(&self) -> impl Iterator<Item = Point> + '_ {
+        let els = &self.0;
+        let mut start = None;
+        els.iter().enumerate().filter_map(move |(i, el)| {
+            let p = match *el {
+                PathEl::ClosePath => return None,
+                PathEl::MoveTo(p) => {
+                    start = Some(p);
+                    return Some(p);
+                }
+                PathEl::LineTo(p) | PathEl::QuadTo(_, p) | PathEl::CurveTo(_, _, p) => p,
+            };
+            if start.is_none() {
+                start = Some(p);
+                return Some(p);
+            }
+            let closes = matches!(els.get(i + 1), Some(PathEl::ClosePath));
+            if closes && start == Some(p) {
+                None
+            } else {
+                Some(p)
+            }
+        })
+    }
+
+    /// The bounding box of the path after applying `affine`, without
+    /// building the transformed path.
+    ///
+    /// This is the same as [`bounding_box`] of `affine * self.clone()`: each
+    /// segment is transformed by transforming its control points, which is
+    /// exact for affine maps, and its extrema are found in the transformed
+    /// coordinates, so the box is tight for curves as well as lines. Under a
+    /// rotation or skew it is usually smaller than the box found by
+    /// [`Affine::transform_rect_bbox`] from the untransformed bounding box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Affine, Rect, Shape};
+    /// let path = Rect::new(0.0, 0.0, 2.0, 1.0).to_path(1e-9);
+    /// let affine = Affine::rotate(std::f64::consts::FRAC_PI_2);
+    /// let bbox = path.transformed_bounding_box(affine);
+    /// assert_eq!(bbox.round(), Rect::new(-1.0, 0.0, 0.0, 2.0));
+    /// ```
+    ///
+    /// [`bounding_box`]: trait.Shape.html#method.bounding_box
+    /// [`Affine::transform_rect_bbox`]: struct.Affine.html#method.transform_rect_bbox
+    pub fn transformed_bounding_box(&self, affine: Affine) -> Rect {
+        segments(affine.transform_elements(self.iter())).bounding_box()
+    }
+
+    /// Iterate over the subpaths, each as a path of its own.
+    ///
+    /// Every path yielded starts with a `MoveTo`, and keeps the `ClosePath`
+    /// of a closed subpath; open subpaths are not closed. Segments following
+    /// a `ClosePath` without a `MoveTo` form a new subpath, which is given a
+    /// `MoveTo` at the start of the closed one. A path starting with a
+    /// segment rather than a `MoveTo` has that segment's end point as its
+    /// start, as in [`segments`]. A lone `MoveTo` is yielded as a path of
+    /// one element.
+    ///
+    /// [`segments`]: fn.segments.html
+    pub fn subpaths(&self) -> impl Iterator<Item = BezPath> + '_ {
+        Subpaths::new(self.iter())
+    }
+
+    /// Split the path into its subpaths, each as a path of its own.
+    ///
+    /// This is the same as [`subpaths`], consuming the path.
+    ///
+    /// [`subpaths`]: #method.subpaths
+    pub fn into_subpaths(self) -> impl Iterator<Item = BezPath> {
+        Subpaths::new(self.0.into_iter())
+    }
+
     /// Flatten the path, invoking the callback repeatedly.
     ///
     /// Flattening is the action of approximating a curve with a succession of line segments.
@@ -241,12 +718,92 @@ impl BezPath {
 
     /// Returns `true` if the path contains no segments.
     pub fn is_empty(&self) -> bool {
-        !self.0.iter().any(|el| match *el {
-            PathEl::LineTo(..) | PathEl::QuadTo(..) | PathEl::CurveTo(..) => true,
-            _ => false,
+        !self.0.iter().any(|el| {
+            matches!(
+                *el,
+                PathEl::LineTo(..) | PathEl::QuadTo(..) | PathEl::CurveTo(..)
+            )
         })
     }
 
+    /// Encode the path as a tag per element and a flat array of coordinates.
+    ///
+    /// The tags are:
+    ///
+    /// | element     | tag | coordinates            |
+    /// |-------------|-----|------------------------|
+    /// | `MoveTo`    | 0   | x, y                   |
+    /// | `LineTo`    | 1   | x, y                   |
+    /// | `QuadTo`    | 2   | x1, y1, x2, y2         |
+    /// | `CurveTo`   | 3   | x1, y1, x2, y2, x3, y3 |
+    /// | `ClosePath` | 4   | none                   |
+    ///
+    /// The coordinates of all elements are concatenated in order. The
+    /// values are copied as they are, so [`from_flat`] restores the path
+    /// exactly.
+    ///
+    /// [`from_flat`]: #method.from_flat
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::BezPath;
+    /// let mut path = BezPath::new();
+    /// path.move_to((1.0, 2.0)).quad_to((3.0, 4.0), (5.0, 6.0)).close_path();
+    /// let (tags, coords) = path.to_flat();
+    /// assert_eq!(tags, [0, 2, 4]);
+    /// assert_eq!(coords, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    /// ```
+    pub fn to_flat(&self) -> (Vec<u8>, Vec<f64>) {
+        let mut tags = Vec::with_capacity(self.0.len());
+        let mut coords = Vec::with_capacity(self.0.len() * 2);
+        for el in &self.0 {
+            tags.push(match el {
+                PathEl::MoveTo(_) => FLAT_MOVE_TO,
+                PathEl::LineTo(_) => FLAT_LINE_TO,
+                PathEl::QuadTo(..) => FLAT_QUAD_TO,
+                PathEl::CurveTo(..) => FLAT_CURVE_TO,
+                PathEl::ClosePath => FLAT_CLOSE_PATH,
+            });
+            for p in el.points() {
+                coords.push(p.x);
+                coords.push(p.y);
+            }
+        }
+        (tags, coords)
+    }
+
+    /// Decode a path encoded by [`to_flat`].
+    ///
+    /// Returns `None` if a tag is unknown, or if the number of coordinates
+    /// does not match the tags exactly.
+    ///
+    /// [`to_flat`]: #method.to_flat
+    pub fn from_flat(tags: &[u8], coords: &[f64]) -> Option<BezPath> {
+        let mut path = BezPath::with_capacity(tags.len());
+        let mut i = 0;
+        let pt = |i: &mut usize| -> Option<Point> {
+            let p = Point::new(*coords.get(*i)?, *coords.get(*i + 1)?);
+            *i += 2;
+            Some(p)
+        };
+        for &tag in tags {
+            let el = match tag {
+                FLAT_MOVE_TO => PathEl::MoveTo(pt(&mut i)?),
+                FLAT_LINE_TO => PathEl::LineTo(pt(&mut i)?),
+                FLAT_QUAD_TO => PathEl::QuadTo(pt(&mut i)?, pt(&mut i)?),
+                FLAT_CURVE_TO => PathEl::CurveTo(pt(&mut i)?, pt(&mut i)?, pt(&mut i)?),
+                FLAT_CLOSE_PATH => PathEl::ClosePath,
+                _ => return None,
+            };
+            path.push(el);
+        }
+        if i != coords.len() {
+            return None;
+        }
+        Some(path)
+    }
+
     /// Apply an affine transform to the path.
     pub fn apply_affine(&mut self, affine: Affine) {
         for el in self.0.iter_mut() {
@@ -254,6 +811,187 @@ impl BezPath {
         }
     }
 
+    /// A copy of the path transformed about a pivot point.
+    ///
+    /// Each point `p` is mapped to `pivot + affine * (p - pivot)`, so a
+    /// transform without translation leaves the pivot exactly fixed, which is
+    /// not guaranteed when composing the equivalent `Affine` by hand.
+    pub fn transform_about(&self, affine: Affine, pivot: Point) -> BezPath {
+        let map = |p: Point| pivot + (affine * (p - pivot).to_point()).to_vec2();
+        BezPath(self.0.iter().map(|el| el.map_points(map)).collect())
+    }
+
+    /// A copy of the path scaled uniformly about a pivot point.
+    ///
+    /// See [`transform_about`](#method.transform_about).
+    pub fn scale_about(&self, factor: f64, pivot: Point) -> BezPath {
+        self.transform_about(Affine::scale(factor), pivot)
+    }
+
+    /// A copy of the path scaled non-uniformly about a pivot point.
+    ///
+    /// See [`transform_about`](#method.transform_about).
+    pub fn scale_about_xy(&self, s_x: f64, s_y: f64, pivot: Point) -> BezPath {
+        self.transform_about(Affine::scale_non_uniform(s_x, s_y), pivot)
+    }
+
+    /// A copy of the path rotated about a pivot point.
+    ///
+    /// The angle is in radians, with the same convention as
+    /// [`Affine::rotate`](struct.Affine.html#method.rotate). See also
+    /// [`transform_about`](#method.transform_about).
+    pub fn rotate_about(&self, angle: f64, pivot: Point) -> BezPath {
+        self.transform_about(Affine::rotate(angle), pivot)
+    }
+
+    /// A copy of the path reflected across the x axis, negating every y
+    /// coordinate.
+    ///
+    /// This converts a path between y-up and y-down coordinates, keeping
+    /// its appearance. The orientation is reversed, so the signs of its
+    /// [`area`] and [`winding`] numbers are too. To
+    /// reflect across another horizontal line, use [`scale_about_xy`] with
+    /// a scale of -1 in y.
+    ///
+    /// [`area`]: trait.Shape.html#tymethod.area
+    /// [`winding`]: trait.Shape.html#tymethod.winding
+    /// [`scale_about_xy`]: #method.scale_about_xy
+    pub fn with_flipped_y(&self) -> BezPath {
+        Affine::FLIP_Y * self
+    }
+
+    /// The area enclosed by the path, regardless of its orientation.
+    ///
+    /// This is the absolute value of the signed [`area`], so subpaths of
+    /// opposite orientations still cancel each other.
+    ///
+    /// [`area`]: trait.Shape.html#tymethod.area
+    pub fn area_abs(&self) -> f64 {
+        self.area().abs()
+    }
+
+    /// The signed area of each subpath, in order.
+    ///
+    /// The subpaths are those yielded by [`subpaths`], and each is treated
+    /// as closed by a line back to its start, whether or not it ends with a
+    /// `ClosePath`, so the areas sum to the [`area`] of the whole path. A
+    /// subpath without segments has an area of zero. The path is traversed
+    /// once, without building the subpaths.
+    ///
+    /// [`subpaths`]: #method.subpaths
+    /// [`area`]: trait.Shape.html#tymethod.area
+    pub fn subpath_areas(&self) -> Vec<f64> {
+        let closing = |last: Point, start: Point| Line::new(last, start).signed_area();
+        let mut areas = Vec::new();
+        // The area of the current subpath so far, if there is one.
+        let mut current: Option<f64> = None;
+        let mut start: Option<Point> = None;
+        let mut last = Point::ORIGIN;
+        for &el in self.elements() {
+            let seg = match el {
+                PathEl::MoveTo(p) => {
+                    if let (Some(area), Some(start)) = (current.take(), start) {
+                        areas.push(area + closing(last, start));
+                    }
+                    start = Some(p);
+                    last = p;
+                    current = Some(0.0);
+                    continue;
+                }
+                PathEl::ClosePath => {
+                    if let (Some(area), Some(start)) = (current.take(), start) {
+                        areas.push(area + closing(last, start));
+                        last = start;
+                    }
+                    continue;
+                }
+                PathEl::LineTo(p) => PathSeg::Line(Line::new(last, p)),
+                PathEl::QuadTo(p1, p2) => PathSeg::Quad(QuadBez::new(last, p1, p2)),
+                PathEl::CurveTo(p1, p2, p3) => PathSeg::Cubic(CubicBez::new(last, p1, p2, p3)),
+            };
+            if start.is_none() {
+                // A path starting with a segment starts at its end.
+                start = Some(seg.end());
+                last = seg.end();
+                current = Some(0.0);
+                continue;
+            }
+            let area = current.get_or_insert(0.0);
+            *area += seg.signed_area();
+            last = seg.end();
+        }
+        if let (Some(area), Some(start)) = (current, start) {
+            areas.push(area + closing(last, start));
+        }
+        areas
+    }
+
+    /// Find where the path meets a circle.
+    ///
+    /// Returns the index of the segment, the parameter within it and the
+    /// point, for each place where the path crosses or touches the circle,
+    /// in order along the path. The segments are intersected as by
+    /// [`PathSeg::intersect_circle`], so tangent contacts within `tolerance`
+    /// count, and a stretch of the path that stays within `tolerance` of the
+    /// circle gives a single result, at its point closest to the circle,
+    /// even where it continues from one segment to the next. As in
+    /// [`nearest`], the index counts segments, not elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{BezPath, Circle, Point};
+    /// let mut path = BezPath::new();
+    /// path.move_to((-2.0, 0.0));
+    /// path.line_to((0.0, 0.0));
+    /// path.line_to((0.0, 1.0));
+    /// path.line_to((2.0, 1.0));
+    /// let hits = path.intersect_circle(Circle::new((0.0, 0.0), 1.0), 1e-9);
+    /// assert_eq!(
+    ///     hits,
+    ///     vec![(0, 0.5, Point::new(-1.0, 0.0)), (1, 1.0, Point::new(0.0, 1.0))]
+    /// );
+    /// ```
+    ///
+    /// [`PathSeg::intersect_circle`]: enum.PathSeg.html#method.intersect_circle
+    /// [`nearest`]: #method.nearest
+    pub fn intersect_circle(&self, circle: Circle, tolerance: f64) -> Vec<(usize, f64, Point)> {
+        let mut result: Vec<(usize, f64, Point)> = Vec::new();
+        // The end of the last segment, and the distance to the circle of the
+        // last result if it is on a stretch reaching that end.
+        let mut last: Option<(Point, Option<f64>)> = None;
+        for (ix, seg) in self.segments().enumerate() {
+            let mut continued = match last {
+                Some((end, gap)) if end == seg.start() => gap,
+                _ => None,
+            };
+            let mut end_gap = None;
+            for contact in circle_contacts(seg, circle, tolerance) {
+                let hit = (ix, contact.t, seg.eval(contact.t));
+                match continued.take() {
+                    Some(gap) if contact.at_start => {
+                        if contact.gap < gap {
+                            *result.last_mut().unwrap() = hit;
+                        } else {
+                            // The stretch is still at its closest point on
+                            // an earlier segment.
+                            end_gap = if contact.at_end { Some(gap) } else { None };
+                            continue;
+                        }
+                    }
+                    _ => result.push(hit),
+                }
+                end_gap = if contact.at_end {
+                    Some(contact.gap)
+                } else {
+                    None
+                };
+            }
+            last = Some((seg.end(), end_gap));
+        }
+        result
+    }
+
     /// Find the nearest point.
     ///
     /// Panics if path is empty or invalid.
@@ -275,809 +1013,4694 @@ impl BezPath {
         }
         best.unwrap()
     }
-}
-
-impl FromIterator<PathEl> for BezPath {
-    fn from_iter<T: IntoIterator<Item = PathEl>>(iter: T) -> Self {
-        let el_vec: Vec<_> = iter.into_iter().collect();
-        BezPath::from_vec(el_vec)
-    }
-}
-
-/// Allow iteration over references to `BezPath`.
-///
-/// Note: the semantics are slightly different than simply iterating over the
-/// slice, as it returns `PathEl` items, rather than references.
-impl<'a> IntoIterator for &'a BezPath {
-    type Item = PathEl;
-    type IntoIter = std::iter::Cloned<std::slice::Iter<'a, PathEl>>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.elements().iter().cloned()
+    /// Find where a point touches the outline, within a tolerance.
+    ///
+    /// Returns the index of the segment and the parameter within it of the
+    /// nearest point on the path, if that is within `tolerance` of `pt`, and
+    /// `None` otherwise, including for an empty path. As with [`nearest`],
+    /// the index counts segments rather than elements, and the closing lines
+    /// of closed subpaths are segments too. Near a corner, where several
+    /// segments are within tolerance, the closest of them is returned.
+    ///
+    /// This is a hit test of the boundary itself, regardless of whether `pt`
+    /// is inside the path; see [`contains`] for that. Segments whose bounding
+    /// box is farther than `tolerance` away are skipped without searching
+    /// them.
+    ///
+    /// [`nearest`]: #method.nearest
+    /// [`contains`]: trait.Shape.html#method.contains
+    pub fn on_boundary(&self, pt: Point, tolerance: f64) -> Option<(usize, f64)> {
+        let tol2 = tolerance * tolerance;
+        let accuracy = tolerance * 1e-3;
+        let mut best: Option<(usize, f64, f64)> = None;
+        for (ix, seg) in self.segments().enumerate() {
+            let bbox = seg.bounding_box();
+            if (bbox.clamp(pt) - pt).hypot2() > tol2 {
+                continue;
+            }
+            let (t, r) = seg.nearest(pt, accuracy);
+            if r <= tol2 && best.map(|(_, _, r_best)| r < r_best).unwrap_or(true) {
+                best = Some((ix, t, r));
+            }
+        }
+        best.map(|(ix, t, _)| (ix, t))
     }
-}
 
-impl IntoIterator for BezPath {
-    type Item = PathEl;
-    type IntoIter = std::vec::IntoIter<PathEl>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
-    }
-}
-
-impl Extend<PathEl> for BezPath {
-    fn extend<I: IntoIterator<Item = PathEl>>(&mut self, iter: I) {
-        self.0.extend(iter);
+    /// Add an on-curve point to the path at the point nearest to `pt`.
+    ///
+    /// The segment found by [`nearest`] is split in two at the nearest
+    /// parameter by de Casteljau subdivision, so the shape of the path is
+    /// unchanged and the new point lies on it. Splitting the line closing a
+    /// subpath inserts a `LineTo` before the `ClosePath`.
+    ///
+    /// Returns `false`, leaving the path unchanged, if it has no segments, or
+    /// if the nearest point is already an endpoint of a segment.
+    ///
+    /// [`nearest`]: #method.nearest
+    pub fn insert_point(&mut self, pt: Point, accuracy: f64) -> bool {
+        if self.segments().next().is_none() {
+            return false;
+        }
+        let (seg_ix, t, _) = self.nearest(pt, accuracy);
+        if t <= 0.0 || t >= 1.0 {
+            return false;
+        }
+        let (el_ix, seg) = match self.segment_elements().nth(seg_ix) {
+            Some(found) => found,
+            None => return false,
+        };
+        let halves = match seg {
+            PathSeg::Line(l) => {
+                let m = l.p0.lerp(l.p1, t);
+                [PathEl::LineTo(m), PathEl::LineTo(l.p1)]
+            }
+            PathSeg::Quad(q) => {
+                let (a, b) = (q.p0.lerp(q.p1, t), q.p1.lerp(q.p2, t));
+                let m = a.lerp(b, t);
+                [PathEl::QuadTo(a, m), PathEl::QuadTo(b, q.p2)]
+            }
+            PathSeg::Cubic(c) => {
+                let (a, b) = c.split_at(t);
+                [
+                    PathEl::CurveTo(a.p1, a.p2, a.p3),
+                    PathEl::CurveTo(b.p1, b.p2, b.p3),
+                ]
+            }
+        };
+        if let PathEl::ClosePath = self.0[el_ix] {
+            self.0.insert(el_ix, halves[0]);
+        } else {
+            self.0[el_ix] = halves[1];
+            self.0.insert(el_ix, halves[0]);
+        }
+        true
     }
-}
 
-/// Proportion of tolerance budget that goes to cubic to quadratic conversion.
-const TO_QUAD_TOL: f64 = 0.1;
-
-/// Flatten the path, invoking the callback repeatedly.
-///
-/// See [`BezPath::flatten`](struct.BezPath.html#method.flatten) for more discussion.
-/// This signature is a bit more general, allowing flattening of `&[PathEl]` slices
-/// and other iterators yielding `PathEl`.
-pub fn flatten(
-    path: impl IntoIterator<Item = PathEl>,
-    tolerance: f64,
-    mut callback: impl FnMut(PathEl),
-) {
-    let sqrt_tol = tolerance.sqrt();
-    let mut last_pt = None;
-    let mut quad_buf = Vec::new();
-    for el in path {
-        match el {
-            PathEl::MoveTo(p) => {
-                last_pt = Some(p);
-                callback(PathEl::MoveTo(p));
+    /// Remove an on-curve point, replacing the segments on either side of it
+    /// with a single cubic.
+    ///
+    /// The point removed is the end point of the element at `element_index`,
+    /// which must be followed in the same subpath by another segment (which
+    /// may be the line closing the subpath). The new cubic starts and ends
+    /// where the two segments did, with the same tangents there, so the path
+    /// stays as smooth as it was at both ends; the lengths of its control
+    /// handles are fitted by least squares to points sampled along the two
+    /// segments, and refined while that reduces the error, as long as no
+    /// handle gets longer than twice the length of the two segments.
+    ///
+    /// If the cubic deviates from the original segments by more than
+    /// `tolerance`, the point is kept as it is, leaving a corner or sharp turn
+    /// where it is needed, and `false` is returned. It is also kept if the
+    /// element does not end a segment with a following segment. Returns
+    /// `true` if the point was removed.
+    pub fn remove_anchor(&mut self, element_index: usize, tolerance: f64) -> bool {
+        let pair = {
+            let mut segs = self
+                .segment_elements()
+                .skip_while(|&(ix, _)| ix != element_index);
+            (segs.next(), segs.next())
+        };
+        let (a, (next_ix, b)) = match pair {
+            (Some((_, a)), Some(next)) if next.0 == element_index + 1 => (a, next),
+            _ => return false,
+        };
+        let (p0, p3) = (a.start(), b.end());
+        let chord = p3 - p0;
+        let t0 = start_tangent(a).unwrap_or_else(|| chord.normalize());
+        let t1 = end_tangent(b).unwrap_or_else(|| chord.normalize());
+        // Points along both segments, with parameters proportional to the
+        // length of the sampled polyline.
+        let n = REMOVE_ANCHOR_SAMPLES;
+        let mut points = Vec::with_capacity(2 * n + 1);
+        points.push(p0);
+        for seg in &[a, b] {
+            points.extend((1..=n).map(|i| seg.eval(i as f64 / n as f64)));
+        }
+        let mut lengths = vec![0.0];
+        for w in points.windows(2) {
+            lengths.push(lengths.last().unwrap() + w[0].distance(w[1]));
+        }
+        let total = *lengths.last().unwrap();
+        if !(total > 0.0 && t0.hypot2() > 0.5 && t1.hypot2() > 0.5) {
+            return false;
+        }
+        let mut params: Vec<f64> = lengths.iter().map(|l| l / total).collect();
+        let mut normals = vec![Vec2::ZERO; points.len()];
+        // Refitting does not always converge, and may run away with the
+        // handles, so it stops when they get too long or the error grows,
+        // keeping the best fit.
+        let max_handle = REMOVE_ANCHOR_MAX_HANDLE * total;
+        let handles_fit = |c: &CubicBez| {
+            (c.p1 - c.p0).hypot() <= max_handle && (c.p2 - c.p3).hypot() <= max_handle
+        };
+        let mut cubic = fit_cubic_handles(&points, &params, None, (p0, t0), (p3, t1));
+        if !handles_fit(&cubic) {
+            return false;
+        }
+        let mut best = (cubic, f64::INFINITY);
+        for i in 0..=REMOVE_ANCHOR_REFINEMENTS {
+            let deriv = cubic.deriv();
+            let mut err: f64 = 0.0;
+            for ((u, n), &p) in params.iter_mut().zip(&mut normals).zip(&points) {
+                let (t, dist2) = cubic.nearest_robust(p, tolerance * 1e-3);
+                *u = t;
+                err = err.max(dist2);
+                let d = deriv.eval(t).to_vec2();
+                *n = Vec2::new(-d.y, d.x).normalize();
             }
-            PathEl::LineTo(p) => {
-                last_pt = Some(p);
-                callback(PathEl::LineTo(p));
+            if err.is_nan() || err >= best.1 {
+                break;
             }
-            PathEl::QuadTo(p1, p2) => {
-                if let Some(p0) = last_pt {
-                    let q = QuadBez::new(p0, p1, p2);
-                    let params = q.estimate_subdiv(sqrt_tol);
-                    let n = ((0.5 * params.val / sqrt_tol).ceil() as usize).max(1);
-                    let step = 1.0 / (n as f64);
-                    for i in 1..(n - 1) {
-                        let u = (i as f64) * step;
-                        let t = q.determine_subdiv_t(&params, u);
-                        let p = q.eval(t);
-                        callback(PathEl::LineTo(p));
-                    }
-                    callback(PathEl::LineTo(p2));
-                }
-                last_pt = Some(p2);
+            best = (cubic, err);
+            if i == REMOVE_ANCHOR_REFINEMENTS || !normals.iter().all(|n| n.hypot2() > 0.5) {
+                break;
             }
-            PathEl::CurveTo(p1, p2, p3) => {
-                if let Some(p0) = last_pt {
-                    let c = CubicBez::new(p0, p1, p2, p3);
+            let normals = Some(&normals[..]);
+            cubic = fit_cubic_handles(&points, &params, normals, (p0, t0), (p3, t1));
+            if !handles_fit(&cubic) {
+                break;
+            }
+        }
+        let cubic = best.0;
+        // The error is measured both ways, so that a fit that misses part of
+        // the original, or strays from it between samples, is rejected.
+        let fine = 4 * n;
+        let err_to_fit = (0..=fine)
+            .flat_map(|i| {
+                let t = i as f64 / fine as f64;
+                vec![a.eval(t), b.eval(t)]
+            })
+            .map(|p| cubic.nearest_robust(p, tolerance * 1e-3).1)
+            .fold(0.0, f64::max);
+        let err_from_fit = (0..=2 * fine)
+            .map(|i| {
+                let p = cubic.eval(i as f64 / (2 * fine) as f64);
+                a.nearest(p, tolerance * 1e-3)
+                    .1
+                    .min(b.nearest(p, tolerance * 1e-3).1)
+            })
+            .fold(0.0, f64::max);
+        if err_to_fit.max(err_from_fit).sqrt() > tolerance {
+            return false;
+        }
+        self.0[element_index] = PathEl::CurveTo(cubic.p1, cubic.p2, cubic.p3);
+        if !matches!(self.0[next_ix], PathEl::ClosePath) {
+            self.0.remove(next_ix);
+        }
+        true
+    }
 
-                    // Subdivide into quadratics, and estimate the number of
-                    // subdivisions required for each, summing to arrive at an
-                    // estimate for the number of subdivisions for the cubic.
-                    // Also retain these parameters for later.
-                    let iter = c.to_quads(tolerance * TO_QUAD_TOL);
-                    quad_buf.clear();
-                    quad_buf.reserve(iter.size_hint().0);
-                    let sqrt_remain_tol = sqrt_tol * (1.0 - TO_QUAD_TOL).sqrt();
-                    let mut sum = 0.0;
-                    for (_, _, q) in iter {
-                        let params = q.estimate_subdiv(sqrt_remain_tol);
-                        sum += params.val;
-                        quad_buf.push((q, params));
-                    }
-                    let n = ((0.5 * sum / sqrt_remain_tol).ceil() as usize).max(1);
-
-                    // Iterate through the quadratics, outputting the points of
-                    // subdivisions that fall within that quadratic.
-                    let step = sum / (n as f64);
-                    let mut i = 1;
-                    let mut val_sum = 0.0;
-                    for (q, params) in &quad_buf {
-                        let mut target = (i as f64) * step;
-                        let recip_val = params.val.recip();
-                        while target < val_sum + params.val {
-                            let u = (target - val_sum) * recip_val;
-                            let t = q.determine_subdiv_t(&params, u);
-                            let p = q.eval(t);
-                            callback(PathEl::LineTo(p));
-                            i += 1;
-                            if i == n + 1 {
-                                break;
-                            }
-                            target = (i as f64) * step;
-                        }
-                        val_sum += params.val;
+    /// The index of the element producing each segment, with the segment.
+    ///
+    /// This matches [`segments`]: a `ClosePath` produces the closing line if
+    /// the subpath does not already end at its start.
+    ///
+    /// [`segments`]: #method.segments
+    fn segment_elements(&self) -> impl Iterator<Item = (usize, PathSeg)> + '_ {
+        let mut start = Point::ORIGIN;
+        let mut last = None;
+        self.0.iter().enumerate().filter_map(move |(ix, &el)| {
+            let p0 = last.unwrap_or_else(|| *el.points().last().unwrap_or(&start));
+            let seg = match el {
+                PathEl::MoveTo(p) => {
+                    start = p;
+                    last = Some(p);
+                    return None;
+                }
+                PathEl::LineTo(p1) => PathSeg::Line(Line::new(p0, p1)),
+                PathEl::QuadTo(p1, p2) => PathSeg::Quad(QuadBez::new(p0, p1, p2)),
+                PathEl::CurveTo(p1, p2, p3) => PathSeg::Cubic(CubicBez::new(p0, p1, p2, p3)),
+                PathEl::ClosePath => {
+                    last = Some(start);
+                    if p0 == start {
+                        return None;
                     }
-                    callback(PathEl::LineTo(p3));
+                    PathSeg::Line(Line::new(p0, start))
                 }
-                last_pt = Some(p3);
-            }
-            PathEl::ClosePath => {
-                last_pt = None;
-                callback(PathEl::ClosePath);
+            };
+            if last.is_none() {
+                start = p0;
             }
-        }
+            last = Some(seg.end());
+            Some((ix, seg))
+        })
     }
-}
 
-impl Mul<PathEl> for Affine {
-    type Output = PathEl;
-
-    fn mul(self, other: PathEl) -> PathEl {
-        match other {
-            PathEl::MoveTo(p) => PathEl::MoveTo(self * p),
-            PathEl::LineTo(p) => PathEl::LineTo(self * p),
-            PathEl::QuadTo(p1, p2) => PathEl::QuadTo(self * p1, self * p2),
-            PathEl::CurveTo(p1, p2, p3) => PathEl::CurveTo(self * p1, self * p2, self * p3),
-            PathEl::ClosePath => PathEl::ClosePath,
-        }
+    /// The signed distance from the point to the path.
+    ///
+    /// The magnitude is the distance to the nearest point of the path, as
+    /// found by [`nearest`], and the sign is negative inside the path, by
+    /// the nonzero [`winding`] rule with subpaths implicitly closed. Returns
+    /// infinity if the path has no segments.
+    ///
+    /// [`nearest`]: #method.nearest
+    /// [`winding`]: trait.Shape.html#tymethod.winding
+    pub fn signed_distance(&self, pt: Point, accuracy: f64) -> f64 {
+        self.signed_distance_and_gradient(pt, accuracy).0
     }
-}
-
-impl Mul<PathSeg> for Affine {
-    type Output = PathSeg;
 
-    fn mul(self, other: PathSeg) -> PathSeg {
-        match other {
-            PathSeg::Line(line) => PathSeg::Line(self * line),
-            PathSeg::Quad(quad) => PathSeg::Quad(self * quad),
-            PathSeg::Cubic(cubic) => PathSeg::Cubic(self * cubic),
+    /// The signed distance from the point to the path, with a choice of how
+    /// the sign is determined.
+    ///
+    /// For [`SignedDistanceMethod::Winding`] this is the same as
+    /// [`signed_distance`]. For [`SignedDistanceMethod::PseudoNormal`], the
+    /// subpaths are implicitly closed, so the magnitude is the same for a
+    /// path whose subpaths are all closed. On the medial axis, the nearest
+    /// point on the segment that comes first is used. A point exactly on the
+    /// line through the nearest point along the boundary, where the dot
+    /// product with the normal is zero, is counted as outside, and where the
+    /// normal is not defined, such as at a cusp, the winding number decides.
+    ///
+    /// Returns infinity if the path has no segments.
+    ///
+    /// [`signed_distance`]: #method.signed_distance
+    /// [`SignedDistanceMethod::Winding`]: enum.SignedDistanceMethod.html#variant.Winding
+    /// [`SignedDistanceMethod::PseudoNormal`]: enum.SignedDistanceMethod.html#variant.PseudoNormal
+    pub fn signed_distance_with(
+        &self,
+        pt: Point,
+        accuracy: f64,
+        method: SignedDistanceMethod,
+    ) -> f64 {
+        if method == SignedDistanceMethod::Winding {
+            return self.signed_distance(pt, accuracy);
+        }
+        // The closed segments of each subpath, to find the neighbors of a
+        // vertex.
+        let subpaths: Vec<Vec<PathSeg>> = self
+            .subpaths()
+            .map(|sub| segments(CloseSubpaths::new(sub.iter())).collect::<Vec<_>>())
+            .filter(|segs| !segs.is_empty())
+            .collect();
+        let mut best: Option<(usize, usize, f64, f64)> = None;
+        for (i, segs) in subpaths.iter().enumerate() {
+            for (j, seg) in segs.iter().enumerate() {
+                let (t, r2) = seg.nearest(pt, accuracy);
+                if best.map(|b| r2 < b.3).unwrap_or(true) {
+                    best = Some((i, j, t, r2));
+                }
+            }
+        }
+        let (i, j, t, r2) = match best {
+            Some(best) => best,
+            None => return f64::INFINITY,
+        };
+        let segs = &subpaths[i];
+        let seg = segs[j];
+        let n = segs.len();
+        let orient = if self.area() >= 0.0 { 1.0 } else { -1.0 };
+        let outward = |tan: Vec2| orient * Vec2::new(tan.y, -tan.x).normalize();
+        let normal = if t <= 0.0 {
+            let prev = segs[(j + n - 1) % n];
+            end_tangent(prev)
+                .zip(start_tangent(seg))
+                .map(|(a, b)| outward(a) + outward(b))
+        } else if t >= 1.0 {
+            let next = segs[(j + 1) % n];
+            end_tangent(seg)
+                .zip(start_tangent(next))
+                .map(|(a, b)| outward(a) + outward(b))
+        } else {
+            seg_tangent(seg, t).map(outward)
+        };
+        let inside = match normal {
+            Some(normal) if normal.hypot2() > 0.0 => (pt - seg.eval(t)).dot(normal) < 0.0,
+            _ => self.winding(pt) != 0,
+        };
+        let dist = r2.sqrt();
+        if inside {
+            -dist
+        } else {
+            dist
         }
     }
-}
-
-impl Mul<BezPath> for Affine {
-    type Output = BezPath;
 
-    fn mul(self, other: BezPath) -> BezPath {
-        BezPath(other.0.iter().map(|&el| self * el).collect())
+    /// The signed distance from the point to the path, and its gradient.
+    ///
+    /// The distance is as for [`signed_distance`]. The gradient is the unit
+    /// vector from the nearest point of the path towards `pt`, negated
+    /// inside, so it always points away from the inside of the path. Near a
+    /// corner this is the direction from the corner itself, and on the
+    /// medial axis, where several points of the path are equally near, the
+    /// one on the segment that comes first is used. When `pt` is on the path,
+    /// the gradient is the normal of the path there, pointing outwards; at a
+    /// vertex, the normal of the segment found nearest is used.
+    ///
+    /// Returns infinity and a zero vector if the path has no segments.
+    ///
+    /// [`signed_distance`]: #method.signed_distance
+    pub fn signed_distance_and_gradient(&self, pt: Point, accuracy: f64) -> (f64, Vec2) {
+        if self.is_empty() {
+            return (f64::INFINITY, Vec2::ZERO);
+        }
+        let (ix, t, r2) = self.nearest(pt, accuracy);
+        let seg = self.segments().nth(ix).unwrap();
+        let q = seg.eval(t);
+        let inside = self.winding(pt) != 0;
+        let sign = if inside { -1.0 } else { 1.0 };
+        let v = pt - q;
+        if v.hypot2() > 0.0 {
+            return (sign * r2.sqrt(), sign * v.normalize());
+        }
+        let grad = match seg_tangent(seg, t) {
+            Some(tan) => {
+                let n = Vec2::new(tan.y, -tan.x);
+                // Probe a short distance to one side to find the outside.
+                let eps = 1e-9 * (1.0 + q.to_vec2().hypot());
+                if self.winding(q + eps * n) != 0 {
+                    -n
+                } else {
+                    n
+                }
+            }
+            None => Vec2::ZERO,
+        };
+        (0.0, grad)
     }
-}
-
-impl<'a> Mul<&'a BezPath> for Affine {
-    type Output = BezPath;
 
-    fn mul(self, other: &BezPath) -> BezPath {
-        BezPath(other.0.iter().map(|&el| self * el).collect())
+    /// Compute the coverage of a row of pixels by the filled path.
+    ///
+    /// The row spans `y` to `y + 1` vertically, and `x0` to `x1`
+    /// horizontally, divided into `out.len()` pixels of equal width; pixel
+    /// `i` starts at `x0 + i * (x1 - x0) / out.len()`. Each value of `out`
+    /// is set to the fraction of its pixel that is inside the path according
+    /// to `fill_rule`, between 0 and 1. Subpaths are implicitly closed.
+    ///
+    /// The path is flattened to a tolerance of a twentieth of a pixel, and
+    /// sampled at 16 evenly spaced heights within the row. At each height
+    /// the horizontal extent of the spans inside the path is exact, so edges
+    /// that are close to vertical are anti-aliased smoothly, while the
+    /// coverage of edges that are close to horizontal is quantized to
+    /// sixteenths.
+    ///
+    /// If `x1` is not greater than `x0`, `out` is filled with zeros.
+    pub fn scanline_coverage(
+        &self,
+        y: f64,
+        x0: f64,
+        x1: f64,
+        fill_rule: FillRule,
+        out: &mut [f32],
+    ) {
+        for c in out.iter_mut() {
+            *c = 0.0;
+        }
+        if out.is_empty() || x1 <= x0 || x1.is_nan() || x0.is_nan() {
+            return;
+        }
+        let width = (x1 - x0) / out.len() as f64;
+        let tolerance = 0.05 * width.min(1.0);
+        let (y_min, y_max) = (y, y + 1.0);
+        // The edges of the flattened path that overlap the row.
+        let mut edges = Vec::new();
+        for seg in segments(CloseSubpaths::new(self.iter())) {
+            let (lo, hi) = match seg {
+                PathSeg::Line(l) => (l.p0.y.min(l.p1.y), l.p0.y.max(l.p1.y)),
+                PathSeg::Quad(q) => (
+                    q.p0.y.min(q.p1.y).min(q.p2.y),
+                    q.p0.y.max(q.p1.y).max(q.p2.y),
+                ),
+                PathSeg::Cubic(c) => (
+                    c.p0.y.min(c.p1.y).min(c.p2.y).min(c.p3.y),
+                    c.p0.y.max(c.p1.y).max(c.p2.y).max(c.p3.y),
+                ),
+            };
+            if hi < y_min || lo > y_max {
+                continue;
+            }
+            let el = match seg {
+                PathSeg::Line(l) => PathEl::LineTo(l.p1),
+                PathSeg::Quad(q) => PathEl::QuadTo(q.p1, q.p2),
+                PathSeg::Cubic(c) => PathEl::CurveTo(c.p1, c.p2, c.p3),
+            };
+            let mut last = seg.start();
+            flatten(
+                [PathEl::MoveTo(last), el].iter().copied(),
+                tolerance,
+                |el| {
+                    if let PathEl::LineTo(p) = el {
+                        edges.push((last, p));
+                        last = p;
+                    }
+                },
+            );
+        }
+        let weight = 1.0 / SCANLINE_SUBSAMPLES as f64;
+        let mut crossings: Vec<(f64, i32)> = Vec::new();
+        for k in 0..SCANLINE_SUBSAMPLES {
+            let ys = y + (k as f64 + 0.5) * weight;
+            crossings.clear();
+            for &(p0, p1) in &edges {
+                // Half-open, so that shared vertices are counted once.
+                if (p0.y <= ys) != (p1.y <= ys) {
+                    let x = p0.x + (ys - p0.y) * (p1.x - p0.x) / (p1.y - p0.y);
+                    crossings.push((x, if p1.y > p0.y { 1 } else { -1 }));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+            let mut winding = 0;
+            for (i, &(x, dir)) in crossings.iter().enumerate() {
+                winding += dir;
+                if !fill_rule.is_inside(winding) {
+                    continue;
+                }
+                if let Some(&(x_next, _)) = crossings.get(i + 1) {
+                    add_span_coverage(out, x0, width, x, x_next, weight);
+                }
+            }
+        }
+        for c in out.iter_mut() {
+            *c = c.min(1.0);
+        }
     }
-}
-
-impl Mul<PathEl> for TranslateScale {
-    type Output = PathEl;
 
-    fn mul(self, other: PathEl) -> PathEl {
-        match other {
-            PathEl::MoveTo(p) => PathEl::MoveTo(self * p),
-            PathEl::LineTo(p) => PathEl::LineTo(self * p),
-            PathEl::QuadTo(p1, p2) => PathEl::QuadTo(self * p1, self * p2),
-            PathEl::CurveTo(p1, p2, p3) => PathEl::CurveTo(self * p1, self * p2, self * p3),
-            PathEl::ClosePath => PathEl::ClosePath,
+    /// The winding numbers of a regular grid of points.
+    ///
+    /// The point in column `i` and row `j` is `origin + (i * spacing,
+    /// j * spacing)`, with the coordinates computed as
+    /// `origin.x + i as f64 * spacing` and similarly for y, and the result
+    /// holds the winding numbers row by row, `cols * rows` of them. Each is
+    /// exactly what [`winding`] returns for that point.
+    ///
+    /// Rather than testing every point against every segment, this works a
+    /// row at a time. The path is split into pieces that are monotonic in y,
+    /// once, and along a row each piece covering its height changes the
+    /// winding by its direction, starting at the first column to the right of
+    /// its crossing. That column is found by binary search with the same
+    /// crossing test as [`winding`], which makes the results identical, so
+    /// a row costs a logarithmic number of tests per piece crossing it
+    /// instead of a test per point for every piece.
+    ///
+    /// [`winding`]: trait.Shape.html#tymethod.winding
+    pub fn winding_grid(&self, origin: Point, spacing: f64, cols: usize, rows: usize) -> Vec<i32> {
+        let mut result = vec![0; cols * rows];
+        if cols == 0 {
+            return result;
         }
+        let pieces: Vec<PathSeg> = segments(CloseSubpaths::new(self.iter()))
+            .flat_map(|seg| {
+                seg.extrema_ranges()
+                    .into_iter()
+                    .map(move |range| seg.subsegment(range))
+            })
+            .collect();
+        let x_at = |i: usize| origin.x + i as f64 * spacing;
+        let mut steps = vec![0; cols + 1];
+        for (j, row) in result.chunks_mut(cols).enumerate() {
+            let y = origin.y + j as f64 * spacing;
+            for c in steps.iter_mut() {
+                *c = 0;
+            }
+            for piece in &pieces {
+                let (y0, y1) = (piece.start().y, piece.end().y);
+                let sign = if y1 > y0 && y >= y0 && y < y1 {
+                    -1
+                } else if y1 < y0 && y >= y1 && y < y0 {
+                    1
+                } else {
+                    continue;
+                };
+                // The crossing test is 0 to the left of the crossing and
+                // `sign` from it onwards, whatever the direction of `spacing`
+                // relative to the test's x ordering; search for the change.
+                let test = |i: usize| piece.winding_inner(Point::new(x_at(i), y)) != 0;
+                let first = test(0);
+                let (mut lo, mut hi) = (0, cols);
+                while lo < hi {
+                    let mid = (lo + hi) / 2;
+                    if test(mid) == first {
+                        lo = mid + 1;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                if first {
+                    steps[0] += sign;
+                    steps[lo] -= sign;
+                } else {
+                    steps[lo] += sign;
+                }
+            }
+            let mut w = 0;
+            for (out, &step) in row.iter_mut().zip(&steps) {
+                w += step;
+                *out = w;
+            }
+        }
+        result
     }
-}
-
-impl Mul<PathSeg> for TranslateScale {
-    type Output = PathSeg;
 
-    fn mul(self, other: PathSeg) -> PathSeg {
-        match other {
-            PathSeg::Line(line) => PathSeg::Line(self * line),
-            PathSeg::Quad(quad) => PathSeg::Quad(self * quad),
-            PathSeg::Cubic(cubic) => PathSeg::Cubic(self * cubic),
+    /// The winding number of a point, computed from the angle the path
+    /// subtends around it.
+    ///
+    /// This sums the signed angle swept by each segment, as seen from `pt`,
+    /// and divides by 2π. Curves are subdivided until the point lies outside
+    /// the bounding box of the control points of each piece, which makes the
+    /// angle of the chord exact. Unclosed subpaths are closed with a line, as
+    /// for filling. Unlike the ray crossing test used by [`winding`], the
+    /// result does not depend on how the point lines up with vertices,
+    /// tangents or horizontal edges, which makes it a useful reference.
+    ///
+    /// It is considerably slower: every segment costs at least one `atan2`,
+    /// and curves passing close to the point are subdivided many times,
+    /// whereas the crossing test skips segments that do not straddle the
+    /// point vertically. For points on the boundary the result is one of the
+    /// windings of the adjacent regions.
+    ///
+    /// [`winding`]: trait.Shape.html#tymethod.winding
+    pub fn winding_robust(&self, pt: Point) -> i32 {
+        let mut angle = 0.0;
+        let mut start = None;
+        let mut last = Point::ORIGIN;
+        for &el in &self.0 {
+            let seg = match el {
+                PathEl::MoveTo(p) => {
+                    if let Some(start) = start {
+                        angle += subtended_angle(Line::new(last, start).into(), pt, 0);
+                    }
+                    start = Some(p);
+                    last = p;
+                    continue;
+                }
+                PathEl::ClosePath => {
+                    let start = start.unwrap_or(last);
+                    Line::new(last, start).into()
+                }
+                // The first element is treated as a `MoveTo`.
+                PathEl::LineTo(p) | PathEl::QuadTo(_, p) | PathEl::CurveTo(_, _, p)
+                    if start.is_none() =>
+                {
+                    start = Some(p);
+                    last = p;
+                    continue;
+                }
+                PathEl::LineTo(p) => Line::new(last, p).into(),
+                PathEl::QuadTo(p1, p2) => QuadBez::new(last, p1, p2).into(),
+                PathEl::CurveTo(p1, p2, p3) => CubicBez::new(last, p1, p2, p3).into(),
+            };
+            angle += subtended_angle(seg, pt, 0);
+            last = seg.end();
+        }
+        if let Some(start) = start {
+            angle += subtended_angle(Line::new(last, start).into(), pt, 0);
         }
+        (angle / (2.0 * std::f64::consts::PI)).round() as i32
     }
-}
-
-impl Mul<BezPath> for TranslateScale {
-    type Output = BezPath;
 
-    fn mul(self, other: BezPath) -> BezPath {
-        BezPath(other.0.iter().map(|&el| self * el).collect())
+    /// The bounding box of each segment, in the order of [`segments`].
+    ///
+    /// The boxes are tight: they are computed from the extrema of each
+    /// curve, not from its control points.
+    ///
+    /// [`segments`]: #method.segments
+    pub fn segment_bboxes(&self) -> Vec<Rect> {
+        self.segments()
+            .map(|seg| ParamCurveExtrema::bounding_box(&seg))
+            .collect()
     }
-}
-
-impl<'a> Mul<&'a BezPath> for TranslateScale {
-    type Output = BezPath;
 
-    fn mul(self, other: &BezPath) -> BezPath {
-        BezPath(other.0.iter().map(|&el| self * el).collect())
+    /// Returns `true` if the point is within `half_width` of the path.
+    ///
+    /// This is a hit test against the stroke of the path, computed as a
+    /// distance query rather than by building the stroke outline. Cap and
+    /// join styles are ignored: the region tested is that of a stroke with
+    /// round caps and round joins. Subpaths without any segments (a lone
+    /// `MoveTo`) are never hit.
+    pub fn hit_stroke(&self, pt: Point, half_width: f64, accuracy: f64) -> bool {
+        if half_width < 0.0 || half_width.is_nan() {
+            return false;
+        }
+        let half_width2 = half_width * half_width;
+        self.segments().any(|seg| {
+            // Cheap rejection using the control points, which bound the curve.
+            let hull = match seg {
+                PathSeg::Line(l) => Rect::from_points(l.p0, l.p1),
+                PathSeg::Quad(q) => Rect::from_points(q.p0, q.p1).union_pt(q.p2),
+                PathSeg::Cubic(c) => Rect::from_points(c.p0, c.p1).union_pt(c.p2).union_pt(c.p3),
+            };
+            let hull = hull.inflate(half_width, half_width);
+            pt.x >= hull.x0
+                && pt.x <= hull.x1
+                && pt.y >= hull.y0
+                && pt.y <= hull.y1
+                && seg.nearest(pt, accuracy).1 <= half_width2
+        })
     }
-}
 
-/// Transform an iterator over path elements into one over path
-/// segments.
-///
-/// See also [`BezPath::segments`](struct.BezPath.html#method.segments).
-/// This signature is a bit more general, allowing `&[PathEl]` slices
-/// and other iterators yielding `PathEl`.
-pub fn segments<I>(elements: I) -> Segments<I::IntoIter>
-where
-    I: IntoIterator<Item = PathEl>,
-{
-    Segments {
-        elements: elements.into_iter(),
-        start_last: None,
+    /// Points spaced evenly by arc length along the path, each with its unit
+    /// tangent and normal.
+    ///
+    /// Each subpath is divided into the smallest number of pieces of equal
+    /// length no longer than `spacing`. An open subpath has a frame at each
+    /// end of each piece, including both of its ends; a closed one wraps
+    /// around, so its start is not repeated at the end. Subpaths are
+    /// concatenated in order, and those of zero length are skipped.
+    ///
+    /// The normal is the tangent rotated by 90°, towards positive y from a
+    /// tangent towards positive x. It changes continuously with the tangent,
+    /// including across inflections. Where the tangent reverses abruptly, at
+    /// a cusp or a corner doubling back on itself, the normal keeps its side
+    /// of the path instead of flipping, and is the tangent rotated the other
+    /// way from then on in the subpath.
+    ///
+    /// Arc lengths are measured with the given accuracy. Returns an empty
+    /// vector if `spacing` is not positive and finite.
+    pub fn sample_frames(&self, spacing: f64, accuracy: f64) -> Vec<(Point, Vec2, Vec2)> {
+        let mut frames = Vec::new();
+        if !(spacing > 0.0 && spacing.is_finite()) {
+            return frames;
+        }
+        for subpath in self.subpaths() {
+            let closed = matches!(subpath.elements().last(), Some(PathEl::ClosePath));
+            let segs: Vec<(PathSeg, f64)> = subpath
+                .segments()
+                .map(|seg| (seg, seg.arclen(accuracy)))
+                .filter(|&(_, len)| len > 0.0)
+                .collect();
+            let total: f64 = segs.iter().map(|&(_, len)| len).sum();
+            if !(total > 0.0 && total.is_finite()) {
+                continue;
+            }
+            let n = ((total / spacing).ceil() as usize).max(1);
+            let count = if closed { n } else { n + 1 };
+            let step = total / n as f64;
+            let (mut ix, mut seg_start) = (0, 0.0);
+            let mut last_normal: Option<Vec2> = None;
+            let mut flip = 1.0;
+            for k in 0..count {
+                let s = k as f64 * step;
+                while ix + 1 < segs.len() && s >= seg_start + segs[ix].1 {
+                    seg_start += segs[ix].1;
+                    ix += 1;
+                }
+                let (seg, len) = segs[ix];
+                let t = if k == n {
+                    1.0
+                } else {
+                    seg.inv_arclen((s - seg_start).min(len), accuracy)
+                        .clamp(0.0, 1.0)
+                };
+                let tangent = match seg_tangent(seg, t) {
+                    Some(tangent) => tangent,
+                    None => continue,
+                };
+                let mut normal = flip * Vec2::new(-tangent.y, tangent.x);
+                if let Some(last) = last_normal {
+                    if normal.dot(last) < 0.0 {
+                        flip = -flip;
+                        normal = -normal;
+                    }
+                }
+                last_normal = Some(normal);
+                frames.push((seg.eval(t), tangent, normal));
+            }
+        }
+        frames
     }
-}
 
-/// An iterator that transforms path elements to path segments.
-///
-/// This struct is created by the [`segments`](fn.segments.html) function.
-pub struct Segments<I: Iterator<Item = PathEl>> {
-    elements: I,
-    start_last: Option<(Point, Point)>,
-}
-
-impl<I: Iterator<Item = PathEl>> Iterator for Segments<I> {
-    type Item = PathSeg;
-
-    fn next(&mut self) -> Option<PathSeg> {
-        while let Some(el) = self.elements.next() {
-            // We first need to check whether this is the first
-            // path element we see to fill in the start position.
-            let (start, last) = self.start_last.get_or_insert_with(|| {
-                let point = match el {
-                    PathEl::MoveTo(p) => p,
-                    PathEl::LineTo(p) => p,
-                    PathEl::QuadTo(_, p2) => p2,
-                    PathEl::CurveTo(_, _, p3) => p3,
-                    PathEl::ClosePath => panic!("Can't start a segment on a ClosePath"),
-                };
-                (point, point)
-            });
-
-            return Some(match el {
-                PathEl::MoveTo(p) => {
-                    *start = p;
-                    *last = p;
-                    continue;
-                }
-                PathEl::LineTo(p) => PathSeg::Line(Line::new(mem::replace(last, p), p)),
-                PathEl::QuadTo(p1, p2) => {
-                    PathSeg::Quad(QuadBez::new(mem::replace(last, p2), p1, p2))
+    /// Short lines across the path at equal arc length intervals, as for the
+    /// ticks of a ruler or an axis.
+    ///
+    /// Ticks are placed every `spacing` along each subpath, starting at its
+    /// start, so unlike [`sample_frames`] the last interval of a subpath may
+    /// be shorter than the others. An open subpath also has a tick at its end
+    /// if its length is a multiple of `spacing`; a closed one wraps around,
+    /// so its start is never repeated at the end. Subpaths are concatenated
+    /// in order, and those of zero length are skipped.
+    ///
+    /// Each tick is `length` long and centered on the path, running from
+    /// the right of the direction of travel to the left, taking positive y
+    /// to be the left of positive x. A tick that falls exactly on a corner
+    /// lies along the bisector of its two normals. Where a tick would cross
+    /// the one before it, as on the inside of a sharp corner, both are cut
+    /// short where they meet; in a closed subpath this includes the last
+    /// tick and the first.
+    ///
+    /// Arc lengths are measured with the given accuracy. Returns an empty
+    /// vector if `spacing` is not positive and finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{BezPath, Line};
+    /// let mut path = BezPath::new();
+    /// path.move_to((0.0, 0.0));
+    /// path.line_to((10.0, 0.0));
+    /// let ticks = path.tick_marks(4.0, 1.0, 1e-9);
+    /// assert_eq!(ticks.len(), 3);
+    /// assert_eq!(ticks[1], Line::new((4.0, -0.5), (4.0, 0.5)));
+    /// ```
+    ///
+    /// [`sample_frames`]: #method.sample_frames
+    pub fn tick_marks(&self, spacing: f64, length: f64, accuracy: f64) -> Vec<Line> {
+        let mut ticks = Vec::new();
+        if !(spacing > 0.0 && spacing.is_finite()) {
+            return ticks;
+        }
+        let half = 0.5 * length;
+        let perp = |v: Vec2| Vec2::new(-v.y, v.x);
+        for subpath in self.subpaths() {
+            let closed = matches!(subpath.elements().last(), Some(PathEl::ClosePath));
+            let segs: Vec<(PathSeg, f64)> = subpath
+                .segments()
+                .map(|seg| (seg, seg.arclen(accuracy)))
+                .filter(|&(_, len)| len > 0.0)
+                .collect();
+            let total: f64 = segs.iter().map(|&(_, len)| len).sum();
+            if !(total > 0.0 && total.is_finite()) {
+                continue;
+            }
+            // Distances this close to a segment boundary are taken to be on
+            // it, so that ticks meant for corners are not lost to rounding.
+            let eps = 1e-9 * total;
+            let first = ticks.len();
+            let (mut ix, mut seg_start) = (0, 0.0);
+            for k in 0.. {
+                let s = k as f64 * spacing;
+                if (closed && s >= total - eps) || s > total + eps {
+                    break;
                 }
-                PathEl::CurveTo(p1, p2, p3) => {
-                    PathSeg::Cubic(CubicBez::new(mem::replace(last, p3), p1, p2, p3))
+                while ix + 1 < segs.len() && s >= seg_start + segs[ix].1 - eps {
+                    seg_start += segs[ix].1;
+                    ix += 1;
                 }
-                PathEl::ClosePath => {
-                    if *last != *start {
-                        PathSeg::Line(Line::new(mem::replace(last, *start), *start))
-                    } else {
-                        continue;
+                let (seg, len) = segs[ix];
+                let along = s - seg_start;
+                let prev = if ix > 0 {
+                    Some(segs[ix - 1].0)
+                } else if closed {
+                    Some(segs[segs.len() - 1].0)
+                } else {
+                    None
+                };
+                let (t, normal) = match prev {
+                    Some(prev) if along <= eps => {
+                        let normal = match (seg_tangent(prev, 1.0), seg_tangent(seg, 0.0)) {
+                            (Some(t0), Some(t1)) => {
+                                let sum = perp(t0) + perp(t1);
+                                if sum.hypot2() > 1e-12 {
+                                    Some(sum.normalize())
+                                } else {
+                                    Some(perp(t1))
+                                }
+                            }
+                            (_, t1) => t1.map(perp),
+                        };
+                        (0.0, normal)
+                    }
+                    _ => {
+                        let t = if along >= len - eps {
+                            1.0
+                        } else {
+                            seg.inv_arclen(along.max(0.0), accuracy).clamp(0.0, 1.0)
+                        };
+                        (t, seg_tangent(seg, t).map(perp))
                     }
+                };
+                let normal = match normal {
+                    Some(normal) => normal,
+                    None => continue,
+                };
+                let p = seg.eval(t);
+                let mut tick = Line::new(p - half * normal, p + half * normal);
+                if ticks.len() > first {
+                    let last = ticks.len() - 1;
+                    clip_crossing(&mut ticks[last], &mut tick);
                 }
-            });
+                ticks.push(tick);
+            }
+            if closed && ticks.len() > first + 2 {
+                let last = ticks.len() - 1;
+                let (head, tail) = ticks.split_at_mut(last);
+                clip_crossing(&mut tail[0], &mut head[first]);
+            }
         }
+        ticks
+    }
 
-        None
+    /// Whether the path is a single straight line, within tolerance.
+    ///
+    /// This is the case when the path can be drawn as one line segment from
+    /// its start to its end without backtracking: every point is within
+    /// `tolerance` of that segment, and the path does not move back towards
+    /// its start by more than `tolerance` along the way. A path that runs
+    /// back and forth along a line, or a closed path, is therefore not
+    /// straight, even though all its points are collinear. A path ending
+    /// within `tolerance` of its start is only straight if it stays close to
+    /// that point, with its bounding box within `tolerance` of it. A path
+    /// with more than one subpath that has segments is not straight, and one
+    /// without segments trivially is.
+    ///
+    /// Otherwise the test is exact: in a frame aligned with the line, each
+    /// segment is checked at its ends and at the extrema of its coordinates,
+    /// where the distance from the line and the progress along it are
+    /// greatest.
+    pub fn is_straight(&self, tolerance: f64) -> bool {
+        let mut subpaths = self
+            .subpaths()
+            .filter(|sub| sub.segments().next().is_some());
+        let sub = match (subpaths.next(), subpaths.next()) {
+            (None, _) => return true,
+            (Some(sub), None) => sub,
+            (Some(_), Some(_)) => return false,
+        };
+        let segs: Vec<PathSeg> = sub.segments().collect();
+        let a = segs[0].start();
+        let b = segs[segs.len() - 1].end();
+        if (b - a).hypot() <= tolerance {
+            return segs.iter().all(|seg| {
+                let bbox = seg.bounding_box();
+                let far = Point::new(
+                    if a.x - bbox.x0 > bbox.x1 - a.x {
+                        bbox.x0
+                    } else {
+                        bbox.x1
+                    },
+                    if a.y - bbox.y0 > bbox.y1 - a.y {
+                        bbox.y0
+                    } else {
+                        bbox.y1
+                    },
+                );
+                (far - a).hypot() <= tolerance
+            });
+        }
+        let frame = Affine::rotate(-(b - a).atan2()) * Affine::translate(-a.to_vec2());
+        let mut max_x = 0.0f64;
+        for &seg in &segs {
+            let seg = frame * seg;
+            for t in seg.extrema().into_iter().chain(Some(1.0)) {
+                let p = seg.eval(t);
+                if p.y.abs() > tolerance || p.x < max_x - tolerance {
+                    return false;
+                }
+                max_x = max_x.max(p.x);
+            }
+        }
+        true
     }
-}
 
-impl<I: Iterator<Item = PathEl>> Segments<I> {
-    /// Here, `accuracy` specifies the accuracy for each Bézier segment. At worst,
-    /// the total error is `accuracy` times the number of Bézier segments.
+    /// The total absolute curvature of the path.
+    ///
+    /// This is the integral of the magnitude of the curvature along the
+    /// path, which is the total angle, in radians, through which the tangent
+    /// turns, regardless of direction. The turning angles at corners between
+    /// the segments of a subpath count too, including at the start of a
+    /// closed subpath, so a convex closed subpath has a total absolute
+    /// curvature of 2π, and a polyline has the sum of its exterior angles. A
+    /// direction reversal, at a cusp or at a corner doubling back, counts as
+    /// π, and segments of zero length are skipped.
+    ///
+    /// Curves are split at their inflection points, and then subdivided
+    /// until the tangent angles at the ends of each piece account for its
+    /// turning within `accuracy`.
+    pub fn total_absolute_curvature(&self, accuracy: f64) -> f64 {
+        let mut total = 0.0;
+        for sub in self.subpaths() {
+            let closed = matches!(sub.elements().last(), Some(PathEl::ClosePath));
+            let mut first = None;
+            let mut last: Option<Vec2> = None;
+            for seg in sub.segments() {
+                let start = match start_tangent(seg) {
+                    Some(tangent) => tangent,
+                    None => continue,
+                };
+                if let Some(last) = last {
+                    total += last.angle_between(start).abs();
+                }
+                first = first.or(Some(start));
+                total += seg_turning(seg, accuracy);
+                last = end_tangent(seg);
+            }
+            if let (true, Some(first), Some(last)) = (closed, first, last) {
+                total += last.angle_between(first).abs();
+            }
+        }
+        total
+    }
 
-    // TODO: pub? Or is this subsumed by method of &[PathEl]?
-    pub(crate) fn perimeter(self, accuracy: f64) -> f64 {
-        self.map(|seg| seg.arclen(accuracy)).sum()
+    /// The vertices of the flattened path as a triangle fan, if it is a
+    /// single convex loop.
+    ///
+    /// The path is flattened to a polyline within `tolerance`, closing it if
+    /// it is open, and repeated points and points in the middle of straight
+    /// edges are dropped. If what remains has at least three vertices, every
+    /// corner turns the same way and the boundary goes around exactly once,
+    /// the vertices are returned in the order of the path, and the triangles
+    /// made of the first vertex and each pair of consecutive others cover
+    /// the interior once, each with the sign of the area of the path.
+    /// Otherwise, including for a path with more than one subpath that has
+    /// segments, this returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Circle, Rect, Shape};
+    /// let fan = Circle::new((0.0, 0.0), 10.0).to_path(0.1).to_fan(0.1).unwrap();
+    /// assert!(fan.len() > 8);
+    /// let rect = Rect::new(0.0, 0.0, 4.0, 3.0).to_path(0.1);
+    /// assert_eq!(rect.to_fan(0.1).unwrap().len(), 4);
+    /// ```
+    pub fn to_fan(&self, tolerance: f64) -> Option<Vec<Point>> {
+        let mut polylines = self
+            .to_polyline(tolerance)
+            .into_iter()
+            .filter(|points| points.len() > 1);
+        let mut points = match (polylines.next(), polylines.next()) {
+            (Some(points), None) => points,
+            _ => return None,
+        };
+        points.dedup();
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        let n = points.len();
+        let mut ring = Vec::with_capacity(n);
+        for i in 0..n {
+            let (prev, p, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+            let (d0, d1) = (p - prev, next - p);
+            if orient2d(prev, p, next) != 0.0 {
+                ring.push(p);
+            } else if d0.dot(d1) < 0.0 {
+                // The boundary doubles back on itself.
+                return None;
+            }
+        }
+        let n = ring.len();
+        if n < 3 {
+            return None;
+        }
+        let edge = |i: usize| ring[(i + 1) % n] - ring[i % n];
+        let corner = |i: usize| orient2d(ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+        let sign = corner(0).signum();
+        let mut turning = 0.0;
+        for i in 0..n {
+            let (d0, d1) = (edge(i + n - 1), edge(i));
+            if corner(i) * sign <= 0.0 {
+                return None;
+            }
+            turning += d0.angle_between(d1);
+        }
+        // A convex loop turns through a full turn; a star polygon, also with
+        // all its corners turning the same way, through two or more.
+        if turning.abs() > 3.0 * std::f64::consts::PI {
+            return None;
+        }
+        Some(ring)
     }
 
-    // Same
-    pub(crate) fn area(self) -> f64 {
-        self.map(|seg| seg.signed_area()).sum()
+    /// The regions into which the path divides the plane, with their winding
+    /// numbers.
+    ///
+    /// The path is flattened with the given tolerance and split at its
+    /// self-intersections, and each subpath is treated as closed. Each
+    /// bounded region is a maximal connected area of constant winding number,
+    /// returned as a path made of lines: a counterclockwise (in y-up) outer
+    /// boundary, followed by a clockwise boundary for each hole. A region
+    /// nested in a hole of another is returned separately, so the regions
+    /// do not overlap and their areas add up to the area enclosed by the
+    /// path's outer boundary.
+    ///
+    /// Bounded regions of winding number 0, such as the inside of a hole
+    /// drawn in the opposite direction, are included. The regions are ordered
+    /// by winding number.
+    pub fn regions(&self, tolerance: f64) -> Vec<(BezPath, i32)> {
+        crate::boolean::regions(self, tolerance)
     }
 
-    // Same
-    pub(crate) fn winding(self, p: Point) -> i32 {
-        self.map(|seg| seg.winding(p)).sum()
+    /// The area filled by the nonzero fill rule but not by even-odd.
+    ///
+    /// This is the total area of the [`regions`] with an even, nonzero
+    /// winding number, where the path overlaps itself in the same direction.
+    /// Every point filled by even-odd is also filled by nonzero, so this is
+    /// the whole difference between the two rules: it is zero for a path
+    /// whose subpaths neither cross nor nest in the same direction. Regions
+    /// thinner than `tolerance`, those whose area is less than `tolerance`
+    /// times their perimeter, are treated as numerical noise and ignored.
+    ///
+    /// [`regions`]: #method.regions
+    pub fn fill_discrepancy(&self, tolerance: f64) -> f64 {
+        self.regions(tolerance)
+            .iter()
+            .filter(|(_, w)| *w != 0 && w % 2 == 0)
+            .map(|(region, _)| (region.area().abs(), region.perimeter(tolerance)))
+            .filter(|&(area, perimeter)| area >= tolerance * perimeter)
+            .map(|(area, _)| area)
+            .sum()
     }
 
-    // Same
-    pub(crate) fn bounding_box(self) -> Rect {
-        let mut bbox: Option<Rect> = None;
-        for seg in self {
-            let seg_bb = seg.bounding_box();
-            if let Some(bb) = bbox {
-                bbox = Some(bb.union(seg_bb));
-            } else {
-                bbox = Some(seg_bb)
+    /// Append another path, making the join continuous.
+    ///
+    /// The first subpath of `other` is attached to the end of this path: its
+    /// `MoveTo` is dropped, and its first segment starts at the current end
+    /// point instead. For [`Continuity::G1`], the first control handle of
+    /// that segment is then rotated about the join to continue the incoming
+    /// tangent, keeping its length. For [`Continuity::G2`], the second
+    /// control handle is also moved perpendicular to the tangent so that the
+    /// curvature matches. A line or quadratic segment that needs adjusting is
+    /// first converted to a cubic. Only the first segment of `other` is
+    /// changed; this path is never modified, and the remaining segments of
+    /// `other` are appended as they are.
+    ///
+    /// Nothing is moved for a condition that already holds, so joining
+    /// paths that are already continuous leaves the geometry untouched. The
+    /// join is left as it is if there is no tangent to continue, such as when
+    /// this path is empty, ends with `ClosePath` or a degenerate segment, or
+    /// `other` does not start with a segment; in these cases `other` is
+    /// appended unchanged, with its `MoveTo`.
+    ///
+    /// [`Continuity::G1`]: enum.Continuity.html#variant.G1
+    /// [`Continuity::G2`]: enum.Continuity.html#variant.G2
+    pub fn join_smooth(&mut self, other: &BezPath, continuity: Continuity) {
+        let end = match self.0.last() {
+            None | Some(PathEl::ClosePath) => None,
+            Some(el) => el.points().last().copied(),
+        };
+        let (end, first) = match (end, other.0.first(), other.0.get(1)) {
+            (Some(end), Some(PathEl::MoveTo(_)), Some(&first))
+                if !matches!(first, PathEl::MoveTo(_) | PathEl::ClosePath) =>
+            {
+                (end, first)
+            }
+            _ => {
+                self.0.extend_from_slice(&other.0);
+                return;
+            }
+        };
+        let mut seg = match first {
+            PathEl::LineTo(p1) => PathSeg::Line(Line::new(end, p1)),
+            PathEl::QuadTo(p1, p2) => PathSeg::Quad(QuadBez::new(end, p1, p2)),
+            PathEl::CurveTo(p1, p2, p3) => PathSeg::Cubic(CubicBez::new(end, p1, p2, p3)),
+            _ => unreachable!(),
+        };
+        let incoming = match self.0.last() {
+            Some(PathEl::MoveTo(_)) => None,
+            _ => self.segments().last(),
+        };
+        if let (Some(incoming), true) = (incoming, continuity != Continuity::C0) {
+            if let Some(tan) = end_tangent(incoming) {
+                let aligned = match start_tangent(seg) {
+                    Some(t) => same_direction(t, tan),
+                    None => false,
+                };
+                if !aligned {
+                    let mut c = raise_to_cubic(seg);
+                    let mut len = (c.p1 - c.p0).hypot();
+                    if len == 0.0 {
+                        len = (c.p3 - c.p0).hypot() / 3.0;
+                    }
+                    c.p1 = c.p0 + len * tan;
+                    seg = PathSeg::Cubic(c);
+                }
+                if continuity == Continuity::G2 {
+                    if let (Some(k_in), Some(k_out)) =
+                        (end_curvature(incoming), start_curvature(seg))
+                    {
+                        if (k_in - k_out).abs() > 1e-9 * (k_in.abs() + k_out.abs()) {
+                            // At the start of a cubic with handle length h and unit
+                            // tangent t, the curvature is 2/3 t × (p2 - p1) / h².
+                            let mut c = raise_to_cubic(seg);
+                            let h = (c.p1 - c.p0).hypot();
+                            let current = tan.cross(c.p2 - c.p1);
+                            c.p2 += (1.5 * k_in * h * h - current) * Vec2::new(-tan.y, tan.x);
+                            seg = PathSeg::Cubic(c);
+                        }
+                    }
+                }
             }
         }
-        bbox.unwrap_or_default()
+        self.push(match seg {
+            PathSeg::Line(l) => PathEl::LineTo(l.p1),
+            PathSeg::Quad(q) => PathEl::QuadTo(q.p1, q.p2),
+            PathSeg::Cubic(c) => PathEl::CurveTo(c.p1, c.p2, c.p3),
+        });
+        self.0.extend_from_slice(&other.0[2..]);
     }
-}
 
-impl ParamCurve for PathSeg {
-    fn eval(&self, t: f64) -> Point {
-        match *self {
-            PathSeg::Line(line) => line.eval(t),
-            PathSeg::Quad(quad) => quad.eval(t),
-            PathSeg::Cubic(cubic) => cubic.eval(t),
+    /// Join subpaths that continue where an earlier one ends.
+    ///
+    /// Subpaths are taken in order. An open subpath whose start is within
+    /// `epsilon` of the end of an earlier open subpath is appended to it,
+    /// without its `MoveTo`; if several earlier subpaths end there, the most
+    /// recent is extended. Subpaths are never reversed, so winding numbers
+    /// are preserved. When a subpath returns to within `epsilon` of its
+    /// start, its last point is moved onto the start and it is closed with a
+    /// `ClosePath`, after which nothing more is appended to it. Subpaths
+    /// that are already closed are kept as they are, and subpaths with no
+    /// segments are dropped when they fall on the end of an earlier subpath.
+    pub fn weld(&self, epsilon: f64) -> BezPath {
+        // Each chain is a list of elements starting with `MoveTo`.
+        let mut chains: Vec<(Vec<PathEl>, bool)> = Vec::new();
+        let near = |a: Point, b: Point| (a - b).hypot() <= epsilon;
+        let chain_start = |chain: &[PathEl]| match chain.first() {
+            Some(PathEl::MoveTo(p)) => *p,
+            _ => Point::ORIGIN,
+        };
+        let chain_end = |chain: &[PathEl]| *chain.last().unwrap().points().last().unwrap();
+        let close_if_loop = |chain: &mut Vec<PathEl>, closed: &mut bool| {
+            let start = chain_start(chain);
+            if chain.len() > 1 && near(chain_end(chain), start) {
+                let last = chain.len() - 1;
+                chain[last] = match chain[last] {
+                    PathEl::LineTo(_) => PathEl::LineTo(start),
+                    PathEl::QuadTo(p1, _) => PathEl::QuadTo(p1, start),
+                    PathEl::CurveTo(p1, p2, _) => PathEl::CurveTo(p1, p2, start),
+                    el => el,
+                };
+                chain.push(PathEl::ClosePath);
+                *closed = true;
+            }
+        };
+        let mut subpath: Vec<PathEl> = Vec::new();
+        let mut flush = |subpath: &mut Vec<PathEl>, closed: bool| {
+            if subpath.is_empty() {
+                return;
+            }
+            let start = chain_start(subpath);
+            if !closed {
+                let target = chains
+                    .iter()
+                    .rposition(|(chain, done)| !done && near(chain_end(chain), start));
+                if let Some(i) = target {
+                    let (chain, done) = &mut chains[i];
+                    chain.extend_from_slice(&subpath[1..]);
+                    close_if_loop(chain, done);
+                    subpath.clear();
+                    return;
+                }
+            }
+            let mut chain = std::mem::take(subpath);
+            let mut done = closed;
+            if !done {
+                close_if_loop(&mut chain, &mut done);
+            }
+            chains.push((chain, done));
+        };
+        // Where a segment without a preceding `MoveTo` starts.
+        let mut start = Point::ORIGIN;
+        for &el in &self.0 {
+            match el {
+                PathEl::MoveTo(p) => {
+                    flush(&mut subpath, false);
+                    subpath.push(el);
+                    start = p;
+                }
+                PathEl::ClosePath => {
+                    if !subpath.is_empty() {
+                        subpath.push(el);
+                        flush(&mut subpath, true);
+                    }
+                }
+                _ => {
+                    if subpath.is_empty() {
+                        subpath.push(PathEl::MoveTo(start));
+                    }
+                    subpath.push(el);
+                }
+            }
         }
+        flush(&mut subpath, false);
+        BezPath(chains.into_iter().flat_map(|(chain, _)| chain).collect())
     }
 
-    fn subsegment(&self, range: Range<f64>) -> PathSeg {
-        match *self {
-            PathSeg::Line(line) => PathSeg::Line(line.subsegment(range)),
-            PathSeg::Quad(quad) => PathSeg::Quad(quad.subsegment(range)),
-            PathSeg::Cubic(cubic) => PathSeg::Cubic(cubic.subsegment(range)),
+    /// An equivalent path for the nonzero fill rule, of one meant for the
+    /// even-odd rule.
+    ///
+    /// Each subpath is treated as closed and flattened with the given
+    /// tolerance, to find how deeply it is nested inside the others. The
+    /// outermost subpaths keep their direction, and every other subpath is
+    /// made to run opposite to the subpath it is immediately inside, by
+    /// reversing it if needed. Holes then subtract under the nonzero rule,
+    /// islands inside holes add again, and so on at any depth, so the path
+    /// fills the same region under either rule. A path whose subpaths
+    /// already alternate in this way is returned unchanged.
+    ///
+    /// This assumes that the subpaths don't intersect themselves or each
+    /// other; subpaths that cross can't be fixed by changing directions
+    /// alone. Subpaths that enclose no area are kept as they are, and don't
+    /// count for the nesting of the others.
+    pub fn to_nonzero(&self, tolerance: f64) -> BezPath {
+        let subpaths: Vec<BezPath> = self.subpaths().collect();
+        let polygons: Vec<Polygon> = subpaths
+            .iter()
+            .map(|sub| {
+                let mut rings = sub.to_polyline(tolerance).into_iter();
+                Polygon::from_vec(rings.next().unwrap_or_default())
+            })
+            .collect();
+        let areas: Vec<f64> = polygons.iter().map(|poly| poly.area()).collect();
+        let n = subpaths.len();
+        // The subpaths containing each one.
+        let containers: Vec<Vec<usize>> = (0..n)
+            .map(|i| match polygons[i].points().first() {
+                Some(&probe) if areas[i] != 0.0 => (0..n)
+                    .filter(|&j| j != i && areas[j] != 0.0 && polygons[j].winding(probe) != 0)
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by_key(|&i| containers[i].len());
+        // Whether each subpath ends up with positive area.
+        let mut positive: Vec<bool> = areas.iter().map(|&a| a > 0.0).collect();
+        let mut flip = vec![false; n];
+        for &i in &order {
+            if areas[i] == 0.0 {
+                continue;
+            }
+            // The innermost container is the one inside all the others.
+            let parent = containers[i]
+                .iter()
+                .copied()
+                .max_by_key(|&j| containers[j].len());
+            if let Some(parent) = parent {
+                let want = !positive[parent];
+                flip[i] = positive[i] != want;
+                positive[i] = want;
+            }
+        }
+        let mut result = BezPath::new();
+        for (sub, flip) in subpaths.iter().zip(flip) {
+            if flip {
+                result.0.extend(reversed_subpath(sub).0);
+            } else {
+                result.0.extend_from_slice(sub.elements());
+            }
         }
+        result
     }
 }
 
-impl ParamCurveArclen for PathSeg {
-    fn arclen(&self, accuracy: f64) -> f64 {
-        match *self {
-            PathSeg::Line(line) => line.arclen(accuracy),
-            PathSeg::Quad(quad) => quad.arclen(accuracy),
-            PathSeg::Cubic(cubic) => cubic.arclen(accuracy),
+/// A subpath traversed in the opposite direction.
+///
+/// The reversed path starts at the same point, and is closed if the original
+/// is.
+/// Append a shortest edit script from `a` to `b` to `edits`, where `b`
+/// starts at `index` in the path being edited.
+///
+/// This splits `a` in half and finds where an optimal script splits `b`
+/// from one row of edit distances from each end, then recurses on both
+/// halves, so it only ever holds a row at a time.
+fn diff_into(a: &[PathEl], b: &[PathEl], index: usize, edits: &mut Vec<PathEdit>) {
+    let (n, m) = (a.len(), b.len());
+    if n == 0 {
+        edits.extend(
+            b.iter()
+                .enumerate()
+                .map(|(j, &el)| PathEdit::Insert(index + j, el)),
+        );
+    } else if m == 0 {
+        edits.extend((0..n).map(|_| PathEdit::Delete(index)));
+    } else if n == 1 {
+        // Keep the element if `b` has it, otherwise replace it with `b[0]`.
+        let keep = b.iter().position(|el| *el == a[0]);
+        for (j, &el) in b.iter().enumerate() {
+            if Some(j) == keep {
+                continue;
+            } else if j == 0 && keep.is_none() {
+                edits.push(PathEdit::Replace(index, el));
+            } else {
+                edits.push(PathEdit::Insert(index + j, el));
+            }
         }
+    } else {
+        let mid = n / 2;
+        let fwd = edit_row(mid, m, |i, j| a[i] == b[j]);
+        let bwd = edit_row(n - mid, m, |i, j| a[n - 1 - i] == b[m - 1 - j]);
+        // `fwd[j]` is the distance from `a[..mid]` to `b[..j]`, and
+        // `bwd[m - j]` the distance from `a[mid..]` to `b[j..]`.
+        let split = (0..=m).min_by_key(|&j| fwd[j] + bwd[m - j]).unwrap();
+        diff_into(&a[..mid], &b[..split], index, edits);
+        diff_into(&a[mid..], &b[split..], index + split, edits);
     }
 }
 
-impl ParamCurveArea for PathSeg {
-    fn signed_area(&self) -> f64 {
-        match *self {
-            PathSeg::Line(line) => line.signed_area(),
-            PathSeg::Quad(quad) => quad.signed_area(),
-            PathSeg::Cubic(cubic) => cubic.signed_area(),
+/// The edit distances from a sequence of length `n` to every prefix of a
+/// sequence of length `m`, given equality of their elements.
+fn edit_row(n: usize, m: usize, eq: impl Fn(usize, usize) -> bool) -> Vec<usize> {
+    let mut row: Vec<usize> = (0..=m).collect();
+    for i in 0..n {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for j in 0..m {
+            let next = if eq(i, j) {
+                diag
+            } else {
+                1 + diag.min(row[j]).min(row[j + 1])
+            };
+            diag = row[j + 1];
+            row[j + 1] = next;
         }
     }
+    row
 }
 
-impl ParamCurveNearest for PathSeg {
-    fn nearest(&self, p: Point, accuracy: f64) -> (f64, f64) {
-        match *self {
-            PathSeg::Line(line) => line.nearest(p, accuracy),
-            PathSeg::Quad(quad) => quad.nearest(p, accuracy),
-            PathSeg::Cubic(cubic) => cubic.nearest(p, accuracy),
+fn reversed_subpath(sub: &BezPath) -> BezPath {
+    let closed = matches!(sub.elements().last(), Some(PathEl::ClosePath));
+    let segs: Vec<PathSeg> = sub.segments().collect();
+    let mut result = BezPath::new();
+    let start = match (segs.first(), segs.last()) {
+        (Some(first), Some(last)) => {
+            if closed {
+                first.start()
+            } else {
+                last.end()
+            }
+        }
+        _ => return sub.clone(),
+    };
+    result.move_to(start);
+    for seg in segs.iter().rev() {
+        result.push(match seg.reverse() {
+            PathSeg::Line(l) => PathEl::LineTo(l.p1),
+            PathSeg::Quad(q) => PathEl::QuadTo(q.p1, q.p2),
+            PathSeg::Cubic(c) => PathEl::CurveTo(c.p1, c.p2, c.p3),
+        });
+    }
+    if closed {
+        // The line back to the start is implied by the `ClosePath`.
+        if let Some(PathEl::LineTo(p)) = result.0.last() {
+            if *p == start {
+                result.0.pop();
+            }
         }
+        result.close_path();
     }
+    result
 }
 
-impl ParamCurveExtrema for PathSeg {
-    fn extrema(&self) -> ArrayVec<[f64; MAX_EXTREMA]> {
-        match *self {
-            PathSeg::Line(line) => line.extrema(),
-            PathSeg::Quad(quad) => quad.extrema(),
-            PathSeg::Cubic(cubic) => cubic.extrema(),
-        }
+impl FromIterator<PathEl> for BezPath {
+    fn from_iter<T: IntoIterator<Item = PathEl>>(iter: T) -> Self {
+        let el_vec: Vec<_> = iter.into_iter().collect();
+        BezPath::from_vec(el_vec)
     }
 }
 
-impl PathSeg {
-    /// Returns a new `PathSeg` describing the same path as `self`, but with
-    /// the points reversed.
-    pub fn reverse(&self) -> PathSeg {
-        match self {
-            PathSeg::Line(Line { p0, p1 }) => PathSeg::Line(Line::new(*p1, *p0)),
-            PathSeg::Quad(q) => PathSeg::Quad(QuadBez::new(q.p2, q.p1, q.p0)),
-            PathSeg::Cubic(c) => PathSeg::Cubic(CubicBez::new(c.p3, c.p2, c.p1, c.p0)),
-        }
+/// Allow iteration over references to `BezPath`.
+///
+/// Note: the semantics are slightly different than simply iterating over the
+/// slice, as it returns `PathEl` items, rather than references.
+impl<'a> IntoIterator for &'a BezPath {
+    type Item = PathEl;
+    type IntoIter = std::iter::Cloned<std::slice::Iter<'a, PathEl>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements().iter().cloned()
     }
+}
 
-    /// Convert this segment to a cubic bezier.
-    pub fn to_cubic(&self) -> CubicBez {
-        match *self {
-            PathSeg::Line(Line { p0, p1 }) => CubicBez::new(p0, p0, p1, p1),
-            PathSeg::Cubic(c) => c,
-            PathSeg::Quad(q) => q.raise(),
-        }
+impl IntoIterator for BezPath {
+    type Item = PathEl;
+    type IntoIter = std::vec::IntoIter<PathEl>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
     }
+}
 
-    // Assumes split at extrema.
-    fn winding_inner(&self, p: Point) -> i32 {
-        let start = self.start();
-        let end = self.end();
-        let sign = if end.y > start.y {
-            if p.y < start.y || p.y >= end.y {
-                return 0;
-            }
-            -1
-        } else if end.y < start.y {
-            if p.y < end.y || p.y >= start.y {
-                return 0;
+impl Extend<PathEl> for BezPath {
+    fn extend<I: IntoIterator<Item = PathEl>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+/// Proportion of tolerance budget that goes to cubic to quadratic conversion.
+pub(crate) const TO_QUAD_TOL: f64 = 0.1;
+
+/// Flatten the path, invoking the callback repeatedly.
+///
+/// See [`BezPath::flatten`](struct.BezPath.html#method.flatten) for more discussion.
+/// This signature is a bit more general, allowing flattening of `&[PathEl]` slices
+/// and other iterators yielding `PathEl`.
+pub fn flatten(
+    path: impl IntoIterator<Item = PathEl>,
+    tolerance: f64,
+    mut callback: impl FnMut(PathEl),
+) {
+    let sqrt_tol = tolerance.sqrt();
+    let mut start_pt = None;
+    let mut last_pt = None;
+    let mut quad_buf = Vec::new();
+    for el in path {
+        match el {
+            PathEl::MoveTo(p) => {
+                start_pt = Some(p);
+                last_pt = Some(p);
+                callback(PathEl::MoveTo(p));
             }
-            1
-        } else {
-            return 0;
-        };
-        match *self {
-            PathSeg::Line(_line) => {
-                if p.x < start.x.min(end.x) {
-                    return 0;
-                }
-                if p.x >= start.x.max(end.x) {
-                    return sign;
-                }
-                // line equation ax + by = c
-                let a = end.y - start.y;
-                let b = start.x - end.x;
-                let c = a * start.x + b * start.y;
-                if (a * p.x + b * p.y - c) * (sign as f64) >= 0.0 {
-                    sign
-                } else {
-                    0
-                }
+            PathEl::LineTo(p) => {
+                last_pt = Some(p);
+                callback(PathEl::LineTo(p));
             }
-            PathSeg::Quad(quad) => {
-                let p1 = quad.p1;
-                if p.x < start.x.min(end.x).min(p1.x) {
-                    return 0;
-                }
-                if p.x >= start.x.max(end.x).max(p1.x) {
-                    return sign;
-                }
-                let a = end.y - 2.0 * p1.y + start.y;
-                let b = 2.0 * (p1.y - start.y);
-                let c = start.y - p.y;
-                for t in solve_quadratic(c, b, a) {
-                    if t >= 0.0 && t <= 1.0 {
-                        let x = quad.eval(t).x;
-                        if p.x >= x {
-                            return sign;
-                        } else {
-                            return 0;
-                        }
+            PathEl::QuadTo(p1, p2) => {
+                if let Some(p0) = last_pt {
+                    let q = QuadBez::new(p0, p1, p2);
+                    let params = q.estimate_subdiv(sqrt_tol);
+                    let n = params.segment_count(sqrt_tol);
+                    let step = 1.0 / (n as f64);
+                    for i in 1..n {
+                        let u = (i as f64) * step;
+                        let t = q.determine_subdiv_t(&params, u);
+                        let p = q.eval(t);
+                        callback(PathEl::LineTo(p));
                     }
+                    callback(PathEl::LineTo(p2));
                 }
-                0
+                last_pt = Some(p2);
             }
-            PathSeg::Cubic(cubic) => {
-                let p1 = cubic.p1;
-                let p2 = cubic.p2;
-                if p.x < start.x.min(end.x).min(p1.x).min(p2.x) {
-                    return 0;
-                }
-                if p.x >= start.x.max(end.x).max(p1.x).max(p2.x) {
-                    return sign;
-                }
-                let a = end.y - 3.0 * p2.y + 3.0 * p1.y - start.y;
-                let b = 3.0 * (p2.y - 2.0 * p1.y + start.y);
-                let c = 3.0 * (p1.y - start.y);
-                let d = start.y - p.y;
-                for t in solve_cubic(d, c, b, a) {
-                    if t >= 0.0 && t <= 1.0 {
-                        let x = cubic.eval(t).x;
-                        if p.x >= x {
-                            return sign;
-                        } else {
-                            return 0;
-                        }
-                    }
+            PathEl::CurveTo(p1, p2, p3) => {
+                if let Some(p0) = last_pt {
+                    let c = CubicBez::new(p0, p1, p2, p3);
+
+                    // Subdivide into quadratics, and estimate the number of
+                    // subdivisions required for each, summing to arrive at an
+                    // estimate for the number of subdivisions for the cubic.
+                    // Then output the points of subdivisions that fall within
+                    // each quadratic.
+                    let n = c.flatten_quads(tolerance, &mut quad_buf);
+                    flatten_quad_vertices(&quad_buf, n, |p, _| callback(PathEl::LineTo(p)));
+                    callback(PathEl::LineTo(p3));
                 }
-                0
+                last_pt = Some(p3);
+            }
+            PathEl::ClosePath => {
+                // The next segment, if there is no `MoveTo`, starts from the
+                // start of this subpath.
+                last_pt = start_pt;
+                callback(PathEl::ClosePath);
             }
         }
     }
+}
 
-    /// Compute the winding number contribution of a single segment.
-    ///
-    /// Cast a ray to the left and count intersections.
-    fn winding(&self, p: Point) -> i32 {
-        self.extrema_ranges()
-            .into_iter()
-            .map(|range| self.subsegment(range).winding_inner(p))
-            .sum()
+impl PathEl {
+    /// The kind of this element.
+    #[inline]
+    pub const fn verb(&self) -> PathVerb {
+        match self {
+            PathEl::MoveTo(_) => PathVerb::MoveTo,
+            PathEl::LineTo(_) => PathVerb::LineTo,
+            PathEl::QuadTo(..) => PathVerb::QuadTo,
+            PathEl::CurveTo(..) => PathVerb::CurveTo,
+            PathEl::ClosePath => PathVerb::ClosePath,
+        }
     }
 
-    /// Compute intersections against a line.
-    ///
-    /// Returns a vector of the intersections. For each intersection, the `t` value of the
-    /// segment and line are given.
-    ///
-    /// Note: This test is designed to be inclusive of points near the endpoints of
-    /// the segment. This is so that testing a line against multiple contiguous segments
-    /// of a path will be guaranteed to catch at least one of them. In such cases, use
-    /// higher level logic to coalesce the hits (the `t` value may be slightly outside
-    /// the range of 0..1).
+    /// The points carried by this element.
     ///
-    /// # Examples
+    /// These are the end point, preceded by any control points, in the order
+    /// they appear in the element. `ClosePath` has no points.
+    pub fn points(&self) -> ArrayVec<[Point; 3]> {
+        let mut result = ArrayVec::new();
+        match *self {
+            PathEl::MoveTo(p) | PathEl::LineTo(p) => result.push(p),
+            PathEl::QuadTo(p1, p2) => {
+                result.push(p1);
+                result.push(p2);
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                result.push(p1);
+                result.push(p2);
+                result.push(p3);
+            }
+            PathEl::ClosePath => (),
+        }
+        result
+    }
+
+    /// Apply a function to each point of this element.
     ///
-    /// ```
-    /// # use kurbo::*;
-    /// let seg = PathSeg::Line(Line::new((0.0, 0.0), (2.0, 0.0)));
-    /// let line = Line::new((1.0, 2.0), (1.0, -2.0));
-    /// let intersection = seg.intersect_line(line);
-    /// assert_eq!(intersection.len(), 1);
-    /// let intersection = intersection[0];
-    /// assert_eq!(intersection.segment_t, 0.5);
-    /// assert_eq!(intersection.line_t, 0.5);
+    /// The variant is preserved, and the function is called on the points in
+    /// the same order as [`points`] returns them.
     ///
-    /// let point = seg.eval(intersection.segment_t);
-    /// assert_eq!(point, Point::new(1.0, 0.0));
-    /// ```
-    pub fn intersect_line(&self, line: Line) -> ArrayVec<[LineIntersection; 3]> {
-        const EPSILON: f64 = 1e-9;
-        let p0 = line.p0;
-        let p1 = line.p1;
-        let dx = p1.x - p0.x;
-        let dy = p1.y - p0.y;
-        let mut result = ArrayVec::new();
+    /// [`points`]: #method.points
+    pub fn map_points(self, mut f: impl FnMut(Point) -> Point) -> PathEl {
         match self {
-            PathSeg::Line(l) => {
-                let det = dx * (l.p1.y - l.p0.y) - dy * (l.p1.x - l.p0.x);
-                if det.abs() < EPSILON {
-                    // Lines are coincident (or nearly so).
-                    return result;
-                }
-                let t = dx * (p0.y - l.p0.y) - dy * (p0.x - l.p0.x);
-                // t = position on self
-                let t = t / det;
-                if t >= -EPSILON && t <= 1.0 + EPSILON {
-                    // u = position on probe line
-                    let u =
-                        (l.p0.x - p0.x) * (l.p1.y - l.p0.y) - (l.p0.y - p0.y) * (l.p1.x - l.p0.x);
-                    let u = u / det;
-                    if u >= 0.0 && u <= 1.0 {
-                        result.push(LineIntersection::new(u, t));
-                    }
-                }
-            }
-            PathSeg::Quad(q) => {
-                // The basic technique here is to determine x and y as a quadratic polynomial
-                // as a function of t. Then plug those values into the line equation for the
-                // probe line (giving a sort of signed distance from the probe line) and solve
-                // that for t.
-                let (px0, px1, px2) = quadratic_bez_coefs(q.p0.x, q.p1.x, q.p2.x);
-                let (py0, py1, py2) = quadratic_bez_coefs(q.p0.y, q.p1.y, q.p2.y);
-                let c0 = dy * (px0 - p0.x) - dx * (py0 - p0.y);
-                let c1 = dy * px1 - dx * py1;
-                let c2 = dy * px2 - dx * py2;
-                let invlen2 = (dx * dx + dy * dy).recip();
-                for t in crate::common::solve_quadratic(c0, c1, c2) {
-                    if t >= -EPSILON && t <= 1.0 + EPSILON {
-                        let x = px0 + t * px1 + t * t * px2;
-                        let y = py0 + t * py1 + t * t * py2;
-                        let u = ((x - p0.x) * dx + (y - p0.y) * dy) * invlen2;
-                        if u >= 0.0 && u <= 1.0 {
-                            result.push(LineIntersection::new(u, t));
-                        }
-                    }
-                }
+            PathEl::MoveTo(p) => PathEl::MoveTo(f(p)),
+            PathEl::LineTo(p) => PathEl::LineTo(f(p)),
+            PathEl::QuadTo(p1, p2) => {
+                let p1 = f(p1);
+                PathEl::QuadTo(p1, f(p2))
             }
-            PathSeg::Cubic(c) => {
-                // Same technique as above, but cubic polynomial.
-                let (px0, px1, px2, px3) = cubic_bez_coefs(c.p0.x, c.p1.x, c.p2.x, c.p3.x);
-                let (py0, py1, py2, py3) = cubic_bez_coefs(c.p0.y, c.p1.y, c.p2.y, c.p3.y);
-                let c0 = dy * (px0 - p0.x) - dx * (py0 - p0.y);
-                let c1 = dy * px1 - dx * py1;
-                let c2 = dy * px2 - dx * py2;
-                let c3 = dy * px3 - dx * py3;
-                let invlen2 = (dx * dx + dy * dy).recip();
-                for t in crate::common::solve_cubic(c0, c1, c2, c3) {
-                    if t >= -EPSILON && t <= 1.0 + EPSILON {
-                        let x = px0 + t * px1 + t * t * px2 + t * t * t * px3;
-                        let y = py0 + t * py1 + t * t * py2 + t * t * t * py3;
-                        let u = ((x - p0.x) * dx + (y - p0.y) * dy) * invlen2;
-                        if u >= 0.0 && u <= 1.0 {
-                            result.push(LineIntersection::new(u, t));
-                        }
-                    }
-                }
+            PathEl::CurveTo(p1, p2, p3) => {
+                let p1 = f(p1);
+                let p2 = f(p2);
+                PathEl::CurveTo(p1, p2, f(p3))
             }
+            PathEl::ClosePath => PathEl::ClosePath,
         }
-        result
     }
 }
 
-impl LineIntersection {
-    fn new(line_t: f64, segment_t: f64) -> Self {
-        LineIntersection { line_t, segment_t }
+impl Mul<PathEl> for Affine {
+    type Output = PathEl;
+
+    fn mul(self, other: PathEl) -> PathEl {
+        match other {
+            PathEl::MoveTo(p) => PathEl::MoveTo(self * p),
+            PathEl::LineTo(p) => PathEl::LineTo(self * p),
+            PathEl::QuadTo(p1, p2) => PathEl::QuadTo(self * p1, self * p2),
+            PathEl::CurveTo(p1, p2, p3) => PathEl::CurveTo(self * p1, self * p2, self * p3),
+            PathEl::ClosePath => PathEl::ClosePath,
+        }
     }
 }
 
-// Return polynomial coefficients given cubic bezier coordinates.
-fn quadratic_bez_coefs(x0: f64, x1: f64, x2: f64) -> (f64, f64, f64) {
-    let p0 = x0;
-    let p1 = 2.0 * x1 - 2.0 * x0;
-    let p2 = x2 - 2.0 * x1 + x0;
-    (p0, p1, p2)
-}
+impl Mul<PathSeg> for Affine {
+    type Output = PathSeg;
 
-// Return polynomial coefficients given cubic bezier coordinates.
-fn cubic_bez_coefs(x0: f64, x1: f64, x2: f64, x3: f64) -> (f64, f64, f64, f64) {
-    let p0 = x0;
-    let p1 = 3.0 * x1 - 3.0 * x0;
-    let p2 = 3.0 * x2 - 6.0 * x1 + 3.0 * x0;
-    let p3 = x3 - 3.0 * x2 + 3.0 * x1 - x0;
-    (p0, p1, p2, p3)
+    fn mul(self, other: PathSeg) -> PathSeg {
+        match other {
+            PathSeg::Line(line) => PathSeg::Line(self * line),
+            PathSeg::Quad(quad) => PathSeg::Quad(self * quad),
+            PathSeg::Cubic(cubic) => PathSeg::Cubic(self * cubic),
+        }
+    }
 }
 
-impl From<CubicBez> for PathSeg {
+impl Mul<BezPath> for Affine {
+    type Output = BezPath;
+
+    fn mul(self, other: BezPath) -> BezPath {
+        BezPath(other.0.iter().map(|&el| self * el).collect())
+    }
+}
+
+impl Mul<&BezPath> for Affine {
+    type Output = BezPath;
+
+    fn mul(self, other: &BezPath) -> BezPath {
+        BezPath(other.0.iter().map(|&el| self * el).collect())
+    }
+}
+
+impl Mul<PathEl> for TranslateScale {
+    type Output = PathEl;
+
+    fn mul(self, other: PathEl) -> PathEl {
+        match other {
+            PathEl::MoveTo(p) => PathEl::MoveTo(self * p),
+            PathEl::LineTo(p) => PathEl::LineTo(self * p),
+            PathEl::QuadTo(p1, p2) => PathEl::QuadTo(self * p1, self * p2),
+            PathEl::CurveTo(p1, p2, p3) => PathEl::CurveTo(self * p1, self * p2, self * p3),
+            PathEl::ClosePath => PathEl::ClosePath,
+        }
+    }
+}
+
+impl Mul<PathSeg> for TranslateScale {
+    type Output = PathSeg;
+
+    fn mul(self, other: PathSeg) -> PathSeg {
+        match other {
+            PathSeg::Line(line) => PathSeg::Line(self * line),
+            PathSeg::Quad(quad) => PathSeg::Quad(self * quad),
+            PathSeg::Cubic(cubic) => PathSeg::Cubic(self * cubic),
+        }
+    }
+}
+
+impl Mul<BezPath> for TranslateScale {
+    type Output = BezPath;
+
+    fn mul(self, other: BezPath) -> BezPath {
+        BezPath(other.0.iter().map(|&el| self * el).collect())
+    }
+}
+
+impl Mul<&BezPath> for TranslateScale {
+    type Output = BezPath;
+
+    fn mul(self, other: &BezPath) -> BezPath {
+        BezPath(other.0.iter().map(|&el| self * el).collect())
+    }
+}
+
+/// An iterator that lazily applies an affine transform to path elements.
+///
+/// This struct is created by the
+/// [`Affine::transform_elements`](struct.Affine.html#method.transform_elements)
+/// method.
+pub struct Transformed<I: Iterator<Item = PathEl>> {
+    affine: Affine,
+    elements: I,
+}
+
+impl<I: Iterator<Item = PathEl>> Transformed<I> {
+    pub(crate) fn new(affine: Affine, elements: I) -> Transformed<I> {
+        Transformed { affine, elements }
+    }
+}
+
+impl<I: Iterator<Item = PathEl>> Iterator for Transformed<I> {
+    type Item = PathEl;
+
+    #[inline]
+    fn next(&mut self) -> Option<PathEl> {
+        self.elements.next().map(|el| self.affine * el)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
+
+/// Transform an iterator over path elements into one over path
+/// segments.
+///
+/// See also [`BezPath::segments`](struct.BezPath.html#method.segments).
+/// This signature is a bit more general, allowing `&[PathEl]` slices
+/// and other iterators yielding `PathEl`.
+///
+/// A `ClosePath` yields the closing line, if the subpath does not already
+/// end at its start, and a segment following it without a `MoveTo` starts
+/// from the start of the closed subpath.
+pub fn segments<I>(elements: I) -> Segments<I::IntoIter>
+where
+    I: IntoIterator<Item = PathEl>,
+{
+    Segments {
+        elements: elements.into_iter(),
+        start_last: None,
+    }
+}
+
+/// The signed area of a slice of path segments.
+///
+/// The segments are assumed to form one or more closed loops, each ending
+/// where it started; they need not be in any particular order. Gaps are not
+/// closed implicitly, so an open chain gives an area that depends on the
+/// position of the origin.
+///
+/// See also [`segments`](fn.segments.html), which produces segments from
+/// path elements.
+pub fn segments_area(segs: &[PathSeg]) -> f64 {
+    segs.iter().map(|seg| seg.signed_area()).sum()
+}
+
+/// The winding number of a point with respect to a slice of path segments.
+///
+/// As with [`segments_area`](fn.segments_area.html), the segments are assumed
+/// to form closed loops. With multiple loops, the winding numbers of the
+/// loops are summed.
+pub fn segments_winding(segs: &[PathSeg], pt: Point) -> i32 {
+    segs.iter().map(|seg| seg.winding(pt)).sum()
+}
+
+/// The signed area of the shape described by a sequence of path elements.
+///
+/// This computes the same area as [`Shape::area`] for a [`BezPath`] with
+/// these elements, treating each subpath as closed, but consumes the
+/// elements as they are produced, without collecting them. Any iterator of
+/// elements may be used, including the one from [`Shape::path_elements`].
+///
+/// As for a slice of elements, the first element must not be a `ClosePath`.
+///
+/// [`Shape::area`]: trait.Shape.html#tymethod.area
+/// [`Shape::path_elements`]: trait.Shape.html#tymethod.path_elements
+/// [`BezPath`]: struct.BezPath.html
+pub fn area_of<I>(elements: I) -> f64
+where
+    I: IntoIterator<Item = PathEl>,
+{
+    segments(CloseSubpaths::new(elements.into_iter())).area()
+}
+
+/// The winding number of a point with respect to a sequence of path
+/// elements.
+///
+/// Like [`area_of`], this agrees with [`Shape::winding`] for a [`BezPath`]
+/// with these elements, without collecting them.
+///
+/// [`area_of`]: fn.area_of.html
+/// [`Shape::winding`]: trait.Shape.html#tymethod.winding
+/// [`BezPath`]: struct.BezPath.html
+pub fn winding_of<I>(elements: I, pt: Point) -> i32
+where
+    I: IntoIterator<Item = PathEl>,
+{
+    segments(CloseSubpaths::new(elements.into_iter())).winding(pt)
+}
+
+/// An iterator that transforms path elements to path segments.
+///
+/// This struct is created by the [`segments`](fn.segments.html) function.
+pub struct Segments<I: Iterator<Item = PathEl>> {
+    elements: I,
+    start_last: Option<(Point, Point)>,
+}
+
+impl<I: Iterator<Item = PathEl>> Iterator for Segments<I> {
+    type Item = PathSeg;
+
+    fn next(&mut self) -> Option<PathSeg> {
+        for el in self.elements.by_ref() {
+            // We first need to check whether this is the first
+            // path element we see to fill in the start position.
+            let (start, last) = self.start_last.get_or_insert_with(|| {
+                let point = match el {
+                    PathEl::MoveTo(p) => p,
+                    PathEl::LineTo(p) => p,
+                    PathEl::QuadTo(_, p2) => p2,
+                    PathEl::CurveTo(_, _, p3) => p3,
+                    PathEl::ClosePath => panic!("Can't start a segment on a ClosePath"),
+                };
+                (point, point)
+            });
+
+            return Some(match el {
+                PathEl::MoveTo(p) => {
+                    *start = p;
+                    *last = p;
+                    continue;
+                }
+                PathEl::LineTo(p) => PathSeg::Line(Line::new(mem::replace(last, p), p)),
+                PathEl::QuadTo(p1, p2) => {
+                    PathSeg::Quad(QuadBez::new(mem::replace(last, p2), p1, p2))
+                }
+                PathEl::CurveTo(p1, p2, p3) => {
+                    PathSeg::Cubic(CubicBez::new(mem::replace(last, p3), p1, p2, p3))
+                }
+                PathEl::ClosePath => {
+                    if *last != *start {
+                        PathSeg::Line(Line::new(mem::replace(last, *start), *start))
+                    } else {
+                        continue;
+                    }
+                }
+            });
+        }
+
+        None
+    }
+}
+
+/// An iterator adding a `ClosePath` to the end of every open subpath.
+struct CloseSubpaths<I: Iterator<Item = PathEl>> {
+    elements: I,
+    /// Whether the current subpath has segments and is not yet closed.
+    open: bool,
+    pending: Option<PathEl>,
+}
+
+impl<I: Iterator<Item = PathEl>> CloseSubpaths<I> {
+    fn new(elements: I) -> CloseSubpaths<I> {
+        CloseSubpaths {
+            elements,
+            open: false,
+            pending: None,
+        }
+    }
+}
+
+impl<I: Iterator<Item = PathEl>> Iterator for CloseSubpaths<I> {
+    type Item = PathEl;
+
+    fn next(&mut self) -> Option<PathEl> {
+        if let Some(el) = self.pending.take() {
+            return Some(el);
+        }
+        match self.elements.next() {
+            Some(el @ PathEl::MoveTo(_)) if self.open => {
+                self.open = false;
+                self.pending = Some(el);
+                Some(PathEl::ClosePath)
+            }
+            Some(el) => {
+                self.open = !matches!(el, PathEl::MoveTo(_) | PathEl::ClosePath);
+                Some(el)
+            }
+            None if self.open => {
+                self.open = false;
+                Some(PathEl::ClosePath)
+            }
+            None => None,
+        }
+    }
+}
+
+/// An iterator splitting path elements into subpaths.
+struct Subpaths<I: Iterator<Item = PathEl>> {
+    elements: std::iter::Peekable<I>,
+    /// The start of the last subpath, where a segment without a `MoveTo`
+    /// begins.
+    start: Option<Point>,
+}
+
+impl<I: Iterator<Item = PathEl>> Subpaths<I> {
+    fn new(elements: I) -> Subpaths<I> {
+        Subpaths {
+            elements: elements.peekable(),
+            start: None,
+        }
+    }
+}
+
+impl<I: Iterator<Item = PathEl>> Iterator for Subpaths<I> {
+    type Item = BezPath;
+
+    fn next(&mut self) -> Option<BezPath> {
+        let mut path = BezPath::new();
+        // A `ClosePath` that does not end a subpath is skipped.
+        let first = loop {
+            match self.elements.next()? {
+                PathEl::ClosePath => continue,
+                el => break el,
+            }
+        };
+        match first {
+            PathEl::MoveTo(p) => {
+                self.start = Some(p);
+                path.move_to(p);
+            }
+            el => match self.start {
+                Some(start) => {
+                    path.move_to(start);
+                    path.push(el);
+                }
+                None => {
+                    let p = *el.points().last().unwrap();
+                    self.start = Some(p);
+                    path.move_to(p);
+                }
+            },
+        }
+        while let Some(&el) = self.elements.peek() {
+            if let PathEl::MoveTo(_) = el {
+                break;
+            }
+            self.elements.next();
+            path.push(el);
+            if let PathEl::ClosePath = el {
+                break;
+            }
+        }
+        Some(path)
+    }
+}
+
+impl<I: Iterator<Item = PathEl>> Segments<I> {
+    // TODO: pub? Or is this subsumed by method of &[PathEl]?
+
+    /// Here, `accuracy` specifies the accuracy for each Bézier segment. At worst,
+    /// the total error is `accuracy` times the number of Bézier segments.
+    pub(crate) fn perimeter(self, accuracy: f64) -> f64 {
+        self.map(|seg| seg.arclen(accuracy)).sum()
+    }
+
+    // Same
+    pub(crate) fn area(self) -> f64 {
+        self.map(|seg| seg.signed_area()).sum()
+    }
+
+    // Same
+    pub(crate) fn winding(self, p: Point) -> i32 {
+        self.map(|seg| seg.winding(p)).sum()
+    }
+
+    // Same
+    pub(crate) fn bounding_box(self) -> Rect {
+        let mut bbox: Option<Rect> = None;
+        for seg in self {
+            let seg_bb = seg.bounding_box();
+            if let Some(bb) = bbox {
+                bbox = Some(bb.union(seg_bb));
+            } else {
+                bbox = Some(seg_bb)
+            }
+        }
+        bbox.unwrap_or_default()
+    }
+}
+
+impl ParamCurve for PathSeg {
+    fn eval(&self, t: f64) -> Point {
+        match *self {
+            PathSeg::Line(line) => line.eval(t),
+            PathSeg::Quad(quad) => quad.eval(t),
+            PathSeg::Cubic(cubic) => cubic.eval(t),
+        }
+    }
+
+    fn subsegment(&self, range: Range<f64>) -> PathSeg {
+        match *self {
+            PathSeg::Line(line) => PathSeg::Line(line.subsegment(range)),
+            PathSeg::Quad(quad) => PathSeg::Quad(quad.subsegment(range)),
+            PathSeg::Cubic(cubic) => PathSeg::Cubic(cubic.subsegment(range)),
+        }
+    }
+}
+
+/// The derivative of a line is constant, so it is represented as a line
+/// from the constant to itself.
+impl ParamCurveDeriv for PathSeg {
+    type DerivResult = PathSeg;
+
+    fn deriv(&self) -> PathSeg {
+        match *self {
+            PathSeg::Line(line) => {
+                let d = (line.p1 - line.p0).to_point();
+                PathSeg::Line(Line::new(d, d))
+            }
+            PathSeg::Quad(quad) => PathSeg::Line(quad.deriv()),
+            PathSeg::Cubic(cubic) => PathSeg::Quad(cubic.deriv()),
+        }
+    }
+}
+
+impl ParamCurveArclen for PathSeg {
+    fn arclen(&self, accuracy: f64) -> f64 {
+        match *self {
+            PathSeg::Line(line) => line.arclen(accuracy),
+            PathSeg::Quad(quad) => quad.arclen(accuracy),
+            PathSeg::Cubic(cubic) => cubic.arclen(accuracy),
+        }
+    }
+}
+
+impl ParamCurveArea for PathSeg {
+    fn signed_area(&self) -> f64 {
+        match *self {
+            PathSeg::Line(line) => line.signed_area(),
+            PathSeg::Quad(quad) => quad.signed_area(),
+            PathSeg::Cubic(cubic) => cubic.signed_area(),
+        }
+    }
+}
+
+impl ParamCurveNearest for PathSeg {
+    fn nearest(&self, p: Point, accuracy: f64) -> (f64, f64) {
+        match *self {
+            PathSeg::Line(line) => line.nearest(p, accuracy),
+            PathSeg::Quad(quad) => quad.nearest(p, accuracy),
+            PathSeg::Cubic(cubic) => cubic.nearest(p, accuracy),
+        }
+    }
+}
+
+impl ParamCurveCurvature for PathSeg {
+    fn curvature(&self, t: f64) -> f64 {
+        match *self {
+            PathSeg::Line(line) => line.curvature(t),
+            PathSeg::Quad(quad) => quad.curvature(t),
+            PathSeg::Cubic(cubic) => cubic.curvature(t),
+        }
+    }
+}
+
+impl ParamCurveExtrema for PathSeg {
+    fn extrema(&self) -> ArrayVec<[f64; MAX_EXTREMA]> {
+        match *self {
+            PathSeg::Line(line) => line.extrema(),
+            PathSeg::Quad(quad) => quad.extrema(),
+            PathSeg::Cubic(cubic) => cubic.extrema(),
+        }
+    }
+}
+
+impl PathSeg {
+    /// The degree of the segment: 1 for a line, 2 for a quadratic and 3 for
+    /// a cubic Bézier.
+    ///
+    /// This is the degree of its representation, even where a curve happens
+    /// to be straight.
+    #[inline]
+    pub const fn degree(&self) -> u8 {
+        match self {
+            PathSeg::Line(_) => 1,
+            PathSeg::Quad(_) => 2,
+            PathSeg::Cubic(_) => 3,
+        }
+    }
+
+    /// Returns a new `PathSeg` describing the same path as `self`, but with
+    /// the points reversed.
+    pub fn reverse(&self) -> PathSeg {
+        match self {
+            PathSeg::Line(Line { p0, p1 }) => PathSeg::Line(Line::new(*p1, *p0)),
+            PathSeg::Quad(q) => PathSeg::Quad(QuadBez::new(q.p2, q.p1, q.p0)),
+            PathSeg::Cubic(c) => PathSeg::Cubic(CubicBez::new(c.p3, c.p2, c.p1, c.p0)),
+        }
+    }
+
+    /// Convert this segment to a cubic bezier.
+    pub fn to_cubic(&self) -> CubicBez {
+        match *self {
+            PathSeg::Line(Line { p0, p1 }) => CubicBez::new(p0, p0, p1, p1),
+            PathSeg::Cubic(c) => c,
+            PathSeg::Quad(q) => q.raise(),
+        }
+    }
+
+    /// The parameters at which the magnitude of the curvature has a local
+    /// maximum.
+    ///
+    /// The curvature is sampled at the ends of 64 equal parameter
+    /// intervals, and each interior sample larger than the one before and
+    /// at least as large as the one after is refined by golden-section
+    /// search until the parameter is known within `accuracy`. Peaks narrower
+    /// than the sample spacing may be missed. The endpoints are not
+    /// reported, and neither is anything for a line, or for a curve with
+    /// constant curvature. A cusp, where the curvature is infinite, is
+    /// reported as a maximum. The result is sorted.
+    pub fn curvature_extrema(&self, accuracy: f64) -> Vec<f64> {
+        let kappa = |t: f64| {
+            let k = self.curvature(t).abs();
+            if k.is_nan() {
+                f64::INFINITY
+            } else {
+                k
+            }
+        };
+        let mut result = Vec::new();
+        if let PathSeg::Line(_) = self {
+            return result;
+        }
+        let n = CURVATURE_SAMPLES;
+        let samples: Vec<f64> = (0..=n).map(|i| kappa(i as f64 / n as f64)).collect();
+        let accuracy = accuracy.max(1e-12);
+        for i in 1..n {
+            if !(samples[i] > samples[i - 1] && samples[i] >= samples[i + 1]) {
+                continue;
+            }
+            // Golden-section search for the maximum in the bracket.
+            let inv_phi = 0.5 * (5f64.sqrt() - 1.0);
+            let (mut a, mut b) = ((i - 1) as f64 / n as f64, (i + 1) as f64 / n as f64);
+            let mut c = b - inv_phi * (b - a);
+            let mut d = a + inv_phi * (b - a);
+            let (mut kc, mut kd) = (kappa(c), kappa(d));
+            while b - a > accuracy {
+                if kc >= kd {
+                    b = d;
+                    d = c;
+                    kd = kc;
+                    c = b - inv_phi * (b - a);
+                    kc = kappa(c);
+                } else {
+                    a = c;
+                    c = d;
+                    kc = kd;
+                    d = a + inv_phi * (b - a);
+                    kd = kappa(d);
+                }
+            }
+            let t = 0.5 * (a + b);
+            if result
+                .last()
+                .map(|&last| t - last > accuracy)
+                .unwrap_or(true)
+            {
+                result.push(t);
+            }
+        }
+        result
+    }
+
+    // Assumes split at extrema.
+    fn winding_inner(&self, p: Point) -> i32 {
+        let start = self.start();
+        let end = self.end();
+        let sign = if end.y > start.y {
+            if p.y < start.y || p.y >= end.y {
+                return 0;
+            }
+            -1
+        } else if end.y < start.y {
+            if p.y < end.y || p.y >= start.y {
+                return 0;
+            }
+            1
+        } else {
+            return 0;
+        };
+        match *self {
+            PathSeg::Line(_line) => {
+                if p.x < start.x.min(end.x) {
+                    return 0;
+                }
+                if p.x >= start.x.max(end.x) {
+                    return sign;
+                }
+                // line equation ax + by = c
+                let a = end.y - start.y;
+                let b = start.x - end.x;
+                let c = a * start.x + b * start.y;
+                if (a * p.x + b * p.y - c) * (sign as f64) <= 0.0 {
+                    sign
+                } else {
+                    0
+                }
+            }
+            PathSeg::Quad(quad) => {
+                let p1 = quad.p1;
+                if p.x < start.x.min(end.x).min(p1.x) {
+                    return 0;
+                }
+                if p.x >= start.x.max(end.x).max(p1.x) {
+                    return sign;
+                }
+                let a = end.y - 2.0 * p1.y + start.y;
+                let b = 2.0 * (p1.y - start.y);
+                let c = start.y - p.y;
+                let t = self.crossing_t(p.y, &solve_quadratic(c, b, a));
+                if p.x >= quad.eval(t).x {
+                    sign
+                } else {
+                    0
+                }
+            }
+            PathSeg::Cubic(cubic) => {
+                let p1 = cubic.p1;
+                let p2 = cubic.p2;
+                if p.x < start.x.min(end.x).min(p1.x).min(p2.x) {
+                    return 0;
+                }
+                if p.x >= start.x.max(end.x).max(p1.x).max(p2.x) {
+                    return sign;
+                }
+                let a = end.y - 3.0 * p2.y + 3.0 * p1.y - start.y;
+                let b = 3.0 * (p2.y - 2.0 * p1.y + start.y);
+                let c = 3.0 * (p1.y - start.y);
+                let d = start.y - p.y;
+                let t = self.crossing_t(p.y, &solve_cubic(d, c, b, a));
+                if p.x >= cubic.eval(t).x {
+                    sign
+                } else {
+                    0
+                }
+            }
+        }
+    }
+
+    /// The parameter at which a segment that is monotonic in y reaches `y`.
+    ///
+    /// The caller has already established that `y` is within the half-open
+    /// y range of the segment, so a crossing exists. Roots that land slightly
+    /// outside 0..1 due to numerical error (which happens near endpoints and
+    /// where the curve is tangent to the ray) are clamped rather than
+    /// dropped, and if the solver finds nothing usable we fall back to
+    /// bisection.
+    fn crossing_t(&self, y: f64, roots: &[f64]) -> f64 {
+        const ROOT_EPSILON: f64 = 1e-6;
+        let nearest = roots
+            .iter()
+            .map(|&t| (t, (-t).max(t - 1.0).max(0.0)))
+            .filter(|&(_, dist)| dist < ROOT_EPSILON)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        if let Some((t, _)) = nearest {
+            return t.clamp(0.0, 1.0);
+        }
+        let increasing = self.end().y > self.start().y;
+        let mut lo = 0.0;
+        let mut hi = 1.0;
+        for _ in 0..53 {
+            let mid = 0.5 * (lo + hi);
+            if (self.eval(mid).y < y) == increasing {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        0.5 * (lo + hi)
+    }
+
+    /// Compute the winding number contribution of a single segment.
+    ///
+    /// Cast a ray to the left and count intersections. Each monotonic piece
+    /// covers a half-open y range (including its lower end, excluding its
+    /// upper end), so a ray through a vertex shared by two segments is
+    /// counted exactly once, and not at all when the vertex is a local
+    /// extremum in y.
+    fn winding(&self, p: Point) -> i32 {
+        self.extrema_ranges()
+            .into_iter()
+            .map(|range| self.subsegment(range).winding_inner(p))
+            .sum()
+    }
+
+    /// Split the segment into `n` pieces of equal arc length.
+    ///
+    /// The split parameters are found with [`inv_arclen`], so the lengths of
+    /// the pieces agree within about `accuracy`. Consecutive pieces meet at
+    /// the same point, and together they trace the whole segment, starting
+    /// at its start and ending at its end. A segment of zero length is
+    /// split at evenly spaced parameters instead, as is any split where the
+    /// inverse fails. For `n` of zero the result is empty.
+    ///
+    /// [`inv_arclen`]: trait.ParamCurveArclen.html#method.inv_arclen
+    pub fn subdivide_equal_arclen(&self, n: usize, accuracy: f64) -> Vec<PathSeg> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let total = self.arclen(accuracy);
+        let mut ts = Vec::with_capacity(n + 1);
+        ts.push(0.0);
+        for i in 1..n {
+            let frac = i as f64 / n as f64;
+            let t = if total > 0.0 {
+                self.inv_arclen(total * frac, accuracy)
+            } else {
+                frac
+            };
+            // The inverse may fail, as on a curve that doubles back onto
+            // its start.
+            let t = if t.is_finite() { t } else { frac };
+            let prev = *ts.last().unwrap();
+            ts.push(t.clamp(prev, 1.0));
+        }
+        ts.push(1.0);
+        ts.windows(2).map(|w| self.subsegment(w[0]..w[1])).collect()
+    }
+
+    /// Find the point on the segment farthest from the given point.
+    ///
+    /// Returns the parameter and the square of the distance, like
+    /// [`nearest`] does for the nearest point. The endpoints are always
+    /// candidates; for a line the farthest point is one of them.
+    ///
+    /// [`nearest`]: trait.ParamCurveNearest.html#tymethod.nearest
+    pub fn farthest(&self, p: Point, accuracy: f64) -> (f64, f64) {
+        match *self {
+            PathSeg::Line(line) => {
+                let (r0, r1) = ((line.p0 - p).hypot2(), (line.p1 - p).hypot2());
+                if r1 > r0 {
+                    (1.0, r1)
+                } else {
+                    (0.0, r0)
+                }
+            }
+            PathSeg::Quad(quad) => quad.farthest(p),
+            PathSeg::Cubic(cubic) => cubic.farthest(p, accuracy),
+        }
+    }
+
+    /// The contribution of this segment to the winding number of a point.
+    ///
+    /// This counts the crossings of the segment with a ray from the point
+    /// towards positive x: +1 for each crossing where the segment goes
+    /// towards positive y, and -1 for each going towards negative y. As in
+    /// [`Shape::winding`], each piece of the segment that is monotonic in y
+    /// covers a half-open range of y, including its lower end and excluding
+    /// its upper end, so a ray through a vertex is counted once for the two
+    /// segments sharing it. A crossing exactly at the point is not on the
+    /// ray. Summing over the segments of a closed path gives its winding
+    /// number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Line, PathSeg, Point, Rect, Shape};
+    /// let rect = Rect::new(0.0, 0.0, 2.0, 2.0);
+    /// let pt = Point::new(1.0, 1.0);
+    /// let contributions: Vec<i32> = rect
+    ///     .path_segments(0.1)
+    ///     .map(|seg| seg.winding_contribution(pt))
+    ///     .collect();
+    /// assert_eq!(contributions, [0, 1, 0, 0]);
+    /// assert_eq!(contributions.iter().sum::<i32>(), rect.winding(pt));
+    /// ```
+    ///
+    /// [`Shape::winding`]: trait.Shape.html#tymethod.winding
+    pub fn winding_contribution(&self, pt: Point) -> i32 {
+        // Crossings to the right are those of the whole piece, less those to
+        // the left that `winding_inner` counts with the opposite sign.
+        self.extrema_ranges()
+            .into_iter()
+            .map(|range| {
+                let piece = self.subsegment(range);
+                let (y0, y1) = (piece.start().y, piece.end().y);
+                let covered = if y1 > y0 && pt.y >= y0 && pt.y < y1 {
+                    1
+                } else if y1 < y0 && pt.y >= y1 && pt.y < y0 {
+                    -1
+                } else {
+                    0
+                };
+                covered + piece.winding_inner(pt)
+            })
+            .sum()
+    }
+
+    /// Compute intersections against a line.
+    ///
+    /// Returns a vector of the intersections. For each intersection, the `t` value of the
+    /// segment and line are given.
+    ///
+    /// Note: This test is designed to be inclusive of points near the endpoints of
+    /// the segment. This is so that testing a line against multiple contiguous segments
+    /// of a path will be guaranteed to catch at least one of them. In such cases, use
+    /// higher level logic to coalesce the hits (the `t` value may be slightly outside
+    /// the range of 0..1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kurbo::*;
+    /// let seg = PathSeg::Line(Line::new((0.0, 0.0), (2.0, 0.0)));
+    /// let line = Line::new((1.0, 2.0), (1.0, -2.0));
+    /// let intersection = seg.intersect_line(line);
+    /// assert_eq!(intersection.len(), 1);
+    /// let intersection = intersection[0];
+    /// assert_eq!(intersection.segment_t, 0.5);
+    /// assert_eq!(intersection.line_t, 0.5);
+    ///
+    /// let point = seg.eval(intersection.segment_t);
+    /// assert_eq!(point, Point::new(1.0, 0.0));
+    /// ```
+    pub fn intersect_line(&self, line: Line) -> ArrayVec<[LineIntersection; 3]> {
+        const EPSILON: f64 = 1e-9;
+        let p0 = line.p0;
+        let p1 = line.p1;
+        let dx = p1.x - p0.x;
+        let dy = p1.y - p0.y;
+        let mut result = ArrayVec::new();
+        match self {
+            PathSeg::Line(l) => {
+                let det = dx * (l.p1.y - l.p0.y) - dy * (l.p1.x - l.p0.x);
+                if det.abs() < EPSILON {
+                    // Lines are coincident (or nearly so).
+                    return result;
+                }
+                let t = dx * (p0.y - l.p0.y) - dy * (p0.x - l.p0.x);
+                // t = position on self
+                let t = t / det;
+                if (-EPSILON..=1.0 + EPSILON).contains(&t) {
+                    // u = position on probe line
+                    let u =
+                        (l.p0.x - p0.x) * (l.p1.y - l.p0.y) - (l.p0.y - p0.y) * (l.p1.x - l.p0.x);
+                    let u = u / det;
+                    if (0.0..=1.0).contains(&u) {
+                        result.push(LineIntersection::new(u, t));
+                    }
+                }
+            }
+            PathSeg::Quad(q) => {
+                // The basic technique here is to determine x and y as a quadratic polynomial
+                // as a function of t. Then plug those values into the line equation for the
+                // probe line (giving a sort of signed distance from the probe line) and solve
+                // that for t.
+                let (px0, px1, px2) = quadratic_bez_coefs(q.p0.x, q.p1.x, q.p2.x);
+                let (py0, py1, py2) = quadratic_bez_coefs(q.p0.y, q.p1.y, q.p2.y);
+                let c0 = dy * (px0 - p0.x) - dx * (py0 - p0.y);
+                let c1 = dy * px1 - dx * py1;
+                let c2 = dy * px2 - dx * py2;
+                let invlen2 = (dx * dx + dy * dy).recip();
+                for t in crate::common::solve_quadratic(c0, c1, c2) {
+                    if (-EPSILON..=1.0 + EPSILON).contains(&t) {
+                        let x = px0 + t * px1 + t * t * px2;
+                        let y = py0 + t * py1 + t * t * py2;
+                        let u = ((x - p0.x) * dx + (y - p0.y) * dy) * invlen2;
+                        if (0.0..=1.0).contains(&u) {
+                            result.push(LineIntersection::new(u, t));
+                        }
+                    }
+                }
+            }
+            PathSeg::Cubic(c) => {
+                // Same technique as above, but cubic polynomial.
+                let (px0, px1, px2, px3) = cubic_bez_coefs(c.p0.x, c.p1.x, c.p2.x, c.p3.x);
+                let (py0, py1, py2, py3) = cubic_bez_coefs(c.p0.y, c.p1.y, c.p2.y, c.p3.y);
+                let c0 = dy * (px0 - p0.x) - dx * (py0 - p0.y);
+                let c1 = dy * px1 - dx * py1;
+                let c2 = dy * px2 - dx * py2;
+                let c3 = dy * px3 - dx * py3;
+                let invlen2 = (dx * dx + dy * dy).recip();
+                for t in crate::common::solve_cubic(c0, c1, c2, c3) {
+                    if (-EPSILON..=1.0 + EPSILON).contains(&t) {
+                        let x = px0 + t * px1 + t * t * px2 + t * t * t * px3;
+                        let y = py0 + t * py1 + t * t * py2 + t * t * t * py3;
+                        let u = ((x - p0.x) * dx + (y - p0.y) * dy) * invlen2;
+                        if (0.0..=1.0).contains(&u) {
+                            result.push(LineIntersection::new(u, t));
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Find where the segment meets a circle.
+    ///
+    /// Returns the parameters of the points where the segment crosses or
+    /// touches the circle, in increasing order. The squared distance from
+    /// the center along the segment is a polynomial in the parameter, of
+    /// degree 2, 4 or 6, and its roots and turning points in the segment are
+    /// found by bisection between the roots of its derivatives. A turning
+    /// point within `tolerance` of the circle is a tangent contact, and is
+    /// reported even if the segment does not quite reach the circle.
+    ///
+    /// Where the segment stays within `tolerance` of the circle over a
+    /// stretch, as when grazing it, or approximating one of its arcs, the
+    /// stretch is reported once, at its point closest to the circle: the
+    /// first crossing in it if there is one. An end point within
+    /// `tolerance` of the circle is reported like any other point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Circle, Line, ParamCurve, PathSeg, Point};
+    /// let circle = Circle::new((0.0, 0.0), 1.0);
+    /// let seg = PathSeg::Line(Line::new((-2.0, 0.0), (2.0, 0.0)));
+    /// assert_eq!(seg.intersect_circle(circle, 1e-9), vec![0.25, 0.75]);
+    /// let tangent = PathSeg::Line(Line::new((-2.0, 1.0), (2.0, 1.0)));
+    /// assert_eq!(tangent.intersect_circle(circle, 1e-9), vec![0.5]);
+    /// ```
+    pub fn intersect_circle(&self, circle: Circle, tolerance: f64) -> Vec<f64> {
+        circle_contacts(*self, circle, tolerance)
+            .into_iter()
+            .map(|contact| contact.t)
+            .collect()
+    }
+}
+
+/// A stretch of a segment within the tolerance of a circle, for
+/// [`PathSeg::intersect_circle`].
+///
+/// [`PathSeg::intersect_circle`]: enum.PathSeg.html#method.intersect_circle
+struct CircleContact {
+    /// The parameter of the point of the stretch closest to the circle.
+    t: f64,
+    /// The distance from that point to the circle.
+    gap: f64,
+    /// Whether the stretch includes the start of the segment.
+    at_start: bool,
+    /// Whether the stretch includes the end of the segment.
+    at_end: bool,
+}
+
+fn circle_contacts(seg: PathSeg, circle: Circle, tolerance: f64) -> Vec<CircleContact> {
+    let c = circle.center.to_vec2();
+    let radius = circle.radius.abs();
+    // The power basis coefficients of the segment relative to the center.
+    let mut a = [Vec2::ZERO; 4];
+    match seg {
+        PathSeg::Line(Line { p0, p1 }) => {
+            a[0] = p0.to_vec2() - c;
+            a[1] = p1 - p0;
+        }
+        PathSeg::Quad(QuadBez { p0, p1, p2 }) => {
+            a[0] = p0.to_vec2() - c;
+            a[1] = 2.0 * (p1 - p0);
+            a[2] = p2.to_vec2() - 2.0 * p1.to_vec2() + p0.to_vec2();
+        }
+        PathSeg::Cubic(CubicBez { p0, p1, p2, p3 }) => {
+            a[0] = p0.to_vec2() - c;
+            a[1] = 3.0 * (p1 - p0);
+            a[2] = 3.0 * (p2.to_vec2() - 2.0 * p1.to_vec2() + p0.to_vec2());
+            a[3] = p3.to_vec2() - 3.0 * p2.to_vec2() + 3.0 * p1.to_vec2() - p0.to_vec2();
+        }
+    }
+    // The squared distance to the center, less the squared radius.
+    let mut f = [0.0; 7];
+    for i in 0..4 {
+        for j in 0..4 {
+            f[i + j] += a[i].dot(a[j]);
+        }
+    }
+    f[0] -= radius * radius;
+    let deriv: Vec<f64> = (1..7).map(|i| i as f64 * f[i]).collect();
+
+    // The roots and turning points split the segment into pieces on which
+    // the distance is monotonic, so the distance to the circle between two
+    // consecutive events is at most the larger of theirs.
+    let gap = |t: f64| ((seg.eval(t) - circle.center).hypot() - radius).abs();
+    let mut events: Vec<(f64, f64)> = unit_roots(&f).into_iter().map(|t| (t, 0.0)).collect();
+    events.extend(
+        unit_roots(&deriv)
+            .into_iter()
+            .chain([0.0, 1.0].iter().copied())
+            .map(|t| (t, gap(t))),
+    );
+    events.sort_by(|a, b| {
+        a.0.partial_cmp(&b.0)
+            .unwrap()
+            .then(a.1.partial_cmp(&b.1).unwrap())
+    });
+    let mut result: Vec<CircleContact> = Vec::new();
+    let mut in_contact = false;
+    for &(t, d) in &events {
+        if d > tolerance {
+            in_contact = false;
+            continue;
+        }
+        if !in_contact {
+            result.push(CircleContact {
+                t,
+                gap: d,
+                at_start: t == 0.0,
+                at_end: false,
+            });
+            in_contact = true;
+        }
+        let contact = result.last_mut().unwrap();
+        if d < contact.gap {
+            contact.t = t;
+            contact.gap = d;
+        }
+        contact.at_end |= t == 1.0;
+    }
+    result
+}
+
+/// The roots of a polynomial in [0, 1], in increasing order.
+///
+/// The coefficients are in increasing order of degree. The roots of the
+/// derivative split the interval into pieces on which the polynomial is
+/// monotonic, and each piece with a sign change is bisected; a linear
+/// polynomial is solved directly. Roots of even
+/// multiplicity are only found if they are exact zeros; a polynomial which
+/// is identically zero has none.
+fn unit_roots(coeffs: &[f64]) -> Vec<f64> {
+    let degree = match coeffs.iter().rposition(|&c| c != 0.0) {
+        Some(degree) => degree,
+        None => return Vec::new(),
+    };
+    if degree == 1 {
+        let t = -coeffs[0] / coeffs[1];
+        return if (0.0..=1.0).contains(&t) {
+            vec![t]
+        } else {
+            Vec::new()
+        };
+    }
+    let eval = |t: f64| {
+        coeffs[..=degree]
+            .iter()
+            .rev()
+            .fold(0.0, |acc, &c| acc * t + c)
+    };
+    let mut breaks = vec![0.0];
+    if degree > 1 {
+        let deriv: Vec<f64> = (1..=degree).map(|i| i as f64 * coeffs[i]).collect();
+        breaks.extend(
+            unit_roots(&deriv)
+                .into_iter()
+                .filter(|&t| t > 0.0 && t < 1.0),
+        );
+    }
+    breaks.push(1.0);
+    let mut roots: Vec<f64> = Vec::new();
+    let push = |t: f64, roots: &mut Vec<f64>| {
+        if roots.last() != Some(&t) {
+            roots.push(t);
+        }
+    };
+    for w in breaks.windows(2) {
+        let (mut lo, mut hi) = (w[0], w[1]);
+        let (f_lo, f_hi) = (eval(lo), eval(hi));
+        if f_lo == 0.0 {
+            push(lo, &mut roots);
+        } else if f_lo.signum() != f_hi.signum() && f_hi != 0.0 {
+            while hi - lo > f64::EPSILON * hi.max(1e-300) {
+                let mid = 0.5 * (lo + hi);
+                if mid <= lo || mid >= hi {
+                    break;
+                }
+                if eval(mid).signum() == f_lo.signum() {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            push(0.5 * (lo + hi), &mut roots);
+        }
+    }
+    if eval(1.0) == 0.0 {
+        push(1.0, &mut roots);
+    }
+    roots
+}
+
+/// The first and second derivatives at the start of a segment.
+fn start_derivs(seg: PathSeg) -> (Vec2, Vec2) {
+    match seg {
+        PathSeg::Line(l) => (l.p1 - l.p0, Vec2::ZERO),
+        PathSeg::Quad(q) => (
+            q.deriv().eval(0.0).to_vec2(),
+            q.deriv().deriv().eval(0.0).to_vec2(),
+        ),
+        PathSeg::Cubic(c) => (
+            c.deriv().eval(0.0).to_vec2(),
+            c.deriv().deriv().eval(0.0).to_vec2(),
+        ),
+    }
+}
+
+/// The unit tangent at the start of a segment, skipping coincident control
+/// points.
+pub(crate) fn start_tangent(seg: PathSeg) -> Option<Vec2> {
+    let p0 = seg.start();
+    let points: ArrayVec<[Point; 3]> = match seg {
+        PathSeg::Line(l) => [l.p1, l.p1, l.p1].into(),
+        PathSeg::Quad(q) => [q.p1, q.p2, q.p2].into(),
+        PathSeg::Cubic(c) => [c.p1, c.p2, c.p3].into(),
+    };
+    points
+        .iter()
+        .map(|&p| p - p0)
+        .find(|v| v.hypot2() > 0.0)
+        .map(Vec2::normalize)
+}
+
+/// The number of points sampled on each segment in `BezPath::remove_anchor`.
+const REMOVE_ANCHOR_SAMPLES: usize = 16;
+
+/// The number of times the fit is improved in `BezPath::remove_anchor`.
+const REMOVE_ANCHOR_REFINEMENTS: usize = 8;
+
+/// The longest handle of the fit in `BezPath::remove_anchor`, relative to
+/// the length of the segments it replaces.
+const REMOVE_ANCHOR_MAX_HANDLE: f64 = 2.0;
+
+/// The number of sample heights per row in `BezPath::scanline_coverage`.
+const SCANLINE_SUBSAMPLES: usize = 16;
+
+/// The number of intervals sampled by `PathSeg::curvature_extrema`.
+const CURVATURE_SAMPLES: usize = 64;
+
+/// Add `weight` times the part of each pixel covered by the span from `xa`
+/// to `xb`.
+fn add_span_coverage(out: &mut [f32], x0: f64, width: f64, xa: f64, xb: f64, weight: f64) {
+    let n = out.len();
+    let u0 = ((xa - x0) / width).max(0.0);
+    let u1 = ((xb - x0) / width).min(n as f64);
+    if u1 <= u0 {
+        return;
+    }
+    let i0 = u0.floor() as usize;
+    let i1 = (u1.ceil() as usize).min(n);
+    for (i, c) in out.iter_mut().enumerate().take(i1).skip(i0) {
+        let lo = u0.max(i as f64);
+        let hi = u1.min(i as f64 + 1.0);
+        *c += ((hi - lo) * weight) as f32;
+    }
+}
+
+/// Cut two lines short where they cross, each on the side of its midpoint
+/// where the crossing is.
+fn clip_crossing(a: &mut Line, b: &mut Line) {
+    if let Some(hit) = PathSeg::Line(*a).intersect_line(*b).first() {
+        let at = b.eval(hit.line_t);
+        if hit.segment_t < 0.5 {
+            a.p0 = at;
+        } else {
+            a.p1 = at;
+        }
+        if hit.line_t < 0.5 {
+            b.p0 = at;
+        } else {
+            b.p1 = at;
+        }
+    }
+}
+
+/// The unit tangent of a segment at `t`, taking the limit where the
+/// derivative vanishes.
+pub(crate) fn seg_tangent(seg: PathSeg, t: f64) -> Option<Vec2> {
+    let d = match seg {
+        PathSeg::Line(l) => l.p1 - l.p0,
+        PathSeg::Quad(q) => q.deriv().eval(t).to_vec2(),
+        PathSeg::Cubic(c) => c.deriv().eval(t).to_vec2(),
+    };
+    if d.hypot2() > 0.0 {
+        Some(d.normalize())
+    } else if t < 0.5 {
+        start_tangent(seg.subsegment(t..1.0))
+    } else {
+        end_tangent(seg.subsegment(0.0..t))
+    }
+}
+
+pub(crate) fn end_tangent(seg: PathSeg) -> Option<Vec2> {
+    start_tangent(seg.reverse()).map(|t| -t)
+}
+
+/// The maximum depth of subdivision in [`seg_turning`].
+///
+/// [`seg_turning`]: fn.seg_turning.html
+const TURNING_MAX_DEPTH: usize = 20;
+
+/// The total angle through which the tangent of a segment turns, regardless
+/// of direction.
+fn seg_turning(seg: PathSeg, accuracy: f64) -> f64 {
+    let c = match seg {
+        PathSeg::Line(_) => return 0.0,
+        PathSeg::Quad(q) => q.raise(),
+        PathSeg::Cubic(c) => c,
+    };
+    // The inflections, where the cross product of the first and second
+    // derivatives changes sign.
+    let (x, y) = c.to_poly();
+    let c0 = 2.0 * (x[1] * y[2] - y[1] * x[2]);
+    let c1 = 6.0 * (x[1] * y[3] - y[1] * x[3]);
+    let c2 = 6.0 * (x[2] * y[3] - x[3] * y[2]);
+    let mut ts: Vec<f64> = solve_quadratic(c0, c1, c2)
+        .iter()
+        .copied()
+        .filter(|&t| t > 0.0 && t < 1.0)
+        .collect();
+    ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    ts.insert(0, 0.0);
+    ts.push(1.0);
+    let seg = PathSeg::Cubic(c);
+    ts.windows(2)
+        .map(|w| turning_between(seg, w[0], w[1], accuracy, 0))
+        .sum()
+}
+
+/// The turning of a piece of a segment without inflections.
+///
+/// Within such a piece the tangent turns one way, so the angle between the
+/// tangents at its ends is exact unless it turns by more than π, or there is
+/// a cusp; halving finds those.
+fn turning_between(seg: PathSeg, t0: f64, t1: f64, accuracy: f64, depth: usize) -> f64 {
+    let tangent = |t| seg_tangent(seg, t);
+    let tm = 0.5 * (t0 + t1);
+    let (a, m, b) = match (tangent(t0), tangent(tm), tangent(t1)) {
+        (Some(a), Some(m), Some(b)) => (a, m, b),
+        _ => return 0.0,
+    };
+    let whole = a.angle_between(b).abs();
+    let halves = a.angle_between(m).abs() + m.angle_between(b).abs();
+    if depth >= TURNING_MAX_DEPTH || halves - whole <= accuracy {
+        halves
+    } else {
+        turning_between(seg, t0, tm, accuracy, depth + 1)
+            + turning_between(seg, tm, t1, accuracy, depth + 1)
+    }
+}
+
+/// The signed curvature at the start of a segment, if the derivative there
+/// is nonzero.
+fn start_curvature(seg: PathSeg) -> Option<f64> {
+    let (d1, d2) = start_derivs(seg);
+    let speed = d1.hypot();
+    if speed == 0.0 {
+        return None;
+    }
+    Some(d1.cross(d2) / (speed * speed * speed))
+}
+
+fn end_curvature(seg: PathSeg) -> Option<f64> {
+    // Reversing the segment flips the sign of the curvature.
+    start_curvature(seg.reverse()).map(|k| -k)
+}
+
+fn same_direction(a: Vec2, b: Vec2) -> bool {
+    a.dot(b) > 0.0 && a.cross(b).abs() <= 1e-12
+}
+
+/// Convert a segment to a cubic, placing the handles of a line at thirds.
+fn raise_to_cubic(seg: PathSeg) -> CubicBez {
+    match seg {
+        PathSeg::Line(Line { p0, p1 }) => {
+            CubicBez::new(p0, p0.lerp(p1, 1.0 / 3.0), p0.lerp(p1, 2.0 / 3.0), p1)
+        }
+        _ => seg.to_cubic(),
+    }
+}
+
+/// The maximum depth of subdivision in [`subtended_angle`].
+const SUBTENDED_ANGLE_MAX_DEPTH: usize = 48;
+
+/// The signed angle swept by the segment, as seen from `pt`.
+fn subtended_angle(seg: PathSeg, pt: Point, depth: usize) -> f64 {
+    let hull = match seg {
+        PathSeg::Line(_) => None,
+        PathSeg::Quad(q) => Some(Rect::from_points(q.p0, q.p1).union_pt(q.p2)),
+        PathSeg::Cubic(c) => Some(Rect::from_points(c.p0, c.p1).union_pt(c.p2).union_pt(c.p3)),
+    };
+    let inside = |r: Rect| pt.x >= r.x0 && pt.x <= r.x1 && pt.y >= r.y0 && pt.y <= r.y1;
+    match hull {
+        Some(hull) if inside(hull) && depth < SUBTENDED_ANGLE_MAX_DEPTH => {
+            let (a, b) = seg.subdivide();
+            subtended_angle(a, pt, depth + 1) + subtended_angle(b, pt, depth + 1)
+        }
+        // The curve cannot wind around a point outside its convex hull, so it
+        // sweeps the same angle as its chord.
+        _ => {
+            let a = seg.start() - pt;
+            let b = seg.end() - pt;
+            a.cross(b).atan2(a.dot(b))
+        }
+    }
+}
+
+impl LineIntersection {
+    fn new(line_t: f64, segment_t: f64) -> Self {
+        LineIntersection { line_t, segment_t }
+    }
+}
+
+// Return polynomial coefficients given cubic bezier coordinates.
+fn quadratic_bez_coefs(x0: f64, x1: f64, x2: f64) -> (f64, f64, f64) {
+    let p0 = x0;
+    let p1 = 2.0 * x1 - 2.0 * x0;
+    let p2 = x2 - 2.0 * x1 + x0;
+    (p0, p1, p2)
+}
+
+// Return polynomial coefficients given cubic bezier coordinates.
+fn cubic_bez_coefs(x0: f64, x1: f64, x2: f64, x3: f64) -> (f64, f64, f64, f64) {
+    let p0 = x0;
+    let p1 = 3.0 * x1 - 3.0 * x0;
+    let p2 = 3.0 * x2 - 6.0 * x1 + 3.0 * x0;
+    let p3 = x3 - 3.0 * x2 + 3.0 * x1 - x0;
+    (p0, p1, p2, p3)
+}
+
+impl From<CubicBez> for PathSeg {
     fn from(cubic_bez: CubicBez) -> PathSeg {
         PathSeg::Cubic(cubic_bez)
     }
-}
+}
+
+impl From<Line> for PathSeg {
+    fn from(line: Line) -> PathSeg {
+        PathSeg::Line(line)
+    }
+}
+
+impl From<QuadBez> for PathSeg {
+    fn from(quad_bez: QuadBez) -> PathSeg {
+        PathSeg::Quad(quad_bez)
+    }
+}
+
+impl Shape for BezPath {
+    type PathElementsIter = std::vec::IntoIter<PathEl>;
+
+    fn path_elements(&self, _tolerance: f64) -> Self::PathElementsIter {
+        self.0.clone().into_iter()
+    }
+
+    fn to_path(&self, _tolerance: f64) -> BezPath {
+        self.clone()
+    }
+
+    fn into_path(self, _tolerance: f64) -> BezPath {
+        self
+    }
+
+    /// Signed area.
+    fn area(&self) -> f64 {
+        self.elements().area()
+    }
+
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        self.elements().perimeter(accuracy)
+    }
+
+    /// Winding number of point.
+    fn winding(&self, pt: Point) -> i32 {
+        self.elements().winding(pt)
+    }
+
+    fn bounding_box(&self) -> Rect {
+        self.elements().bounding_box()
+    }
+
+    fn bounding_box_fast(&self) -> Rect {
+        self.elements().bounding_box_fast()
+    }
+
+    fn as_path_slice(&self) -> Option<&[PathEl]> {
+        Some(&self.0)
+    }
+}
+
+/// Implements [`Shape`] for a slice of [`PathEl`], provided that the first element of the slice is
+/// not a `PathEl::ClosePath`. If it is, several of these functions will panic.
+///
+/// If the slice starts with `LineTo`, `QuadTo`, or `CurveTo`, it will be treated as a `MoveTo`.
+impl<'a> Shape for &'a [PathEl] {
+    type PathElementsIter = std::iter::Cloned<std::slice::Iter<'a, PathEl>>;
+
+    #[inline]
+    fn path_elements(&self, _tolerance: f64) -> Self::PathElementsIter {
+        self.iter().cloned()
+    }
+
+    fn to_path(&self, _tolerance: f64) -> BezPath {
+        BezPath::from_vec(self.to_vec())
+    }
+
+    /// Signed area.
+    ///
+    /// Each subpath is treated as closed by a line from its last point back
+    /// to its start, as when filling, consistently with [`winding`]. Segments
+    /// following a `ClosePath` without a `MoveTo` form a new subpath starting
+    /// where the closed one started.
+    ///
+    /// [`winding`]: #method.winding
+    fn area(&self) -> f64 {
+        segments(CloseSubpaths::new(self.iter().copied())).area()
+    }
+
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        segments(self.iter().copied()).perimeter(accuracy)
+    }
+
+    /// Winding number of point.
+    ///
+    /// As for [`area`], each subpath is treated as closed.
+    ///
+    /// [`area`]: #method.area
+    fn winding(&self, pt: Point) -> i32 {
+        segments(CloseSubpaths::new(self.iter().copied())).winding(pt)
+    }
+
+    fn bounding_box(&self) -> Rect {
+        segments(self.iter().copied()).bounding_box()
+    }
+
+    /// The bounding box of all points, including control points.
+    fn bounding_box_fast(&self) -> Rect {
+        let mut bbox: Option<Rect> = None;
+        for el in self.iter() {
+            for p in el.points() {
+                bbox = Some(match bbox {
+                    Some(bb) => bb.union_pt(p),
+                    None => Rect::from_points(p, p),
+                });
+            }
+        }
+        bbox.unwrap_or_default()
+    }
+
+    #[inline]
+    fn as_path_slice(&self) -> Option<&[PathEl]> {
+        Some(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_approx_eq(x: f64, y: f64) {
+        assert!((x - y).abs() < 1e-8, "{} != {}", x, y);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't start a segment on a ClosePath")]
+    fn test_elements_to_segments_starts_on_closepath() {
+        let mut path = BezPath::new();
+        path.close_path();
+        path.segments().next();
+    }
+
+    #[test]
+    fn test_elements_to_segments_closepath_refers_to_last_moveto() {
+        let mut path = BezPath::new();
+        path.move_to((5.0, 5.0));
+        path.line_to((15.0, 15.0));
+        path.move_to((10.0, 10.0));
+        path.line_to((15.0, 15.0));
+        path.close_path();
+        assert_eq!(
+            path.segments().collect::<Vec<_>>().last(),
+            Some(&Line::new((15.0, 15.0), (10.0, 10.0)).into()),
+        );
+    }
+
+    #[test]
+    fn test_elements_to_segments_starts_on_quad() {
+        let mut path = BezPath::new();
+        path.quad_to((5.0, 5.0), (10.0, 10.0));
+        path.line_to((15.0, 15.0));
+        path.close_path();
+
+        let mut segments = path.segments();
+        assert_eq!(
+            segments.next(),
+            Some(QuadBez::new((10.0, 10.0), (5.0, 5.0), (10.0, 10.0)).into()),
+        );
+        assert_eq!(
+            segments.next(),
+            Some(Line::new((10.0, 10.0), (15.0, 15.0)).into()),
+        );
+        assert_eq!(
+            segments.next(),
+            Some(Line::new((15.0, 15.0), (10.0, 10.0)).into()),
+        );
+        assert_eq!(segments.next(), None);
+    }
+
+    #[test]
+    fn test_intersect_line() {
+        let h_line = Line::new((0.0, 0.0), (100.0, 0.0));
+        let v_line = Line::new((10.0, -10.0), (10.0, 10.0));
+        let intersection = PathSeg::Line(h_line).intersect_line(v_line)[0];
+        assert_approx_eq(intersection.segment_t, 0.1);
+        assert_approx_eq(intersection.line_t, 0.5);
+
+        let v_line = Line::new((-10.0, -10.0), (-10.0, 10.0));
+        assert!(PathSeg::Line(h_line).intersect_line(v_line).is_empty());
+
+        let v_line = Line::new((10.0, 10.0), (10.0, 20.0));
+        assert!(PathSeg::Line(h_line).intersect_line(v_line).is_empty());
+    }
+
+    #[test]
+    fn test_intersect_qad() {
+        let q = QuadBez::new((0.0, -10.0), (10.0, 20.0), (20.0, -10.0));
+        let v_line = Line::new((10.0, -10.0), (10.0, 10.0));
+        assert_eq!(PathSeg::Quad(q).intersect_line(v_line).len(), 1);
+        let intersection = PathSeg::Quad(q).intersect_line(v_line)[0];
+        assert_approx_eq(intersection.segment_t, 0.5);
+        assert_approx_eq(intersection.line_t, 0.75);
+
+        let h_line = Line::new((0.0, 0.0), (100.0, 0.0));
+        assert_eq!(PathSeg::Quad(q).intersect_line(h_line).len(), 2);
+    }
+
+    #[test]
+    fn test_intersect_cubic() {
+        let c = CubicBez::new((0.0, -10.0), (10.0, 20.0), (20.0, -20.0), (30.0, 10.0));
+        let v_line = Line::new((10.0, -10.0), (10.0, 10.0));
+        assert_eq!(PathSeg::Cubic(c).intersect_line(v_line).len(), 1);
+        let intersection = PathSeg::Cubic(c).intersect_line(v_line)[0];
+        assert_approx_eq(intersection.segment_t, 0.333333333);
+        assert_approx_eq(intersection.line_t, 0.592592592);
+
+        let h_line = Line::new((0.0, 0.0), (100.0, 0.0));
+        assert_eq!(PathSeg::Cubic(c).intersect_line(h_line).len(), 3);
+    }
+
+    #[test]
+    fn test_flatten_segment_count() {
+        let q = QuadBez::new((0.0, 0.0), (50.0, 100.0), (100.0, 0.0));
+        let c = CubicBez::new((0.0, 0.0), (10.0, 100.0), (90.0, -100.0), (100.0, 0.0));
+        for &tolerance in &[10.0, 1.0, 0.1, 0.01, 1e-4] {
+            let mut path = BezPath::new();
+            path.move_to(q.p0);
+            path.quad_to(q.p1, q.p2);
+            let mut count = 0;
+            path.flatten(tolerance, |el| {
+                count += matches!(el, PathEl::LineTo(_)) as usize
+            });
+            assert_eq!(count, q.flatten_segment_count(tolerance));
+
+            let mut path = BezPath::new();
+            path.move_to(c.p0);
+            path.curve_to(c.p1, c.p2, c.p3);
+            let mut count = 0;
+            path.flatten(tolerance, |el| {
+                count += matches!(el, PathEl::LineTo(_)) as usize
+            });
+            assert_eq!(count, c.flatten_segment_count(tolerance));
+        }
+    }
+
+    #[test]
+    fn test_transform_elements() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 20.0);
+        let affine = Affine::translate((5.0, 5.0)) * Affine::scale(2.0);
+        let transformed: BezPath = affine.transform_elements(rect.path_elements(0.1)).collect();
+        let expected = affine * rect.to_path(0.1);
+        assert_eq!(transformed.elements().len(), expected.elements().len());
+        for (a, b) in transformed.segments().zip(expected.segments()) {
+            assert_eq!(a, b);
+        }
+        assert_approx_eq(transformed.area(), 800.0);
+    }
+
+    #[test]
+    fn test_fluent_builder() {
+        let mut path = BezPath::with_capacity(5);
+        path.move_to((0.0, 0.0))
+            .line_to((1.0, 0.0))
+            .quad_to((2.0, 0.0), (2.0, 1.0))
+            .curve_to((2.0, 2.0), (1.0, 2.0), (0.0, 2.0))
+            .close_path();
+        assert_eq!(path.elements().len(), 5);
+        assert_eq!(path.segments().count(), 4);
+
+        let mut other = BezPath::new();
+        other.extend_from_slice(path.elements());
+        other.extend_from_slice(&path.elements()[..2]);
+        assert_eq!(other.elements().len(), 7);
+    }
+
+    #[test]
+    fn test_extend_path() {
+        let motif = Rect::new(0.0, 0.0, 1.0, 1.0).to_path(0.1);
+        let mut path = BezPath::new();
+        for i in 0..3 {
+            path.extend_path(&motif, Affine::translate((2.0 * i as f64, 0.0)));
+        }
+        path.extend_path(&motif, Affine::IDENTITY);
+        path.extend(motif.iter().map(|el| Affine::scale(0.5) * el));
+        assert_eq!(path.elements().len(), 5 * motif.elements().len());
+        assert_approx_eq(path.area(), 4.25);
+        assert_eq!(path.winding(Point::new(4.5, 0.5)), 1);
+        assert_eq!(path.winding(Point::new(3.0, 0.5)), 0);
+    }
+
+    #[test]
+    fn test_winding_level_with_vertices() {
+        let mut diamond = BezPath::new();
+        diamond
+            .move_to((0.0, -1.0))
+            .line_to((1.0, 0.0))
+            .line_to((0.0, 1.0))
+            .line_to((-1.0, 0.0))
+            .close_path();
+        assert_eq!(diamond.winding(Point::new(0.0, 0.0)), 1);
+        assert_eq!(diamond.winding(Point::new(0.5, 0.0)), 1);
+        assert_eq!(diamond.winding(Point::new(2.0, 0.0)), 0);
+        assert_eq!(diamond.winding(Point::new(-2.0, 0.0)), 0);
+        assert_eq!(diamond.winding(Point::new(0.5, -1.0)), 0);
+        assert_eq!(diamond.winding(Point::new(-0.5, 1.0)), 0);
+        assert_eq!(diamond.winding(Point::new(0.2, 0.5)), 1);
+
+        // A polygon with local y extrema at vertices, and a vertex where the
+        // boundary passes straight through the ray.
+        let mut zigzag = BezPath::new();
+        zigzag
+            .move_to((0.0, 0.0))
+            .line_to((2.0, 2.0))
+            .line_to((4.0, 0.0))
+            .line_to((6.0, 2.0))
+            .line_to((6.0, 4.0))
+            .line_to((0.0, 4.0))
+            .close_path();
+        for &x in &[1.0, 3.0, 5.0] {
+            assert_eq!(zigzag.winding(Point::new(x, 2.0)), 1);
+            assert_eq!(zigzag.winding(Point::new(x, 2.0 - 1e-9)), 1);
+        }
+        for &x in &[-1.0, 7.0] {
+            assert_eq!(zigzag.winding(Point::new(x, 2.0)), 0);
+            assert_eq!(zigzag.winding(Point::new(x, 0.0)), 0);
+        }
+        assert_eq!(zigzag.winding(Point::new(2.0, 0.0)), 0);
+        assert_eq!(zigzag.winding(Point::new(5.0, 0.0)), 0);
+        assert_eq!(zigzag.winding(Point::new(4.0, 1.0)), 1);
+    }
+
+    #[test]
+    fn test_winding_tangent_to_curve() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0))
+            .quad_to((1.0, 2.0), (2.0, 0.0))
+            .close_path();
+        // The curve has its extremum at (1, 1).
+        assert_eq!(path.winding(Point::new(1.0, 1.0 - 1e-12)), -1);
+        assert_eq!(path.winding(Point::new(3.0, 1.0 - 1e-12)), 0);
+        assert_eq!(path.winding(Point::new(1.0, 1.0)), 0);
+        assert_eq!(path.winding(Point::new(1.0, 1.5)), 0);
+        assert_eq!(path.winding(Point::new(1.0, -0.5)), 0);
+
+        let circle = crate::Circle::new((0.0, 0.0), 1.0).to_path(1e-3);
+        for &y in &[0.0, 1.0 - 1e-12, -1.0 + 1e-12] {
+            assert_eq!(circle.winding(Point::new(0.0, y)), 1);
+            assert_eq!(circle.winding(Point::new(2.0, y)), 0);
+            assert_eq!(circle.winding(Point::new(-2.0, y)), 0);
+        }
+    }
+
+    #[test]
+    fn test_segments_area_winding() {
+        let mut path = Rect::new(0.0, 0.0, 4.0, 4.0).to_path(0.1);
+        path.extend(Rect::new(1.0, 1.0, 2.0, 2.0).path_elements(0.1));
+        let mut hole = BezPath::new();
+        hole.move_to((3.0, 3.0))
+            .line_to((3.0, 3.5))
+            .line_to((3.5, 3.5))
+            .line_to((3.5, 3.0))
+            .close_path();
+        path.extend(hole);
+        let segs: Vec<PathSeg> = path.segments().collect();
+        assert_approx_eq(segments_area(&segs), 16.0 + 1.0 - 0.25);
+        assert_eq!(segments_winding(&segs, Point::new(0.5, 0.5)), 1);
+        assert_eq!(segments_winding(&segs, Point::new(1.5, 1.5)), 2);
+        assert_eq!(segments_winding(&segs, Point::new(3.25, 3.25)), 0);
+        assert_eq!(segments_winding(&segs, Point::new(5.0, 0.5)), 0);
+        // Order doesn't matter for closed loops.
+        let mut reversed = segs.clone();
+        reversed.reverse();
+        assert_approx_eq(segments_area(&reversed), segments_area(&segs));
+    }
+
+    #[test]
+    fn test_bounding_box_fast() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0))
+            .curve_to((10.0, 30.0), (-5.0, 30.0), (20.0, 0.0))
+            .quad_to((25.0, -10.0), (15.0, -5.0))
+            .close_path();
+        let exact = path.bounding_box();
+        let fast = path.bounding_box_fast();
+        assert_eq!(fast, Rect::new(-5.0, -10.0, 25.0, 30.0));
+        assert!(fast.x0 <= exact.x0 && fast.y0 <= exact.y0);
+        assert!(fast.x1 >= exact.x1 && fast.y1 >= exact.y1);
+        assert_eq!(BezPath::new().bounding_box_fast(), Rect::ZERO);
+        let circle = crate::Circle::new((1.0, 2.0), 3.0);
+        assert_eq!(circle.bounding_box_fast(), circle.bounding_box());
+    }
+
+    #[test]
+    fn test_hit_stroke() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .quad_to((15.0, 5.0), (10.0, 10.0));
+        assert!(path.hit_stroke(Point::new(5.0, 0.9), 1.0, 1e-9));
+        assert!(!path.hit_stroke(Point::new(5.0, 1.1), 1.0, 1e-9));
+        // Round caps: beyond the start point, but within the half width.
+        assert!(path.hit_stroke(Point::new(-0.6, -0.6), 1.0, 1e-9));
+        assert!(!path.hit_stroke(Point::new(-0.8, -0.8), 1.0, 1e-9));
+        assert!(path.hit_stroke(Point::new(12.0, 5.0), 0.6, 1e-9));
+        assert!(!path.hit_stroke(Point::new(10.0, 5.0), 2.0, 1e-9));
+        assert!(!BezPath::new().hit_stroke(Point::ORIGIN, 1.0, 1e-9));
+    }
+
+    #[test]
+    fn test_segment_bboxes() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0))
+            .curve_to((0.0, 4.0), (4.0, 4.0), (4.0, 0.0))
+            .close_path();
+        let bboxes = path.segment_bboxes();
+        assert_eq!(bboxes.len(), path.segments().count());
+        let curve = bboxes[0];
+        assert!((curve.y1 - 3.0).abs() < 1e-12);
+        assert_eq!((curve.x0, curve.y0, curve.x1), (0.0, 0.0, 4.0));
+        assert_eq!(bboxes[1], Rect::new(0.0, 0.0, 4.0, 0.0));
+        assert!(BezPath::new().segment_bboxes().is_empty());
+    }
+
+    #[test]
+    fn test_winding_robust() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0))
+            .line_to((4.0, 0.0))
+            .curve_to((6.0, 1.0), (6.0, 3.0), (4.0, 4.0))
+            .quad_to((2.0, 6.0), (0.0, 4.0))
+            .close_path();
+        // A second, overlapping subpath in the same direction.
+        path.move_to((1.0, 1.0))
+            .line_to((3.0, 1.0))
+            .line_to((3.0, 3.0))
+            .line_to((1.0, 3.0))
+            .close_path();
+        for i in 0..30 {
+            for j in 0..30 {
+                let pt = Point::new(i as f64 * 0.25 - 0.6, j as f64 * 0.25 - 0.55);
+                assert_eq!(path.winding_robust(pt), path.winding(pt), "{:?}", pt);
+            }
+        }
+        // Level with vertices, where crossing tests need care.
+        assert_eq!(path.winding_robust(Point::new(0.5, 1.0)), 1);
+        assert_eq!(path.winding_robust(Point::new(5.0, 4.0)), 0);
+        assert_eq!(path.winding_robust(Point::new(-1.0, 0.0)), 0);
+        assert_eq!(path.winding_robust(Point::new(2.0, 0.5)), 1);
+
+        // Unclosed subpaths are closed implicitly.
+        let mut open = BezPath::new();
+        open.move_to((0.0, 0.0))
+            .line_to((0.0, 2.0))
+            .line_to((2.0, 2.0));
+        assert_eq!(open.winding_robust(Point::new(0.5, 1.5)), -1);
+        assert_eq!(BezPath::new().winding_robust(Point::ORIGIN), 0);
+    }
+
+    #[test]
+    fn test_path_el_points() {
+        let els = [
+            PathEl::MoveTo(Point::new(1.0, 2.0)),
+            PathEl::QuadTo(Point::new(3.0, 4.0), Point::new(5.0, 6.0)),
+            PathEl::CurveTo(
+                Point::new(7.0, 8.0),
+                Point::new(9.0, 10.0),
+                Point::new(11.0, 12.0),
+            ),
+            PathEl::ClosePath,
+        ];
+        let counts: Vec<usize> = els.iter().map(|el| el.points().len()).collect();
+        assert_eq!(counts, [1, 2, 3, 0]);
+        assert_eq!(els[2].points()[2], Point::new(11.0, 12.0));
+
+        let mut order = Vec::new();
+        for &el in &els {
+            let mapped = el.map_points(|p| {
+                order.push(p.x);
+                Point::new(p.y, p.x)
+            });
+            assert_eq!(mem::discriminant(&mapped), mem::discriminant(&el));
+            let swapped: Vec<Point> = el.points().iter().map(|p| Point::new(p.y, p.x)).collect();
+            assert_eq!(&mapped.points()[..], &swapped[..]);
+        }
+        assert_eq!(order, [1.0, 3.0, 5.0, 7.0, 9.0, 11.0]);
+    }
+
+    #[test]
+    fn test_transform_about() {
+        let pivot = Point::new(0.1, 0.7);
+        let mut path = BezPath::new();
+        path.move_to(pivot)
+            .line_to((2.0, 0.3))
+            .quad_to((1.0, 1.0), (0.3, 2.0))
+            .close_path();
+        let scaled = path.scale_about(3.0, pivot);
+        assert_eq!(scaled.elements()[0].points()[0], pivot);
+        let p = scaled.elements()[1].points()[0];
+        assert!((p - Point::new(0.1 + 3.0 * 1.9, 0.7 - 3.0 * 0.4)).hypot() < 1e-12);
+
+        let rotated = path.rotate_about(1.234, pivot);
+        assert_eq!(rotated.elements()[0].points()[0], pivot);
+        assert!((rotated.area() - path.area()).abs() < 1e-12);
+
+        let stretched = path.scale_about_xy(2.0, -1.0, pivot);
+        assert_eq!(stretched.elements()[0].points()[0], pivot);
+        assert!((stretched.area() + 2.0 * path.area()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_implicit_close() {
+        let mut open = BezPath::new();
+        open.move_to((1.0, 1.0))
+            .line_to((4.0, 1.0))
+            .quad_to((5.0, 3.0), (4.0, 4.0))
+            .line_to((1.0, 4.0));
+        // A second open subpath, run clockwise.
+        open.move_to((6.0, 6.0))
+            .line_to((6.0, 8.0))
+            .line_to((8.0, 8.0));
+        let mut closed = BezPath::new();
+        for el in open.iter() {
+            if let (PathEl::MoveTo(_), false) = (el, closed.is_empty()) {
+                closed.close_path();
+            }
+            closed.push(el);
+        }
+        closed.close_path();
+        assert!((open.area() - closed.area()).abs() < 1e-12);
+        // The result doesn't depend on the position of the origin.
+        let moved = Affine::translate((10.0, -3.0)) * &open;
+        assert!((moved.area() - closed.area()).abs() < 1e-12);
+        for &pt in &[
+            Point::new(2.0, 2.5),
+            Point::new(4.3, 2.5),
+            Point::new(0.5, 2.5),
+            Point::new(6.5, 7.5),
+            Point::new(7.5, 6.5),
+        ] {
+            assert_eq!(open.winding(pt), closed.winding(pt), "{:?}", pt);
+            assert_eq!(open.winding(pt), open.winding_robust(pt), "{:?}", pt);
+        }
+        assert_eq!(open.winding(Point::new(2.0, 2.5)), 1);
+        assert_eq!(open.winding(Point::new(6.5, 7.5)), -1);
+    }
+
+    #[test]
+    fn test_join_smooth() {
+        let mut a = BezPath::new();
+        a.move_to((0.0, 0.0))
+            .curve_to((1.0, 0.0), (2.0, 1.0), (3.0, 1.0));
+        let mut b = BezPath::new();
+        b.move_to((3.0, 1.0))
+            .curve_to((3.0, 2.0), (4.0, 3.0), (5.0, 3.0))
+            .line_to((6.0, 3.0));
+
+        let mut c0 = a.clone();
+        c0.join_smooth(&b, Continuity::C0);
+        assert_eq!(c0.elements().len(), 4);
+        assert_eq!(c0.segments().count(), 3);
+
+        let incoming = a.segments().last().unwrap();
+        let mut g1 = a.clone();
+        g1.join_smooth(&b, Continuity::G1);
+        let out = g1.get_seg(2).unwrap();
+        let tan = start_tangent(out).unwrap();
+        assert!((tan - end_tangent(incoming).unwrap()).hypot() < 1e-12);
+        // The handle length is kept.
+        assert_approx_eq(out.to_cubic().p1.distance(out.start()), 1.0);
+        assert!(matches!(g1.elements()[3], PathEl::LineTo(p) if p == Point::new(6.0, 3.0)));
+
+        let mut g2 = a.clone();
+        g2.join_smooth(&b, Continuity::G2);
+        let out = g2.get_seg(2).unwrap();
+        assert!((start_tangent(out).unwrap() - tan).hypot() < 1e-12);
+        assert_approx_eq(
+            start_curvature(out).unwrap(),
+            end_curvature(incoming).unwrap(),
+        );
+
+        // Already tangent: nothing moves, and the line stays a line.
+        let mut line = BezPath::new();
+        line.move_to((3.0, 1.0)).line_to((5.0, 1.0));
+        let mut joined = a.clone();
+        joined.join_smooth(&line, Continuity::G1);
+        assert!(matches!(joined.elements()[2], PathEl::LineTo(p) if p == Point::new(5.0, 1.0)));
+
+        // A closed path has no free end.
+        let mut closed = a.clone();
+        closed.close_path();
+        closed.join_smooth(&b, Continuity::G1);
+        assert_eq!(closed.elements().len(), 6);
+        assert!(matches!(closed.elements()[3], PathEl::MoveTo(_)));
+    }
+
+    #[test]
+    fn test_flat_round_trip() {
+        let mut path = BezPath::new();
+        path.move_to((0.1, -0.0))
+            .line_to((1e300, 3.0))
+            .quad_to((1.0, 2.0), (f64::MIN_POSITIVE, 4.0))
+            .curve_to((5.0, 6.0), (7.0, 8.0), (9.0, 0.3))
+            .close_path()
+            .move_to((1.0, 1.0));
+        let (tags, coords) = path.to_flat();
+        assert_eq!(tags, [0, 1, 2, 3, 4, 0]);
+        assert_eq!(coords.len(), 2 + 2 + 4 + 6 + 2);
+        let back = BezPath::from_flat(&tags, &coords).unwrap();
+        assert_eq!(back.to_flat(), (tags.clone(), coords.clone()));
+        assert!(back.elements()[0].points()[0].y.is_sign_negative());
+
+        assert!(BezPath::from_flat(&tags, &coords[1..]).is_none());
+        assert!(BezPath::from_flat(&tags[..5], &coords).is_none());
+        assert!(BezPath::from_flat(&[5], &[]).is_none());
+    }
+
+    #[test]
+    fn test_signed_distance_methods() {
+        use SignedDistanceMethod::{PseudoNormal, Winding};
+        // An L shape with a reflex corner at (2, 2), and a square hole
+        // drawn clockwise in the foot.
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((6.0, 0.0));
+        path.line_to((6.0, 2.0));
+        path.line_to((2.0, 2.0));
+        path.line_to((2.0, 6.0));
+        path.line_to((0.0, 6.0));
+        path.close_path();
+        path.move_to((3.0, 0.5));
+        path.line_to((3.0, 1.5));
+        path.line_to((4.0, 1.5));
+        path.line_to((4.0, 0.5));
+        path.close_path();
+        // Inside, near the reflex corner, the nearest feature is the vertex,
+        // and the point is on the outer side of one of the edges meeting
+        // there but not of their pseudo-normal.
+        for &pt in &[
+            Point::new(1.8, 1.7),
+            Point::new(1.5, 1.9),
+            Point::new(1.9, 1.2),
+        ] {
+            let d = path.signed_distance_with(pt, 1e-9, PseudoNormal);
+            assert!(d < 0.0, "{:?}", pt);
+            assert_approx_eq(d, -pt.distance(Point::new(2.0, 2.0)));
+            assert_eq!(d, path.signed_distance_with(pt, 1e-9, Winding));
+        }
+        // Outside a convex corner.
+        assert_approx_eq(
+            path.signed_distance_with(Point::new(6.5, -0.5), 1e-9, PseudoNormal),
+            0.5 * 2f64.sqrt(),
+        );
+        // Inside, near the convex corners and in the hole.
+        assert_approx_eq(
+            path.signed_distance_with(Point::new(0.5, 0.25), 1e-9, PseudoNormal),
+            -0.25,
+        );
+        assert_approx_eq(
+            path.signed_distance_with(Point::new(3.5, 1.0), 1e-9, PseudoNormal),
+            0.5,
+        );
+        // Away from the medial axis, both methods agree everywhere, and the
+        // mirrored path, with the opposite orientation, has the same signs.
+        let mirrored = Affine::FLIP_Y * &path;
+        for i in 0..40 {
+            for j in 0..40 {
+                let pt = Point::new(-1.0 + 0.2 * i as f64 + 0.01, -1.0 + 0.2 * j as f64 + 0.03);
+                let w = path.signed_distance_with(pt, 1e-9, Winding);
+                let p = path.signed_distance_with(pt, 1e-9, PseudoNormal);
+                assert_approx_eq(w, p);
+                let mirror_pt = Point::new(pt.x, -pt.y);
+                assert_approx_eq(
+                    mirrored.signed_distance_with(mirror_pt, 1e-9, PseudoNormal),
+                    p,
+                );
+            }
+        }
+        assert_eq!(
+            BezPath::new().signed_distance_with(Point::ZERO, 1e-9, PseudoNormal),
+            f64::INFINITY
+        );
+    }
 
-impl From<Line> for PathSeg {
-    fn from(line: Line) -> PathSeg {
-        PathSeg::Line(line)
+    #[test]
+    fn test_signed_distance_and_gradient() {
+        let path = Rect::new(0.0, 0.0, 4.0, 2.0).to_path(1e-9);
+        let (d, g) = path.signed_distance_and_gradient(Point::new(1.0, 0.5), 1e-9);
+        assert_approx_eq(d, -0.5);
+        assert_eq!(g, Vec2::new(0.0, -1.0));
+        // Outside a corner, the gradient points away from the corner.
+        let (d, g) = path.signed_distance_and_gradient(Point::new(7.0, 6.0), 1e-9);
+        assert_approx_eq(d, 5.0);
+        assert!((g - Vec2::new(0.6, 0.8)).hypot() < 1e-12);
+        // On the boundary, the outward normal.
+        let (d, g) = path.signed_distance_and_gradient(Point::new(4.0, 1.0), 1e-9);
+        assert_eq!(d, 0.0);
+        assert!((g - Vec2::new(1.0, 0.0)).hypot() < 1e-12);
+        // The same for the reversed path.
+        let mut rev = BezPath::new();
+        rev.move_to((0.0, 0.0))
+            .line_to((0.0, 2.0))
+            .line_to((4.0, 2.0))
+            .line_to((4.0, 0.0))
+            .close_path();
+        let (_, g) = rev.signed_distance_and_gradient(Point::new(4.0, 1.0), 1e-9);
+        assert!((g - Vec2::new(1.0, 0.0)).hypot() < 1e-12);
+        assert_approx_eq(rev.signed_distance(Point::new(1.0, 0.5), 1e-9), -0.5);
+        assert_eq!(
+            BezPath::new().signed_distance(Point::ZERO, 1e-9),
+            f64::INFINITY
+        );
     }
-}
 
-impl From<QuadBez> for PathSeg {
-    fn from(quad_bez: QuadBez) -> PathSeg {
-        PathSeg::Quad(quad_bez)
+    #[test]
+    fn test_scanline_coverage() {
+        let rect = Rect::new(1.25, 0.0, 3.5, 10.0).to_path(1e-9);
+        let mut out = [0.0f32; 5];
+        rect.scanline_coverage(2.0, 0.0, 5.0, FillRule::NonZero, &mut out);
+        assert_eq!(out, [0.0, 0.75, 1.0, 0.5, 0.0]);
+        // Half the row height is covered, with pixels of width 2.
+        rect.scanline_coverage(9.5, 0.0, 10.0, FillRule::NonZero, &mut out);
+        assert_eq!(out, [0.1875, 0.375, 0.0, 0.0, 0.0]);
+
+        // Two overlapping squares, covering [0, 3) and [1, 4).
+        let mut path = Rect::new(0.0, 0.0, 3.0, 1.0).to_path(1e-9);
+        path.extend_path(
+            &Rect::new(1.0, 0.0, 4.0, 1.0).to_path(1e-9),
+            Affine::IDENTITY,
+        );
+        let mut out = [0.0f32; 4];
+        path.scanline_coverage(0.0, 0.0, 4.0, FillRule::NonZero, &mut out);
+        assert_eq!(out, [1.0; 4]);
+        path.scanline_coverage(0.0, 0.0, 4.0, FillRule::EvenOdd, &mut out);
+        assert_eq!(out, [1.0, 0.0, 0.0, 1.0]);
+
+        // A circle covers its area, up to the flattening tolerance along its
+        // perimeter.
+        let circle = crate::Circle::new((8.0, 8.0), 5.0).to_path(1e-3);
+        let mut total = 0.0;
+        let mut row = [0.0f32; 16];
+        for y in 0..16 {
+            circle.scanline_coverage(y as f64, 0.0, 16.0, FillRule::NonZero, &mut row);
+            total += row.iter().map(|&c| c as f64).sum::<f64>();
+        }
+        let expected = 25.0 * std::f64::consts::PI;
+        assert!((total - expected).abs() < 0.05 * 10.0 * std::f64::consts::PI);
     }
-}
 
-impl Shape for BezPath {
-    type PathElementsIter = std::vec::IntoIter<PathEl>;
+    #[test]
+    fn test_weld() {
+        // Three pieces of a square, out of order at the end, and a separate
+        // line elsewhere.
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0)).line_to((1.0, 0.0));
+        path.move_to((5.0, 5.0)).line_to((6.0, 5.0));
+        path.move_to((1.0, 1e-12)).line_to((1.0, 1.0));
+        path.move_to((1.0, 1.0))
+            .line_to((0.0, 1.0))
+            .line_to((1e-12, 0.0));
+        let welded = path.weld(1e-9);
+        let els = welded.elements();
+        assert_eq!(els.len(), 8);
+        assert!(matches!(els[0], PathEl::MoveTo(_)));
+        assert!(matches!(els[4], PathEl::LineTo(p) if p == Point::ZERO));
+        assert!(matches!(els[5], PathEl::ClosePath));
+        assert!(matches!(els[6], PathEl::MoveTo(p) if p == Point::new(5.0, 5.0)));
+        assert_approx_eq(welded.area(), 1.0);
 
-    fn path_elements(&self, _tolerance: f64) -> Self::PathElementsIter {
-        self.0.clone().into_iter()
+        // Pieces that only nearly meet stay apart, and closed subpaths are
+        // untouched.
+        let mut path = Rect::new(0.0, 0.0, 1.0, 1.0).to_path(1e-9);
+        path.move_to((0.0, 0.0)).line_to((2.0, 0.0));
+        path.move_to((2.0, 0.1)).line_to((3.0, 0.0));
+        let welded = path.weld(1e-3);
+        assert_eq!(welded.elements().len(), path.elements().len());
     }
 
-    fn to_path(&self, _tolerance: f64) -> BezPath {
-        self.clone()
+    #[test]
+    fn test_curvature_extrema() {
+        assert!(PathSeg::Line(Line::new((0.0, 0.0), (1.0, 1.0)))
+            .curvature_extrema(1e-9)
+            .is_empty());
+        // A parabola is sharpest at its vertex.
+        let q = QuadBez::new((-1.0, 1.0), (0.0, -1.0), (1.0, 1.0));
+        let ts = PathSeg::Quad(q).curvature_extrema(1e-9);
+        assert_eq!(ts.len(), 1);
+        assert!((ts[0] - 0.5).abs() < 1e-8);
+        // An S curve has a peak in each bend.
+        let c = CubicBez::new((0.0, 0.0), (3.0, 0.0), (-2.0, 2.0), (1.0, 2.0));
+        let ts = PathSeg::Cubic(c).curvature_extrema(1e-9);
+        assert_eq!(ts.len(), 2);
+        assert!((ts[0] + ts[1] - 1.0).abs() < 1e-8);
+        for &t in &ts {
+            let k = c.curvature(t).abs();
+            assert!(k >= c.curvature(t - 1e-4).abs() && k >= c.curvature(t + 1e-4).abs());
+        }
+        // A straight cubic has no peaks.
+        let c = CubicBez::new((0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0));
+        assert!(PathSeg::Cubic(c).curvature_extrema(1e-9).is_empty());
     }
 
-    fn into_path(self, _tolerance: f64) -> BezPath {
-        self
+    #[test]
+    fn test_close_then_line_to() {
+        // A triangle, closed, followed by a second triangle starting from
+        // the same point without a `MoveTo`.
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0))
+            .line_to((2.0, 0.0))
+            .line_to((2.0, 2.0))
+            .close_path()
+            .line_to((-2.0, 0.0))
+            .quad_to((-2.0, -2.0), (-1.0, -2.0));
+        let segs: Vec<PathSeg> = path.segments().collect();
+        assert_eq!(segs.len(), 5);
+        assert_eq!(segs[3], PathSeg::Line(Line::new((0.0, 0.0), (-2.0, 0.0))));
+        assert_eq!(segs[4].start(), Point::new(-2.0, 0.0));
+        // The second subpath is implicitly closed back to the origin.
+        let second = {
+            let mut p = BezPath::new();
+            p.move_to((0.0, 0.0))
+                .line_to((-2.0, 0.0))
+                .quad_to((-2.0, -2.0), (-1.0, -2.0))
+                .close_path();
+            p
+        };
+        assert_approx_eq(path.area(), 2.0 + second.area());
+        assert_eq!(path.winding(Point::new(1.5, 0.5)), 1);
+        assert_eq!(
+            path.winding(Point::new(-1.0, -1.0)),
+            second.winding(Point::new(-1.0, -1.0))
+        );
+        assert_ne!(path.winding(Point::new(-1.0, -1.0)), 0);
+        // Flattening continues from the start of the closed subpath too.
+        let mut flat = BezPath::new();
+        path.flatten(0.01, |el| flat.push(el));
+        assert!((flat.area() - path.area()).abs() < 0.05);
     }
 
-    /// Signed area.
-    fn area(&self) -> f64 {
-        self.elements().area()
+    #[test]
+    fn test_subpaths() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0))
+            .line_to((1.0, 0.0))
+            .line_to((1.0, 1.0))
+            .close_path()
+            .line_to((0.0, 2.0))
+            .move_to((5.0, 5.0))
+            .move_to((3.0, 3.0))
+            .quad_to((4.0, 4.0), (5.0, 3.0));
+        let subpaths: Vec<BezPath> = path.subpaths().collect();
+        let lens: Vec<usize> = subpaths.iter().map(|p| p.elements().len()).collect();
+        assert_eq!(lens, [4, 2, 1, 2]);
+        assert!(subpaths
+            .iter()
+            .all(|p| matches!(p.elements()[0], PathEl::MoveTo(_))));
+        assert!(matches!(subpaths[1].elements()[0], PathEl::MoveTo(p) if p == Point::ZERO));
+        assert!(matches!(subpaths[3].elements()[1], PathEl::QuadTo(..)));
+        let total: usize = path
+            .clone()
+            .into_subpaths()
+            .map(|p| p.segments().count())
+            .sum();
+        assert_eq!(total, path.segments().count());
+        assert_eq!(BezPath::new().subpaths().count(), 0);
+        // A long run of stray `ClosePath`s is skipped without recursing.
+        let mut closes = BezPath::new();
+        closes.move_to((0.0, 0.0)).line_to((1.0, 0.0)).close_path();
+        for _ in 0..1_000_000 {
+            closes.close_path();
+        }
+        closes.line_to((0.0, 1.0));
+        let lens: Vec<usize> = closes.subpaths().map(|p| p.elements().len()).collect();
+        assert_eq!(lens, [3, 2]);
     }
 
-    fn perimeter(&self, accuracy: f64) -> f64 {
-        self.elements().perimeter(accuracy)
+    #[test]
+    fn test_winding_contribution() {
+        // A single arch, crossing the ray twice in opposite directions.
+        let arch = PathSeg::Cubic(CubicBez::new(
+            (0.0, 0.0),
+            (0.0, 4.0),
+            (4.0, 4.0),
+            (4.0, 0.0),
+        ));
+        assert_eq!(arch.winding_contribution(Point::new(-1.0, 1.0)), 0);
+        assert_eq!(arch.winding_contribution(Point::new(2.0, 1.0)), -1);
+        assert_eq!(arch.winding_contribution(Point::new(5.0, 1.0)), 0);
+        // Sums over closed paths match the winding number, including for
+        // rays through vertices.
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0))
+            .line_to((2.0, 1.0))
+            .quad_to((3.0, 2.0), (1.0, 3.0))
+            .line_to((0.5, 1.0))
+            .close_path();
+        for &(x, y) in &[
+            (0.2, 0.1),
+            (1.0, 1.0),
+            (1.5, 2.0),
+            (0.5, 1.0),
+            (-1.0, 1.0),
+            (1.0, 3.0),
+        ] {
+            let pt = Point::new(x, y);
+            let sum: i32 = path.segments().map(|s| s.winding_contribution(pt)).sum();
+            assert_eq!(sum, path.winding(pt), "{:?}", pt);
+        }
     }
 
-    /// Winding number of point.
-    fn winding(&self, pt: Point) -> i32 {
-        self.elements().winding(pt)
+    #[test]
+    fn test_with_flipped_y() {
+        let path = Rect::new(1.0, 2.0, 3.0, 5.0).to_path(1e-9);
+        let flipped = path.with_flipped_y();
+        assert_eq!(flipped.area(), -path.area());
+        assert_eq!(flipped.area_abs(), 6.0);
+        assert_eq!(path.area_abs(), 6.0);
+        assert_eq!(flipped.winding(Point::new(2.0, -3.0)), -1);
+        assert_eq!(path.winding(Point::new(2.0, 3.0)), 1);
+        assert_eq!(flipped.bounding_box(), Rect::new(1.0, -5.0, 3.0, -2.0));
     }
 
-    fn bounding_box(&self) -> Rect {
-        self.elements().bounding_box()
+    #[test]
+    fn test_subpath_areas() {
+        let mut path = BezPath::new();
+        // A closed counterclockwise square.
+        path.move_to((0.0, 0.0));
+        path.line_to((4.0, 0.0));
+        path.line_to((4.0, 4.0));
+        path.line_to((0.0, 4.0));
+        path.close_path();
+        // An implicitly closed clockwise hole.
+        path.move_to((1.0, 1.0));
+        path.line_to((1.0, 2.0));
+        path.line_to((2.0, 2.0));
+        path.line_to((2.0, 1.0));
+        // A lone move.
+        path.move_to((9.0, 9.0));
+        // A curved subpath, closed explicitly.
+        path.move_to((5.0, 0.0));
+        path.quad_to((6.0, 2.0), (7.0, 0.0));
+        path.close_path();
+        // Segments after a close continue from its start.
+        path.line_to((5.0, -1.0));
+        path.line_to((6.0, -1.0));
+        let areas = path.subpath_areas();
+        let expected: Vec<f64> = path.subpaths().map(|sub| sub.area()).collect();
+        assert_eq!(areas.len(), 5);
+        assert_eq!(areas.len(), expected.len());
+        for (a, e) in areas.iter().zip(&expected) {
+            assert_approx_eq(*a, *e);
+        }
+        assert_approx_eq(areas[0], 16.0);
+        assert_approx_eq(areas[1], -1.0);
+        assert_eq!(areas[2], 0.0);
+        assert_approx_eq(areas[3], -4.0 / 3.0);
+        assert_approx_eq(areas[4], 0.5);
+        assert_approx_eq(areas.iter().sum(), path.area());
+        assert!(BezPath::new().subpath_areas().is_empty());
     }
 
-    fn as_path_slice(&self) -> Option<&[PathEl]> {
-        Some(&self.0)
+    #[test]
+    fn test_vertices() {
+        let p = |x, y| Point::new(x, y);
+        let mut path = BezPath::new();
+        // A closed triangle with an explicit line back to the start.
+        path.move_to(p(0.0, 0.0));
+        path.line_to(p(2.0, 0.0));
+        path.quad_to(p(3.0, 1.0), p(2.0, 2.0));
+        path.line_to(p(0.0, 0.0));
+        path.close_path();
+        // Continuing after the close, and an implicitly closed curve.
+        path.line_to(p(-1.0, 0.0));
+        path.move_to(p(5.0, 5.0));
+        path.curve_to(p(6.0, 6.0), p(7.0, 6.0), p(8.0, 5.0));
+        path.close_path();
+        // An open subpath ending at its start keeps both.
+        path.move_to(p(9.0, 9.0));
+        path.line_to(p(9.0, 10.0));
+        path.line_to(p(9.0, 9.0));
+        let vertices: Vec<Point> = path.vertices().collect();
+        assert_eq!(
+            vertices,
+            vec![
+                p(0.0, 0.0),
+                p(2.0, 0.0),
+                p(2.0, 2.0),
+                p(-1.0, 0.0),
+                p(5.0, 5.0),
+                p(8.0, 5.0),
+                p(9.0, 9.0),
+                p(9.0, 10.0),
+                p(9.0, 9.0),
+            ]
+        );
+        assert_eq!(BezPath::new().vertices().count(), 0);
     }
-}
 
-/// Implements [`Shape`] for a slice of [`PathEl`], provided that the first element of the slice is
-/// not a `PathEl::ClosePath`. If it is, several of these functions will panic.
-///
-/// If the slice starts with `LineTo`, `QuadTo`, or `CurveTo`, it will be treated as a `MoveTo`.
-impl<'a> Shape for &'a [PathEl] {
-    type PathElementsIter = std::iter::Cloned<std::slice::Iter<'a, PathEl>>;
+    #[test]
+    fn test_to_fan() {
+        let p = |x, y| Point::new(x, y);
+        // A square with a point in the middle of an edge and a repeated
+        // point, open, and drawn in the direction of negative area.
+        let mut square = BezPath::new();
+        square.move_to(p(0.0, 0.0));
+        square.line_to(p(0.0, 2.0));
+        square.line_to(p(2.0, 2.0));
+        square.line_to(p(2.0, 2.0));
+        square.line_to(p(2.0, 1.0));
+        square.line_to(p(2.0, 0.0));
+        square.move_to(p(5.0, 5.0));
+        let fan = square.to_fan(0.1).unwrap();
+        assert_eq!(
+            fan,
+            vec![p(0.0, 0.0), p(0.0, 2.0), p(2.0, 2.0), p(2.0, 0.0)]
+        );
+        let fan_area: f64 = (1..fan.len() - 1)
+            .map(|i| 0.5 * (fan[i] - fan[0]).cross(fan[i + 1] - fan[0]))
+            .sum();
+        assert_approx_eq(fan_area, square.area());
 
-    #[inline]
-    fn path_elements(&self, _tolerance: f64) -> Self::PathElementsIter {
-        self.iter().cloned()
+        // A curved convex loop.
+        let mut lens = BezPath::new();
+        lens.move_to(p(0.0, 0.0));
+        lens.quad_to(p(5.0, -3.0), p(10.0, 0.0));
+        lens.quad_to(p(5.0, 3.0), p(0.0, 0.0));
+        lens.close_path();
+        let tolerance = 0.01;
+        let fan = lens.to_fan(tolerance).unwrap();
+        assert!(fan.len() > 6);
+        let fan_area: f64 = (1..fan.len() - 1)
+            .map(|i| 0.5 * (fan[i] - fan[0]).cross(fan[i + 1] - fan[0]))
+            .sum();
+        assert!((fan_area - lens.area()).abs() < 40.0 * tolerance);
+
+        // Not convex: a dent, an S-shaped lens, a star and two loops.
+        let mut dent = BezPath::new();
+        dent.move_to(p(0.0, 0.0));
+        dent.line_to(p(4.0, 0.0));
+        dent.line_to(p(2.0, 1.0));
+        dent.line_to(p(4.0, 4.0));
+        dent.line_to(p(0.0, 4.0));
+        dent.close_path();
+        assert_eq!(dent.to_fan(0.1), None);
+        let mut s_curve = BezPath::new();
+        s_curve.move_to(p(0.0, 0.0));
+        s_curve.curve_to(p(3.0, -3.0), p(7.0, 3.0), p(10.0, 0.0));
+        s_curve.line_to(p(5.0, 10.0));
+        s_curve.close_path();
+        assert_eq!(s_curve.to_fan(0.01), None);
+        let mut star = BezPath::new();
+        for i in 0..5 {
+            let th = i as f64 * 4.0 * std::f64::consts::PI / 5.0;
+            let pt = Point::new(th.cos(), th.sin());
+            if i == 0 {
+                star.move_to(pt);
+            } else {
+                star.line_to(pt);
+            }
+        }
+        star.close_path();
+        assert_eq!(star.to_fan(0.1), None);
+        let mut two = Rect::new(0.0, 0.0, 1.0, 1.0).to_path(0.1);
+        two.extend(Rect::new(2.0, 0.0, 3.0, 1.0).to_path(0.1));
+        assert_eq!(two.to_fan(0.1), None);
+        let mut flat = BezPath::new();
+        flat.move_to(p(0.0, 0.0));
+        flat.line_to(p(1.0, 0.0));
+        flat.line_to(p(2.0, 0.0));
+        assert_eq!(flat.to_fan(0.1), None);
+        assert_eq!(BezPath::new().to_fan(0.1), None);
     }
 
-    fn to_path(&self, _tolerance: f64) -> BezPath {
-        BezPath::from_vec(self.to_vec())
+    #[test]
+    fn test_transformed_bounding_box() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.curve_to((3.0, 4.0), (-1.0, 6.0), (5.0, 2.0));
+        path.quad_to((8.0, -3.0), (2.0, -1.0));
+        path.close_path();
+        for &affine in &[
+            Affine::IDENTITY,
+            Affine::rotate(0.7) * Affine::scale_non_uniform(2.0, -0.5),
+            Affine::new([1.0, 0.3, -2.0, 1.5, 4.0, -7.0]),
+        ] {
+            let expected = (affine * path.clone()).bounding_box();
+            assert_eq!(path.transformed_bounding_box(affine), expected);
+            assert!(affine
+                .transform_rect_bbox(path.bounding_box())
+                .contains(expected.center()));
+        }
+        let mut lines = BezPath::new();
+        lines.move_to((1.0, 1.0));
+        lines.line_to((3.0, 2.0));
+        lines.line_to((2.0, 5.0));
+        let affine = Affine::translate((1.0, -1.0)) * Affine::scale(2.0);
+        assert_eq!(
+            lines.transformed_bounding_box(affine),
+            Rect::new(3.0, 1.0, 7.0, 9.0)
+        );
+        assert_eq!(BezPath::new().transformed_bounding_box(affine), Rect::ZERO);
     }
 
-    /// Signed area.
-    fn area(&self) -> f64 {
-        segments(self.iter().copied()).area()
+    #[test]
+    fn test_intersect_circle() {
+        let circle = Circle::new((1.0, -0.5), 2.0);
+        let on_circle = |p: Point| ((p - circle.center).hypot() - circle.radius).abs();
+        let cubic = PathSeg::Cubic(CubicBez::new(
+            (-3.0, 0.0),
+            (0.0, 6.0),
+            (2.0, -6.0),
+            (5.0, 1.0),
+        ));
+        let ts = cubic.intersect_circle(circle, 1e-9);
+        // Count the crossings by sampling.
+        let n = 10_000;
+        let inside = |t: f64| (cubic.eval(t) - circle.center).hypot() < circle.radius;
+        let crossings = (0..n)
+            .filter(|&i| inside(i as f64 / n as f64) != inside((i + 1) as f64 / n as f64))
+            .count();
+        assert_eq!(ts.len(), crossings);
+        assert!(ts.len() >= 2);
+        for &t in &ts {
+            assert!(on_circle(cubic.eval(t)) < 1e-9);
+        }
+        assert!(ts.windows(2).all(|w| w[0] < w[1]));
+
+        // A parabola touching the unit circle at its apex, then lifted off
+        // by less and more than the tolerance.
+        let unit = Circle::new((0.0, 0.0), 1.0);
+        let tolerance = 1e-6;
+        for &(lift, expected) in &[(0.0, 1), (0.5 * tolerance, 1), (2.0 * tolerance, 0)] {
+            let quad = PathSeg::Quad(QuadBez::new(
+                (-1.0, 2.0 + lift),
+                (0.0, lift),
+                (1.0, 2.0 + lift),
+            ));
+            let ts = quad.intersect_circle(unit, tolerance);
+            assert_eq!(ts.len(), expected, "{}", lift);
+            if expected == 1 {
+                assert!((ts[0] - 0.5).abs() < 1e-9);
+            }
+        }
+
+        // A path approximating the circle touches it along its whole
+        // length, which is reported once.
+        let approx = circle.to_path(1e-4);
+        let hits = approx.intersect_circle(circle, 1e-3);
+        assert_eq!(hits.len(), 1);
+        assert!(on_circle(hits[0].2) < 1e-4);
+
+        let mut path = BezPath::new();
+        path.move_to((-5.0, -0.5));
+        path.line_to((5.0, -0.5));
+        path.quad_to((6.0, 3.0), (1.0, 1.5));
+        let hits = path.intersect_circle(circle, 1e-9);
+        assert_eq!(hits.len(), 3);
+        assert_eq!((hits[0].0, hits[1].0, hits[2].0), (0, 0, 1));
+        assert!((hits[0].2 - Point::new(-1.0, -0.5)).hypot() < 1e-12);
+        assert!((hits[1].2 - Point::new(3.0, -0.5)).hypot() < 1e-12);
+        assert!(BezPath::new().intersect_circle(circle, 1e-9).is_empty());
+
+        // A negative radius is the same circle.
+        let flipped = Circle::new(circle.center, -circle.radius);
+        assert_eq!(cubic.intersect_circle(flipped, 1e-9), ts);
+        let tangent = PathSeg::Line(Line::new((-2.0, 1.0), (2.0, 1.0)));
+        assert_eq!(
+            tangent.intersect_circle(Circle::new((0.0, 0.0), -1.0), 1e-9),
+            vec![0.5]
+        );
     }
 
-    fn perimeter(&self, accuracy: f64) -> f64 {
-        segments(self.iter().copied()).perimeter(accuracy)
+    #[test]
+    fn test_subdivide_equal_arclen() {
+        let cubic = PathSeg::Cubic(CubicBez::new(
+            (0.0, 0.0),
+            (0.0, 5.0),
+            (1.0, 5.0),
+            (8.0, -1.0),
+        ));
+        let accuracy = 1e-9;
+        let total = cubic.arclen(accuracy);
+        let pieces = cubic.subdivide_equal_arclen(7, accuracy);
+        assert_eq!(pieces.len(), 7);
+        for piece in &pieces {
+            assert!((piece.arclen(accuracy) - total / 7.0).abs() < 1e-7);
+        }
+        assert_eq!(pieces[0].start(), cubic.start());
+        assert_eq!(pieces[6].end(), cubic.end());
+        for w in pieces.windows(2) {
+            assert_eq!(w[0].end(), w[1].start());
+        }
+        // The pieces follow the original curve.
+        let mid = pieces[3].eval(0.5);
+        assert!(cubic.nearest(mid, 1e-9).1 < 1e-12);
+
+        let line = PathSeg::Line(Line::new((0.0, 0.0), (3.0, 0.0)));
+        let thirds = line.subdivide_equal_arclen(3, accuracy);
+        assert!((thirds[1].start() - Point::new(1.0, 0.0)).hypot() < 1e-9);
+        let point = PathSeg::Line(Line::new((1.0, 1.0), (1.0, 1.0)));
+        assert_eq!(point.subdivide_equal_arclen(4, accuracy).len(), 4);
+        assert!(line.subdivide_equal_arclen(0, accuracy).is_empty());
+        // A closed quad, where the arc length inverse gives NaN.
+        let closed = PathSeg::Quad(QuadBez::new((7.06, 2.8), (-2.69, 8.41), (7.06, 2.8)));
+        let pieces = closed.subdivide_equal_arclen(5, accuracy);
+        assert_eq!(pieces.len(), 5);
+        assert_eq!(pieces[4].end(), closed.end());
+        for w in pieces.windows(2) {
+            assert_eq!(w[0].end(), w[1].start());
+        }
     }
 
-    /// Winding number of point.
-    fn winding(&self, pt: Point) -> i32 {
-        segments(self.iter().copied()).winding(pt)
+    #[test]
+    fn test_farthest_point() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.quad_to((3.0, 4.0), (6.0, 0.0));
+        path.curve_to((6.0, -3.0), (2.0, -5.0), (0.0, -1.0));
+        let pt = Point::new(1.0, 0.5);
+        let (far, d) = path.farthest_point(pt, 1e-9);
+        assert_approx_eq(far.distance(pt), d);
+        let mut best = 0.0f64;
+        for seg in path.segments() {
+            for i in 0..=10_000 {
+                best = best.max(seg.eval(i as f64 / 10_000.0).distance(pt));
+            }
+        }
+        assert!(d >= best - 1e-9 && d < best + 1e-6);
+
+        // An endpoint is the farthest point of a line.
+        let line = PathSeg::Line(Line::new((0.0, 0.0), (4.0, 0.0)));
+        assert_eq!(line.farthest(Point::new(1.0, 1.0), 1e-9), (1.0, 10.0));
+        // And of a curve when the query is beyond its start.
+        let quad = PathSeg::Quad(QuadBez::new((0.0, 0.0), (1.0, 1.0), (2.0, 0.0)));
+        assert_eq!(quad.farthest(Point::new(3.0, 0.0), 1e-9).0, 0.0);
+        assert_eq!(BezPath::new().farthest_point(pt, 1e-9), (pt, 0.0));
     }
 
-    fn bounding_box(&self) -> Rect {
-        segments(self.iter().copied()).bounding_box()
+    #[test]
+    fn test_on_boundary() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0))
+            .line_to((4.0, 0.0))
+            .quad_to((6.0, 2.0), (4.0, 4.0))
+            .close_path();
+        let (ix, t) = path.on_boundary(Point::new(2.0, 0.05), 0.1).unwrap();
+        assert_eq!(ix, 0);
+        assert_approx_eq(t, 0.5);
+        // Near the corner at (4, 0), on the side of the quadratic.
+        let (ix, t) = path.on_boundary(Point::new(4.08, 0.05), 0.1).unwrap();
+        assert_eq!(ix, 1);
+        assert!(t < 0.1);
+        // On the closing line, from inside and outside the path.
+        assert_eq!(path.on_boundary(Point::new(2.0, 2.05), 0.1).unwrap().0, 2);
+        assert_eq!(path.on_boundary(Point::new(2.0, 1.95), 0.1).unwrap().0, 2);
+        assert!(path.on_boundary(Point::new(3.0, 1.0), 0.1).is_none());
+        assert!(path.on_boundary(Point::new(10.0, 0.0), 0.1).is_none());
+        assert!(BezPath::new().on_boundary(Point::ORIGIN, 1.0).is_none());
     }
 
-    #[inline]
-    fn as_path_slice(&self) -> Option<&[PathEl]> {
-        Some(self)
+    #[test]
+    fn test_insert_point() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0))
+            .curve_to((1.0, 2.0), (3.0, 2.0), (4.0, 0.0))
+            .quad_to((5.0, -2.0), (6.0, 0.0))
+            .close_path();
+        let original = path.clone();
+        // On the cubic.
+        assert!(path.insert_point(Point::new(2.0, 2.0), 1e-9));
+        assert_eq!(path.elements().len(), 5);
+        let pt = path.elements()[1].points()[2];
+        assert!((pt - Point::new(2.0, 1.5)).hypot() < 1e-6);
+        // On the closing line.
+        assert!(path.insert_point(Point::new(3.0, -0.5), 1e-9));
+        assert_eq!(path.elements().len(), 6);
+        assert!(matches!(path.elements()[4], PathEl::LineTo(p) if p == Point::new(3.0, 0.0)));
+        // On the quadratic.
+        assert!(path.insert_point(Point::new(5.0, -2.0), 1e-9));
+        assert_eq!(path.elements().len(), 7);
+        assert!(matches!(path.elements()[3], PathEl::QuadTo(..)));
+        // The shape is unchanged.
+        assert_approx_eq(path.area(), original.area());
+        for i in 0..=20 {
+            let p = original
+                .segments()
+                .nth(i % 3)
+                .unwrap()
+                .eval((i / 3) as f64 / 7.0);
+            assert!(path.nearest(p, 1e-9).2 < 1e-12);
+        }
+        // Nothing to insert at an existing vertex, or into an empty path.
+        assert!(!path.insert_point(Point::new(-1.0, -1.0), 1e-9));
+        assert!(!BezPath::new().insert_point(Point::ZERO, 1e-9));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_remove_anchor() {
+        // Splitting a cubic and removing the new point restores it.
+        let c = CubicBez::new((0.0, 0.0), (1.0, 3.0), (4.0, 3.0), (6.0, 0.0));
+        let (a, b) = c.split_at(0.4);
+        let mut path = BezPath::new();
+        path.move_to(a.p0)
+            .curve_to(a.p1, a.p2, a.p3)
+            .curve_to(b.p1, b.p2, b.p3)
+            .line_to((6.0, -2.0));
+        assert!(path.remove_anchor(1, 1e-6));
+        assert_eq!(path.elements().len(), 3);
+        match path.elements()[1] {
+            PathEl::CurveTo(p1, p2, p3) => {
+                assert!((p1 - c.p1).hypot() < 1e-4);
+                assert!((p2 - c.p2).hypot() < 1e-4);
+                assert_eq!(p3, c.p3);
+            }
+            _ => panic!("expected a cubic"),
+        }
+        // A corner between two lines cannot be removed within tolerance.
+        let mut square = Rect::new(0.0, 0.0, 1.0, 1.0).to_path(1e-9);
+        assert!(!square.remove_anchor(1, 1e-3));
+        assert!(!square.remove_anchor(4, 1e-3));
+        // Nor can the end of an open subpath.
+        assert!(!path.remove_anchor(2, 1e-3));
+        // An anchor on the closing line's start.
+        let mut arc = BezPath::new();
+        arc.move_to((1.0, 0.0))
+            .quad_to((1.0, 1.0), (0.0, 1.0))
+            .quad_to((-1.0, 1.0), (-1.0, 0.0));
+        assert!(arc.remove_anchor(1, 0.05));
+        assert_eq!(arc.elements().len(), 2);
+        let mut t = arc.clone();
+        t.close_path();
+        assert!(!t.remove_anchor(1, 0.05));
+        // Refitting that runs away with the handles gives up with a bounded
+        // fit rather than diverging.
+        let mut wild = BezPath::new();
+        wild.move_to((-5.44, 3.47))
+            .line_to((-6.25, 0.54))
+            .quad_to((7.32, 7.06), (9.28, 8.31))
+            .curve_to((-9.59, -6.88), (-6.27, 6.57), (5.32, 8.94));
+        for &tolerance in &[1e-3, 0.1, 1.0, 10.0] {
+            let mut p = wild.clone();
+            if p.remove_anchor(2, tolerance) {
+                assert!(p.bounding_box().width() < 100.0);
+            } else {
+                assert_eq!(p, wild);
+            }
+        }
+    }
 
-    fn assert_approx_eq(x: f64, y: f64) {
-        assert!((x - y).abs() < 1e-8, "{} != {}", x, y);
+    #[test]
+    fn test_path_seg_param_curve() {
+        let segs = [
+            PathSeg::Line(Line::new((1.0, 2.0), (4.0, -2.0))),
+            PathSeg::Quad(QuadBez::new((0.0, 0.0), (2.0, 3.0), (5.0, 1.0))),
+            PathSeg::Cubic(CubicBez::new(
+                (0.0, 0.0),
+                (1.0, 3.0),
+                (4.0, -1.0),
+                (6.0, 2.0),
+            )),
+        ];
+        let h = 1e-6;
+        for seg in &segs {
+            let (d, d2) = (seg.deriv(), seg.deriv().deriv());
+            for &t in &[0.0, 0.3, 0.7, 1.0] {
+                let fd = (seg.eval(t + h) - seg.eval(t - h)) / (2.0 * h);
+                assert!((d.eval(t).to_vec2() - fd).hypot() < 1e-6);
+                let fd2 = (d.eval(t + h) - d.eval(t - h)) / (2.0 * h);
+                assert!((d2.eval(t).to_vec2() - fd2).hypot() < 1e-6);
+            }
+            let k = match *seg {
+                PathSeg::Line(l) => l.curvature(0.4),
+                PathSeg::Quad(q) => q.curvature(0.4),
+                PathSeg::Cubic(c) => c.curvature(0.4),
+            };
+            assert_eq!(seg.curvature(0.4), k);
+        }
+        assert_eq!(segs[0].curvature(0.5), 0.0);
+        assert!(matches!(segs[0].deriv(), PathSeg::Line(l) if l.p0 == l.p1));
+        assert!(matches!(segs[1].deriv(), PathSeg::Line(_)));
+        assert!(matches!(segs[2].deriv(), PathSeg::Quad(_)));
     }
 
     #[test]
-    #[should_panic(expected = "Can't start a segment on a ClosePath")]
-    fn test_elements_to_segments_starts_on_closepath() {
+    fn test_area_winding_of() {
+        let circle = crate::Circle::new((0.0, 0.0), 2.0);
+        let area = area_of(circle.path_elements(1e-9));
+        assert_approx_eq(area, circle.to_path(1e-9).area());
+        assert_eq!(winding_of(circle.path_elements(1e-9), Point::ORIGIN), 1);
+
+        // Two subpaths, the second left open and following a `ClosePath`.
         let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((2.0, 0.0));
+        path.line_to((2.0, 2.0));
         path.close_path();
-        path.segments().next();
+        path.line_to((0.0, -2.0));
+        path.line_to((-2.0, -2.0));
+        let els = || path.elements().iter().copied();
+        assert_approx_eq(area_of(els()), path.area());
+        assert_approx_eq(area_of(els()), 0.0);
+        for &pt in &[(1.5, 0.5), (-0.5, -1.5), (1.0, 1.5)] {
+            let pt = Point::from(pt);
+            assert_eq!(winding_of(els(), pt), path.winding(pt));
+        }
+        assert_eq!(winding_of(els(), Point::new(1.5, 0.5)), 1);
+        assert_eq!(winding_of(els(), Point::new(-0.5, -1.5)), -1);
     }
 
     #[test]
-    fn test_elements_to_segments_closepath_refers_to_last_moveto() {
+    fn test_from_tt_points() {
+        let on = |x: f64, y: f64| (Point::new(x, y), true);
+        let off = |x: f64, y: f64| (Point::new(x, y), false);
+        // A rounded shape starting off the curve, with two consecutive
+        // off-curve points and a straight edge.
+        let pts = [
+            off(2.0, 2.0),
+            on(0.0, 2.0),
+            on(0.0, 0.0),
+            off(2.0, 0.0),
+            off(4.0, 0.0),
+            on(4.0, 2.0),
+        ];
+        let path = BezPath::from_tt_points(&pts, true);
+        let els = path.elements();
+        assert!(matches!(els[0], PathEl::MoveTo(p) if p == Point::new(0.0, 2.0)));
+        assert!(matches!(els[1], PathEl::LineTo(p) if p == Point::new(0.0, 0.0)));
+        assert!(matches!(els[2], PathEl::QuadTo(_, p) if p == Point::new(3.0, 0.0)));
+        assert!(matches!(els[3], PathEl::QuadTo(c, _) if c == Point::new(4.0, 0.0)));
+        // The wrap: from (4, 2) through the leading off-curve point.
+        assert!(matches!(els[4], PathEl::QuadTo(c, p)
+            if c == Point::new(2.0, 2.0) && p == Point::new(0.0, 2.0)));
+        assert!(matches!(els[5], PathEl::ClosePath));
+        assert_eq!(els.len(), 6);
+
+        // A closing line is left implicit.
+        let tri = BezPath::from_tt_points(&[on(0.0, 0.0), on(1.0, 0.0), on(0.0, 1.0)], true);
+        assert_eq!(tri.elements().len(), 4);
+        assert_approx_eq(tri.area(), 0.5);
+
+        // Only off-curve points: a closed curve through the midpoints.
+        let square = [
+            off(1.0, 1.0),
+            off(-1.0, 1.0),
+            off(-1.0, -1.0),
+            off(1.0, -1.0),
+        ];
+        let path = BezPath::from_tt_points(&square, true);
+        assert_eq!(path.elements().len(), 6);
+        assert_approx_eq(path.area(), 2.0 + 4.0 * 2.0 / 3.0 * 0.5);
+
+        // Open contours end at their first and last points.
+        let open = BezPath::from_tt_points(&[off(0.0, 0.0), off(1.0, 1.0), off(2.0, 0.0)], false);
+        let segs: Vec<PathSeg> = open.segments().collect();
+        assert_eq!(segs.len(), 1);
+        assert_eq!(segs[0].start(), Point::new(0.0, 0.0));
+        assert_eq!(segs[0].end(), Point::new(2.0, 0.0));
+        assert!(BezPath::from_tt_points(&[], true).is_empty());
+    }
+
+    #[test]
+    fn test_winding_grid() {
+        // Overlapping subpaths with curves, a horizontal edge and vertices
+        // at grid points, one open.
         let mut path = BezPath::new();
-        path.move_to((5.0, 5.0));
-        path.line_to((15.0, 15.0));
-        path.move_to((10.0, 10.0));
-        path.line_to((15.0, 15.0));
+        path.move_to((0.0, 0.0));
+        path.curve_to((8.0, -3.0), (12.0, 9.0), (2.0, 8.0));
+        path.line_to((2.0, 4.0));
+        path.line_to((0.0, 4.0));
         path.close_path();
+        path.move_to((1.0, 1.0));
+        path.quad_to((9.0, 2.0), (5.0, 6.0));
+        path.line_to((1.0, 6.0));
+        let origin = Point::new(-1.0, -2.0);
+        for &spacing in &[0.25, 1.0 / 3.0, -0.5] {
+            let (cols, rows) = (50, 45);
+            let grid = path.winding_grid(origin, spacing, cols, rows);
+            assert_eq!(grid.len(), cols * rows);
+            for j in 0..rows {
+                for i in 0..cols {
+                    let pt =
+                        Point::new(origin.x + i as f64 * spacing, origin.y + j as f64 * spacing);
+                    assert_eq!(grid[j * cols + i], path.winding(pt), "{:?}", pt);
+                }
+            }
+        }
+        assert!(path.winding_grid(origin, 1.0, 0, 5).is_empty());
+        assert!(BezPath::new()
+            .winding_grid(origin, 1.0, 3, 3)
+            .iter()
+            .all(|&w| w == 0));
+    }
+
+    #[test]
+    fn test_diff() {
+        let p = |x, y| Point::new(x, y);
+        let mut a = BezPath::new();
+        a.move_to(p(0.0, 0.0));
+        a.line_to(p(1.0, 0.0));
+        a.quad_to(p(2.0, 1.0), p(3.0, 0.0));
+        a.line_to(p(3.0, 3.0));
+        a.close_path();
+        a.move_to(p(5.0, 5.0));
+        a.line_to(p(6.0, 5.0));
+
+        let mut b = BezPath::new();
+        b.move_to(p(0.0, 0.0));
+        b.quad_to(p(2.0, 1.5), p(3.0, 0.0));
+        b.line_to(p(3.0, 3.0));
+        b.line_to(p(0.0, 3.0));
+        b.close_path();
+        b.move_to(p(5.0, 5.0));
+        b.line_to(p(6.0, 5.0));
+        b.line_to(p(6.0, 6.0));
+
+        let edits = a.diff(&b);
+        // Delete the first line, change the quad, and add two lines.
+        assert_eq!(edits.len(), 4);
+        let mut edited = a.clone();
+        edited.apply_edits(&edits);
+        assert_eq!(edited, b);
+        let mut back = b.clone();
+        back.apply_edits(&b.diff(&a));
+        assert_eq!(back, a);
+
+        assert!(a.diff(&a).is_empty());
+        let empty = BezPath::new();
+        let mut built = empty.clone();
+        built.apply_edits(&empty.diff(&b));
+        assert_eq!(built, b);
+        assert_eq!(a.diff(&empty).len(), a.elements().len());
+        assert!(a
+            .diff(&empty)
+            .iter()
+            .all(|edit| *edit == PathEdit::Delete(0)));
+
+        // Longer sequences that are split before solving still get a
+        // shortest script.
+        let mut c = BezPath::new();
+        let mut d = BezPath::new();
+        for i in 0..40 {
+            let x = i as f64;
+            c.line_to(p(x, (i % 3) as f64));
+            d.line_to(p(x, (i % 4) as f64));
+        }
+        let (ce, de) = (c.elements(), d.elements());
+        let edits = c.diff(&d);
         assert_eq!(
-            path.segments().collect::<Vec<_>>().last(),
-            Some(&Line::new((15.0, 15.0), (10.0, 10.0)).into()),
+            edits.len(),
+            edit_row(ce.len(), de.len(), |i, j| ce[i] == de[j])[de.len()]
         );
+        let mut edited = c.clone();
+        edited.apply_edits(&edits);
+        assert_eq!(edited, d);
     }
 
     #[test]
-    fn test_elements_to_segments_starts_on_quad() {
+    fn test_degree_verb() {
         let mut path = BezPath::new();
-        path.quad_to((5.0, 5.0), (10.0, 10.0));
-        path.line_to((15.0, 15.0));
-        path.close_path();
-
-        let mut segments = path.segments();
+        path.move_to((0.0, 0.0))
+            .line_to((1.0, 0.0))
+            .quad_to((2.0, 0.0), (2.0, 1.0))
+            .curve_to((2.0, 2.0), (1.0, 2.0), (0.0, 1.0))
+            .close_path();
+        let verbs: Vec<PathVerb> = path.iter().map(|el| el.verb()).collect();
         assert_eq!(
-            segments.next(),
-            Some(QuadBez::new((10.0, 10.0), (5.0, 5.0), (10.0, 10.0)).into()),
+            verbs,
+            [
+                PathVerb::MoveTo,
+                PathVerb::LineTo,
+                PathVerb::QuadTo,
+                PathVerb::CurveTo,
+                PathVerb::ClosePath
+            ]
         );
+        let degrees: Vec<u8> = path.segments().map(|seg| seg.degree()).collect();
+        assert_eq!(degrees, [1, 2, 3, 1]);
+        const LINE: PathSeg = PathSeg::Line(Line {
+            p0: Point::ORIGIN,
+            p1: Point::ORIGIN,
+        });
+        const DEGREE: u8 = LINE.degree();
+        assert_eq!(DEGREE, 1);
+    }
+
+    #[test]
+    fn test_to_nonzero() {
+        // Nested squares, all counterclockwise, with a circle beside them.
+        let mut path = BezPath::new();
+        for &(c, r) in &[(5.0, 5.0), (5.0, 3.0), (5.0, 1.0), (5.0, 0.5)] {
+            path.extend(Rect::new(c - r, c - r, c + r, c + r).to_path(1e-9));
+        }
+        path.extend(crate::Circle::new((20.0, 5.0), 2.0).to_path(1e-9));
+        let fixed = path.to_nonzero(0.1);
+        assert_eq!(fixed.elements().len(), path.elements().len());
+        for &x in &[1.0, 3.0, 4.7, 4.8, 5.0, 8.0, 20.0, 30.0] {
+            let pt = Point::new(x, 5.1);
+            let even_odd = FillRule::EvenOdd.is_inside(path.winding(pt));
+            assert_eq!(FillRule::NonZero.is_inside(fixed.winding(pt)), even_odd);
+            assert_eq!(FillRule::EvenOdd.is_inside(fixed.winding(pt)), even_odd);
+        }
+        // The outer subpaths keep their direction.
+        assert!(fixed.subpaths().next().unwrap().area() > 0.0);
+        assert!(fixed.subpaths().nth(1).unwrap().area() < 0.0);
+        assert!(fixed.subpaths().nth(4).unwrap().area() > 0.0);
+        // An already correct path, clockwise on the outside, is unchanged.
+        let flipped = fixed.with_flipped_y();
         assert_eq!(
-            segments.next(),
-            Some(Line::new((10.0, 10.0), (15.0, 15.0)).into()),
+            format!("{:?}", flipped.to_nonzero(0.1)),
+            format!("{:?}", flipped)
         );
-        assert_eq!(
-            segments.next(),
-            Some(Line::new((15.0, 15.0), (10.0, 10.0)).into()),
+    }
+
+    #[test]
+    fn test_is_straight() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0)).line_to((3.0, 0.01)).curve_to(
+            (4.0, 0.0),
+            (5.0, -0.01),
+            (8.0, 0.0),
         );
-        assert_eq!(segments.next(), None);
+        assert!(path.is_straight(0.1));
+        assert!(!path.is_straight(0.001));
+        // Back and forth along a line.
+        let mut back = BezPath::new();
+        back.move_to((0.0, 0.0))
+            .line_to((5.0, 0.0))
+            .line_to((3.0, 0.0));
+        assert!(!back.is_straight(0.1));
+        // A cubic that overshoots its end and comes back.
+        let mut overshoot = BezPath::new();
+        overshoot
+            .move_to((0.0, 0.0))
+            .curve_to((6.0, 0.0), (6.0, 0.0), (2.0, 0.0));
+        assert!(!overshoot.is_straight(0.1));
+        let rect = Rect::new(0.0, 0.0, 1.0, 1e-3).to_path(1e-9);
+        assert!(!rect.is_straight(0.1));
+        assert!(rect.is_straight(2.0));
+        assert!(BezPath::new().is_straight(0.1));
     }
 
     #[test]
-    fn test_intersect_line() {
-        let h_line = Line::new((0.0, 0.0), (100.0, 0.0));
-        let v_line = Line::new((10.0, -10.0), (10.0, 10.0));
-        let intersection = PathSeg::Line(h_line).intersect_line(v_line)[0];
-        assert_approx_eq(intersection.segment_t, 0.1);
-        assert_approx_eq(intersection.line_t, 0.5);
+    fn test_total_absolute_curvature() {
+        use std::f64::consts::PI;
+        let circle = crate::Circle::new((0.0, 0.0), 3.0).to_path(1e-9);
+        assert_approx_eq(circle.total_absolute_curvature(1e-9), 2.0 * PI);
+        // Reversing and reflecting don't change it.
+        let flipped = circle.with_flipped_y();
+        assert_approx_eq(flipped.total_absolute_curvature(1e-9), 2.0 * PI);
+        // The exterior angles of a polyline, and a reversal.
+        let mut poly = BezPath::new();
+        poly.move_to((0.0, 0.0))
+            .line_to((1.0, 0.0))
+            .line_to((1.0, 1.0))
+            .line_to((0.0, 1.0))
+            .line_to((2.0, 1.0));
+        assert_approx_eq(poly.total_absolute_curvature(1e-9), 2.0 * PI);
+        // An S curve turns both ways, and a loop turns by more than π.
+        let mut s = BezPath::new();
+        s.move_to((0.0, 0.0))
+            .curve_to((1.0, 1.0), (2.0, -1.0), (3.0, 0.0));
+        let seg = s.segments().next().unwrap();
+        let (a, b) = (
+            seg_tangent(seg, 0.0).unwrap(),
+            seg_tangent(seg, 0.5).unwrap(),
+        );
+        assert_approx_eq(
+            s.total_absolute_curvature(1e-9),
+            2.0 * a.angle_between(b).abs(),
+        );
+        let mut lp = BezPath::new();
+        lp.move_to((0.0, 0.0))
+            .curve_to((3.0, 3.0), (-1.0, 3.0), (2.0, 0.0));
+        let total = lp.total_absolute_curvature(1e-9);
+        assert!(total > PI, "{}", total);
+        let mut flat = BezPath::new();
+        lp.flatten(1e-6, |el| flat.push(el));
+        assert!((total - flat.total_absolute_curvature(1e-9)).abs() < 1e-2);
+    }
 
-        let v_line = Line::new((-10.0, -10.0), (-10.0, 10.0));
-        assert!(PathSeg::Line(h_line).intersect_line(v_line).is_empty());
+    #[test]
+    fn test_sample_frames() {
+        // An open S curve, with an inflection.
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.curve_to((10.0, 0.0), (0.0, 10.0), (10.0, 10.0));
+        let len = path.perimeter(1e-9);
+        let frames = path.sample_frames(1.0, 1e-9);
+        let n = (len / 1.0).ceil() as usize;
+        assert_eq!(frames.len(), n + 1);
+        assert_eq!(frames[0].0, Point::new(0.0, 0.0));
+        assert!((frames[n].0 - Point::new(10.0, 10.0)).hypot() < 1e-9);
+        for w in frames.windows(2) {
+            assert!((w[0].0.distance(w[1].0) - len / n as f64).abs() < 0.05);
+            assert!(w[0].2.dot(w[1].2) > 0.5);
+        }
+        for &(_, t, nrm) in &frames {
+            assert_approx_eq(t.hypot(), 1.0);
+            assert_approx_eq(t.cross(nrm), 1.0);
+        }
 
-        let v_line = Line::new((10.0, 10.0), (10.0, 20.0));
-        assert!(PathSeg::Line(h_line).intersect_line(v_line).is_empty());
+        // A closed square wraps around without repeating its start.
+        let square = Rect::new(0.0, 0.0, 4.0, 4.0).to_path(1e-9);
+        let frames = square.sample_frames(1.0, 1e-9);
+        assert_eq!(frames.len(), 16);
+        assert_eq!(frames[1].0, Point::new(1.0, 0.0));
+        assert_eq!(frames[15].0, Point::new(0.0, 1.0));
+
+        // Doubling back keeps the normal on the same side.
+        let mut spike = BezPath::new();
+        spike.move_to((0.0, 0.0));
+        spike.line_to((2.0, 0.0));
+        spike.line_to((0.0, 0.0));
+        let frames = spike.sample_frames(0.5, 1e-9);
+        assert_eq!(frames.len(), 9);
+        assert!(frames.iter().all(|f| f.2 == Vec2::new(0.0, 1.0)));
+        assert!(path.sample_frames(0.0, 1e-9).is_empty());
     }
 
     #[test]
-    fn test_intersect_qad() {
-        let q = QuadBez::new((0.0, -10.0), (10.0, 20.0), (20.0, -10.0));
-        let v_line = Line::new((10.0, -10.0), (10.0, 10.0));
-        assert_eq!(PathSeg::Quad(q).intersect_line(v_line).len(), 1);
-        let intersection = PathSeg::Quad(q).intersect_line(v_line)[0];
-        assert_approx_eq(intersection.segment_t, 0.5);
-        assert_approx_eq(intersection.line_t, 0.75);
+    fn test_explode_from_segments() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((4.0, 0.0));
+        path.curve_to((5.0, 1.0), (5.0, 3.0), (4.0, 4.0));
+        path.close_path();
+        path.move_to((10.0, 0.0));
+        path.quad_to((11.0, 1.0), (12.0, 0.0));
+        path.line_to((13.0, 0.0));
+        let segs = path.explode();
+        assert_eq!(segs, path.segments().collect::<Vec<_>>());
+        assert_eq!(segs.len(), 5);
+        // The closing line is left to the `ClosePath`, giving back the path.
+        assert_eq!(BezPath::from_segments(&segs), path);
 
-        let h_line = Line::new((0.0, 0.0), (100.0, 0.0));
-        assert_eq!(PathSeg::Quad(q).intersect_line(h_line).len(), 2);
+        // Ends within epsilon are joined, and a chain within epsilon of its
+        // start is closed.
+        let nudge = Vec2::new(1e-12, -1e-12);
+        let mut moved = segs.clone();
+        moved[1] = PathSeg::Cubic(CubicBez::new(
+            Point::new(4.0, 0.0) + nudge,
+            Point::new(5.0, 1.0),
+            Point::new(5.0, 3.0),
+            Point::new(4.0, 4.0),
+        ));
+        moved[2] = PathSeg::Line(Line::new((4.0, 4.0), Point::new(0.0, 0.0) + nudge));
+        let rebuilt = BezPath::from_segments(&moved);
+        assert_eq!(rebuilt.elements().len(), path.elements().len());
+        assert_eq!(rebuilt.elements()[3], PathEl::ClosePath);
+
+        // A gap starts a new subpath, and a chain that does not come back is
+        // left open.
+        let gap = [
+            PathSeg::Line(Line::new((0.0, 0.0), (1.0, 0.0))),
+            PathSeg::Line(Line::new((1.0, 1e-3), (2.0, 0.0))),
+        ];
+        let rebuilt = BezPath::from_segments(&gap);
+        assert_eq!(
+            rebuilt.elements(),
+            &[
+                PathEl::MoveTo(Point::new(0.0, 0.0)),
+                PathEl::LineTo(Point::new(1.0, 0.0)),
+                PathEl::MoveTo(Point::new(1.0, 1e-3)),
+                PathEl::LineTo(Point::new(2.0, 0.0)),
+            ]
+        );
+        // A single closed curve keeps its segment.
+        let lobe = [PathSeg::Cubic(CubicBez::new(
+            (0.0, 0.0),
+            (1.0, 1.0),
+            (-1.0, 1.0),
+            (0.0, 0.0),
+        ))];
+        assert_eq!(BezPath::from_segments(&lobe).explode(), lobe);
+        assert!(BezPath::from_segments(&[]).elements().is_empty());
     }
 
     #[test]
-    fn test_intersect_cubic() {
-        let c = CubicBez::new((0.0, -10.0), (10.0, 20.0), (20.0, -20.0), (30.0, 10.0));
-        let v_line = Line::new((10.0, -10.0), (10.0, 10.0));
-        assert_eq!(PathSeg::Cubic(c).intersect_line(v_line).len(), 1);
-        let intersection = PathSeg::Cubic(c).intersect_line(v_line)[0];
-        assert_approx_eq(intersection.segment_t, 0.333333333);
-        assert_approx_eq(intersection.line_t, 0.592592592);
+    fn test_tick_marks() {
+        // Whether two lines cross away from their ends.
+        let crosses = |a: Line, b: Line| {
+            let inside = |t: f64| t > 1e-9 && t < 1.0 - 1e-9;
+            PathSeg::Line(a)
+                .intersect_line(b)
+                .iter()
+                .any(|hit| inside(hit.line_t) && inside(hit.segment_t))
+        };
 
-        let h_line = Line::new((0.0, 0.0), (100.0, 0.0));
-        assert_eq!(PathSeg::Cubic(c).intersect_line(h_line).len(), 3);
+        // A closed square wraps around, with a diagonal tick on each corner.
+        let square = Rect::new(0.0, 0.0, 10.0, 10.0).to_path(1e-9);
+        let ticks = square.tick_marks(2.5, 1.0, 1e-9);
+        assert_eq!(ticks.len(), 16);
+        let d = 0.5 * 0.5f64.sqrt();
+        assert!((ticks[0].p0 - Point::new(-d, -d)).hypot() < 1e-12);
+        assert!((ticks[0].p1 - Point::new(d, d)).hypot() < 1e-12);
+        assert!((ticks[4].midpoint() - Point::new(10.0, 0.0)).hypot() < 1e-12);
+        assert_eq!(ticks[1], Line::new((2.5, -0.5), (2.5, 0.5)));
+        assert_eq!(ticks[15], Line::new((-0.5, 2.5), (0.5, 2.5)));
+        for (i, a) in ticks.iter().enumerate() {
+            assert_approx_eq(a.length(), 1.0);
+            for b in &ticks[i + 1..] {
+                assert!(!crosses(*a, *b));
+            }
+        }
+
+        // An open path that is not a multiple of the spacing long.
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.curve_to((10.0, 0.0), (0.0, 10.0), (10.0, 10.0));
+        let len = path.perimeter(1e-9);
+        let ticks = path.tick_marks(1.0, 0.2, 1e-9);
+        assert_eq!(ticks.len(), len.floor() as usize + 1);
+        let seg = path.segments().next().unwrap();
+        for (k, tick) in ticks.iter().enumerate() {
+            let t = seg.inv_arclen(k as f64, 1e-9);
+            assert!((seg.eval(t) - tick.midpoint()).hypot() < 1e-9);
+            assert_approx_eq(tick.length(), 0.2);
+            assert!(seg_tangent(seg, t).unwrap().dot(tick.p1 - tick.p0).abs() < 1e-9);
+        }
+
+        // The ticks on either side of a sharp corner are cut where they meet,
+        // and no longer cross.
+        let mut spike = BezPath::new();
+        spike.move_to((0.0, 0.0));
+        spike.line_to((10.0, 0.0));
+        spike.line_to((0.0, 1.0));
+        let ticks = spike.tick_marks(0.5, 2.0, 1e-9);
+        assert!(ticks.iter().any(|tick| tick.length() < 2.0 - 1e-6));
+        for w in ticks.windows(2) {
+            assert!(!crosses(w[0], w[1]));
+        }
+        assert!(path.tick_marks(0.0, 1.0, 1e-9).is_empty());
     }
 }
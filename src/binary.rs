@@ -0,0 +1,214 @@
+//! A compact binary encoding of paths.
+
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use crate::{BezPath, PathEl, Point};
+
+/// The version of the encoding written by [`BezPath::to_bytes`].
+///
+/// [`BezPath::to_bytes`]: struct.BezPath.html#method.to_bytes
+const FORMAT_VERSION: u8 = 1;
+
+const TAG_MOVE_TO: u8 = 0;
+const TAG_LINE_TO: u8 = 1;
+const TAG_QUAD_TO: u8 = 2;
+const TAG_CURVE_TO: u8 = 3;
+const TAG_CLOSE_PATH: u8 = 4;
+
+impl BezPath {
+    /// Encode the path in a compact binary format.
+    ///
+    /// The encoding is a version byte (currently 1), the number of elements
+    /// as a little-endian `u64`, then for each element a tag byte (0 to 4
+    /// for `MoveTo`, `LineTo`, `QuadTo`, `CurveTo` and `ClosePath`) followed
+    /// by the coordinates of its points as little-endian `f64`s, x before y.
+    /// It is read back, exactly, by [`from_bytes`].
+    ///
+    /// [`from_bytes`]: #method.from_bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let n_points: usize = self.elements().iter().map(|el| el.points().len()).sum();
+        let mut out = Vec::with_capacity(9 + self.elements().len() + 16 * n_points);
+        out.push(FORMAT_VERSION);
+        out.extend_from_slice(&(self.elements().len() as u64).to_le_bytes());
+        for el in self.elements() {
+            out.push(match el {
+                PathEl::MoveTo(_) => TAG_MOVE_TO,
+                PathEl::LineTo(_) => TAG_LINE_TO,
+                PathEl::QuadTo(..) => TAG_QUAD_TO,
+                PathEl::CurveTo(..) => TAG_CURVE_TO,
+                PathEl::ClosePath => TAG_CLOSE_PATH,
+            });
+            for p in el.points() {
+                out.extend_from_slice(&p.x.to_le_bytes());
+                out.extend_from_slice(&p.y.to_le_bytes());
+            }
+        }
+        out
+    }
+
+    /// Decode a path encoded by [`to_bytes`].
+    ///
+    /// The whole input must be a single encoded path: it is an error if it
+    /// ends early or continues after the last element, if the version is not
+    /// one this version of the crate can read, or if an element has an
+    /// unknown tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{BezPath, DecodeError};
+    /// let mut path = BezPath::new();
+    /// path.move_to((1.0, 2.0));
+    /// path.quad_to((3.0, 4.0), (5.0, 0.5));
+    /// let bytes = path.to_bytes();
+    /// assert_eq!(BezPath::from_bytes(&bytes), Ok(path));
+    /// assert_eq!(
+    ///     BezPath::from_bytes(&bytes[..bytes.len() - 1]),
+    ///     Err(DecodeError::UnexpectedEof)
+    /// );
+    /// ```
+    ///
+    /// [`to_bytes`]: #method.to_bytes
+    pub fn from_bytes(data: &[u8]) -> Result<BezPath, DecodeError> {
+        let mut reader = Reader { data };
+        let version = reader.byte()?;
+        if version != FORMAT_VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        let count = u64::from_le_bytes(reader.eight()?);
+        // Every element takes at least a byte, so a larger count is corrupt,
+        // and not trusted for the allocation.
+        if count > reader.data.len() as u64 {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        let mut path = BezPath::new();
+        for _ in 0..count {
+            let el = match reader.byte()? {
+                TAG_MOVE_TO => PathEl::MoveTo(reader.point()?),
+                TAG_LINE_TO => PathEl::LineTo(reader.point()?),
+                TAG_QUAD_TO => PathEl::QuadTo(reader.point()?, reader.point()?),
+                TAG_CURVE_TO => PathEl::CurveTo(reader.point()?, reader.point()?, reader.point()?),
+                TAG_CLOSE_PATH => PathEl::ClosePath,
+                tag => return Err(DecodeError::InvalidTag(tag)),
+            };
+            path.push(el);
+        }
+        if !reader.data.is_empty() {
+            return Err(DecodeError::TrailingData);
+        }
+        Ok(path)
+    }
+}
+
+/// An error which can be returned when decoding a path from bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The version byte is not one this version of the crate can read.
+    UnsupportedVersion(u8),
+    /// The input ended while still expecting data.
+    UnexpectedEof,
+    /// An element has an unknown tag byte.
+    InvalidTag(u8),
+    /// There is data after the last element.
+    TrailingData,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnsupportedVersion(v) => write!(f, "Unsupported format version {}", v),
+            DecodeError::UnexpectedEof => write!(f, "Unexpected end of data"),
+            DecodeError::InvalidTag(tag) => write!(f, "Invalid element tag {}", tag),
+            DecodeError::TrailingData => write!(f, "Unexpected data after the path"),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn slice(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.data.len() < len {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        let (head, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(head)
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.slice(1)?[0])
+    }
+
+    fn eight(&mut self) -> Result<[u8; 8], DecodeError> {
+        Ok(self.slice(8)?.try_into().unwrap())
+    }
+
+    fn point(&mut self) -> Result<Point, DecodeError> {
+        let x = f64::from_le_bytes(self.eight()?);
+        let y = f64::from_le_bytes(self.eight()?);
+        Ok(Point::new(x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, DecodeError, PathEl};
+
+    #[test]
+    fn binary_round_trip() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, -1.5));
+        path.line_to((1e300, f64::MIN_POSITIVE));
+        path.quad_to((2.0, 3.0), (4.0, 5.0));
+        path.curve_to((6.0, 7.0), (8.0, 9.0), (-0.0, 0.1));
+        path.close_path();
+        path.line_to((1.0, 1.0));
+        let bytes = path.to_bytes();
+        assert_eq!(bytes.len(), 9 + 6 + 16 * 8);
+        assert_eq!(bytes[0], 1);
+        let decoded = BezPath::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, path);
+        assert!(
+            matches!(decoded.elements()[3], PathEl::CurveTo(_, _, p) if p.x.is_sign_negative())
+        );
+        assert_eq!(
+            BezPath::from_bytes(&BezPath::new().to_bytes()),
+            Ok(BezPath::new())
+        );
+
+        // Every truncation is an error.
+        for len in 0..bytes.len() {
+            assert_eq!(
+                BezPath::from_bytes(&bytes[..len]),
+                Err(DecodeError::UnexpectedEof),
+                "{}",
+                len
+            );
+        }
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert_eq!(BezPath::from_bytes(&extra), Err(DecodeError::TrailingData));
+        let mut future = bytes.clone();
+        future[0] = 2;
+        assert_eq!(
+            BezPath::from_bytes(&future),
+            Err(DecodeError::UnsupportedVersion(2))
+        );
+        let mut corrupt = bytes.clone();
+        corrupt[9] = 7;
+        assert_eq!(
+            BezPath::from_bytes(&corrupt),
+            Err(DecodeError::InvalidTag(7))
+        );
+        let mut huge = bytes;
+        huge[1..9].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(BezPath::from_bytes(&huge), Err(DecodeError::UnexpectedEof));
+    }
+}
@@ -0,0 +1,1541 @@
+//! Boolean operations on shapes.
+//!
+//! The general case works on flattened outlines: both shapes are flattened to
+//! polygons, all edges are split at their mutual intersections, and each of the
+//! resulting edges is classified by the winding numbers on either side of it.
+//! Edges that overlap along a line, such as the shared side of two adjacent
+//! rectangles, are split where the overlap starts and ends, and the coincident
+//! pieces are merged into a single edge carrying the winding contributions of
+//! both, so a shared boundary is neither counted twice nor lost.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::iter;
+
+use crate::bezpath::seg_tangent;
+use crate::{
+    flatten, Affine, BezPath, Circle, FillRule, Line, ParamCurve, ParamCurveNearest, PathEl,
+    PathSeg, Point, Polygon, Shape, Vec2,
+};
+
+/// Relative tolerance for snapping vertices and detecting coincident edges.
+const SNAP_EPSILON: f64 = 1e-9;
+
+/// The region covered by both shapes, as a path.
+///
+/// The shapes are flattened with the given tolerance, so the result consists
+/// of straight lines only. The nonzero fill rule is used for both inputs, and
+/// the result has positive area.
+pub fn intersection(a: &impl Shape, b: &impl Shape, tolerance: f64) -> BezPath {
+    Overlay::new(a, b, tolerance).to_path(both_inside(FillRule::NonZero))
+}
+
+/// The implementation of [`Shape::clip_to`].
+///
+/// [`Shape::clip_to`]: trait.Shape.html#method.clip_to
+pub(crate) fn clip(shape: &impl Shape, clip: &impl Shape, tolerance: f64) -> BezPath {
+    let (rs, rc) = (shape.bounding_box().abs(), clip.bounding_box().abs());
+    if rs.x1 <= rc.x0 || rc.x1 <= rs.x0 || rs.y1 <= rc.y0 || rc.y1 <= rs.y0 {
+        return BezPath::new();
+    }
+    if let Some(rect) = clip.as_rect() {
+        let rect = rect.abs();
+        if rs.x0 >= rect.x0 && rs.x1 <= rect.x1 && rs.y0 >= rect.y0 && rs.y1 <= rect.y1 {
+            return shape.to_path(tolerance);
+        }
+    }
+    intersection(shape, clip, tolerance)
+}
+
+/// The area of the region covered by both shapes.
+///
+/// Pairs of rectangles and pairs of circles are computed analytically. Other
+/// shapes are flattened with the given tolerance and intersected as polygons,
+/// using the nonzero fill rule for both.
+pub fn intersection_area(a: &impl Shape, b: &impl Shape, tolerance: f64) -> f64 {
+    if let (Some(r0), Some(r1)) = (a.as_rect(), b.as_rect()) {
+        return r0.abs().intersect(r1.abs()).area();
+    }
+    if let (Some(c0), Some(c1)) = (a.as_circle(), b.as_circle()) {
+        return circle_intersection_area(c0, c1);
+    }
+    Overlay::new(a, b, tolerance).area(both_inside(FillRule::NonZero))
+}
+
+/// The intersection over union of two shapes.
+///
+/// This is the area covered by both shapes divided by the area covered by
+/// either, from 1 for shapes covering the same region down to 0 for shapes
+/// that do not overlap. It is 0 as well when the union has no area, for
+/// example when both shapes are empty.
+///
+/// Both areas are computed as by [`intersection_area`], analytically for
+/// pairs of rectangles and pairs of circles, and otherwise from a single
+/// overlay of the flattened shapes, using the nonzero fill rule for both.
+///
+/// # Examples
+///
+/// ```
+/// use kurbo::{shape_iou, Rect};
+/// let a = Rect::new(0.0, 0.0, 2.0, 1.0);
+/// let b = Rect::new(1.0, 0.0, 3.0, 1.0);
+/// assert_eq!(shape_iou(&a, &b, 0.1), 1.0 / 3.0);
+/// assert_eq!(shape_iou(&Rect::ZERO, &Rect::ZERO, 0.1), 0.0);
+/// ```
+///
+/// [`intersection_area`]: fn.intersection_area.html
+pub fn shape_iou(a: &impl Shape, b: &impl Shape, tolerance: f64) -> f64 {
+    let (both, either) = if let (Some(r0), Some(r1)) = (a.as_rect(), b.as_rect()) {
+        let (r0, r1) = (r0.abs(), r1.abs());
+        let both = r0.intersect(r1).area();
+        (both, r0.area() + r1.area() - both)
+    } else if let (Some(c0), Some(c1)) = (a.as_circle(), b.as_circle()) {
+        let both = circle_intersection_area(c0, c1);
+        let disk = |r: f64| PI * r * r;
+        (both, disk(c0.radius) + disk(c1.radius) - both)
+    } else {
+        let overlay = Overlay::new(a, b, tolerance);
+        let rule = FillRule::NonZero;
+        let either = overlay.area(|w| rule.is_inside(w[0]) || rule.is_inside(w[1]));
+        (overlay.area(both_inside(rule)), either)
+    };
+    if either > 0.0 {
+        (both / either).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// The region covered by a shape as it moves along a path.
+///
+/// The origin of the shape's coordinate system follows the path, without
+/// rotating; see [`sweep_oriented`] for a shape that turns with the path. A
+/// circle centered on the origin swept along a path gives its stroke with
+/// round joins and caps.
+///
+/// The shape is flattened, and the path is sampled at positions where the
+/// polyline through them is within half of `accuracy` of it. Between two
+/// consecutive positions, each edge of the shape covers a quadrilateral, and
+/// the result is the union of those with the shape at the ends of each
+/// subpath, computed with the same overlay as [`intersection`]. The nonzero
+/// fill rule is used for the shape, and the result consists of straight
+/// lines only, with positive area. A closed subpath brings the shape back to
+/// its start, and a subpath consisting of a single `MoveTo` places one copy
+/// of the shape there.
+///
+/// [`sweep_oriented`]: fn.sweep_oriented.html
+/// [`intersection`]: fn.intersection.html
+pub fn sweep(shape: &impl Shape, path: &BezPath, accuracy: f64) -> BezPath {
+    sweep_impl(shape, path, accuracy, false)
+}
+
+/// The region covered by a shape as it moves along a path, turning with it.
+///
+/// This is the same as [`sweep`], except that the shape is also rotated
+/// about its origin by the angle of the tangent of the path, so that its
+/// x axis points along the path. The path is sampled more densely where it
+/// turns, so that the arcs followed by the points of the shape farthest from
+/// its origin also stay within half of `accuracy` of the polylines through
+/// their samples, and corners of the path turn the shape in place.
+///
+/// [`sweep`]: fn.sweep.html
+pub fn sweep_oriented(shape: &impl Shape, path: &BezPath, accuracy: f64) -> BezPath {
+    sweep_impl(shape, path, accuracy, true)
+}
+
+/// The spatial relationship between two shapes.
+///
+/// This is returned by [`Shape::relate`], where `A` is the receiver and `B`
+/// the argument.
+///
+/// [`Shape::relate`]: trait.Shape.html#method.relate
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpatialRelation {
+    /// The shapes have no points in common.
+    Disjoint,
+    /// The boundaries meet, but the interiors do not overlap.
+    Touching,
+    /// The interiors overlap, and neither shape contains the other.
+    Overlapping,
+    /// A contains all of B, and they are not equal.
+    AContainsB,
+    /// B contains all of A, and they are not equal.
+    BContainsA,
+    /// The shapes cover the same region.
+    Equal,
+}
+
+/// The implementation of [`Shape::relate`].
+///
+/// [`Shape::relate`]: trait.Shape.html#method.relate
+pub(crate) fn relate(a: &impl Shape, b: &impl Shape, tolerance: f64) -> SpatialRelation {
+    let (ra, rb) = (a.bounding_box().abs(), b.bounding_box().abs());
+    if ra.x1 + tolerance < rb.x0
+        || rb.x1 + tolerance < ra.x0
+        || ra.y1 + tolerance < rb.y0
+        || rb.y1 + tolerance < ra.y0
+    {
+        return SpatialRelation::Disjoint;
+    }
+    let overlay = Overlay::new(a, b, tolerance);
+    let rule = FillRule::NonZero;
+    let boundary_a: Vec<_> = overlay.boundary(|w| rule.is_inside(w[0])).collect();
+    let boundary_b: Vec<_> = overlay.boundary(|w| rule.is_inside(w[1])).collect();
+    // Regions thinner than the tolerance are attributed to flattening.
+    let length =
+        |edges: &[(Point, Point)]| -> f64 { edges.iter().map(|&(p0, p1)| (p1 - p0).hypot()).sum() };
+    let area_eps = tolerance * (length(&boundary_a) + length(&boundary_b));
+    let both = overlay.area(both_inside(rule));
+    if both <= area_eps {
+        let dist2 = |pts: &[(Point, Point)], edges: &[(Point, Point)]| {
+            pts.iter()
+                .flat_map(|&(p0, p1)| iter::once(p0).chain(iter::once(p1)))
+                .flat_map(|p| {
+                    edges
+                        .iter()
+                        .map(move |&(q0, q1)| Line::new(q0, q1).nearest(p, 0.0).1)
+                })
+                .fold(f64::INFINITY, f64::min)
+        };
+        let d2 = dist2(&boundary_a, &boundary_b).min(dist2(&boundary_b, &boundary_a));
+        return if d2 <= tolerance * tolerance {
+            SpatialRelation::Touching
+        } else {
+            SpatialRelation::Disjoint
+        };
+    }
+    let a_only = overlay.area(|w| rule.is_inside(w[0]) && !rule.is_inside(w[1]));
+    let b_only = overlay.area(|w| rule.is_inside(w[1]) && !rule.is_inside(w[0]));
+    match (a_only <= area_eps, b_only <= area_eps) {
+        (true, true) => SpatialRelation::Equal,
+        (false, true) => SpatialRelation::AContainsB,
+        (true, false) => SpatialRelation::BContainsA,
+        (false, false) => SpatialRelation::Overlapping,
+    }
+}
+
+/// The areas of the convex hull of a shape and of its filled interior, for
+/// [`Shape::convexity_defect`] and [`Shape::solidity`].
+///
+/// [`Shape::convexity_defect`]: trait.Shape.html#method.convexity_defect
+/// [`Shape::solidity`]: trait.Shape.html#method.solidity
+pub(crate) fn hull_and_filled_area(shape: &impl Shape, tolerance: f64) -> (f64, f64) {
+    let hull = Polygon::convex_hull(shape.to_polyline(tolerance).into_iter().flatten());
+    let filled = Overlay::from_elements(shape.path_elements(tolerance), iter::empty(), tolerance)
+        .area(|w| w[0] != 0);
+    (hull.area(), filled)
+}
+
+/// The implementation of [`BezPath::regions`].
+///
+/// [`BezPath::regions`]: struct.BezPath.html#method.regions
+pub(crate) fn regions(path: &BezPath, tolerance: f64) -> Vec<(BezPath, i32)> {
+    let overlay = Overlay::from_elements(path, iter::empty(), tolerance);
+    let mut windings: Vec<i32> = overlay
+        .edges
+        .iter()
+        .flat_map(|e| iter::once(e.left[0]).chain(iter::once(e.right[0])))
+        .collect();
+    windings.sort_unstable();
+    windings.dedup();
+    let mut result = Vec::new();
+    for w in windings {
+        for face in faces(&overlay, |wn| wn[0] == w) {
+            let mut region = BezPath::new();
+            for poly in face {
+                region.move_to(poly[0]);
+                for &p in &poly[1..] {
+                    region.line_to(p);
+                }
+                region.close_path();
+            }
+            result.push((region, w));
+        }
+    }
+    result
+}
+
+/// The implementation of [`Shape::tessellate`].
+///
+/// [`Shape::tessellate`]: trait.Shape.html#method.tessellate
+pub(crate) fn tessellate(
+    shape: &impl Shape,
+    tolerance: f64,
+    fill_rule: FillRule,
+) -> (Vec<Point>, Vec<[u32; 3]>) {
+    let overlay = Overlay::from_elements(shape.path_elements(tolerance), iter::empty(), tolerance);
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    let mut index_of: BTreeMap<[u64; 2], u32> = BTreeMap::new();
+    for face in faces(&overlay, |wn| fill_rule.is_inside(wn[0])) {
+        let mut rings = face.into_iter().map(Polygon::from_vec);
+        let outer = rings.next().unwrap();
+        let holes: Vec<Polygon> = rings.collect();
+        for tri in outer.triangulate_with_holes(&holes) {
+            let mut ix = [0; 3];
+            for (k, &p) in tri.iter().enumerate() {
+                ix[k] = *index_of.entry(point_key(p)).or_insert_with(|| {
+                    vertices.push(p);
+                    (vertices.len() - 1) as u32
+                });
+            }
+            indices.push(ix);
+        }
+    }
+    (vertices, indices)
+}
+
+/// The faces of the region where `pred` holds, each given by its outer
+/// boundary, counterclockwise, followed by its holes, clockwise.
+fn faces(overlay: &Overlay, pred: impl Fn([i32; 2]) -> bool) -> Vec<Vec<Vec<Point>>> {
+    // The loops bounding the region have it on their left: outer boundaries
+    // are counterclockwise, holes clockwise.
+    let loops = chain_loops(overlay.boundary(pred).collect());
+    let (outers, holes): (Vec<_>, Vec<_>) =
+        loops.into_iter().partition(|poly| polygon_area(poly) > 0.0);
+    let mut faces: Vec<Vec<Vec<Point>>> = outers.into_iter().map(|poly| vec![poly]).collect();
+    for hole in holes {
+        // A face containing a hole contains the hole's edges, and the
+        // smallest such face is the one the hole belongs to. Holes outside
+        // every face bound the unbounded region.
+        let p = hole[0].midpoint(hole[1]);
+        let owner = faces
+            .iter_mut()
+            .filter(|face| polygon_winding(&face[0], p) != 0)
+            .min_by(|a, b| cmp_f64(polygon_area(&a[0]), polygon_area(&b[0])));
+        if let Some(face) = owner {
+            face.push(hole);
+        }
+    }
+    faces
+}
+
+/// The signed area of a closed polygon.
+fn polygon_area(poly: &[Point]) -> f64 {
+    let n = poly.len();
+    (0..n)
+        .map(|i| poly[i].to_vec2().cross(poly[(i + 1) % n].to_vec2()))
+        .sum::<f64>()
+        * 0.5
+}
+
+/// The winding number of a closed polygon around a point not on it.
+fn polygon_winding(poly: &[Point], p: Point) -> i32 {
+    let n = poly.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (poly[i], poly[(i + 1) % n]);
+            crossing_sign(p.y, p.x, a.y, a.x, b.y, b.x)
+        })
+        .sum()
+}
+
+/// The predicate for the intersection of the two inputs of an overlay.
+fn both_inside(rule: FillRule) -> impl Fn([i32; 2]) -> bool {
+    move |w| rule.is_inside(w[0]) && rule.is_inside(w[1])
+}
+
+/// The number of pieces each curved segment is split into before adaptive
+/// subdivision in [`sweep`].
+///
+/// [`sweep`]: fn.sweep.html
+const SWEEP_INITIAL_STEPS: usize = 4;
+
+/// The maximum depth of adaptive subdivision in [`sweep`].
+///
+/// [`sweep`]: fn.sweep.html
+const SWEEP_MAX_DEPTH: usize = 16;
+
+/// The implementation of [`sweep`] and [`sweep_oriented`].
+///
+/// [`sweep`]: fn.sweep.html
+/// [`sweep_oriented`]: fn.sweep_oriented.html
+fn sweep_impl(shape: &impl Shape, path: &BezPath, accuracy: f64, oriented: bool) -> BezPath {
+    let tolerance = 0.5 * accuracy;
+    let mut rings = shape.to_polyline(tolerance);
+    rings.retain(|ring| !ring.is_empty());
+    for ring in &mut rings {
+        if ring.first() != ring.last() {
+            let p = ring[0];
+            ring.push(p);
+        }
+    }
+    // Make the inside of the shape positive, so that nothing cancels out.
+    let area: f64 = rings
+        .iter()
+        .flat_map(|ring| ring.windows(2))
+        .map(|w| w[0].to_vec2().cross(w[1].to_vec2()))
+        .sum();
+    if area < 0.0 {
+        rings.iter_mut().for_each(|ring| ring.reverse());
+    }
+    let radius = rings
+        .iter()
+        .flatten()
+        .fold(0.0f64, |r, p| r.max(p.to_vec2().hypot()));
+    // A point at distance `radius` from the origin, turned by an angle `a`,
+    // strays by about `radius * a^2 / 8` from the chord of its arc.
+    let max_angle = if oriented && radius > 0.0 {
+        (8.0 * tolerance / radius).sqrt()
+    } else {
+        f64::INFINITY
+    };
+
+    // The pieces covered by each step, unioned pairwise at the end so that
+    // the intermediate results stay small.
+    let mut pieces = Vec::new();
+    let add_poly = |piece: &mut BezPath, poly: &[Point]| {
+        piece.move_to(poly[0]);
+        for &p in &poly[1..] {
+            piece.line_to(p);
+        }
+        piece.close_path();
+    };
+    let place = |(pos, angle): (Point, f64)| -> Vec<Vec<Point>> {
+        let affine = Affine::translate(pos.to_vec2()) * Affine::rotate(angle);
+        rings
+            .iter()
+            .map(|ring| ring.iter().map(|&p| affine * p).collect())
+            .collect()
+    };
+    for frames in sweep_frames(path, tolerance, max_angle, oriented) {
+        let (first, last) = match (frames.first(), frames.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => continue,
+        };
+        let mut piece = BezPath::new();
+        for ring in place(first).iter().chain(&place(last)) {
+            add_poly(&mut piece, ring);
+        }
+        pieces.push(piece);
+        let mut prev = place(first);
+        for &frame in &frames[1..] {
+            let cur = place(frame);
+            let mut piece = BezPath::new();
+            for (r0, r1) in prev.iter().zip(&cur) {
+                for k in 0..r0.len() - 1 {
+                    let (a0, b0, a1, b1) = (r0[k], r0[k + 1], r1[k], r1[k + 1]);
+                    for &tri in &[[a0, b0, b1], [a0, b1, a1]] {
+                        let cross = (tri[1] - tri[0]).cross(tri[2] - tri[0]);
+                        if cross > 0.0 {
+                            add_poly(&mut piece, &tri);
+                        } else if cross < 0.0 {
+                            add_poly(&mut piece, &[tri[0], tri[2], tri[1]]);
+                        }
+                    }
+                }
+            }
+            pieces.push(piece);
+            prev = cur;
+        }
+    }
+    union_all(&pieces, tolerance)
+}
+
+/// The union of polygonal paths, using the nonzero fill rule for each.
+///
+/// This divides and conquers, so that each overlay only has the outlines of
+/// the unions of its halves as input.
+fn union_all(pieces: &[BezPath], tolerance: f64) -> BezPath {
+    let either = |w: [i32; 2]| w[0] != 0 || w[1] != 0;
+    match pieces.len() {
+        0 => BezPath::new(),
+        1 => Overlay::from_elements(&pieces[0], iter::empty(), tolerance).to_path(either),
+        n => {
+            let a = union_all(&pieces[..n / 2], tolerance);
+            let b = union_all(&pieces[n / 2..], tolerance);
+            Overlay::from_elements(&a, &b, tolerance).to_path(either)
+        }
+    }
+}
+
+/// The positions and angles at which the shape is placed in [`sweep`], for
+/// each subpath.
+///
+/// [`sweep`]: fn.sweep.html
+fn sweep_frames(
+    path: &BezPath,
+    tolerance: f64,
+    max_angle: f64,
+    oriented: bool,
+) -> Vec<Vec<(Point, f64)>> {
+    let mut result = Vec::new();
+    for sub in path.subpaths() {
+        let mut frames: Vec<(Point, f64)> = Vec::new();
+        let mut push = |p: Point, tangent: Option<Vec2>| {
+            let last = frames.last().copied();
+            // Keep the angles continuous, turning the short way round.
+            let angle = match (tangent.filter(|_| oriented), last) {
+                (Some(t), Some((_, a))) => a + (t.atan2() - a + PI).rem_euclid(2.0 * PI) - PI,
+                (Some(t), None) => t.atan2(),
+                (None, Some((_, a))) => a,
+                (None, None) => 0.0,
+            };
+            match last {
+                Some((q, a)) if q == p && a == angle => (),
+                Some((q, a)) => {
+                    let n = ((angle - a).abs() / max_angle).ceil().max(1.0) as usize;
+                    for i in 1..=n {
+                        let s = i as f64 / n as f64;
+                        frames.push((q.lerp(p, s), a + (angle - a) * s));
+                    }
+                }
+                None => frames.push((p, angle)),
+            }
+        };
+        let mut empty = true;
+        for seg in sub.segments() {
+            empty = false;
+            let steps = match seg {
+                PathSeg::Line(_) => 1,
+                _ => SWEEP_INITIAL_STEPS,
+            };
+            let mut ts = Vec::new();
+            for i in 0..steps {
+                let (t0, t1) = (i as f64 / steps as f64, (i + 1) as f64 / steps as f64);
+                sweep_params(seg, t0, t1, tolerance, max_angle, 0, &mut ts);
+            }
+            // The tangent at the start of the segment turns the shape in
+            // place at a corner.
+            push(seg.start(), seg_tangent(seg, 0.0));
+            for t in ts {
+                push(seg.eval(t), seg_tangent(seg, t));
+            }
+        }
+        if empty {
+            if let Some(PathEl::MoveTo(p)) = sub.elements().first() {
+                push(*p, None);
+            }
+        } else if let Some(PathEl::ClosePath) = sub.elements().last() {
+            if let Some(seg) = sub.segments().next() {
+                push(seg.start(), seg_tangent(seg, 0.0));
+            }
+        }
+        result.push(frames);
+    }
+    result
+}
+
+/// Add the end parameters of the pieces of `seg` between `t0` and `t1`, into
+/// which it is subdivided for [`sweep`].
+///
+/// [`sweep`]: fn.sweep.html
+fn sweep_params(
+    seg: PathSeg,
+    t0: f64,
+    t1: f64,
+    tolerance: f64,
+    max_angle: f64,
+    depth: usize,
+    out: &mut Vec<f64>,
+) {
+    let tm = 0.5 * (t0 + t1);
+    let mut split = false;
+    if depth < SWEEP_MAX_DEPTH {
+        if !matches!(seg, PathSeg::Line(_)) {
+            let chord_mid = seg.eval(t0).midpoint(seg.eval(t1));
+            split = (seg.eval(tm) - chord_mid).hypot() > tolerance;
+        }
+        if !split && max_angle.is_finite() {
+            if let (Some(a), Some(b)) = (seg_tangent(seg, t0), seg_tangent(seg, t1)) {
+                split = a.angle_between(b).abs() > max_angle;
+            }
+        }
+    }
+    if split {
+        sweep_params(seg, t0, tm, tolerance, max_angle, depth + 1, out);
+        sweep_params(seg, tm, t1, tolerance, max_angle, depth + 1, out);
+    } else {
+        out.push(t1);
+    }
+}
+
+/// The area of the lens formed by two intersecting circles.
+fn circle_intersection_area(c0: Circle, c1: Circle) -> f64 {
+    let r0 = c0.radius.abs();
+    let r1 = c1.radius.abs();
+    let d = (c1.center - c0.center).hypot();
+    if d >= r0 + r1 {
+        return 0.0;
+    }
+    if d <= (r0 - r1).abs() {
+        let r = r0.min(r1);
+        return PI * r * r;
+    }
+    let a0 = ((d * d + r0 * r0 - r1 * r1) / (2.0 * d * r0))
+        .clamp(-1.0, 1.0)
+        .acos();
+    let a1 = ((d * d + r1 * r1 - r0 * r0) / (2.0 * d * r1))
+        .clamp(-1.0, 1.0)
+        .acos();
+    let kite = ((-d + r0 + r1) * (d + r0 - r1) * (d - r0 + r1) * (d + r0 + r1))
+        .max(0.0)
+        .sqrt();
+    r0 * r0 * a0 + r1 * r1 * a1 - 0.5 * kite
+}
+
+/// An edge of the overlay of two flattened shapes.
+///
+/// In a y-up coordinate system, "left" is the side to the left when walking
+/// from `p0` to `p1`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct OverlayEdge {
+    pub(crate) p0: Point,
+    pub(crate) p1: Point,
+    /// The change in winding number of each input, crossing from right to left.
+    pub(crate) delta: [i32; 2],
+    /// The winding number of each input on the left side.
+    pub(crate) left: [i32; 2],
+    /// The winding number of each input on the right side.
+    pub(crate) right: [i32; 2],
+}
+
+/// A planar overlay of two flattened shapes.
+///
+/// No two edges of the overlay cross or overlap, and every edge knows the
+/// winding number of both inputs on either of its sides. A boolean operation
+/// is then a predicate on a pair of winding numbers: the boundary of the result
+/// consists of the edges where the predicate differs between the two sides.
+pub(crate) struct Overlay {
+    pub(crate) edges: Vec<OverlayEdge>,
+}
+
+/// An input edge, before splitting.
+struct RawEdge {
+    p0: Point,
+    p1: Point,
+    owner: usize,
+}
+
+impl Overlay {
+    /// Build the overlay of two shapes.
+    pub(crate) fn new(a: &impl Shape, b: &impl Shape, tolerance: f64) -> Overlay {
+        Overlay::from_elements(
+            a.path_elements(tolerance),
+            b.path_elements(tolerance),
+            tolerance,
+        )
+    }
+
+    /// Build the overlay of two sequences of path elements.
+    ///
+    /// Every subpath is treated as closed.
+    pub(crate) fn from_elements(
+        a: impl IntoIterator<Item = PathEl>,
+        b: impl IntoIterator<Item = PathEl>,
+        tolerance: f64,
+    ) -> Overlay {
+        let mut raw = Vec::new();
+        add_polygon_edges(&mut raw, a, 0, tolerance);
+        add_polygon_edges(&mut raw, b, 1, tolerance);
+        let eps = snap_epsilon(&raw);
+        let split = split_edges(&raw, eps);
+        let mut edges = merge_edges(snap_vertices(split, eps));
+        classify_edges(&mut edges);
+        Overlay { edges }
+    }
+
+    /// The area of the region where `pred` holds.
+    ///
+    /// The predicate is given the winding numbers of both inputs, and must be
+    /// false when both are 0 (otherwise the region would be unbounded).
+    pub(crate) fn area(&self, pred: impl Fn([i32; 2]) -> bool) -> f64 {
+        self.boundary(pred)
+            .map(|(p0, p1)| p0.to_vec2().cross(p1.to_vec2()))
+            .sum::<f64>()
+            * 0.5
+    }
+
+    /// The edges of the boundary of the region where `pred` holds.
+    ///
+    /// The edges are oriented so that the region is on the left, which gives
+    /// positive area.
+    pub(crate) fn boundary<'a>(
+        &'a self,
+        pred: impl Fn([i32; 2]) -> bool + 'a,
+    ) -> impl Iterator<Item = (Point, Point)> + 'a {
+        self.edges
+            .iter()
+            .filter_map(move |e| match (pred(e.left), pred(e.right)) {
+                (true, false) => Some((e.p0, e.p1)),
+                (false, true) => Some((e.p1, e.p0)),
+                _ => None,
+            })
+    }
+
+    /// The boundary of the region where `pred` holds, as closed polygons.
+    pub(crate) fn to_path(&self, pred: impl Fn([i32; 2]) -> bool) -> BezPath {
+        let mut path = BezPath::new();
+        for poly in chain_loops(self.boundary(pred).collect()) {
+            path.move_to(poly[0]);
+            for &p in &poly[1..] {
+                path.line_to(p);
+            }
+            path.close_path();
+        }
+        path
+    }
+}
+
+/// Flatten path elements and add the edges of the resulting polygons.
+fn add_polygon_edges(
+    raw: &mut Vec<RawEdge>,
+    elements: impl IntoIterator<Item = PathEl>,
+    owner: usize,
+    tolerance: f64,
+) {
+    let mut start = None;
+    let mut last = None;
+    let push = |raw: &mut Vec<RawEdge>, p0: Point, p1: Point| {
+        if p0 != p1 {
+            raw.push(RawEdge { p0, p1, owner });
+        }
+    };
+    let close = |raw: &mut Vec<RawEdge>, start: Option<Point>, last: Option<Point>| {
+        if let (Some(s), Some(l)) = (start, last) {
+            push(raw, l, s);
+        }
+    };
+    flatten(elements, tolerance, |el| match el {
+        PathEl::MoveTo(p) => {
+            close(raw, start, last);
+            start = Some(p);
+            last = Some(p);
+        }
+        PathEl::LineTo(p) => {
+            if let Some(l) = last {
+                push(raw, l, p);
+            } else {
+                start = Some(p);
+            }
+            last = Some(p);
+        }
+        PathEl::ClosePath => {
+            close(raw, start, last);
+            last = start;
+        }
+        // Flattening only produces lines.
+        _ => unreachable!(),
+    });
+    close(raw, start, last);
+}
+
+/// The absolute snapping tolerance, relative to the magnitude of the input.
+fn snap_epsilon(raw: &[RawEdge]) -> f64 {
+    let scale = raw
+        .iter()
+        .flat_map(|e| iter::once(e.p0).chain(iter::once(e.p1)))
+        .fold(1.0f64, |acc, p| acc.max(p.x.abs()).max(p.y.abs()));
+    SNAP_EPSILON * scale
+}
+
+/// Split every edge at its intersections with other edges.
+///
+/// This also handles T-junctions and collinear overlaps, splitting at the
+/// endpoints of the overlapping portion.
+fn split_edges(raw: &[RawEdge], eps: f64) -> Vec<RawEdge> {
+    let mut splits: Vec<Vec<(f64, Point)>> = raw.iter().map(|_| Vec::new()).collect();
+    // Sort by minimum x so that only edges with overlapping x ranges are tested.
+    let mut order: Vec<usize> = (0..raw.len()).collect();
+    let min_x = |e: &RawEdge| e.p0.x.min(e.p1.x);
+    order.sort_by(|&i, &j| cmp_f64(min_x(&raw[i]), min_x(&raw[j])));
+    for (k, &i) in order.iter().enumerate() {
+        let max_x = raw[i].p0.x.max(raw[i].p1.x) + eps;
+        for &j in &order[k + 1..] {
+            if min_x(&raw[j]) > max_x {
+                break;
+            }
+            intersect_edges(raw, i, j, eps, &mut splits);
+        }
+    }
+    let mut result = Vec::with_capacity(raw.len());
+    for (e, mut ts) in raw.iter().zip(splits) {
+        ts.sort_by(|a, b| cmp_f64(a.0, b.0));
+        let mut p0 = e.p0;
+        for (_, p) in ts.into_iter().chain(Some((1.0, e.p1))) {
+            if p != p0 {
+                result.push(RawEdge {
+                    p0,
+                    p1: p,
+                    owner: e.owner,
+                });
+                p0 = p;
+            }
+        }
+    }
+    result
+}
+
+/// Record the split points of a pair of edges.
+fn intersect_edges(
+    raw: &[RawEdge],
+    i: usize,
+    j: usize,
+    eps: f64,
+    splits: &mut [Vec<(f64, Point)>],
+) {
+    let (e, f) = (&raw[i], &raw[j]);
+    let r = e.p1 - e.p0;
+    let s = f.p1 - f.p0;
+    let r_len = r.hypot();
+    let s_len = s.hypot();
+    let qp = f.p0 - e.p0;
+    let denom = r.cross(s);
+    if denom.abs() <= SNAP_EPSILON * r_len * s_len {
+        // Parallel. If also collinear, split each edge at the endpoints of the
+        // other that fall within it.
+        if qp.cross(r).abs() <= eps * r_len {
+            split_at_point(e, f.p0, eps, &mut splits[i]);
+            split_at_point(e, f.p1, eps, &mut splits[i]);
+            split_at_point(f, e.p0, eps, &mut splits[j]);
+            split_at_point(f, e.p1, eps, &mut splits[j]);
+        }
+        return;
+    }
+    let t = qp.cross(s) / denom;
+    let u = qp.cross(r) / denom;
+    let eps_t = eps / r_len;
+    let eps_u = eps / s_len;
+    if t < -eps_t || t > 1.0 + eps_t || u < -eps_u || u > 1.0 + eps_u {
+        return;
+    }
+    let t_end = t <= eps_t || t >= 1.0 - eps_t;
+    let u_end = u <= eps_u || u >= 1.0 - eps_u;
+    match (t_end, u_end) {
+        // Shared (or nearly shared) vertex, resolved by snapping.
+        (true, true) => (),
+        (true, false) => {
+            let p = if t < 0.5 { e.p0 } else { e.p1 };
+            splits[j].push((u, p));
+        }
+        (false, true) => {
+            let p = if u < 0.5 { f.p0 } else { f.p1 };
+            splits[i].push((t, p));
+        }
+        (false, false) => {
+            let p = e.p0 + t * r;
+            splits[i].push((t, p));
+            splits[j].push((u, p));
+        }
+    }
+}
+
+/// Split an edge at a point known to be on its line, if it is strictly inside.
+fn split_at_point(e: &RawEdge, p: Point, eps: f64, splits: &mut Vec<(f64, Point)>) {
+    let r = e.p1 - e.p0;
+    let len2 = r.hypot2();
+    let t = (p - e.p0).dot(r) / len2;
+    let eps_t = eps / len2.sqrt();
+    if t > eps_t && t < 1.0 - eps_t {
+        splits.push((t, p));
+    }
+}
+
+/// Merge vertices that are within `eps` of each other.
+fn snap_vertices(mut edges: Vec<RawEdge>, eps: f64) -> Vec<RawEdge> {
+    let mut pts: Vec<Point> = edges
+        .iter()
+        .flat_map(|e| iter::once(e.p0).chain(iter::once(e.p1)))
+        .collect();
+    let mut order: Vec<usize> = (0..pts.len()).collect();
+    order.sort_by(|&i, &j| cmp_f64(pts[i].x, pts[j].x));
+    let mut rep: Vec<usize> = (0..pts.len()).collect();
+    for (k, &i) in order.iter().enumerate() {
+        if rep[i] != i {
+            continue;
+        }
+        for &j in &order[k + 1..] {
+            if pts[j].x - pts[i].x > eps {
+                break;
+            }
+            if rep[j] == j && (pts[j] - pts[i]).hypot2() <= eps * eps {
+                rep[j] = i;
+            }
+        }
+    }
+    for i in 0..pts.len() {
+        pts[i] = pts[rep[i]];
+    }
+    for (k, e) in edges.iter_mut().enumerate() {
+        e.p0 = pts[2 * k];
+        e.p1 = pts[2 * k + 1];
+    }
+    edges.retain(|e| e.p0 != e.p1);
+    edges
+}
+
+/// Combine coincident edges into one, summing their winding contributions.
+///
+/// Edges whose contributions cancel are removed.
+fn merge_edges(raw: Vec<RawEdge>) -> Vec<OverlayEdge> {
+    let mut map: BTreeMap<[u64; 4], (Point, Point, [i32; 2])> = BTreeMap::new();
+    for e in raw {
+        let (p0, p1, sign) = if cmp_point(e.p0, e.p1) == Ordering::Less {
+            (e.p0, e.p1, 1)
+        } else {
+            (e.p1, e.p0, -1)
+        };
+        let key = [
+            p0.x.to_bits(),
+            p0.y.to_bits(),
+            p1.x.to_bits(),
+            p1.y.to_bits(),
+        ];
+        let entry = map.entry(key).or_insert((p0, p1, [0, 0]));
+        entry.2[e.owner] += sign;
+    }
+    map.into_iter()
+        .filter(|(_, (_, _, delta))| *delta != [0, 0])
+        .map(|(_, (p0, p1, delta))| OverlayEdge {
+            p0,
+            p1,
+            delta,
+            left: [0, 0],
+            right: [0, 0],
+        })
+        .collect()
+}
+
+/// Compute the winding numbers on either side of each edge.
+///
+/// A ray is cast from the midpoint of each edge, horizontally for edges that
+/// are closer to vertical, and vertically otherwise. Crossings use the
+/// half-open convention so that shared vertices are counted once.
+fn classify_edges(edges: &mut [OverlayEdge]) {
+    for i in 0..edges.len() {
+        let e = edges[i];
+        let m = e.p0.midpoint(e.p1);
+        let d = e.p1 - e.p0;
+        let horiz_ray = d.y.abs() >= d.x.abs();
+        let mut far = [0, 0];
+        for (j, f) in edges.iter().enumerate() {
+            if j == i {
+                continue;
+            }
+            let sign = if horiz_ray {
+                crossing_sign(m.y, m.x, f.p0.y, f.p0.x, f.p1.y, f.p1.x)
+            } else {
+                -crossing_sign(m.x, m.y, f.p0.x, f.p0.y, f.p1.x, f.p1.y)
+            };
+            if sign != 0 {
+                far[0] += sign * f.delta[0];
+                far[1] += sign * f.delta[1];
+            }
+        }
+        // The ray crosses this edge when cast from slightly before it.
+        let e_sign = if horiz_ray {
+            d.y.signum() as i32
+        } else {
+            -d.x.signum() as i32
+        };
+        let near = [far[0] + e_sign * e.delta[0], far[1] + e_sign * e.delta[1]];
+        // The ray origin side is the left one if the edge crosses upwards.
+        let (left, right) = if e_sign > 0 { (near, far) } else { (far, near) };
+        edges[i].left = left;
+        edges[i].right = right;
+    }
+}
+
+/// The signed crossing of a ray from `(u, v)` towards increasing `v` with an
+/// edge, in coordinates where `u` is the axis perpendicular to the ray.
+///
+/// Returns 1 if the edge crosses in the direction of increasing `u`, -1 if
+/// decreasing, and 0 if it doesn't cross.
+fn crossing_sign(u: f64, v: f64, u0: f64, v0: f64, u1: f64, v1: f64) -> i32 {
+    if (u0 > u) == (u1 > u) {
+        return 0;
+    }
+    let v_cross = v0 + (u - u0) * (v1 - v0) / (u1 - u0);
+    if v_cross > v {
+        if u1 > u0 {
+            1
+        } else {
+            -1
+        }
+    } else {
+        0
+    }
+}
+
+/// Chain oriented edges into closed polygons.
+///
+/// At vertices with several outgoing edges, the sharpest left turn is taken
+/// so that each polygon bounds a single face.
+fn chain_loops(edges: Vec<(Point, Point)>) -> Vec<Vec<Point>> {
+    let mut outgoing: BTreeMap<[u64; 2], Vec<usize>> = BTreeMap::new();
+    for (i, (p0, _)) in edges.iter().enumerate() {
+        outgoing.entry(point_key(*p0)).or_default().push(i);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut loops = Vec::new();
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let mut poly = vec![edges[start].0];
+        let mut cur = start;
+        loop {
+            let (p0, p1) = edges[cur];
+            let din = p1 - p0;
+            let next = outgoing.get(&point_key(p1)).and_then(|cands| {
+                cands
+                    .iter()
+                    .copied()
+                    .filter(|&k| !used[k])
+                    .max_by(|&a, &b| {
+                        cmp_f64(turn_angle(din, &edges[a]), turn_angle(din, &edges[b]))
+                    })
+            });
+            match next {
+                Some(k) => {
+                    used[k] = true;
+                    poly.push(p1);
+                    cur = k;
+                }
+                None => break,
+            }
+        }
+        simplify_polygon(&mut poly);
+        if poly.len() >= 3 {
+            loops.push(poly);
+        }
+    }
+    loops
+}
+
+/// The signed angle of the turn from `din` onto an edge.
+fn turn_angle(din: Vec2, edge: &(Point, Point)) -> f64 {
+    let dout = edge.1 - edge.0;
+    din.cross(dout).atan2(din.dot(dout))
+}
+
+/// Remove vertices in the middle of straight runs.
+fn simplify_polygon(poly: &mut Vec<Point>) {
+    let mut i = 0;
+    while poly.len() >= 3 && i < poly.len() {
+        let n = poly.len();
+        let prev = poly[(i + n - 1) % n];
+        let next = poly[(i + 1) % n];
+        let d0 = poly[i] - prev;
+        let d1 = next - poly[i];
+        if d0.cross(d1).abs() <= SNAP_EPSILON * d0.hypot() * d1.hypot() && d0.dot(d1) > 0.0 {
+            poly.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+fn point_key(p: Point) -> [u64; 2] {
+    [p.x.to_bits(), p.y.to_bits()]
+}
+
+fn cmp_f64(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+fn cmp_point(a: Point, b: Point) -> Ordering {
+    cmp_f64(a.x, b.x).then(cmp_f64(a.y, b.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        intersection, intersection_area, shape_iou, sweep, sweep_oriented, Affine, BezPath, Circle,
+        FillRule, Line, ParamCurveArclen, PathEl, Point, Rect, Shape, SpatialRelation, Triangle,
+    };
+    use std::f64::consts::PI;
+
+    use super::Overlay;
+
+    fn assert_approx_eq(x: f64, y: f64, epsilon: f64) {
+        assert!((x - y).abs() < epsilon, "{} != {}", x, y);
+    }
+
+    fn triangle(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64)) -> BezPath {
+        let mut path = BezPath::new();
+        path.move_to(p0).line_to(p1).line_to(p2).close_path();
+        path
+    }
+
+    #[test]
+    fn rect_intersection_area() {
+        let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let b = Rect::new(5.0, 5.0, 20.0, 20.0);
+        assert_approx_eq(intersection_area(&a, &b, 0.1), 25.0, 1e-12);
+        let c = Rect::new(11.0, 0.0, 20.0, 1.0);
+        assert_approx_eq(intersection_area(&a, &c, 0.1), 0.0, 1e-12);
+        // The general path agrees with the analytic one.
+        let area = Overlay::new(&a, &b, 0.1).area(|w| w[0] != 0 && w[1] != 0);
+        assert_approx_eq(area, 25.0, 1e-9);
+    }
+
+    #[test]
+    fn circle_intersection_area() {
+        let a = Circle::new((0.0, 0.0), 1.0);
+        let b = Circle::new((1.0, 0.0), 1.0);
+        let expected = 2.0 * PI / 3.0 - 0.5 * 3.0f64.sqrt();
+        assert_approx_eq(intersection_area(&a, &b, 0.1), expected, 1e-12);
+        let inner = Circle::new((0.2, 0.0), 0.5);
+        assert_approx_eq(intersection_area(&a, &inner, 0.1), 0.25 * PI, 1e-12);
+        let far = Circle::new((3.0, 0.0), 1.0);
+        assert_approx_eq(intersection_area(&a, &far, 0.1), 0.0, 1e-12);
+        let area = Overlay::new(&a, &b, 1e-6).area(|w| w[0] != 0 && w[1] != 0);
+        assert_approx_eq(area, expected, 1e-4);
+    }
+
+    #[test]
+    fn general_intersection_area() {
+        let a = triangle((0.0, 0.0), (4.0, 0.0), (0.0, 4.0));
+        let b = Rect::new(0.0, 0.0, 2.0, 2.0);
+        // The rect is entirely inside the triangle.
+        assert_approx_eq(intersection_area(&a, &b, 0.1), 4.0, 1e-9);
+        let b = Rect::new(1.0, 1.0, 3.0, 3.0);
+        // The square minus the part above the line x + y = 4.
+        assert_approx_eq(intersection_area(&a, &b, 0.1), 2.0, 1e-9);
+        // Orientation of the inputs doesn't matter under nonzero.
+        let reversed = triangle((0.0, 0.0), (0.0, 4.0), (4.0, 0.0));
+        assert_approx_eq(intersection_area(&reversed, &b, 0.1), 2.0, 1e-9);
+    }
+
+    #[test]
+    fn intersection_over_union() {
+        let a = Rect::new(0.0, 0.0, 2.0, 2.0);
+        let b = Rect::new(1.0, 1.0, 3.0, 3.0);
+        assert_approx_eq(shape_iou(&a, &b, 0.1), 1.0 / 7.0, 1e-12);
+        assert_eq!(shape_iou(&a, &a, 0.1), 1.0);
+        assert_eq!(shape_iou(&a, &Rect::new(5.0, 5.0, 6.0, 6.0), 0.1), 0.0);
+
+        let c0 = Circle::new((0.0, 0.0), 1.0);
+        let c1 = Circle::new((0.0, 0.0), 0.5);
+        assert_approx_eq(shape_iou(&c0, &c1, 0.1), 0.25, 1e-12);
+
+        // The general case agrees with the areas computed separately.
+        let t = triangle((0.0, 0.0), (4.0, 0.0), (0.0, 4.0));
+        let inter = intersection_area(&t, &b, 0.1);
+        let union = 8.0 + 4.0 - inter;
+        assert_approx_eq(shape_iou(&t, &b, 0.1), inter / union, 1e-9);
+        assert_approx_eq(shape_iou(&b, &t, 0.1), inter / union, 1e-9);
+        let path = c0.to_path(1e-6);
+        assert_approx_eq(shape_iou(&path, &c1, 1e-6), 0.25, 1e-5);
+
+        // An empty union gives 0 rather than NaN.
+        assert_eq!(shape_iou(&BezPath::new(), &BezPath::new(), 0.1), 0.0);
+        assert_eq!(shape_iou(&BezPath::new(), &t, 0.1), 0.0);
+    }
+
+    #[test]
+    fn intersection_path() {
+        let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let b = Rect::new(5.0, -5.0, 15.0, 5.0);
+        let path = intersection(&a, &b, 0.1);
+        assert_eq!(path.elements().len(), 5);
+        assert_approx_eq(path.area(), 25.0, 1e-9);
+        assert_eq!(path.winding(Point::new(7.0, 2.0)), 1);
+        assert_eq!(path.winding(Point::new(2.0, 2.0)), 0);
+    }
+
+    #[test]
+    fn relate_rects() {
+        let a = Rect::new(0.0, 0.0, 4.0, 4.0);
+        let rel = |b: Rect| a.relate(&b, 1e-3);
+        assert_eq!(
+            rel(Rect::new(5.0, 0.0, 6.0, 1.0)),
+            SpatialRelation::Disjoint
+        );
+        assert_eq!(
+            rel(Rect::new(1.0, 5.0, 2.0, 6.0)),
+            SpatialRelation::Disjoint
+        );
+        assert_eq!(
+            rel(Rect::new(4.0, 1.0, 6.0, 2.0)),
+            SpatialRelation::Touching
+        );
+        // Touching only at a corner.
+        assert_eq!(
+            rel(Rect::new(4.0, 4.0, 6.0, 6.0)),
+            SpatialRelation::Touching
+        );
+        assert_eq!(
+            rel(Rect::new(3.0, 1.0, 6.0, 2.0)),
+            SpatialRelation::Overlapping
+        );
+        assert_eq!(
+            rel(Rect::new(1.0, 1.0, 2.0, 2.0)),
+            SpatialRelation::AContainsB
+        );
+        // Containment where the boundaries share an edge.
+        assert_eq!(
+            rel(Rect::new(0.0, 1.0, 2.0, 2.0)),
+            SpatialRelation::AContainsB
+        );
+        assert_eq!(
+            rel(Rect::new(-1.0, -1.0, 5.0, 5.0)),
+            SpatialRelation::BContainsA
+        );
+        assert_eq!(rel(a), SpatialRelation::Equal);
+        let reversed = triangle((0.0, 0.0), (0.0, 4.0), (4.0, 4.0));
+        assert_eq!(reversed.relate(&a, 1e-3), SpatialRelation::BContainsA);
+    }
+
+    #[test]
+    fn relate_circles() {
+        let a = Circle::new((0.0, 0.0), 1.0);
+        let tangent = Circle::new((2.0, 0.0), 1.0);
+        assert_eq!(a.relate(&tangent, 1e-3), SpatialRelation::Touching);
+        let near = Circle::new((2.1, 0.0), 1.0);
+        assert_eq!(a.relate(&near, 1e-3), SpatialRelation::Disjoint);
+        assert_eq!(a.relate(&a.to_path(1e-6), 1e-2), SpatialRelation::Equal);
+        let inner = Circle::new((0.2, 0.0), 0.5);
+        assert_eq!(inner.relate(&a, 1e-3), SpatialRelation::BContainsA);
+    }
+
+    #[test]
+    fn path_regions() {
+        // Two overlapping squares, both counterclockwise.
+        let mut path = Rect::new(0.0, 0.0, 2.0, 2.0).to_path(1e-9);
+        path.extend_path(
+            &Rect::new(1.0, 1.0, 3.0, 3.0).to_path(1e-9),
+            Affine::IDENTITY,
+        );
+        let regions = path.regions(1e-3);
+        let windings: Vec<i32> = regions.iter().map(|r| r.1).collect();
+        assert_eq!(windings, [1, 1, 2]);
+        for (region, w) in &regions {
+            let expected = if *w == 2 { 1.0 } else { 3.0 };
+            assert_approx_eq(region.area(), expected, 1e-9);
+        }
+
+        // Nesting: a square with a hole of winding 0, containing an island,
+        // containing a square of winding 2.
+        let mut path = Rect::new(0.0, 0.0, 10.0, 10.0).to_path(1e-9);
+        path.extend_path(
+            &Rect::new(2.0, 2.0, 8.0, 8.0).to_path(1e-9),
+            Affine::FLIP_Y * Affine::translate((0.0, -10.0)),
+        );
+        path.extend_path(
+            &Rect::new(3.0, 3.0, 7.0, 7.0).to_path(1e-9),
+            Affine::IDENTITY,
+        );
+        path.extend_path(
+            &Rect::new(4.0, 4.0, 6.0, 6.0).to_path(1e-9),
+            Affine::IDENTITY,
+        );
+        let regions = path.regions(1e-3);
+        let summary: Vec<(i32, f64, usize)> = regions
+            .iter()
+            .map(|(r, w)| (*w, r.area(), r.elements().len()))
+            .collect();
+        assert_eq!(summary.len(), 4);
+        // Each region with a hole has two closed subpaths of four vertices.
+        assert_eq!(summary[0], (0, 36.0 - 16.0, 10));
+        assert_eq!(summary[1], (1, 100.0 - 36.0, 10));
+        assert_eq!(summary[2], (1, 16.0 - 4.0, 10));
+        assert_eq!(summary[3], (2, 4.0, 5));
+    }
+
+    #[test]
+    fn fill_discrepancy() {
+        // Overlapping squares in the same direction differ by their overlap.
+        let mut path = Rect::new(0.0, 0.0, 2.0, 2.0).to_path(1e-9);
+        path.extend_path(
+            &Rect::new(1.0, 1.0, 3.0, 3.0).to_path(1e-9),
+            Affine::IDENTITY,
+        );
+        assert_approx_eq(path.fill_discrepancy(1e-6), 1.0, 1e-9);
+        // In opposite directions, the overlap has winding 0 under both rules.
+        let mut path = Rect::new(0.0, 0.0, 2.0, 2.0).to_path(1e-9);
+        path.extend_path(
+            &Rect::new(1.0, 1.0, 3.0, 3.0).to_path(1e-9),
+            Affine::FLIP_X * Affine::translate((-4.0, 0.0)),
+        );
+        assert_eq!(path.fill_discrepancy(1e-6), 0.0);
+        // A sliver of overlap thinner than the tolerance is ignored.
+        let mut path = Rect::new(0.0, 0.0, 2.0, 2.0).to_path(1e-9);
+        path.extend_path(
+            &Rect::new(2.0 - 1e-7, 0.0, 4.0, 2.0).to_path(1e-9),
+            Affine::IDENTITY,
+        );
+        assert!(path.fill_discrepancy(1e-9) > 0.0);
+        assert_eq!(path.fill_discrepancy(1e-6), 0.0);
+        assert_eq!(
+            Circle::new((0.0, 0.0), 1.0)
+                .to_path(1e-3)
+                .fill_discrepancy(1e-3),
+            0.0
+        );
+    }
+
+    fn mesh_triangles(vertices: &[Point], indices: &[[u32; 3]]) -> Vec<Triangle> {
+        indices
+            .iter()
+            .map(|t| {
+                let v = |k: usize| vertices[t[k] as usize];
+                Triangle::new(v(0), v(1), v(2))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tessellate_donut() {
+        let mut donut = Circle::new((0.0, 0.0), 2.0).to_path(1e-3);
+        donut.extend_path(
+            &Circle::new((0.0, 0.0), 1.0).to_path(1e-3),
+            Affine::IDENTITY,
+        );
+        let (vertices, indices) = donut.tessellate(1e-3, FillRule::EvenOdd);
+        let tris = mesh_triangles(&vertices, &indices);
+        assert!(tris.iter().all(|t| t.area() > 0.0));
+        let area: f64 = tris.iter().map(|t| t.area()).sum();
+        assert_approx_eq(area, 3.0 * PI, 0.01);
+        // The hole is not covered.
+        assert!(tris.iter().all(|t| t.winding(Point::ZERO) == 0));
+        // Vertices are shared between triangles.
+        let mut unique = vertices.clone();
+        unique.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+        unique.dedup();
+        assert_eq!(unique.len(), vertices.len());
+        assert!(vertices.len() < 3 * indices.len() / 2);
+
+        // With the nonzero rule, the inner circle has winding 2 and is filled.
+        let (vertices, indices) = donut.tessellate(1e-3, FillRule::NonZero);
+        let area: f64 = mesh_triangles(&vertices, &indices)
+            .iter()
+            .map(|t| t.area())
+            .sum();
+        assert_approx_eq(area, 4.0 * PI, 0.01);
+    }
+
+    #[test]
+    fn clip_with_hole() {
+        // A square with a square hole, clipped to a circle.
+        let mut path = Rect::new(-2.0, -2.0, 2.0, 2.0).to_path(1e-9);
+        path.extend_path(
+            &Rect::new(-1.0, -1.0, 1.0, 1.0).to_path(1e-9),
+            Affine::FLIP_X,
+        );
+        let circle = Circle::new((2.0, 0.0), 1.5);
+        let clipped = path.clip_to(&circle, 1e-4);
+        assert!(clipped.contains(Point::new(1.5, 0.0)));
+        assert!(!clipped.contains(Point::new(0.75, 0.0)));
+        assert!(!clipped.contains(Point::new(2.5, 0.0)));
+        assert_approx_eq(
+            clipped.area(),
+            intersection_area(&path, &circle, 1e-4),
+            1e-9,
+        );
+
+        // A rectangle containing the shape leaves its curves intact.
+        let circle = Circle::new((0.0, 0.0), 1.0);
+        let clipped = circle.clip_to(&Rect::new(-2.0, -2.0, 2.0, 2.0), 1e-4);
+        assert!(matches!(clipped.elements()[1], PathEl::CurveTo(..)));
+        assert!(circle
+            .clip_to(&Rect::new(3.0, 3.0, 4.0, 4.0), 1e-4)
+            .is_empty());
+    }
+
+    #[test]
+    fn coincident_edges() {
+        // Two squares sharing an exact edge: the shared edge is interior to
+        // their union and bounds their (empty) intersection.
+        let a = Rect::new(0.0, 0.0, 1.0, 1.0);
+        let b = Rect::new(1.0, 0.0, 2.0, 1.0);
+        let overlay = Overlay::new(&a, &b, 0.1);
+        assert_approx_eq(overlay.area(|w| w[0] != 0 || w[1] != 0), 2.0, 1e-12);
+        assert_approx_eq(overlay.area(|w| w[0] != 0 && w[1] != 0), 0.0, 1e-12);
+        assert_approx_eq(overlay.area(|w| w[0] != 0 && w[1] == 0), 1.0, 1e-12);
+        assert!(intersection(&a, &b, 0.1).is_empty());
+        assert_eq!(a.relate(&b, 1e-9), SpatialRelation::Touching);
+        // The union is a single rectangle, without the shared edge.
+        let union = overlay.to_path(|w| w[0] != 0 || w[1] != 0);
+        assert_eq!(union.elements().len(), 5);
+
+        // Both squares in one path: one region, and no sliver or gap.
+        let mut path = a.to_path(1e-9);
+        path.extend_path(&b.to_path(1e-9), Affine::IDENTITY);
+        let regions = path.regions(1e-3);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].1, 1);
+        assert_approx_eq(regions[0].0.area(), 2.0, 1e-12);
+        assert_approx_eq(path.area(), 2.0, 1e-12);
+        assert_approx_eq(path.fill_discrepancy(1e-6), 0.0, 1e-12);
+
+        // A partially shared edge, and an edge shared in the same direction
+        // by a square overlapping another.
+        let c = Rect::new(0.5, 1.0, 1.5, 2.0);
+        let overlay = Overlay::new(&a, &c, 0.1);
+        assert_approx_eq(overlay.area(|w| w[0] != 0 || w[1] != 0), 2.0, 1e-12);
+        assert_approx_eq(overlay.area(|w| w[0] != 0 && w[1] != 0), 0.0, 1e-12);
+        let d = Rect::new(0.0, 0.0, 0.5, 1.0);
+        let overlay = Overlay::new(&a, &d, 0.1);
+        assert_approx_eq(overlay.area(|w| w[0] != 0 && w[1] != 0), 0.5, 1e-12);
+        assert_approx_eq(overlay.area(|w| w[0] != 0 && w[1] == 0), 0.5, 1e-12);
+        assert_approx_eq(intersection(&a, &d, 0.1).area(), 0.5, 1e-12);
+        assert_approx_eq(intersection(&a, &a, 0.1).area(), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn coincident_edges_rounded() {
+        // A rotated square, and a triangle on one of its edges whose base
+        // vertices are computed points of that edge, so they lie on it only
+        // up to rounding.
+        let t = Affine::rotate(0.3);
+        let square = t * Rect::new(0.0, 0.0, 1.0, 1.0).to_path(1e-9);
+        let (q0, q1) = (t * Point::new(1.0, 0.0), t * Point::new(1.0, 1.0));
+        let (b0, b1) = (q0.lerp(q1, 0.3), q0.lerp(q1, 0.9));
+        let apex = t * Point::new(1.5, 0.6);
+        let mut tri = BezPath::new();
+        tri.move_to(b0).line_to(apex).line_to(b1).close_path();
+        let tri_area = tri.area().abs();
+        let overlay = Overlay::new(&square, &tri, 0.1);
+        assert_approx_eq(
+            overlay.area(|w| w[0] != 0 || w[1] != 0),
+            1.0 + tri_area,
+            1e-9,
+        );
+        assert_approx_eq(overlay.area(|w| w[0] != 0 && w[1] != 0), 0.0, 1e-9);
+        let mut path = square.clone();
+        path.extend_path(&tri, Affine::IDENTITY);
+        let regions = path.regions(1e-3);
+        assert_eq!(regions.len(), 1);
+        assert_approx_eq(regions[0].0.area(), 1.0 + tri_area, 1e-9);
+    }
+
+    #[test]
+    fn sweep_circle() {
+        let circle = Circle::new((0.0, 0.0), 1.0);
+        let mut line = BezPath::new();
+        line.move_to((0.0, 0.0)).line_to((10.0, 0.0));
+        let swept = sweep(&circle, &line, 1e-3);
+        assert_approx_eq(swept.area(), 20.0 + PI, 1e-2);
+        assert!(swept.contains(Point::new(5.0, 0.99)));
+        assert!(!swept.contains(Point::new(5.0, 1.01)));
+        assert!(swept.contains(Point::new(10.9, 0.0)));
+
+        // Along a curve, the sweep of a circle is its stroke.
+        let mut curve = BezPath::new();
+        curve.move_to((0.0, 0.0));
+        curve.curve_to((0.0, 10.0), (10.0, 10.0), (10.0, 0.0));
+        let swept = sweep(&circle, &curve, 1e-3);
+        let len = curve.segments().next().unwrap().arclen(1e-9);
+        assert_approx_eq(swept.area(), 2.0 * len + PI, 0.05);
+
+        // A single point gives the shape itself.
+        let mut point = BezPath::new();
+        point.move_to((3.0, 4.0));
+        assert_approx_eq(sweep(&circle, &point, 1e-3).area(), PI, 1e-2);
+    }
+
+    #[test]
+    fn area_in_rect() {
+        // A quarter of a circle, and all of it.
+        let circle = Circle::new((0.0, 0.0), 2.0);
+        let quadrant = Rect::new(0.0, 0.0, 5.0, 5.0);
+        assert_approx_eq(circle.area_in_rect(quadrant, 1e-6), PI, 1e-5);
+        let all = Rect::new(-3.0, -3.0, 3.0, 3.0);
+        assert_approx_eq(circle.area_in_rect(all, 1e-6), 4.0 * PI, 1e-5);
+        assert_eq!(
+            circle.area_in_rect(Rect::new(3.0, 3.0, 4.0, 4.0), 1e-6),
+            0.0
+        );
+        // The orientation of the shape and the rectangle doesn't matter.
+        let reversed = Affine::FLIP_Y * circle.to_path(1e-9);
+        assert_approx_eq(reversed.area_in_rect(quadrant, 1e-6), PI, 1e-5);
+        assert_approx_eq(
+            circle.area_in_rect(Rect::new(5.0, 5.0, 0.0, 0.0), 1e-6),
+            PI,
+            1e-5,
+        );
+
+        // A square ring: holes inside the rect are not counted.
+        let mut ring = Rect::new(0.0, 0.0, 4.0, 4.0).to_path(1e-9);
+        let hole = Rect::new(1.0, 1.0, 3.0, 3.0).to_path(1e-9);
+        ring.extend(Affine::new([-1.0, 0.0, 0.0, 1.0, 4.0, 0.0]).transform_elements(hole));
+        assert_approx_eq(
+            ring.area_in_rect(Rect::new(0.0, 0.0, 2.0, 2.0), 1e-6),
+            3.0,
+            1e-9,
+        );
+        assert_approx_eq(
+            ring.area_in_rect(Rect::new(2.0, -1.0, 5.0, 5.0), 1e-6),
+            6.0,
+            1e-9,
+        );
+        assert_approx_eq(
+            ring.area_in_rect(Rect::new(1.5, 1.5, 2.5, 2.5), 1e-6),
+            0.0,
+            1e-9,
+        );
+        // Rectangles are computed exactly.
+        let r = Rect::new(0.0, 0.0, 3.0, 3.0);
+        assert_eq!(r.area_in_rect(Rect::new(1.0, 2.0, 5.0, 5.0), 1e-6), 2.0);
+    }
+
+    #[test]
+    fn convexity_defect() {
+        let circle = Circle::new((1.0, 2.0), 3.0);
+        assert!(circle.convexity_defect(1e-3) < 1e-9);
+        assert_approx_eq(circle.solidity(1e-3), 1.0, 1e-9);
+
+        // A C shape: a 3x3 square missing a 1x2 notch on the right.
+        let mut c = BezPath::new();
+        c.move_to((0.0, 0.0));
+        c.line_to((3.0, 0.0));
+        c.line_to((3.0, 1.0));
+        c.line_to((1.0, 1.0));
+        c.line_to((1.0, 2.0));
+        c.line_to((3.0, 2.0));
+        c.line_to((3.0, 3.0));
+        c.line_to((0.0, 3.0));
+        c.close_path();
+        assert_approx_eq(c.convexity_defect(1e-3), 2.0, 1e-9);
+        assert_approx_eq(c.solidity(1e-3), 7.0 / 9.0, 1e-9);
+        // The defect scales with area, the solidity not at all.
+        let big = Affine::scale(2.0) * &c;
+        assert_approx_eq(big.convexity_defect(1e-3), 8.0, 1e-9);
+        assert_approx_eq(big.solidity(1e-3), 7.0 / 9.0, 1e-9);
+
+        // A hole drawn in the opposite direction is uncovered, while one in
+        // the same direction covers its area a second time.
+        for &(flip, defect) in &[(true, 1.0), (false, 0.0)] {
+            let mut ring = Rect::new(0.0, 0.0, 4.0, 4.0).to_path(1e-9);
+            let hole = Rect::new(1.0, 1.0, 2.0, 2.0).to_path(1e-9);
+            let affine = if flip {
+                Affine::new([-1.0, 0.0, 0.0, 1.0, 3.0, 0.0])
+            } else {
+                Affine::default()
+            };
+            ring.extend(affine.transform_elements(hole));
+            assert_approx_eq(ring.convexity_defect(1e-3), defect, 1e-9);
+        }
+        assert_eq!(Line::new((0.0, 0.0), (1.0, 1.0)).solidity(1e-3), 1.0);
+    }
+
+    #[test]
+    fn sweep_square() {
+        // Without rotation, an axis-aligned square swept along an L shape.
+        let square = Rect::new(-0.5, -0.5, 0.5, 0.5);
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0))
+            .line_to((4.0, 0.0))
+            .line_to((4.0, 4.0));
+        let swept = sweep(&square, &path, 1e-3);
+        assert_approx_eq(swept.area(), 2.0 * 4.0 + 1.0, 1e-9);
+        // Reversing the orientation of the shape changes nothing.
+        let reversed = Affine::FLIP_X * square.to_path(1e-9);
+        assert_approx_eq(sweep(&reversed, &path, 1e-3).area(), 9.0, 1e-9);
+
+        // Oriented along a circle, the square stays between the radii of
+        // its nearest and farthest points.
+        let circle = Circle::new((0.0, 0.0), 5.0).to_path(1e-9);
+        let swept = sweep_oriented(&square, &circle, 1e-2);
+        assert_approx_eq(swept.area(), PI * (30.5 - 20.25), 0.05);
+        assert!(swept.contains(Point::new(0.0, 5.5)));
+        assert!(!swept.contains(Point::new(0.0, 4.4)));
+        // Without rotation, the corners sweep a wider band.
+        assert!(sweep(&square, &circle, 1e-2).area() > swept.area() + 1.0);
+    }
+}
@@ -0,0 +1,376 @@
+//! A bounding volume hierarchy over the segments of a path.
+
+use crate::{BezPath, Line, LineIntersection, ParamCurveNearest, PathSeg, Point, Rect, Vec2};
+
+/// The maximum number of segments in a leaf node.
+const LEAF_SIZE: usize = 4;
+
+/// A bounding volume hierarchy over the segments of a [`BezPath`].
+///
+/// This accelerates nearest point and intersection queries on paths with
+/// many segments. Results agree with the corresponding linear scans, such as
+/// [`BezPath::nearest`], and segment indices count segments as in
+/// [`BezPath::segments`].
+///
+/// Construction computes the tight bounding box of each segment, then splits
+/// the segments recursively at the median of their box centers along the
+/// longer axis. This takes O(n log n) time and O(n) space for n segments.
+///
+/// [`BezPath`]: struct.BezPath.html
+/// [`BezPath::nearest`]: struct.BezPath.html#method.nearest
+/// [`BezPath::segments`]: struct.BezPath.html#method.segments
+#[derive(Clone, Debug)]
+pub struct PathBvh {
+    segs: Vec<PathSeg>,
+    bboxes: Vec<Rect>,
+    /// Segment indices, permuted so that each leaf covers a contiguous range.
+    order: Vec<usize>,
+    /// The nodes of the tree; the root is the first.
+    nodes: Vec<BvhNode>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum BvhNode {
+    Leaf {
+        bbox: Rect,
+        start: usize,
+        end: usize,
+    },
+    Internal {
+        bbox: Rect,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl BvhNode {
+    fn bbox(&self) -> Rect {
+        match *self {
+            BvhNode::Leaf { bbox, .. } | BvhNode::Internal { bbox, .. } => bbox,
+        }
+    }
+}
+
+impl PathBvh {
+    /// Build the hierarchy for a path.
+    pub fn new(path: &BezPath) -> PathBvh {
+        let segs: Vec<PathSeg> = path.segments().collect();
+        let bboxes = path.segment_bboxes();
+        let mut bvh = PathBvh {
+            order: (0..segs.len()).collect(),
+            segs,
+            bboxes,
+            nodes: Vec::new(),
+        };
+        if !bvh.segs.is_empty() {
+            bvh.build(0, bvh.segs.len());
+        }
+        bvh
+    }
+
+    /// The segments of the path.
+    pub fn segments(&self) -> &[PathSeg] {
+        &self.segs
+    }
+
+    /// Find the nearest point on the path.
+    ///
+    /// Returns the index of the segment, the parameter within that segment,
+    /// and the square of the distance to the point, as for
+    /// [`BezPath::nearest`], or `None` if the path has no segments.
+    ///
+    /// [`BezPath::nearest`]: struct.BezPath.html#method.nearest
+    pub fn nearest(&self, pt: Point, accuracy: f64) -> Option<(usize, f64, f64)> {
+        let mut best: Option<(usize, f64, f64)> = None;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(ix) = stack.pop() {
+            let node = self.nodes[ix];
+            if let Some((_, _, r_best)) = best {
+                if rect_dist2(node.bbox(), pt) > r_best {
+                    continue;
+                }
+            }
+            match node {
+                BvhNode::Leaf { start, end, .. } => {
+                    for &seg_ix in &self.order[start..end] {
+                        let (t, r) = self.segs[seg_ix].nearest(pt, accuracy);
+                        // Break ties by the lower index, as the linear scan does.
+                        let better = best
+                            .map(|(ix_best, _, r_best)| {
+                                r < r_best || (r == r_best && seg_ix < ix_best)
+                            })
+                            .unwrap_or(true);
+                        if better {
+                            best = Some((seg_ix, t, r));
+                        }
+                    }
+                }
+                BvhNode::Internal { left, right, .. } => {
+                    // Visit the nearer child first, so it is pushed last.
+                    let d_left = rect_dist2(self.nodes[left].bbox(), pt);
+                    let d_right = rect_dist2(self.nodes[right].bbox(), pt);
+                    if d_left <= d_right {
+                        stack.push(right);
+                        stack.push(left);
+                    } else {
+                        stack.push(left);
+                        stack.push(right);
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Find the first intersection of a ray with the path.
+    ///
+    /// The ray starts at `origin` and points along `dir`. Returns the index of
+    /// the segment that is hit, and the intersection, or `None` if the ray
+    /// misses the path. The `line_t` of the intersection is the ray
+    /// parameter, so the hit point is `origin + line_t * dir`.
+    pub fn intersect_ray(&self, origin: Point, dir: Vec2) -> Option<(usize, LineIntersection)> {
+        let root = self.nodes.first()?.bbox();
+        let (_, t_exit) = ray_box(origin, dir, root, f64::INFINITY)?;
+        if dir.hypot2() == 0.0 {
+            return None;
+        }
+        // A finite probe line reaching past the far side of the root box.
+        let t_max = t_exit.max(0.0) + 1.0;
+        let probe = Line::new(origin, origin + t_max * dir);
+        let mut best: Option<(usize, LineIntersection)> = None;
+        let mut stack = vec![0];
+        while let Some(ix) = stack.pop() {
+            let node = self.nodes[ix];
+            let limit = best.map(|(_, hit)| hit.line_t).unwrap_or(t_max);
+            if ray_box(origin, dir, node.bbox(), limit).is_none() {
+                continue;
+            }
+            match node {
+                BvhNode::Leaf { start, end, .. } => {
+                    for &seg_ix in &self.order[start..end] {
+                        for hit in self.segs[seg_ix].intersect_line(probe) {
+                            let hit = LineIntersection {
+                                line_t: hit.line_t * t_max,
+                                segment_t: hit.segment_t,
+                            };
+                            let better = best
+                                .map(|(ix_best, best)| {
+                                    hit.line_t < best.line_t
+                                        || (hit.line_t == best.line_t && seg_ix < ix_best)
+                                })
+                                .unwrap_or(true);
+                            if better {
+                                best = Some((seg_ix, hit));
+                            }
+                        }
+                    }
+                }
+                BvhNode::Internal { left, right, .. } => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
+        }
+        best
+    }
+
+    /// Find all intersections of a line segment with the path.
+    ///
+    /// The result is the same as calling [`PathSeg::intersect_line`] on each
+    /// segment in turn, and is ordered by segment index.
+    ///
+    /// [`PathSeg::intersect_line`]: enum.PathSeg.html#method.intersect_line
+    pub fn intersect_segment(&self, line: Line) -> Vec<(usize, LineIntersection)> {
+        let mut result = Vec::new();
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(ix) = stack.pop() {
+            let node = self.nodes[ix];
+            if ray_box(line.p0, line.p1 - line.p0, node.bbox(), 1.0).is_none() {
+                continue;
+            }
+            match node {
+                BvhNode::Leaf { start, end, .. } => {
+                    for &seg_ix in &self.order[start..end] {
+                        for hit in self.segs[seg_ix].intersect_line(line) {
+                            result.push((seg_ix, hit));
+                        }
+                    }
+                }
+                BvhNode::Internal { left, right, .. } => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
+        }
+        // The sort is stable, so hits within a segment keep their order.
+        result.sort_by_key(|&(ix, _)| ix);
+        result
+    }
+
+    /// Build the subtree for `order[start..end]`, returning its node index.
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let bbox = self.order[start..end]
+            .iter()
+            .map(|&i| self.bboxes[i])
+            .fold(self.bboxes[self.order[start]], |acc, b| acc.union(b));
+        let ix = self.nodes.len();
+        if end - start <= LEAF_SIZE {
+            self.nodes.push(BvhNode::Leaf { bbox, start, end });
+            return ix;
+        }
+        // Placeholder, replaced once the children are built.
+        self.nodes.push(BvhNode::Leaf { bbox, start, end });
+        let bboxes = &self.bboxes;
+        let key: fn(Point) -> f64 = if bbox.width() >= bbox.height() {
+            |p| p.x
+        } else {
+            |p| p.y
+        };
+        let mid = start + (end - start) / 2;
+        self.order[start..end].select_nth_unstable_by(mid - start, |&a, &b| {
+            let ka = key(bboxes[a].center());
+            let kb = key(bboxes[b].center());
+            ka.partial_cmp(&kb).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let left = self.build(start, mid);
+        let right = self.build(mid, end);
+        self.nodes[ix] = BvhNode::Internal { bbox, left, right };
+        ix
+    }
+}
+
+/// The squared distance from a point to a rectangle (zero inside).
+fn rect_dist2(rect: Rect, pt: Point) -> f64 {
+    (pt - rect.clamp(pt)).hypot2()
+}
+
+/// Clip the ray `origin + t * dir`, for `t` in `0..=t_max`, to a rectangle.
+///
+/// Returns the range of `t` inside the rectangle (boundary included), or
+/// `None` if the ray misses it.
+fn ray_box(origin: Point, dir: Vec2, rect: Rect, t_max: f64) -> Option<(f64, f64)> {
+    let mut t0 = 0.0f64;
+    let mut t1 = t_max;
+    for &(o, d, lo, hi) in &[
+        (origin.x, dir.x, rect.x0, rect.x1),
+        (origin.y, dir.y, rect.y0, rect.y1),
+    ] {
+        if d == 0.0 {
+            if o < lo || o > hi {
+                return None;
+            }
+        } else {
+            let ta = (lo - o) / d;
+            let tb = (hi - o) / d;
+            t0 = t0.max(ta.min(tb));
+            t1 = t1.min(ta.max(tb));
+        }
+    }
+    if t0 <= t1 {
+        Some((t0, t1))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, Line, ParamCurve, PathBvh, Point, Vec2};
+
+    /// A long wiggly path, from a simple linear congruential generator.
+    fn test_path(n: usize) -> BezPath {
+        let mut state = 12345u64;
+        let mut rand = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        for i in 0..n {
+            let x = i as f64;
+            let p = Point::new(x + rand(), 10.0 * rand());
+            match i % 3 {
+                0 => path.line_to(p),
+                1 => path.quad_to(Point::new(x + rand(), 10.0 * rand()), p),
+                _ => path.curve_to(
+                    Point::new(x, 10.0 * rand()),
+                    Point::new(x + rand(), 10.0 * rand()),
+                    p,
+                ),
+            };
+        }
+        path
+    }
+
+    #[test]
+    fn bvh_nearest() {
+        let path = test_path(200);
+        let bvh = PathBvh::new(&path);
+        assert_eq!(bvh.segments().len(), 200);
+        for i in 0..50 {
+            let pt = Point::new(i as f64 * 4.3 - 5.0, (i % 7) as f64 * 2.1 - 2.0);
+            let (ix, t, r) = bvh.nearest(pt, 1e-9).unwrap();
+            let (ix_brute, _, r_brute) = path.nearest(pt, 1e-9);
+            assert!((r - r_brute).abs() < 1e-9, "{} != {}", r, r_brute);
+            let seg_pt = bvh.segments()[ix].eval(t);
+            assert!(((pt - seg_pt).hypot2() - r).abs() < 1e-6);
+            if ix != ix_brute {
+                assert!(r == r_brute);
+            }
+        }
+        assert!(PathBvh::new(&BezPath::new())
+            .nearest(Point::ORIGIN, 1e-9)
+            .is_none());
+    }
+
+    #[test]
+    fn bvh_intersect_segment() {
+        let path = test_path(200);
+        let bvh = PathBvh::new(&path);
+        for i in 0..20 {
+            let x = i as f64 * 9.7;
+            let line = Line::new((x, -1.0), (x + 13.0, 11.0));
+            let hits = bvh.intersect_segment(line);
+            let brute: Vec<_> = path
+                .segments()
+                .enumerate()
+                .flat_map(|(ix, seg)| seg.intersect_line(line).into_iter().map(move |h| (ix, h)))
+                .collect();
+            assert_eq!(hits.len(), brute.len());
+            for ((ix, h), (ix_b, h_b)) in hits.iter().zip(&brute) {
+                assert_eq!(ix, ix_b);
+                assert_eq!(h.segment_t, h_b.segment_t);
+                assert_eq!(h.line_t, h_b.line_t);
+            }
+        }
+    }
+
+    #[test]
+    fn bvh_intersect_ray() {
+        let path = test_path(200);
+        let bvh = PathBvh::new(&path);
+        for i in 0..20 {
+            let origin = Point::new(i as f64 * 9.7, -1.0);
+            let dir = Vec2::new(0.3, 1.0);
+            let (ix, hit) = bvh.intersect_ray(origin, dir).unwrap();
+            // Compare with the nearest hit of a long probe line.
+            let probe = Line::new(origin, origin + 100.0 * dir);
+            let brute = path
+                .segments()
+                .flat_map(|seg| seg.intersect_line(probe))
+                .map(|h| h.line_t * 100.0)
+                .fold(f64::INFINITY, f64::min);
+            assert!((hit.line_t - brute).abs() < 1e-9);
+            let seg_pt = bvh.segments()[ix].eval(hit.segment_t);
+            assert!((seg_pt - (origin + hit.line_t * dir)).hypot() < 1e-6);
+        }
+        let miss = bvh.intersect_ray(Point::new(-1.0, -1.0), Vec2::new(-1.0, 0.0));
+        assert!(miss.is_none());
+    }
+}
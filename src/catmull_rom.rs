@@ -0,0 +1,227 @@
+//! Catmull-Rom splines.
+
+use crate::{BezPath, CubicBez, ParamCurve, ParamCurveArclen, Point};
+
+/// A uniform Catmull-Rom spline through a sequence of points.
+///
+/// The spline passes through every point, and is made of one cubic span
+/// between each pair of consecutive points, and also from the last point
+/// back to the first if it is closed. The tangent at each point is half the
+/// difference of its neighbours. At the ends of an open spline the missing
+/// neighbour is the reflection of the other one, so the tangent there points
+/// at the next point.
+///
+/// Each span only depends on the four points around it, so the spline can be
+/// evaluated directly from the points, without converting it to a
+/// [`BezPath`], and moving a point only changes the four spans nearest to
+/// it: the two it is an end of, and one more on each side.
+///
+/// [`BezPath`]: struct.BezPath.html
+#[derive(Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CatmullRom {
+    /// The points the spline passes through.
+    pub points: Vec<Point>,
+    /// Whether the spline continues from the last point back to the first.
+    pub closed: bool,
+}
+
+impl CatmullRom {
+    /// Create a spline through the given points.
+    #[inline]
+    pub fn new(points: Vec<Point>, closed: bool) -> CatmullRom {
+        CatmullRom { points, closed }
+    }
+
+    /// The number of cubic spans.
+    ///
+    /// This is the number of points, less one if the spline is open, or 0
+    /// if there are fewer than two points.
+    pub fn span_count(&self) -> usize {
+        match self.points.len() {
+            0 | 1 => 0,
+            n if self.closed => n,
+            n => n - 1,
+        }
+    }
+
+    /// The span from point `i` to the next, as a cubic Bézier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than [`span_count`].
+    ///
+    /// [`span_count`]: #method.span_count
+    pub fn span(&self, i: usize) -> CubicBez {
+        assert!(i < self.span_count(), "span index out of range");
+        let n = self.points.len();
+        let p1 = self.points[i];
+        let p2 = self.points[(i + 1) % n];
+        let p0 = if i > 0 || self.closed {
+            self.points[(i + n - 1) % n]
+        } else {
+            p1 - (p2 - p1)
+        };
+        let p3 = if i + 2 < n || self.closed {
+            self.points[(i + 2) % n]
+        } else {
+            p2 + (p2 - p1)
+        };
+        CubicBez::new(
+            p1,
+            p1 + (p2 - p0) * (1.0 / 6.0),
+            p2 - (p3 - p1) * (1.0 / 6.0),
+            p2,
+        )
+    }
+
+    /// The point at parameter `t` along the whole spline.
+    ///
+    /// Span `i` covers the parameters from `i` to `i + 1`, so point `i` is
+    /// at parameter `i`. The parameter is clamped to the spline, from 0 to
+    /// [`span_count`]. A spline with a single point is that point
+    /// everywhere, and one with no points is the origin.
+    ///
+    /// [`span_count`]: #method.span_count
+    pub fn eval(&self, t: f64) -> Point {
+        let n_spans = self.span_count();
+        if n_spans == 0 {
+            return self.points.first().copied().unwrap_or(Point::ORIGIN);
+        }
+        let t = t.clamp(0.0, n_spans as f64);
+        let i = (t.floor() as usize).min(n_spans - 1);
+        self.span(i).eval(t - i as f64)
+    }
+
+    /// The arc length of the whole spline, within `accuracy` for each span.
+    pub fn arclen(&self, accuracy: f64) -> f64 {
+        (0..self.span_count())
+            .map(|i| self.span(i).arclen(accuracy))
+            .sum()
+    }
+
+    /// The point at arc length `distance` along the spline from its start.
+    ///
+    /// The spans are measured in order until the one containing the given
+    /// distance, which is then solved for it, so the cost grows with the
+    /// distance, and nothing is kept between calls. Distances outside the
+    /// spline are clamped to its ends.
+    pub fn point_at_distance(&self, distance: f64, accuracy: f64) -> Point {
+        let n_spans = self.span_count();
+        let mut remaining = distance.max(0.0);
+        for i in 0..n_spans {
+            let span = self.span(i);
+            let len = span.arclen(accuracy);
+            if remaining <= len || i + 1 == n_spans {
+                let t = span.inv_arclen(remaining.min(len), accuracy);
+                return span.eval(t);
+            }
+            remaining -= len;
+        }
+        self.eval(0.0)
+    }
+
+    /// Convert the spline to a path of cubic Béziers, one for each span.
+    ///
+    /// A closed spline ends with a `ClosePath`.
+    pub fn to_path(&self) -> BezPath {
+        let mut path = BezPath::new();
+        let n_spans = self.span_count();
+        if n_spans == 0 {
+            return path;
+        }
+        path.move_to(self.points[0]);
+        for i in 0..n_spans {
+            let c = self.span(i);
+            path.curve_to(c.p1, c.p2, c.p3);
+        }
+        if self.closed {
+            path.close_path();
+        }
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CatmullRom, ParamCurve, ParamCurveArclen, PathSeg, Point};
+
+    fn spline(closed: bool) -> CatmullRom {
+        CatmullRom::new(
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(2.0, 3.0),
+                Point::new(5.0, 3.5),
+                Point::new(6.0, 0.0),
+                Point::new(9.0, -1.0),
+                Point::new(10.0, 2.0),
+            ],
+            closed,
+        )
+    }
+
+    #[test]
+    fn catmull_rom_matches_path() {
+        for &closed in &[false, true] {
+            let spline = spline(closed);
+            let path = spline.to_path();
+            let segs: Vec<PathSeg> = path.segments().collect();
+            assert_eq!(segs.len(), spline.span_count());
+            for (i, seg) in segs.iter().enumerate() {
+                for j in 0..=4 {
+                    let t = j as f64 / 4.0;
+                    let d = spline.eval(i as f64 + t) - seg.eval(t);
+                    assert!(d.hypot() < 1e-12);
+                }
+            }
+            // The spline passes through its points.
+            for (i, &p) in spline.points.iter().enumerate() {
+                assert!((spline.eval(i as f64) - p).hypot() < 1e-12);
+            }
+
+            let accuracy = 1e-9;
+            let total: f64 = segs.iter().map(|seg| seg.arclen(accuracy)).sum();
+            assert!((spline.arclen(accuracy) - total).abs() < 1e-8);
+            for k in 0..=10 {
+                let distance = total * k as f64 / 10.0;
+                // The same point, found along the path.
+                let mut remaining = distance;
+                let mut expected = segs[segs.len() - 1].end();
+                for seg in &segs {
+                    let len = seg.arclen(accuracy);
+                    if remaining <= len {
+                        expected = seg.eval(seg.inv_arclen(remaining, accuracy));
+                        break;
+                    }
+                    remaining -= len;
+                }
+                let p = spline.point_at_distance(distance, accuracy);
+                assert!((p - expected).hypot() < 1e-7, "{:?} {:?}", p, expected);
+            }
+            assert!((spline.point_at_distance(-1.0, accuracy) - spline.points[0]).hypot() < 1e-12);
+        }
+        let spline = spline(false);
+        assert_eq!(spline.eval(-3.0), spline.points[0]);
+        assert_eq!(spline.eval(100.0), spline.points[5]);
+        assert_eq!(CatmullRom::default().eval(1.0), Point::ORIGIN);
+        assert!(CatmullRom::default().to_path().elements().is_empty());
+        let single = CatmullRom::new(vec![Point::new(1.0, 1.0)], false);
+        assert_eq!(single.eval(0.5), Point::new(1.0, 1.0));
+        assert_eq!(single.point_at_distance(1.0, 1e-9), Point::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn catmull_rom_locality() {
+        for &closed in &[false, true] {
+            let spline = spline(closed);
+            let mut moved = spline.clone();
+            moved.points[3] = Point::new(7.0, 1.0);
+            let n = spline.span_count();
+            for i in 0..n {
+                // The spans from points 1 to 5 depend on point 3.
+                let near = (1..5).contains(&i);
+                assert_eq!(spline.span(i) != moved.span(i), near, "{} {}", closed, i);
+            }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Recovering the centerline of a stroke from its outline.
+
+use std::f64::consts::PI;
+
+use crate::{BezPath, Point, Shape, Vec2};
+
+/// The number of samples taken along the outline, at most, when the
+/// tolerance allows it.
+const CENTERLINE_SAMPLES: f64 = 1024.0;
+
+/// The widths within this fraction of the typical width, plus a multiple of
+/// the tolerance, are taken to belong to the stroke.
+const WIDTH_RELATIVE_TOLERANCE: f64 = 0.01;
+
+/// The largest length of the parts of the sides of the outline with a
+/// different width, relative to the parts with the stroke width.
+const MAX_MISMATCH_FRACTION: f64 = 0.25;
+
+/// A point on the outline, with what is across the outline from it.
+struct Sample {
+    /// The index of the edge of the outline the point is on.
+    edge: usize,
+    /// The arc length along the outline to the point.
+    pos: f64,
+    /// The length of outline the sample stands for.
+    weight: f64,
+    /// The distance to the first point of the outline in the inward normal
+    /// direction, or infinity if there is none.
+    width: f64,
+    /// The arc length along the outline to that point.
+    hit_pos: f64,
+    /// The point halfway between them.
+    mid: Point,
+}
+
+impl BezPath {
+    /// The centerline and width of a stroke, given its outline.
+    ///
+    /// This is an approximate inverse of stroking an open path with a
+    /// constant width: the path should be a single closed subpath made of
+    /// the two sides of the stroke, joined by the caps at its ends. It is
+    /// flattened within `tolerance`, and from points all along it, the
+    /// distance across the outline along the inward normal is measured. The
+    /// width of the stroke is the median of these distances, and the points
+    /// halfway across, from the parts of the outline where the distance is
+    /// the width, make up the centerline. The caps are the two parts where
+    /// the distance is different and along which the outline turns by half
+    /// a turn, so both butt and round caps are recognized.
+    ///
+    /// The centerline is returned as a polyline, simplified within
+    /// `tolerance`, going in the direction of one of the sides of the
+    /// outline. Near the joins of the stroke it cuts the corners, and turns
+    /// of more than about 135 degrees are not recognized.
+    ///
+    /// Returns `None` if the path does not look like the outline of a
+    /// constant-width stroke: if it has more than one subpath with segments
+    /// or encloses no area, if it has the same width everywhere, as a disc
+    /// does, if there are not exactly two caps, or if too much of the sides
+    /// has a different width.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Rect, Shape};
+    /// let outline = Rect::new(0.0, 0.0, 10.0, 2.0).to_path(1e-3);
+    /// let (centerline, width) = outline.centerline(1e-3).unwrap();
+    /// assert!((width - 2.0).abs() < 1e-9);
+    /// assert_eq!(centerline.elements().len(), 2);
+    /// assert!(Rect::new(0.0, 1.0, 10.0, 1.0).to_path(1e-3).centerline(1e-3).is_none());
+    /// ```
+    pub fn centerline(&self, tolerance: f64) -> Option<(BezPath, f64)> {
+        let mut polylines = self
+            .to_polyline(tolerance)
+            .into_iter()
+            .filter(|points| points.len() > 1);
+        let mut ring = match (polylines.next(), polylines.next()) {
+            (Some(points), None) => points,
+            _ => return None,
+        };
+        ring.dedup();
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        let n = ring.len();
+        if n < 3 {
+            return None;
+        }
+        let edge = |i: usize| ring[(i + 1) % n] - ring[i % n];
+        let area: f64 = (0..n).map(|i| ring[i].to_vec2().cross(edge(i))).sum();
+        if area == 0.0 {
+            return None;
+        }
+        let mut cum_len = Vec::with_capacity(n + 1);
+        cum_len.push(0.0);
+        for i in 0..n {
+            cum_len.push(cum_len[i] + edge(i).hypot());
+        }
+        let perimeter = cum_len[n];
+        let spacing = (perimeter / CENTERLINE_SAMPLES).max(tolerance);
+
+        let mut samples = Vec::new();
+        for i in 0..n {
+            let d = edge(i);
+            let len = d.hypot();
+            // The interior is to the left of the outline if its area is
+            // positive.
+            let normal = area.signum() / len * Vec2::new(-d.y, d.x);
+            let k = (len / spacing).ceil().max(1.0) as usize;
+            for j in 0..=k {
+                let t = j as f64 / k as f64;
+                let p = ring[i] + t * d;
+                let (width, hit_pos) = cast_ray(&ring, &cum_len, p, normal);
+                samples.push(Sample {
+                    edge: i,
+                    pos: cum_len[i] + t * len,
+                    weight: len / (k + 1) as f64,
+                    width,
+                    hit_pos,
+                    mid: p + 0.5 * width * normal,
+                });
+            }
+        }
+        let mut widths: Vec<f64> = samples
+            .iter()
+            .map(|s| s.width)
+            .filter(|w| w.is_finite())
+            .collect();
+        if widths.is_empty() {
+            return None;
+        }
+        widths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let width = widths[widths.len() / 2];
+        let band = 4.0 * tolerance + WIDTH_RELATIVE_TOLERANCE * width;
+        let matches = |s: &Sample| (s.width - width).abs() <= band;
+
+        // Split the samples into runs with the stroke width, starting from
+        // the beginning of one.
+        let m = samples.len();
+        let start =
+            (0..m).find(|&i| matches(&samples[i]) && !matches(&samples[(i + m - 1) % m]))?;
+        let mut runs: Vec<Vec<usize>> = Vec::new();
+        let mut in_run = false;
+        for i in (start..m).chain(0..start) {
+            if matches(&samples[i]) {
+                if !in_run {
+                    runs.push(Vec::new());
+                }
+                runs.last_mut().unwrap().push(i);
+                in_run = true;
+            } else {
+                in_run = false;
+            }
+        }
+
+        // The gap after each run is a cap if the outline turns by half a
+        // turn along it.
+        let n_runs = runs.len();
+        let mut caps = Vec::new();
+        let mut mismatch = 0.0;
+        for r in 0..n_runs {
+            let a = *runs[r].last().unwrap();
+            let b = runs[(r + 1) % n_runs][0];
+            let mut turning = 0.0;
+            let mut e = samples[a].edge;
+            while e != samples[b].edge {
+                turning += edge(e).angle_between(edge(e + 1));
+                e = (e + 1) % n;
+            }
+            if (turning * area.signum() - PI).abs() < 0.25 * PI {
+                caps.push(r);
+            } else {
+                let gap = (samples[b].pos - samples[a].pos).rem_euclid(perimeter);
+                mismatch += gap;
+            }
+        }
+        let matched: f64 = samples
+            .iter()
+            .filter(|s| matches(s))
+            .map(|s| s.weight)
+            .sum();
+        if caps.len() != 2 || mismatch > MAX_MISMATCH_FRACTION * matched {
+            return None;
+        }
+
+        // Order the points from both sides by where they are along the
+        // first side, those from the second by the point across from them.
+        let side_a = (caps[0] + 1..=caps[1]).flat_map(|r| runs[r].iter().copied());
+        let side_b = (caps[1] + 1..n_runs)
+            .chain(0..=caps[0])
+            .flat_map(|r| runs[r].iter().copied());
+        let a_start = samples[runs[caps[0] + 1][0]].pos;
+        let a_len = (samples[*runs[caps[1]].last().unwrap()].pos - a_start).rem_euclid(perimeter);
+        let along = |pos: f64| (pos - a_start).rem_euclid(perimeter);
+        let mut keyed: Vec<(f64, Point)> = side_a
+            .map(|i| (along(samples[i].pos), samples[i].mid))
+            .collect();
+        keyed.extend(
+            side_b
+                .map(|i| (along(samples[i].hit_pos), samples[i].mid))
+                .filter(|&(key, _)| key <= a_len),
+        );
+        keyed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        let mids: Vec<Point> = keyed.into_iter().map(|(_, p)| p).collect();
+
+        let mut centerline = BezPath::new();
+        centerline.move_to(mids[0]);
+        simplify_polyline(&mids, tolerance, &mut centerline);
+        Some((centerline, width))
+    }
+}
+
+/// The distance from `p` to the first crossing of the closed polyline
+/// `ring` in the direction `dir`, a unit vector, with the arc length along
+/// the polyline to that crossing.
+fn cast_ray(ring: &[Point], cum_len: &[f64], p: Point, dir: Vec2) -> (f64, f64) {
+    let n = ring.len();
+    // Crossings this close are the edges through `p` itself.
+    let eps = 1e-9 * cum_len[n];
+    let mut best = (f64::INFINITY, 0.0);
+    for i in 0..n {
+        let a = ring[i];
+        let e = ring[(i + 1) % n] - a;
+        let denom = dir.cross(e);
+        if denom == 0.0 {
+            continue;
+        }
+        let w = a - p;
+        let s = w.cross(e) / denom;
+        let u = w.cross(dir) / denom;
+        if s > eps && s < best.0 && (0.0..=1.0).contains(&u) {
+            best = (s, cum_len[i] + u * (cum_len[i + 1] - cum_len[i]));
+        }
+    }
+    best
+}
+
+/// Append lines to `path` approximating the polyline within `tolerance`,
+/// by the Douglas-Peucker algorithm, leaving out its first point.
+fn simplify_polyline(points: &[Point], tolerance: f64, path: &mut BezPath) {
+    let n = points.len();
+    if n < 2 {
+        return;
+    }
+    let (a, b) = (points[0], points[n - 1]);
+    let chord = b - a;
+    let len2 = chord.hypot2();
+    let mut farthest = (0.0, 0);
+    for (i, &p) in points.iter().enumerate().take(n - 1).skip(1) {
+        let t = if len2 > 0.0 {
+            ((p - a).dot(chord) / len2).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let dist = (p - (a + t * chord)).hypot();
+        if dist > farthest.0 {
+            farthest = (dist, i);
+        }
+    }
+    if farthest.0 > tolerance {
+        simplify_polyline(&points[..=farthest.1], tolerance, path);
+        simplify_polyline(&points[farthest.1..], tolerance, path);
+    } else {
+        path.line_to(b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        variable_width_outline, Arc, BezPath, Circle, Ellipse, ParamCurve, Point, Rect, Shape, Vec2,
+    };
+
+    fn assert_near_polyline(path: &BezPath, polyline: &[Point], eps: f64) {
+        for seg in path.segments() {
+            for i in 0..=4 {
+                let p = seg.eval(i as f64 / 4.0);
+                let dist = polyline
+                    .windows(2)
+                    .map(|w| {
+                        let chord = w[1] - w[0];
+                        let t = ((p - w[0]).dot(chord) / chord.hypot2()).clamp(0.0, 1.0);
+                        (p - (w[0] + t * chord)).hypot()
+                    })
+                    .fold(f64::INFINITY, f64::min);
+                assert!(dist < eps, "{:?} is {} away", p, dist);
+            }
+        }
+    }
+
+    #[test]
+    fn centerline_of_strokes() {
+        let tolerance = 1e-3;
+        // Round caps and a join.
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(20.0, 5.0),
+        ];
+        let outline = variable_width_outline(&points, &[1.0]);
+        let (centerline, width) = outline.centerline(tolerance).unwrap();
+        assert!((width - 2.0).abs() < 0.02, "{}", width);
+        // Near the join the centerline cuts the corner.
+        assert_near_polyline(&centerline, &points, 0.2);
+        let ends = [
+            centerline.segments().next().unwrap().start(),
+            centerline.segments().last().unwrap().end(),
+        ];
+        assert!(ends.iter().any(|&p| (p - points[0]).hypot() < 0.05));
+        assert!(ends.iter().any(|&p| (p - points[2]).hypot() < 0.05));
+
+        // Butt caps, turned.
+        let outline = Rect::new(0.0, 0.0, 30.0, 3.0).to_path(tolerance);
+        let (centerline, width) = (crate::Affine::rotate(0.3) * outline)
+            .centerline(tolerance)
+            .unwrap();
+        assert!((width - 3.0).abs() < 1e-9);
+        let axis = [Point::new(0.0, 1.5), Point::new(30.0, 1.5)];
+        let axis = [
+            crate::Affine::rotate(0.3) * axis[0],
+            crate::Affine::rotate(0.3) * axis[1],
+        ];
+        assert_near_polyline(&centerline, &axis, 1e-6);
+        assert_eq!(centerline.segments().count(), 1);
+
+        // A curved stroke, an annular sector drawn in the other direction.
+        let center = Point::new(2.0, 3.0);
+        let arc = |radius: f64, start_angle: f64, sweep_angle: f64| Arc {
+            center,
+            radii: Vec2::new(radius, radius),
+            start_angle,
+            sweep_angle,
+            x_rotation: 0.0,
+        };
+        let mut outline = BezPath::new();
+        outline.move_to(center + Vec2::new(11.0, 0.0));
+        outline.extend(arc(11.0, 0.0, -2.0).append_iter(1e-6));
+        outline.line_to(center + Vec2::from_angle(-2.0) * 9.0);
+        outline.extend(arc(9.0, -2.0, 2.0).append_iter(1e-6));
+        outline.close_path();
+        let (centerline, width) = outline.centerline(tolerance).unwrap();
+        assert!((width - 2.0).abs() < 0.01, "{}", width);
+        for seg in centerline.segments() {
+            let r = (seg.start() - center).hypot();
+            assert!((r - 10.0).abs() < 0.01, "{}", r);
+        }
+        assert!(centerline.segments().count() > 10);
+    }
+
+    #[test]
+    fn centerline_not_a_stroke() {
+        let tolerance = 1e-3;
+        let circle = Circle::new((0.0, 0.0), 5.0).to_path(tolerance);
+        assert!(circle.centerline(tolerance).is_none());
+        let ellipse = Ellipse::new((0.0, 0.0), (8.0, 3.0), 0.2).to_path(tolerance);
+        assert!(ellipse.centerline(tolerance).is_none());
+        let mut star = BezPath::new();
+        for i in 0..10 {
+            let r = if i % 2 == 0 { 10.0 } else { 4.0 };
+            let pt = Point::ORIGIN + Vec2::from_angle(i as f64 * 0.2 * std::f64::consts::PI) * r;
+            if i == 0 {
+                star.move_to(pt);
+            } else {
+                star.line_to(pt);
+            }
+        }
+        star.close_path();
+        assert!(star.centerline(tolerance).is_none());
+        let mut two = Rect::new(0.0, 0.0, 10.0, 1.0).to_path(tolerance);
+        two.extend(Rect::new(0.0, 5.0, 10.0, 6.0).to_path(tolerance));
+        assert!(two.centerline(tolerance).is_none());
+        assert!(BezPath::new().centerline(tolerance).is_none());
+    }
+}
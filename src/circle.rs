@@ -6,7 +6,9 @@ use std::{
     ops::{Add, Mul, Sub},
 };
 
-use crate::{Affine, Arc, ArcAppendIter, Ellipse, PathEl, Point, Rect, Shape, Vec2};
+use arrayvec::ArrayVec;
+
+use crate::{Affine, Arc, ArcAppendIter, BezPath, Ellipse, Line, PathEl, Point, Rect, Shape, Vec2};
 
 /// A circle.
 #[derive(Clone, Copy, Default, Debug, PartialEq)]
@@ -19,6 +21,12 @@ pub struct Circle {
 }
 
 impl Circle {
+    /// The circle of radius 1 centered at the origin.
+    pub const UNIT: Circle = Circle {
+        center: Point::ORIGIN,
+        radius: 1.0,
+    };
+
     /// A new circle from center and radius.
     #[inline]
     pub fn new(center: impl Into<Point>, radius: f64) -> Circle {
@@ -28,6 +36,16 @@ impl Circle {
         }
     }
 
+    /// The circle with the line from `p0` to `p1` as a diameter.
+    ///
+    /// The center is the midpoint of the two points, and the radius is half
+    /// the distance between them.
+    #[inline]
+    pub fn from_diameter(p0: impl Into<Point>, p1: impl Into<Point>) -> Circle {
+        let (p0, p1) = (p0.into(), p1.into());
+        Circle::new(p0.midpoint(p1), 0.5 * p0.distance(p1))
+    }
+
     /// Create a [`CircleSegment`] by cutting out parts of this circle.
     ///
     /// [`CircleSegment`]: struct.CircleSegment.html
@@ -40,6 +58,86 @@ impl Circle {
             sweep_angle,
         }
     }
+
+    /// The lines from a point tangent to this circle.
+    ///
+    /// Each line runs from `pt` to the point where it touches the circle.
+    /// For a point outside the circle there are two, the first touching the
+    /// circle counterclockwise (in a y-up coordinate system) of the ray from
+    /// the center toward `pt`, and the second clockwise of it. For a point on
+    /// the circle there is a single tangent, returned as the line from `pt`
+    /// in the counterclockwise direction around the circle, with a length of
+    /// the radius. For a point inside there are none.
+    pub fn tangent_lines_from(self, pt: Point) -> ArrayVec<[Line; 2]> {
+        let mut result = ArrayVec::new();
+        let r = self.radius.abs();
+        let v = pt - self.center;
+        let d2 = v.hypot2();
+        let r2 = r * r;
+        if d2 < r2 || d2 == 0.0 {
+            return result;
+        }
+        let perp = Vec2::new(-v.y, v.x);
+        if d2 == r2 {
+            result.push(Line::new(pt, pt + perp * (r / d2.sqrt())));
+            return result;
+        }
+        // The tangent point is at `v (r / d)^2 ± perp r sqrt(d^2 - r^2) / d^2`
+        // from the center, with `d = |v|`.
+        let along = v * (r2 / d2);
+        let across = perp * (r * (d2 - r2).sqrt() / d2);
+        result.push(Line::new(pt, self.center + along + across));
+        result.push(Line::new(pt, self.center + along - across));
+        result
+    }
+
+    /// The common tangents not crossing between the centers of two circles.
+    ///
+    /// Each line runs from the point where it touches this circle to the one
+    /// where it touches `other`, and both circles are on the same side of
+    /// it. The first line is to the left of the direction from this center
+    /// to the other (in a y-up coordinate system), and the second to the
+    /// right. There are none when one circle is strictly inside the other or
+    /// they touch from inside, including equal circles.
+    pub fn outer_tangents(self, other: Circle) -> ArrayVec<[Line; 2]> {
+        let (r0, r1) = (self.radius.abs(), other.radius.abs());
+        self.common_tangents(other, r0 - r1, 1.0)
+    }
+
+    /// The common tangents crossing between the centers of two circles.
+    ///
+    /// Each line runs from the point where it touches this circle to the one
+    /// where it touches `other`, and the circles are on opposite sides of
+    /// it. The first line touches this circle to the left of the direction
+    /// from this center to the other (in a y-up coordinate system), and the
+    /// second to the right. There are none when the circles overlap or
+    /// touch.
+    pub fn inner_tangents(self, other: Circle) -> ArrayVec<[Line; 2]> {
+        let (r0, r1) = (self.radius.abs(), other.radius.abs());
+        self.common_tangents(other, r0 + r1, -1.0)
+    }
+
+    /// The tangents touching this circle at `c0 + r0 n` and the other at
+    /// `c1 + sign r1 n`, for the unit normals `n` with `n . (c1 - c0) = k`.
+    fn common_tangents(self, other: Circle, k: f64, sign: f64) -> ArrayVec<[Line; 2]> {
+        let mut result = ArrayVec::new();
+        let (r0, r1) = (self.radius.abs(), other.radius.abs());
+        let v = other.center - self.center;
+        let d2 = v.hypot2();
+        if d2 <= k * k {
+            return result;
+        }
+        let perp = Vec2::new(-v.y, v.x);
+        let along = v * (k / d2);
+        let across = perp * ((d2 - k * k).sqrt() / d2);
+        for &n in &[along + across, along - across] {
+            result.push(Line::new(
+                self.center + r0 * n,
+                other.center + (sign * r1) * n,
+            ));
+        }
+        result
+    }
 }
 
 impl Add<Vec2> for Circle {
@@ -118,13 +216,38 @@ impl Shape for Circle {
     }
 
     fn winding(&self, pt: Point) -> i32 {
-        if (pt - self.center).hypot2() < self.radius.powi(2) {
+        if self.contains(pt) {
             1
         } else {
             0
         }
     }
 
+    #[inline]
+    fn contains(&self, pt: Point) -> bool {
+        (pt - self.center).hypot2() < self.radius.powi(2)
+    }
+
+    #[inline]
+    fn distance_to_point(&self, pt: Point, _accuracy: f64) -> f64 {
+        ((pt - self.center).hypot() - self.radius.abs()).abs()
+    }
+
+    /// The farthest point is on the ray from `pt` through the center. If
+    /// `pt` is the center, every point on the circle is equally far, and
+    /// the one in the positive x direction is returned.
+    fn farthest_point(&self, pt: Point, _accuracy: f64) -> (Point, f64) {
+        let r = self.radius.abs();
+        let d = self.center - pt;
+        let dist = d.hypot();
+        let dir = if dist > 0.0 {
+            d / dist
+        } else {
+            Vec2::new(1.0, 0.0)
+        };
+        (self.center + r * dir, dist + r)
+    }
+
     #[inline]
     fn bounding_box(&self) -> Rect {
         let r = self.radius.abs();
@@ -132,6 +255,12 @@ impl Shape for Circle {
         Rect::new(x - r, y - r, x + r, y + r)
     }
 
+    /// The circle with the radius changed by `distance`, clamped at zero.
+    fn inset(&self, distance: f64, tolerance: f64) -> BezPath {
+        let radius = (self.radius.abs() + distance).max(0.0);
+        Circle::new(self.center, radius).to_path(tolerance)
+    }
+
     fn as_circle(&self) -> Option<Circle> {
         Some(*self)
     }
@@ -318,9 +447,18 @@ impl Shape for CircleSegment {
     }
 }
 
+#[inline]
+fn point_on_circle(center: Point, radius: f64, angle: f64) -> Point {
+    center
+        + Vec2 {
+            x: angle.cos() * radius,
+            y: angle.sin() * radius,
+        }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{Circle, Point, Shape};
+    use crate::{Circle, Line, Point, Shape};
     use std::f64::consts::PI;
 
     fn assert_approx_eq(x: f64, y: f64) {
@@ -350,13 +488,101 @@ mod tests {
         assert_approx_eq(c_neg_radius.area(), p_neg_radius.area());
         assert_eq!(c_neg_radius.winding(center), p_neg_radius.winding(center));
     }
-}
 
-#[inline]
-fn point_on_circle(center: Point, radius: f64, angle: f64) -> Point {
-    center
-        + Vec2 {
-            x: angle.cos() * radius,
-            y: angle.sin() * radius,
+    #[test]
+    fn contains() {
+        let c = Circle::new((5.0, 5.0), 5.0);
+        assert!(c.contains(Point::new(5.0, 5.0)));
+        assert!(c.contains(Point::new(8.0, 8.0)));
+        assert!(!c.contains(Point::new(9.0, 9.0)));
+        assert!(!c.contains(Point::new(10.0, 5.0)));
+        let p = c.to_path(1e-9);
+        assert!(p.contains(Point::new(8.0, 8.0)));
+        assert!(!p.contains(Point::new(9.0, 9.0)));
+    }
+
+    #[test]
+    fn distance_to_point() {
+        let c = Circle::new((5.0, 5.0), 5.0);
+        assert_approx_eq(c.distance_to_point(Point::new(5.0, 5.0), 1e-9), 5.0);
+        assert_approx_eq(c.distance_to_point(Point::new(5.0, 13.0), 1e-9), 3.0);
+        assert_approx_eq(c.distance_to_point(Point::new(8.0, 9.0), 1e-9), 0.0);
+        let p = c.to_path(1e-9);
+        let pt = Point::new(1.0, 2.0);
+        assert!((p.distance_to_point(pt, 1e-9) - c.distance_to_point(pt, 1e-9)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn farthest_point() {
+        let c = Circle::new((5.0, 5.0), 5.0);
+        let (far, d) = c.farthest_point(Point::new(5.0, 13.0), 1e-9);
+        assert_approx_eq(d, 13.0);
+        assert_approx_eq(far.distance(Point::new(5.0, 0.0)), 0.0);
+        assert_approx_eq(c.farthest_point(c.center, 1e-9).1, 5.0);
+        // The general case on the path agrees.
+        let pt = Point::new(1.0, 2.0);
+        let (path_far, path_d) = c.to_path(1e-9).farthest_point(pt, 1e-9);
+        let (far, d) = c.farthest_point(pt, 1e-9);
+        assert!((path_d - d).abs() < 1e-6);
+        assert!(path_far.distance(far) < 1e-3);
+    }
+
+    #[test]
+    fn tangents() {
+        let c = Circle::new((1.0, 1.0), 3.0);
+        let pt = Point::new(6.0, 1.0);
+        let lines = c.tangent_lines_from(pt);
+        assert_eq!(lines.len(), 2);
+        for line in &lines {
+            assert_eq!(line.p0, pt);
+            assert_approx_eq(line.p1.distance(c.center), 3.0);
+            // The radius to the tangent point is perpendicular to the line.
+            assert_approx_eq((line.p1 - c.center).dot(line.p1 - line.p0), 0.0);
         }
+        // A 3-4-5 triangle: the tangent points are at (2.8, 3.4) and (2.8, -1.4).
+        assert!(lines[0].p1.distance(Point::new(2.8, 3.4)) < 1e-12);
+        assert!(lines[1].p1.distance(Point::new(2.8, -1.4)) < 1e-12);
+        assert!(c.tangent_lines_from(Point::new(2.0, 2.0)).is_empty());
+        assert!(c.tangent_lines_from(c.center).is_empty());
+        let on = c.tangent_lines_from(Point::new(4.0, 1.0));
+        assert_eq!(on.len(), 1);
+        assert_eq!(on[0], Line::new((4.0, 1.0), (4.0, 4.0)));
+
+        let a = Circle::new((0.0, 0.0), 2.0);
+        let b = Circle::new((10.0, 0.0), 1.0);
+        let check = |lines: &[Line], inner: bool| {
+            assert_eq!(lines.len(), 2);
+            for line in lines {
+                assert_approx_eq(line.p0.distance(a.center), 2.0);
+                assert_approx_eq(line.p1.distance(b.center), 1.0);
+                let dir = line.p1 - line.p0;
+                assert_approx_eq((line.p0 - a.center).dot(dir), 0.0);
+                assert_approx_eq((line.p1 - b.center).dot(dir), 0.0);
+                let side = |p: Point| dir.cross(p - line.p0);
+                assert_eq!(side(a.center) * side(b.center) < 0.0, inner);
+            }
+            assert!(lines[0].p0.y > 0.0 && lines[1].p0.y < 0.0);
+        };
+        check(&a.outer_tangents(b), false);
+        check(&a.inner_tangents(b), true);
+        // Outer tangents of equal circles are parallel to the centers' line.
+        let outer = a.outer_tangents(Circle::new((0.0, 5.0), 2.0));
+        assert!(outer[0].p0.distance(Point::new(-2.0, 0.0)) < 1e-12);
+        assert!(outer[0].p1.distance(Point::new(-2.0, 5.0)) < 1e-12);
+
+        assert!(a.inner_tangents(Circle::new((3.0, 0.0), 1.0)).is_empty());
+        assert!(a.outer_tangents(Circle::new((0.5, 0.0), 1.0)).is_empty());
+        assert!(a.outer_tangents(a).is_empty());
+        assert_eq!(a.outer_tangents(Circle::new((3.0, 0.0), 2.0)).len(), 2);
+    }
+
+    #[test]
+    fn constructors() {
+        let c = Circle::from_diameter((1.0, 1.0), (4.0, 5.0));
+        assert_eq!(c.center, Point::new(2.5, 3.0));
+        assert_eq!(c.radius, 2.5);
+        assert_approx_eq(Circle::UNIT.area(), PI);
+        assert!(Circle::UNIT.contains(Point::new(0.5, 0.5)));
+        assert_eq!(crate::Rect::UNIT.area(), 1.0);
+    }
 }
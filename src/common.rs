@@ -4,6 +4,8 @@
 
 use arrayvec::ArrayVec;
 
+use crate::Point;
+
 /// Adds convenience methods to `f32` and `f64`.
 pub trait FloatExt<T> {
     /// Rounds to the nearest integer away from zero,
@@ -43,6 +45,28 @@ impl FloatExt<f32> for f32 {
     }
 }
 
+/// The smoothstep easing function.
+///
+/// This is `3t² - 2t³`, rising from 0 at `t = 0` to 1 at `t = 1` with zero
+/// slope at both ends, for easing an interpolation parameter in animation,
+/// as in `a.lerp(b, smoothstep(t))`. Unlike the interpolation itself, this
+/// does not extrapolate: `t` is clamped to `[0, 1]` first.
+///
+/// # Examples
+///
+/// ```
+/// use kurbo::common::smoothstep;
+///
+/// assert_eq!(smoothstep(0.0), 0.0);
+/// assert_eq!(smoothstep(0.5), 0.5);
+/// assert_eq!(smoothstep(1.5), 1.0);
+/// ```
+#[inline]
+pub fn smoothstep(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
 /// Find real roots of cubic equation.
 ///
 /// The implementation is not (yet) fully robust, but it does handle the case
@@ -148,6 +172,113 @@ pub fn solve_quadratic(c0: f64, c1: f64, c2: f64) -> ArrayVec<[f64; 2]> {
     result
 }
 
+/// The signed area of the triangle `a`, `b`, `c`, with a reliable sign.
+///
+/// The area is positive if the points turn counterclockwise in a y-up
+/// coordinate system, as for the [`Shape::area`] of the triangle, negative
+/// if they turn clockwise, and zero exactly when they are collinear. Unlike
+/// the naive formula, the sign is always correct, however close the points
+/// are to a line: the determinant is first computed in floating point, as
+/// in Shewchuk's adaptive predicate, and when it is too small for its sign
+/// to be certain, it is recomputed exactly from the coordinates, as a sum of
+/// exact products. The magnitude is accurate to rounding in either case.
+///
+/// Overflowing and underflowing products, far outside the range of
+/// geometric coordinates, are not handled.
+///
+/// # Examples
+///
+/// ```
+/// use kurbo::common::orient2d;
+/// use kurbo::Point;
+///
+/// let a = Point::new(0.5, 0.5);
+/// let b = Point::new(12.0, 12.0);
+/// let c = Point::new(24.0, 24.0);
+/// assert_eq!(orient2d(a, b, c), 0.0);
+/// let nudged = Point::new(24.0, 24.0 + 24.0 * f64::EPSILON);
+/// assert!(orient2d(a, b, nudged) > 0.0);
+/// assert_eq!(orient2d(Point::ORIGIN, Point::new(2.0, 0.0), Point::new(0.0, 1.0)), 1.0);
+/// ```
+///
+/// [`Shape::area`]: ../trait.Shape.html#method.area
+pub fn orient2d(a: Point, b: Point, c: Point) -> f64 {
+    // The error bound of the floating point determinant, from Shewchuk.
+    const ERR_BOUND: f64 = (3.0 + 16.0 * f64::EPSILON / 2.0) * f64::EPSILON / 2.0;
+    let det_left = (a.x - c.x) * (b.y - c.y);
+    let det_right = (a.y - c.y) * (b.x - c.x);
+    let det = det_left - det_right;
+    let det_sum = if det_left > 0.0 && det_right > 0.0 {
+        det_left + det_right
+    } else if det_left < 0.0 && det_right < 0.0 {
+        -det_left - det_right
+    } else {
+        // The terms have opposite signs or one is zero, so there is no
+        // cancellation.
+        return 0.5 * det;
+    };
+    if det.abs() >= ERR_BOUND * det_sum {
+        return 0.5 * det;
+    }
+    0.5 * orient2d_exact(a, b, c)
+}
+
+/// The determinant of [`orient2d`], computed exactly and then rounded.
+///
+/// Expanding the determinant and cancelling leaves six products of
+/// coordinates. Each is split into a rounded product and its exact error,
+/// and the twelve terms are summed into a nonoverlapping expansion, whose
+/// largest component has the sign of the exact sum.
+///
+/// [`orient2d`]: fn.orient2d.html
+fn orient2d_exact(a: Point, b: Point, c: Point) -> f64 {
+    let mut expansion: Vec<f64> = Vec::with_capacity(12);
+    let products = [
+        (b.x, c.y),
+        (-b.x, a.y),
+        (-a.x, c.y),
+        (-b.y, c.x),
+        (b.y, a.x),
+        (a.y, c.x),
+    ];
+    for &(x, y) in &products {
+        let hi = x * y;
+        let lo = x.mul_add(y, -hi);
+        grow_expansion(&mut expansion, lo);
+        grow_expansion(&mut expansion, hi);
+    }
+    // The components increase in magnitude, so summing them in order
+    // rounds to the correct sign.
+    expansion.iter().sum()
+}
+
+/// Add `x` to a nonoverlapping expansion, keeping it nonoverlapping and in
+/// increasing order of magnitude, and dropping zero components.
+fn grow_expansion(expansion: &mut Vec<f64>, x: f64) {
+    let mut q = x;
+    let mut j = 0;
+    for i in 0..expansion.len() {
+        let (sum, err) = two_sum(q, expansion[i]);
+        q = sum;
+        if err != 0.0 {
+            expansion[j] = err;
+            j += 1;
+        }
+    }
+    expansion.truncate(j);
+    if q != 0.0 {
+        expansion.push(q);
+    }
+}
+
+/// The rounded sum of two numbers and its exact error.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let a_virtual = sum - b_virtual;
+    (sum, (a - a_virtual) + (b - b_virtual))
+}
+
 // Tables of Legendre-Gauss quadrature coefficients, adapted from:
 // <https://pomax.github.io/bezierinfo/legendre-gauss.html>
 
@@ -253,6 +384,42 @@ mod tests {
         verify(solve_cubic(2.0 + 1e-12, 5.0, 4.0, 1.0), &[-2.0]);
     }
 
+    #[test]
+    fn test_orient2d() {
+        let p = |x, y| crate::Point::new(x, y);
+        assert_eq!(orient2d(p(0.0, 0.0), p(1.0, 0.0), p(0.0, 1.0)), 0.5);
+        assert_eq!(orient2d(p(0.0, 0.0), p(0.0, 1.0), p(1.0, 0.0)), -0.5);
+        // Points near a line through the origin with an inexact slope: the
+        // naive formula gets many of these signs wrong.
+        let a = p(0.5, 0.5);
+        let c = p(12.0, 12.0);
+        let mut naive_wrong = 0;
+        for i in 0..64 {
+            for j in 0..64 {
+                let b = p(0.5 + i as f64 * f64::EPSILON, 0.5 + j as f64 * f64::EPSILON);
+                let orient = orient2d(a, b, c);
+                // The exact sign, since b - a is exact: b is left of the
+                // diagonal when its y offset is larger.
+                let expected = (j as f64 - i as f64).signum();
+                if i == j {
+                    assert_eq!(orient, 0.0);
+                } else {
+                    assert_eq!(orient.signum(), -expected, "{} {}", i, j);
+                }
+                let naive = (b - a).cross(c - a);
+                if naive.signum() != orient.signum() || (naive == 0.0) != (orient == 0.0) {
+                    naive_wrong += 1;
+                }
+            }
+        }
+        assert!(naive_wrong > 0);
+        // Permuting the points keeps or flips the sign.
+        let (a, b, c) = (p(0.1, 0.3), p(17.3, 51.9), p(-8.55, -25.65));
+        let orient = orient2d(a, b, c);
+        assert_eq!(orient2d(b, c, a), orient);
+        assert_eq!(orient2d(b, a, c), -orient);
+    }
+
     #[test]
     fn test_solve_quadratic() {
         verify(
@@ -5,11 +5,13 @@ use std::ops::{Mul, Range};
 use crate::MAX_EXTREMA;
 use arrayvec::ArrayVec;
 
+use crate::bezpath::TO_QUAD_TOL;
 use crate::common::solve_quadratic;
 use crate::common::GAUSS_LEGENDRE_COEFFS_9;
+use crate::quadbez::FlattenParams;
 use crate::{
-    Affine, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveCurvature, ParamCurveDeriv,
-    ParamCurveExtrema, ParamCurveNearest, Point, QuadBez,
+    Affine, Line, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveCurvature,
+    ParamCurveDeriv, ParamCurveExtrema, ParamCurveNearest, PathSeg, Point, QuadBez, Rect, Vec2,
 };
 
 /// A single cubic Bézier segment.
@@ -23,6 +25,38 @@ pub struct CubicBez {
     pub p3: Point,
 }
 
+/// The intermediate points of de Casteljau's algorithm for a cubic Bézier.
+///
+/// This is created by the [`CubicBez::de_casteljau`] method.
+///
+/// [`CubicBez::de_casteljau`]: struct.CubicBez.html#method.de_casteljau
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DeCasteljau {
+    /// The parameter at which the curve was evaluated.
+    pub t: f64,
+    /// The control points of the curve (level 0).
+    pub control: [Point; 4],
+    /// Interpolations between adjacent control points (level 1).
+    pub level1: [Point; 3],
+    /// Interpolations between adjacent level 1 points (level 2).
+    pub level2: [Point; 2],
+    /// The point on the curve at `t` (level 3).
+    pub point: Point,
+}
+
+impl DeCasteljau {
+    /// The two curves on either side of `t`.
+    ///
+    /// Their control points are exactly the outer points of each level.
+    #[inline]
+    pub fn split(&self) -> (CubicBez, CubicBez) {
+        (
+            CubicBez::new(self.control[0], self.level1[0], self.level2[0], self.point),
+            CubicBez::new(self.point, self.level2[1], self.level1[2], self.control[3]),
+        )
+    }
+}
+
 /// An iterator which produces quadratic Bézier segments.
 struct ToQuads {
     c: CubicBez,
@@ -72,6 +106,412 @@ impl CubicBez {
 
         ToQuads { c: *self, n, i: 0 }
     }
+
+    /// The power basis coefficients of the x and y coordinates.
+    ///
+    /// Each array is in increasing order of degree, so that
+    /// `x(t) = x[0] + x[1] t + x[2] t^2 + x[3] t^3`. This is the argument order
+    /// of [`solve_cubic`].
+    ///
+    /// [`solve_cubic`]: common/fn.solve_cubic.html
+    pub fn to_poly(&self) -> ([f64; 4], [f64; 4]) {
+        let p0 = self.p0.to_vec2();
+        let c = 3.0 * (self.p1 - self.p0);
+        let b = 3.0 * (self.p2.to_vec2() - 2.0 * self.p1.to_vec2() + p0);
+        let a = self.p3.to_vec2() - p0 - c - b;
+        ([p0.x, c.x, b.x, a.x], [p0.y, c.y, b.y, a.y])
+    }
+
+    /// Create a cubic Bézier from power basis coefficients.
+    ///
+    /// This is the inverse of [`to_poly`].
+    ///
+    /// [`to_poly`]: #method.to_poly
+    pub fn from_poly(x: [f64; 4], y: [f64; 4]) -> CubicBez {
+        let d = Vec2::new(x[0], y[0]);
+        let c = Vec2::new(x[1], y[1]);
+        let b = Vec2::new(x[2], y[2]);
+        let a = Vec2::new(x[3], y[3]);
+        let p1 = d + c * (1.0 / 3.0);
+        let p2 = p1 + (b + c) * (1.0 / 3.0);
+        let p3 = d + c + b + a;
+        CubicBez::new(d.to_point(), p1.to_point(), p2.to_point(), p3.to_point())
+    }
+
+    /// The power basis coefficients of the derivative.
+    ///
+    /// The arrays are in increasing order of degree, as in [`to_poly`].
+    ///
+    /// [`to_poly`]: #method.to_poly
+    pub fn deriv_poly(&self) -> ([f64; 3], [f64; 3]) {
+        let (x, y) = self.to_poly();
+        (
+            [x[1], 2.0 * x[2], 3.0 * x[3]],
+            [y[1], 2.0 * y[2], 3.0 * y[3]],
+        )
+    }
+
+    /// Evaluate the curve at many parameters.
+    ///
+    /// The power basis coefficients are computed once and each point is then
+    /// evaluated with Horner's method, which is faster than repeated calls to
+    /// [`eval`]. Results match `eval` up to floating point rounding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ts` and `out` have different lengths.
+    ///
+    /// [`eval`]: trait.ParamCurve.html#tymethod.eval
+    pub fn eval_many(&self, ts: &[f64], out: &mut [Point]) {
+        assert_eq!(ts.len(), out.len(), "eval_many: length mismatch");
+        let p0 = self.p0.to_vec2();
+        let p1 = self.p1.to_vec2();
+        let p2 = self.p2.to_vec2();
+        let p3 = self.p3.to_vec2();
+        let c = 3.0 * (p1 - p0);
+        let b = 3.0 * (p2 - p1) - c;
+        let a = p3 - p0 - c - b;
+        for (&t, p) in ts.iter().zip(out.iter_mut()) {
+            *p = (((a * t + b) * t + c) * t + p0).to_point();
+        }
+    }
+
+    /// Run de Casteljau's algorithm at `t`, keeping all intermediate points.
+    pub fn de_casteljau(&self, t: f64) -> DeCasteljau {
+        let control = [self.p0, self.p1, self.p2, self.p3];
+        let level1 = [
+            self.p0.lerp(self.p1, t),
+            self.p1.lerp(self.p2, t),
+            self.p2.lerp(self.p3, t),
+        ];
+        let level2 = [level1[0].lerp(level1[1], t), level1[1].lerp(level1[2], t)];
+        let point = level2[0].lerp(level2[1], t);
+        DeCasteljau {
+            t,
+            control,
+            level1,
+            level2,
+            point,
+        }
+    }
+
+    /// Split the curve at `t` into two, using de Casteljau.
+    ///
+    /// See [`de_casteljau`] to also get the intermediate points.
+    ///
+    /// [`de_casteljau`]: #method.de_casteljau
+    #[inline]
+    pub fn split_at(&self, t: f64) -> (CubicBez, CubicBez) {
+        self.de_casteljau(t).split()
+    }
+
+    /// The number of line segments produced when flattening this curve.
+    ///
+    /// This uses the same estimate as [`flatten`], so the result matches the
+    /// number of `LineTo` elements it emits for this segment. It can be used to
+    /// pre-size buffers.
+    ///
+    /// [`flatten`]: fn.flatten.html
+    pub fn flatten_segment_count(&self, tolerance: f64) -> usize {
+        self.flatten_quads(tolerance, &mut Vec::new())
+    }
+
+    /// Prepare the curve for flattening.
+    ///
+    /// This fills `quads` with quadratic approximations of the curve, each
+    /// with its estimate for flattening, and returns the number of line
+    /// segments. It is shared by everything that flattens cubics, so that
+    /// they agree on the subdivision.
+    pub(crate) fn flatten_quads(&self, tolerance: f64, quads: &mut Vec<FlattenQuad>) -> usize {
+        let sqrt_remain_tol = tolerance.sqrt() * (1.0 - TO_QUAD_TOL).sqrt();
+        quads.clear();
+        let mut sum = 0.0;
+        for (t0, t1, q) in self.to_quads(tolerance * TO_QUAD_TOL) {
+            let params = q.estimate_subdiv(sqrt_remain_tol);
+            sum += params.val;
+            quads.push(FlattenQuad { t0, t1, q, params });
+        }
+        ((0.5 * sum / sqrt_remain_tol).ceil() as usize).max(1)
+    }
+
+    /// Flatten the curve, calling back with each vertex and its parameter.
+    ///
+    /// The vertices are the same as those emitted by [`flatten`], each
+    /// reported along with the parameter of the point on the cubic that it
+    /// approximates, within a tenth of `tolerance`. The first callback is
+    /// `(p0, 0.0)`, the last is exactly `(p3, 1.0)`, and the parameters in
+    /// between never decrease.
+    ///
+    /// [`flatten`]: fn.flatten.html
+    pub fn flatten_with_t(&self, tolerance: f64, callback: &mut impl FnMut(Point, f64)) {
+        callback(self.p0, 0.0);
+        let mut quads = Vec::new();
+        let n = self.flatten_quads(tolerance, &mut quads);
+        let mut last_t = 0.0;
+        flatten_quad_vertices(&quads, n, |p, t| {
+            // Rounding must not make the parameters go backwards.
+            last_t = t.clamp(last_t, 1.0);
+            callback(p, last_t);
+        });
+        callback(self.p3, 1.0);
+    }
+
+    /// The maximum distance of the inner control points from the chord.
+    ///
+    /// The chord is the line segment from `p0` to `p3`. As the curve lies
+    /// within the convex hull of its control points, this is an upper bound on
+    /// the distance of any point of the curve from the chord, the quantity
+    /// that flattening keeps within tolerance. It is zero for a cubic whose
+    /// control points all lie on the chord.
+    pub fn flatness(&self) -> f64 {
+        let chord = Line::new(self.p0, self.p3);
+        let d1 = chord.nearest(self.p1, 0.0).1;
+        let d2 = chord.nearest(self.p2, 0.0).1;
+        d1.max(d2).sqrt()
+    }
+
+    /// Reduce to a lower order segment, if one is within tolerance.
+    ///
+    /// Returns a line if the curve is within `tolerance` of straight (as
+    /// measured by [`flatness`]), otherwise a quadratic if the best fitting
+    /// quadratic deviates from the curve by at most `tolerance`, and
+    /// otherwise the cubic itself. The endpoints are always preserved.
+    ///
+    /// [`flatness`]: #method.flatness
+    pub fn reduce(&self, tolerance: f64) -> PathSeg {
+        if self.flatness() <= tolerance {
+            return PathSeg::Line(Line::new(self.p0, self.p3));
+        }
+        // The quadratic control point is the average of the two estimates
+        // from either end. The difference between the cubic and the raised
+        // quadratic is then 3t(1 - t)(1 - 2t) d, whose magnitude peaks at
+        // |d| / (2√3).
+        let p1 = ((3.0 * (self.p1.to_vec2() + self.p2.to_vec2())
+            - self.p0.to_vec2()
+            - self.p3.to_vec2())
+            * 0.25)
+            .to_point();
+        let quad = QuadBez::new(self.p0, p1, self.p3);
+        let d = self.p1 - quad.raise().p1;
+        if d.hypot() * (0.5 / 3f64.sqrt()) <= tolerance {
+            PathSeg::Quad(quad)
+        } else {
+            PathSeg::Cubic(*self)
+        }
+    }
+
+    /// Find the point on the curve farthest from the given point.
+    ///
+    /// Returns the parameter and the square of the distance. The curve is
+    /// approximated by quadratic Béziers within `accuracy`, as for
+    /// [`nearest`], and the farthest point on each is found; the distance
+    /// is then measured at that parameter on the cubic itself. The endpoints
+    /// are always candidates.
+    ///
+    /// [`nearest`]: trait.ParamCurveNearest.html#tymethod.nearest
+    pub fn farthest(&self, p: Point, accuracy: f64) -> (f64, f64) {
+        let mut best = (0.0, (self.p0 - p).hypot2());
+        let mut consider = |t: f64| {
+            let r = (self.eval(t) - p).hypot2();
+            if r > best.1 {
+                best = (t, r);
+            }
+        };
+        consider(1.0);
+        for (t0, t1, q) in self.to_quads(accuracy) {
+            let (t, _) = q.farthest(p);
+            consider(t0 + t * (t1 - t0));
+        }
+        best
+    }
+
+    /// Find the nearest point, refining several candidates by Newton's
+    /// method.
+    ///
+    /// The squared distance is sampled at the ends of 16 equal parameter
+    /// intervals, and Newton iteration is started from each sample that is
+    /// a local minimum, as well as from the result of [`nearest`]; the
+    /// endpoints are also candidates. The best result is kept, so it is never
+    /// worse than that of [`nearest`], and a local minimum found by a single
+    /// Newton start on an S-shaped curve is not mistaken for the global one.
+    ///
+    /// Returns the parameter and the squared distance, as [`nearest`] does.
+    ///
+    /// [`nearest`]: trait.ParamCurveNearest.html#tymethod.nearest
+    pub fn nearest_robust(&self, p: Point, accuracy: f64) -> (f64, f64) {
+        let dist2 = |t: f64| (self.eval(t) - p).hypot2();
+        let mut best = (0.0, dist2(0.0));
+        let mut consider = |t: f64| {
+            let r = dist2(t);
+            if r < best.1 {
+                best = (t, r);
+            }
+        };
+        consider(1.0);
+        let (t_nearest, _) = self.nearest(p, accuracy);
+        consider(self.newton_nearest(p, t_nearest, accuracy));
+        let n = NEAREST_SAMPLES;
+        let samples: Vec<f64> = (0..=n).map(|i| dist2(i as f64 / n as f64)).collect();
+        for i in 1..n {
+            if samples[i] <= samples[i - 1] && samples[i] <= samples[i + 1] {
+                consider(self.newton_nearest(p, i as f64 / n as f64, accuracy));
+            }
+        }
+        best
+    }
+
+    /// Returns `true` if the point is within `half_width` of the curve.
+    ///
+    /// Points outside the bounding box of the control points, inflated by
+    /// `half_width`, are rejected without further work. Otherwise the
+    /// distance is found by [`nearest_robust`], on the curve itself rather
+    /// than a flattened approximation, so the result is exact up to
+    /// `accuracy`.
+    ///
+    /// [`nearest_robust`]: #method.nearest_robust
+    pub fn stroke_hit(&self, pt: Point, half_width: f64, accuracy: f64) -> bool {
+        if half_width < 0.0 || half_width.is_nan() {
+            return false;
+        }
+        let hull = Rect::from_points(self.p0, self.p1)
+            .union_pt(self.p2)
+            .union_pt(self.p3)
+            .inflate(half_width, half_width);
+        if pt.x < hull.x0 || pt.x > hull.x1 || pt.y < hull.y0 || pt.y > hull.y1 {
+            return false;
+        }
+        self.nearest_robust(pt, accuracy).1 <= half_width * half_width
+    }
+
+    /// Newton iteration for a stationary point of the distance to `p`,
+    /// starting from `t` and staying within [0, 1].
+    fn newton_nearest(&self, p: Point, mut t: f64, accuracy: f64) -> f64 {
+        let d1 = self.deriv();
+        let d2 = d1.deriv();
+        for _ in 0..NEWTON_ITERATIONS {
+            let v = self.eval(t) - p;
+            let b1 = d1.eval(t).to_vec2();
+            let g = v.dot(b1);
+            let dg = b1.hypot2() + v.dot(d2.eval(t).to_vec2());
+            if dg <= 0.0 {
+                break;
+            }
+            let next = (t - g / dg).clamp(0.0, 1.0);
+            let step = (next - t).abs();
+            t = next;
+            if step * b1.hypot() <= accuracy * 1e-3 {
+                break;
+            }
+        }
+        t
+    }
+}
+
+/// The number of intervals sampled by `CubicBez::nearest_robust`.
+const NEAREST_SAMPLES: usize = 16;
+
+/// The maximum number of Newton steps from each starting point.
+const NEWTON_ITERATIONS: usize = 16;
+
+/// A quadratic approximation of part of a cubic, for flattening.
+pub(crate) struct FlattenQuad {
+    /// The start of the covered range of the cubic's parameter.
+    t0: f64,
+    /// The end of the covered range of the cubic's parameter.
+    t1: f64,
+    q: QuadBez,
+    params: FlattenParams,
+}
+
+/// Call `f` with each interior vertex of a flattened cubic, given the
+/// quadratics and segment count from [`CubicBez::flatten_quads`].
+///
+/// The vertex is evaluated on the quadratic, and given along with the
+/// corresponding parameter on the cubic. The end point is not included.
+///
+/// [`CubicBez::flatten_quads`]: struct.CubicBez.html#method.flatten_quads
+pub(crate) fn flatten_quad_vertices(
+    quads: &[FlattenQuad],
+    n: usize,
+    mut f: impl FnMut(Point, f64),
+) {
+    let sum: f64 = quads.iter().map(|fq| fq.params.val).sum();
+    let step = sum / (n as f64);
+    let mut i = 1;
+    let mut val_sum = 0.0;
+    for fq in quads {
+        let mut target = (i as f64) * step;
+        let recip_val = fq.params.val.recip();
+        while i < n && target < val_sum + fq.params.val {
+            let u = (target - val_sum) * recip_val;
+            let t = fq.q.determine_subdiv_t(&fq.params, u);
+            f(fq.q.eval(t), fq.t0 + (fq.t1 - fq.t0) * t);
+            i += 1;
+            target = (i as f64) * step;
+        }
+        val_sum += fq.params.val;
+    }
+}
+
+/// Fit a cubic with the given ends and end tangents to points at the given
+/// parameters, choosing the lengths of the handles by least squares.
+///
+/// If `normals` is given, only the error along the normal at each point is
+/// minimized. With the parameters of the nearest points on the previous fit
+/// and the normals there, this is a Gauss-Newton step for the distance from
+/// the points to the curve, which converges much faster than refitting the
+/// full error.
+///
+/// Handle lengths that come out too short, or negative, are replaced by a
+/// third of the chord, the usual fallback in Schneider's algorithm.
+pub(crate) fn fit_cubic_handles(
+    points: &[Point],
+    params: &[f64],
+    normals: Option<&[Vec2]>,
+    (p0, t0): (Point, Vec2),
+    (p3, t1): (Point, Vec2),
+) -> CubicBez {
+    let (mut c00, mut c01, mut c11, mut x0, mut x1) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    let mut add = |a0: f64, a1: f64, r: f64| {
+        c00 += a0 * a0;
+        c01 += a0 * a1;
+        c11 += a1 * a1;
+        x0 += a0 * r;
+        x1 += a1 * r;
+    };
+    for (i, (&p, &u)) in points.iter().zip(params).enumerate() {
+        let mt = 1.0 - u;
+        let b1 = 3.0 * mt * mt * u;
+        let b2 = 3.0 * mt * u * u;
+        let (a0, a1) = (b1 * t0, -b2 * t1);
+        // The residual of the curve with zero-length handles.
+        let base = (mt * mt * mt + b1) * p0.to_vec2() + (b2 + u * u * u) * p3.to_vec2();
+        let r = p.to_vec2() - base;
+        match normals {
+            Some(normals) => {
+                let n = normals[i];
+                add(a0.dot(n), a1.dot(n), r.dot(n));
+            }
+            None => {
+                add(a0.x, a1.x, r.x);
+                add(a0.y, a1.y, r.y);
+            }
+        }
+    }
+    let det = c00 * c11 - c01 * c01;
+    let chord = p0.distance(p3);
+    let fallback = chord / 3.0;
+    let (mut alpha, mut beta) = if det.abs() > 1e-12 * (c00 * c11).max(f64::MIN_POSITIVE) {
+        ((x0 * c11 - x1 * c01) / det, (c00 * x1 - c01 * x0) / det)
+    } else {
+        (fallback, fallback)
+    };
+    let min = 1e-6 * chord;
+    if !(alpha > min && beta > min) {
+        alpha = fallback;
+        beta = fallback;
+    }
+    CubicBez::new(p0, p0 + alpha * t0, p3 - beta * t1, p3)
 }
 
 impl ParamCurve for CubicBez {
@@ -279,10 +719,14 @@ impl Iterator for ToQuads {
 #[cfg(test)]
 mod tests {
     use crate::{
-        Affine, CubicBez, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveDeriv,
-        ParamCurveExtrema, ParamCurveNearest, Point,
+        Affine, BezPath, CubicBez, Line, ParamCurve, ParamCurveArclen, ParamCurveArea,
+        ParamCurveDeriv, ParamCurveExtrema, ParamCurveNearest, PathSeg, Point, QuadBez,
     };
 
+    fn assert_near(p0: Point, p1: Point, epsilon: f64) {
+        assert!((p1 - p0).hypot() < epsilon, "{:?} != {:?}", p0, p1);
+    }
+
     #[test]
     fn cubicbez_deriv() {
         // y = x^2
@@ -428,6 +872,58 @@ mod tests {
         assert_eq!(extrema.len(), 4);
     }
 
+    #[test]
+    fn cubicbez_de_casteljau() {
+        let c = CubicBez::new((0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0));
+        let dc = c.de_casteljau(0.5);
+        assert_eq!(dc.level1[1], Point::new(0.5, 1.0));
+        assert_eq!(dc.level2[0], Point::new(0.25, 0.75));
+        assert_eq!(dc.point, Point::new(0.5, 0.75));
+        assert_eq!(dc.point, c.eval(0.5));
+        assert_eq!(dc.split(), c.subdivide());
+
+        let t = 0.3;
+        let (c0, c1) = c.split_at(t);
+        assert_eq!(c0.p0, c.p0);
+        assert_eq!(c1.p3, c.p3);
+        assert_eq!(c0.p3, c1.p0);
+        for i in 0..=10 {
+            let u = i as f64 * 0.1;
+            assert_near(c0.eval(u), c.eval(u * t), 1e-12);
+            assert_near(c1.eval(u), c.eval(t + u * (1.0 - t)), 1e-12);
+        }
+    }
+
+    #[test]
+    fn cubicbez_eval_many() {
+        let c = CubicBez::new((0.0, 0.0), (-1.0, 3.0), (4.0, 2.0), (3.0, -1.0));
+        let ts: Vec<f64> = (0..=20).map(|i| i as f64 / 20.0).collect();
+        let mut out = vec![Point::ORIGIN; ts.len()];
+        c.eval_many(&ts, &mut out);
+        for (&t, &p) in ts.iter().zip(&out) {
+            assert_near(p, c.eval(t), 1e-12);
+        }
+    }
+
+    #[test]
+    fn cubicbez_poly() {
+        let c = CubicBez::new((100.0, -20.0), (140.5, 310.0), (-7.25, 80.0), (250.0, 12.5));
+        let (x, y) = c.to_poly();
+        let poly = |k: &[f64], t: f64| k.iter().rev().fold(0.0, |acc, &k| acc * t + k);
+        let (dx, dy) = c.deriv_poly();
+        let deriv = c.deriv();
+        for i in 0..=10 {
+            let t = i as f64 * 0.1;
+            assert_near(Point::new(poly(&x, t), poly(&y, t)), c.eval(t), 1e-10);
+            assert_near(Point::new(poly(&dx, t), poly(&dy, t)), deriv.eval(t), 1e-10);
+        }
+        let round_trip = CubicBez::from_poly(x, y);
+        assert_near(round_trip.p0, c.p0, 1e-12);
+        assert_near(round_trip.p1, c.p1, 1e-12);
+        assert_near(round_trip.p2, c.p2, 1e-12);
+        assert_near(round_trip.p3, c.p3, 1e-12);
+    }
+
     #[test]
     fn cubicbez_toquads() {
         // y = x^3
@@ -435,7 +931,7 @@ mod tests {
         for i in 0..10 {
             let accuracy = 0.1f64.powi(i);
             let mut worst: f64 = 0.0;
-            for (_count, (t0, t1, q)) in c.to_quads(accuracy).enumerate() {
+            for (t0, t1, q) in c.to_quads(accuracy) {
                 let epsilon = 1e-12;
                 assert!((q.start() - c.eval(t0)).hypot() < epsilon);
                 assert!((q.end() - c.eval(t1)).hypot() < epsilon);
@@ -450,4 +946,111 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn cubicbez_flatness() {
+        let c = CubicBez::new((0.0, 0.0), (1.0, 2.0), (3.0, 3.0), (4.0, 0.0));
+        assert!((c.flatness() - 3.0).abs() < 1e-12);
+        // The curve itself stays within the bound.
+        let chord = Line::new(c.p0, c.p3);
+        for i in 0..=10 {
+            let d = chord.nearest(c.eval(i as f64 * 0.1), 0.0).1.sqrt();
+            assert!(d <= c.flatness());
+        }
+        let straight = CubicBez::new((0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0));
+        assert_eq!(straight.flatness(), 0.0);
+        // Control points past the end of the chord.
+        let overshoot = CubicBez::new((0.0, 0.0), (-1.0, 0.0), (3.0, 0.0), (2.0, 0.0));
+        assert_eq!(overshoot.flatness(), 1.0);
+    }
+
+    #[test]
+    fn cubicbez_flatten_with_t() {
+        let c = CubicBez::new((0.0, 0.0), (0.0, 10.0), (20.0, -10.0), (10.0, 0.0));
+        let tolerance = 0.01;
+        let mut verts = Vec::new();
+        c.flatten_with_t(tolerance, &mut |p, t| verts.push((p, t)));
+        assert_eq!(verts.len(), c.flatten_segment_count(tolerance) + 1);
+        assert_eq!(verts[0], (c.p0, 0.0));
+        assert_eq!(*verts.last().unwrap(), (c.p3, 1.0));
+        // The vertices are those of `flatten`.
+        let mut path = BezPath::new();
+        path.move_to(c.p0);
+        path.curve_to(c.p1, c.p2, c.p3);
+        let mut flat = Vec::new();
+        path.flatten(tolerance, |el| flat.extend(el.points().first().copied()));
+        let points: Vec<Point> = verts.iter().map(|v| v.0).collect();
+        assert_eq!(points, flat);
+        for w in verts.windows(2) {
+            assert!(w[0].1 < w[1].1);
+            assert!((w[1].0 - c.eval(w[1].1)).hypot() < 0.1 * tolerance);
+            // Each chord stays close to the curve.
+            let chord = Line::new(w[0].0, w[1].0);
+            let mid = c.eval(0.5 * (w[0].1 + w[1].1));
+            assert!(chord.nearest(mid, 0.0).1.sqrt() < 2.0 * tolerance);
+        }
+    }
+
+    #[test]
+    fn cubicbez_reduce() {
+        let straight = CubicBez::new((0.0, 0.0), (1.0, 0.001), (2.0, -0.001), (3.0, 0.0));
+        assert!(matches!(straight.reduce(0.01), PathSeg::Line(_)));
+        assert!(matches!(straight.reduce(1e-6), PathSeg::Cubic(_)));
+
+        let q = QuadBez::new((0.0, 0.0), (2.0, 4.0), (4.0, 0.0));
+        match q.raise().reduce(1e-9) {
+            PathSeg::Quad(q2) => assert_near(q2.p1, q.p1, 1e-12),
+            _ => panic!("expected a quadratic"),
+        }
+
+        // Close to a quadratic; check the error bound against samples.
+        let c = CubicBez::new(
+            (0.0, 0.0),
+            (4.0 / 3.0, 2.0),
+            (8.0 / 3.0 + 0.1, 2.0),
+            (4.0, 0.0),
+        );
+        let tol = 0.03;
+        match c.reduce(tol) {
+            PathSeg::Quad(q) => {
+                for i in 0..=20 {
+                    let t = i as f64 * 0.05;
+                    assert!((q.eval(t) - c.eval(t)).hypot() <= tol);
+                }
+            }
+            _ => panic!("expected a quadratic"),
+        }
+        assert!(matches!(c.reduce(0.01), PathSeg::Cubic(_)));
+    }
+
+    #[test]
+    fn cubicbez_nearest_robust() {
+        // An S curve, with the point inside the lower bend. Newton's method
+        // started at the middle settles on a local minimum further along.
+        let c = CubicBez::new((0.0, 0.0), (3.0, 0.0), (-2.0, 2.0), (1.0, 2.0));
+        let p = Point::new(0.4, 0.2);
+        let brute = (0..=10_000)
+            .map(|i| (c.eval(i as f64 / 1e4) - p).hypot2())
+            .fold(f64::INFINITY, f64::min);
+        let local = c.newton_nearest(p, 0.5, 1e-9);
+        assert!((c.eval(local) - p).hypot2() > 10.0 * brute);
+        let (t, r) = c.nearest_robust(p, 1e-9);
+        assert!(r <= brute + 1e-9);
+        assert!(((c.eval(t) - p).hypot2() - r).abs() < 1e-12);
+        assert!(r <= c.nearest(p, 1e-9).1);
+    }
+
+    #[test]
+    fn cubicbez_stroke_hit() {
+        let c = CubicBez::new((0.0, 0.0), (1.0, 2.0), (3.0, 2.0), (4.0, 0.0));
+        // The peak of the curve is at (2, 1.5).
+        assert!(c.stroke_hit(Point::new(2.0, 1.9), 0.5, 1e-9));
+        assert!(c.stroke_hit(Point::new(2.0, 1.0), 0.5, 1e-9));
+        assert!(!c.stroke_hit(Point::new(2.0, 2.1), 0.5, 1e-9));
+        // Inside the control polygon, but far from the curve.
+        assert!(!c.stroke_hit(Point::new(2.0, 0.2), 0.5, 1e-9));
+        assert!(!c.stroke_hit(Point::new(10.0, 0.0), 0.5, 1e-9));
+        assert!(c.stroke_hit(Point::new(4.0, 0.0), 0.0, 1e-9));
+        assert!(!c.stroke_hit(Point::new(4.0, 0.0), -1.0, 1e-9));
+    }
 }
@@ -0,0 +1,176 @@
+//! Delaunay triangulation of point sets.
+
+use crate::common::orient2d;
+use crate::{Point, Rect, Triangle};
+
+/// The size of the initial triangle, relative to the extent of the points.
+const SUPER_TRIANGLE_SCALE: f64 = 100.0;
+
+/// The Delaunay triangulation of a set of points.
+///
+/// This is the Bowyer–Watson algorithm: points are inserted one at a time,
+/// in the order given, into a triangulation of a large enclosing triangle.
+/// Each insertion removes the triangles whose circumcircle contains the new
+/// point, and connects the point to the boundary of the hole left behind.
+/// The triangles using the vertices of the enclosing triangle are dropped at
+/// the end.
+///
+/// Every triangle is counterclockwise (positive [`area`] in a y-up
+/// coordinate system), and none has zero area. Duplicate points are
+/// ignored, as are points with infinite or NaN coordinates, and if all
+/// points are collinear the result is empty.
+///
+/// Where four or more points lie on a common circle, the Delaunay
+/// triangulation is not unique, and the one produced depends on the order of
+/// the input: a point exactly on a circumcircle does not invalidate it. The
+/// output only depends on the input, though, and is sorted: each triangle
+/// starts at its vertex that comes first in `points`, and triangles are
+/// ordered by the positions of their vertices in `points`.
+///
+/// The geometric tests use floating point arithmetic rather than exact
+/// predicates, so nearly degenerate inputs may produce slightly non-Delaunay
+/// triangles. Edges of the convex hull close to collinear with the
+/// enclosing triangle's vertices may also be missing, leaving the union of
+/// the triangles slightly concave. This takes O(n²) time in the number of
+/// points.
+///
+/// [`area`]: trait.Shape.html#tymethod.area
+pub fn triangulate(points: &[Point]) -> Vec<Triangle> {
+    // Indices into `points` of the distinct points, in order.
+    let mut order: Vec<usize> = (0..points.len())
+        .filter(|&i| points[i].x.is_finite() && points[i].y.is_finite())
+        .collect();
+    order.sort_by(|&a, &b| {
+        let (pa, pb) = (points[a], points[b]);
+        (pa.x, pa.y, a)
+            .partial_cmp(&(pb.x, pb.y, b))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let mut input: Vec<usize> = order
+        .iter()
+        .enumerate()
+        .filter(|&(k, &i)| k == 0 || points[order[k - 1]] != points[i])
+        .map(|(_, &i)| i)
+        .collect();
+    input.sort_unstable();
+    if input.len() < 3 {
+        return Vec::new();
+    }
+
+    let bbox = input.iter().fold(
+        Rect::from_points(points[input[0]], points[input[0]]),
+        |r, &i| r.union_pt(points[i]),
+    );
+    let size = bbox.width().max(bbox.height()).max(f64::MIN_POSITIVE);
+    let c = bbox.center();
+    let d = SUPER_TRIANGLE_SCALE * size;
+    let n = points.len();
+    let mut verts: Vec<Point> = points.to_vec();
+    verts.push(Point::new(c.x - 2.0 * d, c.y - d));
+    verts.push(Point::new(c.x + 2.0 * d, c.y - d));
+    verts.push(Point::new(c.x, c.y + 2.0 * d));
+    let mut tris: Vec<[usize; 3]> = vec![[n, n + 1, n + 2]];
+
+    let mut edges: Vec<[usize; 2]> = Vec::new();
+    for &i in &input {
+        let p = verts[i];
+        edges.clear();
+        let mut k = 0;
+        while k < tris.len() {
+            let t = tris[k];
+            if in_circumcircle(verts[t[0]], verts[t[1]], verts[t[2]], p) {
+                edges.push([t[0], t[1]]);
+                edges.push([t[1], t[2]]);
+                edges.push([t[2], t[0]]);
+                tris.swap_remove(k);
+            } else {
+                k += 1;
+            }
+        }
+        // The boundary of the cavity: the edges that are not shared by two of
+        // the removed triangles.
+        for &[a, b] in &edges {
+            if !edges.contains(&[b, a]) {
+                tris.push([a, b, i]);
+            }
+        }
+    }
+
+    let mut result: Vec<[usize; 3]> = tris
+        .into_iter()
+        .filter(|t| t.iter().all(|&v| v < n))
+        .filter(|t| orient2d(verts[t[0]], verts[t[1]], verts[t[2]]) > 0.0)
+        .map(|t| {
+            let first = (0..3).min_by_key(|&j| t[j]).unwrap();
+            [t[first], t[(first + 1) % 3], t[(first + 2) % 3]]
+        })
+        .collect();
+    result.sort_unstable();
+    result
+        .into_iter()
+        .map(|t| Triangle::new(points[t[0]], points[t[1]], points[t[2]]))
+        .collect()
+}
+
+/// Whether `p` is strictly inside the circumcircle of the counterclockwise
+/// triangle `a`, `b`, `c`.
+fn in_circumcircle(a: Point, b: Point, c: Point, p: Point) -> bool {
+    let (a, b, c) = (a - p, b - p, c - p);
+    let det = a.hypot2() * b.cross(c) + b.hypot2() * c.cross(a) + c.hypot2() * a.cross(b);
+    det > 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{triangulate, Point, Shape};
+
+    fn lcg_points(n: usize, seed: u64) -> Vec<Point> {
+        let mut state = seed;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..n)
+            .map(|_| Point::new(next() * 10.0, next() * 10.0))
+            .collect()
+    }
+
+    #[test]
+    fn delaunay_empty_circumcircles() {
+        let points = lcg_points(60, 3);
+        let tris = triangulate(&points);
+        // A triangulation of n points with h on the hull has 2n - h - 2
+        // triangles.
+        assert!(tris.len() > 60 && tris.len() < 2 * 60);
+        for t in &tris {
+            assert!(t.area() > 0.0);
+            let c = t.circumcircle().unwrap();
+            for &p in &points {
+                assert!((p - c.center).hypot() > c.radius - 1e-9);
+            }
+        }
+        assert_eq!(tris, triangulate(&points));
+    }
+
+    #[test]
+    fn delaunay_degenerate() {
+        // A grid: every cell's corners are cocircular, and rows are collinear.
+        let mut grid = Vec::new();
+        for j in 0..4 {
+            for i in 0..5 {
+                grid.push(Point::new(i as f64, j as f64));
+            }
+        }
+        grid.push(Point::new(2.0, 1.0));
+        let tris = triangulate(&grid);
+        assert_eq!(tris.len(), 2 * 4 * 3);
+        let area: f64 = tris.iter().map(|t| t.area()).sum();
+        assert!((area - 12.0).abs() < 1e-12);
+
+        let line: Vec<Point> = (0..5)
+            .map(|i| Point::new(i as f64, 2.0 * i as f64))
+            .collect();
+        assert!(triangulate(&line).is_empty());
+        assert!(triangulate(&grid[..2]).is_empty());
+    }
+}
@@ -263,4 +263,16 @@ mod tests {
         assert_approx_eq(e_neg_radius.area(), p_neg_radius.area());
         assert_eq!(e_neg_radius.winding(center), p_neg_radius.winding(center));
     }
+
+    #[test]
+    fn winding_rotated() {
+        let e = Ellipse::new((1.0, -1.0), (4.0, 1.0), 0.6);
+        let p = e.to_path(1e-9);
+        for i in 0..30 {
+            for j in 0..30 {
+                let pt = Point::new(-4.0 + 0.3 * i as f64, -4.0 + 0.3 * j as f64);
+                assert_eq!(e.winding(pt), p.winding(pt), "{:?}", pt);
+            }
+        }
+    }
 }
@@ -0,0 +1,678 @@
+//! Euler spirals, also known as clothoids.
+
+use std::f64::consts::PI;
+use std::ops::Range;
+
+use crate::common::GAUSS_LEGENDRE_COEFFS_24;
+use crate::{BezPath, CubicBez, ParamCurve, ParamCurveArclen, Point, Vec2};
+
+/// The maximum number of cubic segments generated by [`to_path`].
+///
+/// [`to_path`]: struct.EulerSpiral.html#method.to_path
+const MAX_CUBICS: usize = 1 << 16;
+
+/// The maximum number of pieces the integration of a spiral is split into.
+///
+/// Each piece turns by at most about two radians, so spirals turning by
+/// more than twice this are evaluated with reduced accuracy.
+const MAX_INTEG_PIECES: usize = 1 << 17;
+
+/// The maximum number of Newton iterations in [`euler_spline`].
+///
+/// [`euler_spline`]: fn.euler_spline.html
+const SPLINE_MAX_ITER: usize = 50;
+
+/// A segment of an Euler spiral (clothoid).
+///
+/// The curvature of an Euler spiral varies linearly with arc length, which
+/// makes it the natural choice for transition curves (in road and track
+/// design, for example). The spiral starts at `p0` heading in the direction
+/// `theta0`, with curvature `k0`; the curvature then changes by `k1` per
+/// unit of arc length.
+///
+/// The parameter `t` of [`ParamCurve`] is proportional to arc length, so
+/// `t = 1` is the point at distance `len` along the curve.
+///
+/// Points are computed by numerically integrating the (generalized) Fresnel
+/// integrals, so evaluation is considerably more expensive than for a Bézier
+/// segment. The cost grows with the angle the tangent turns through, up to a
+/// limit of about 2¹⁸ radians (some 40,000 full turns), beyond which the
+/// integration is no longer refined and loses accuracy.
+///
+/// [`ParamCurve`]: trait.ParamCurve.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EulerSpiral {
+    /// The start point.
+    pub p0: Point,
+    /// The tangent angle at the start, in radians.
+    pub theta0: f64,
+    /// The curvature at the start.
+    pub k0: f64,
+    /// The rate of change of curvature with respect to arc length.
+    pub k1: f64,
+    /// The arc length.
+    ///
+    /// This may be negative, in which case the spiral is traversed backwards
+    /// from `p0` (against the tangent direction). Reversed subsegments have
+    /// this form.
+    pub len: f64,
+}
+
+impl EulerSpiral {
+    /// Create a new Euler spiral segment.
+    #[inline]
+    pub fn new(p0: impl Into<Point>, theta0: f64, k0: f64, k1: f64, len: f64) -> EulerSpiral {
+        EulerSpiral {
+            p0: p0.into(),
+            theta0,
+            k0,
+            k1,
+            len,
+        }
+    }
+
+    /// Fit an Euler spiral between two points with the given tangent angles.
+    ///
+    /// The angles are in radians; the resulting spiral starts at `p0` heading
+    /// in direction `theta0` and ends at `p1` heading in direction `theta1`.
+    /// The angles are interpreted relative to the chord from `p0` to `p1`,
+    /// reduced to the range `(-π, π]`.
+    ///
+    /// Returns `None` if the points coincide, or if the solver fails to
+    /// converge, which happens when the tangents are close to pointing back
+    /// along the chord.
+    pub fn fit(p0: Point, theta0: f64, p1: Point, theta1: f64) -> Option<EulerSpiral> {
+        let chord = p1 - p0;
+        let chord_len = chord.hypot();
+        if chord_len == 0.0 || !chord_len.is_finite() {
+            return None;
+        }
+        let chord_angle = chord.atan2();
+        let th0 = normalize_angle(theta0 - chord_angle);
+        let th1 = normalize_angle(theta1 - chord_angle);
+        let (a, b, end) = fit_normalized(th0, th1)?;
+        let len = chord_len / end.hypot();
+        Some(EulerSpiral {
+            p0,
+            theta0: chord_angle + th0,
+            k0: a / len,
+            k1: 2.0 * b / (len * len),
+            len,
+        })
+    }
+
+    /// The tangent angle at parameter `t`, in radians.
+    #[inline]
+    pub fn tangent_angle(&self, t: f64) -> f64 {
+        let s = t * self.len;
+        self.theta0 + (self.k0 + 0.5 * self.k1 * s) * s
+    }
+
+    /// The curvature at parameter `t`.
+    #[inline]
+    pub fn curvature(&self, t: f64) -> f64 {
+        self.k0 + self.k1 * t * self.len
+    }
+
+    /// The derivative of the curve with respect to `t`.
+    ///
+    /// Its length is always `len`.
+    #[inline]
+    pub fn deriv(&self, t: f64) -> Vec2 {
+        self.len * Vec2::from_angle(self.tangent_angle(t))
+    }
+
+    /// Approximate the spiral by cubic Bézier segments.
+    ///
+    /// The spiral is divided into pieces of equal arc length, each of which
+    /// is approximated by the cubic with matching endpoints and tangents.
+    /// The number of pieces is doubled until the approximation is within
+    /// `tolerance` at sample points, up to a limit of 65536 pieces.
+    ///
+    /// Returns an empty vector if any of the spiral's fields is not finite,
+    /// or if its tangent turns through more than about 2¹⁸ radians, where
+    /// its points are no longer accurate.
+    pub fn to_cubics(&self, tolerance: f64) -> Vec<CubicBez> {
+        self.cubics_to(None, tolerance)
+    }
+
+    /// The cubics of [`to_cubics`], with the last one ending exactly at
+    /// `end` if given, which should then be close to the end of the spiral.
+    ///
+    /// [`to_cubics`]: #method.to_cubics
+    fn cubics_to(&self, end: Option<Point>, tolerance: f64) -> Vec<CubicBez> {
+        let finite = self.p0.x.is_finite()
+            && self.p0.y.is_finite()
+            && self.theta0.is_finite()
+            && self.k0.is_finite()
+            && self.k1.is_finite()
+            && self.len.is_finite();
+        let (a, b) = self.normalized_coeffs();
+        let turn = a.abs() + 2.0 * b.abs();
+        if !finite || turn > 2.0 * MAX_INTEG_PIECES as f64 {
+            return Vec::new();
+        }
+        let end = end.unwrap_or_else(|| self.end());
+        let estimate = (turn * (2.0 / PI)).ceil();
+        let mut n = estimate.clamp(1.0, MAX_CUBICS as f64) as usize;
+        loop {
+            let cubics = self.cubics(n, end);
+            if n >= MAX_CUBICS || self.cubics_error(&cubics) <= tolerance {
+                return cubics;
+            }
+            n = (2 * n).min(MAX_CUBICS);
+        }
+    }
+
+    /// Approximate the spiral by a Bézier path.
+    ///
+    /// This is a `MoveTo` followed by the segments of [`to_cubics`].
+    ///
+    /// [`to_cubics`]: #method.to_cubics
+    pub fn to_path(&self, tolerance: f64) -> BezPath {
+        let mut path = BezPath::new();
+        path.move_to(self.p0);
+        self.append_to(&mut path, None, tolerance);
+        path
+    }
+
+    /// Append the cubic approximation to a path, without a `MoveTo`, ending
+    /// exactly at `end` if given.
+    pub(crate) fn append_to(&self, path: &mut BezPath, end: Option<Point>, tolerance: f64) {
+        for c in self.cubics_to(end, tolerance) {
+            path.curve_to(c.p1, c.p2, c.p3);
+        }
+    }
+
+    /// The coefficients of the tangent angle as a polynomial in `t`.
+    fn normalized_coeffs(&self) -> (f64, f64) {
+        (self.k0 * self.len, 0.5 * self.k1 * self.len * self.len)
+    }
+
+    /// The point at `t1`, given the point `p` at `t0`.
+    ///
+    /// This only integrates over the step, so it is much cheaper than
+    /// [`eval`] for a short piece of a tightly wound spiral.
+    ///
+    /// [`eval`]: trait.ParamCurve.html#tymethod.eval
+    fn advance(&self, p: Point, t0: f64, t1: f64) -> Point {
+        let (_, b) = self.normalized_coeffs();
+        let a = self.curvature(t0) * self.len;
+        p + self.len * integ_euler(self.tangent_angle(t0), a, b, t1 - t0)
+    }
+
+    fn cubics(&self, n: usize, end: Point) -> Vec<CubicBez> {
+        let step = 1.0 / n as f64;
+        let mut p0 = self.p0;
+        let mut d0 = self.deriv(0.0);
+        (1..=n)
+            .map(|i| {
+                let t = i as f64 * step;
+                let p3 = if i == n {
+                    end
+                } else {
+                    self.advance(p0, t - step, t)
+                };
+                let d3 = self.deriv(t);
+                let c = CubicBez::new(p0, p0 + (step / 3.0) * d0, p3 - (step / 3.0) * d3, p3);
+                p0 = p3;
+                d0 = d3;
+                c
+            })
+            .collect()
+    }
+
+    /// The maximum distance between the cubics and the spiral at matching
+    /// parameters. This overestimates the geometric error.
+    fn cubics_error(&self, cubics: &[CubicBez]) -> f64 {
+        let step = 1.0 / cubics.len() as f64;
+        let mut err: f64 = 0.0;
+        for (i, c) in cubics.iter().enumerate() {
+            for &u in &[0.25, 0.5, 0.75] {
+                let t = (i as f64 + u) * step;
+                let p = self.advance(c.p0, i as f64 * step, t);
+                err = err.max((c.eval(u) - p).hypot());
+            }
+        }
+        err
+    }
+}
+
+impl ParamCurve for EulerSpiral {
+    fn eval(&self, t: f64) -> Point {
+        let (a, b) = self.normalized_coeffs();
+        self.p0 + self.len * integ_euler(self.theta0, a, b, t)
+    }
+
+    #[inline]
+    fn start(&self) -> Point {
+        self.p0
+    }
+
+    fn subsegment(&self, range: Range<f64>) -> EulerSpiral {
+        EulerSpiral {
+            p0: self.eval(range.start),
+            theta0: self.tangent_angle(range.start),
+            k0: self.curvature(range.start),
+            k1: self.k1,
+            len: self.len * (range.end - range.start),
+        }
+    }
+}
+
+impl ParamCurveArclen for EulerSpiral {
+    #[inline]
+    fn arclen(&self, _accuracy: f64) -> f64 {
+        self.len.abs()
+    }
+
+    #[inline]
+    fn inv_arclen(&self, arclen: f64, _accuracy: f64) -> f64 {
+        arclen / self.len.abs()
+    }
+}
+
+/// Reduce an angle to the range `(-π, π]`.
+fn normalize_angle(th: f64) -> f64 {
+    let th = th % (2.0 * PI);
+    if th > PI {
+        th - 2.0 * PI
+    } else if th <= -PI {
+        th + 2.0 * PI
+    } else {
+        th
+    }
+}
+
+/// The integral of the unit vector at angle `theta0 + a u + b u²` for `u`
+/// from 0 to `t`.
+///
+/// The range is split so that the angle changes by at most about two
+/// radians over each piece, which keeps 24 point Gauss-Legendre quadrature
+/// accurate to near machine precision, up to [`MAX_INTEG_PIECES`] pieces.
+///
+/// [`MAX_INTEG_PIECES`]: constant.MAX_INTEG_PIECES.html
+fn integ_euler(theta0: f64, a: f64, b: f64, t: f64) -> Vec2 {
+    let turn = (a.abs() + 2.0 * (b * t).abs()) * t.abs();
+    let n = (0.5 * turn).ceil().clamp(1.0, MAX_INTEG_PIECES as f64);
+    let h = t / n;
+    let mut sum = Vec2::ZERO;
+    for i in 0..n as usize {
+        let u0 = i as f64 * h;
+        for &(wi, xi) in GAUSS_LEGENDRE_COEFFS_24 {
+            let u = u0 + 0.5 * h * (xi + 1.0);
+            sum += wi * Vec2::from_angle(theta0 + (a + b * u) * u);
+        }
+    }
+    (0.5 * h) * sum
+}
+
+/// Solve for a spiral from (0, 0) with angles `th0` and `th1` relative to
+/// the x axis, whose end lies on the positive x axis.
+///
+/// The tangent angle is `th0 + a t + b t²`, and the result is `(a, b, end)`,
+/// where `end` is the end point for unit arc length.
+fn fit_normalized(th0: f64, th1: f64) -> Option<(f64, f64, Vec2)> {
+    let dth = th1 - th0;
+    let end_angle = |b: f64| {
+        let end = integ_euler(th0, dth - b, b, 1.0);
+        (end.atan2(), end)
+    };
+    // Small angle approximation of the end angle is (th0 + th1) / 2 - b / 6.
+    let mut b = 3.0 * (th0 + th1);
+    for _ in 0..32 {
+        let (err, end) = end_angle(b);
+        if err.abs() < 1e-12 {
+            return if end.x > 0.0 {
+                Some((dth - b, b, end))
+            } else {
+                None
+            };
+        }
+        let h = 1e-6;
+        let deriv = (end_angle(b + h).0 - end_angle(b - h).0) / (2.0 * h);
+        if deriv == 0.0 || !deriv.is_finite() {
+            return None;
+        }
+        b -= err / deriv;
+        if !b.is_finite() {
+            return None;
+        }
+    }
+    None
+}
+
+/// Interpolate points with a smooth spline made of Euler spiral segments.
+///
+/// The result passes through every point, with curvature that is
+/// continuous at each of them (G2 continuity). Within each segment the
+/// curvature varies linearly. An open spline has zero curvature at its
+/// ends; a closed spline joins the last point back to the first.
+///
+/// The tangent angles at the points are found by Newton iteration. Returns
+/// `None` if there are too few points (two for an open spline, three for a
+/// closed one), if consecutive points coincide, or if the solver does not
+/// converge, which may happen when the points turn sharply relative to
+/// their spacing.
+///
+/// See [`euler_spline_path`] for a Bézier path approximation of the result.
+///
+/// [`euler_spline_path`]: fn.euler_spline_path.html
+pub fn euler_spline(points: &[Point], closed: bool) -> Option<Vec<EulerSpiral>> {
+    let n = points.len();
+    if n < 2 || (closed && n < 3) {
+        return None;
+    }
+    let n_segs = if closed { n } else { n - 1 };
+    let point = |i: usize| points[i % n];
+    let chord_len: Vec<f64> = (0..n_segs)
+        .map(|i| (point(i + 1) - point(i)).hypot())
+        .collect();
+    let chord_angle = |i: usize| (point(i + 1) - point(i)).atan2();
+    let mut th: Vec<f64> = (0..n)
+        .map(|i| {
+            if closed || (i > 0 && i < n - 1) {
+                (point(i + 1) - points[(i + n - 1) % n]).atan2()
+            } else {
+                chord_angle(i.min(n_segs - 1))
+            }
+        })
+        .collect();
+    let fit_seg =
+        |th: &[f64], i: usize| EulerSpiral::fit(point(i), th[i], point(i + 1), th[(i + 1) % n]);
+    // The curvature mismatch at point `i`, scaled by the neighbouring chord
+    // lengths to be independent of the size of the input.
+    let residual = |segs: &[EulerSpiral], i: usize| -> f64 {
+        let before = if closed || i > 0 {
+            Some((i + n_segs - 1) % n_segs)
+        } else {
+            None
+        };
+        let after = if closed || i < n_segs { Some(i) } else { None };
+        let k_before = before.map(|j| segs[j].curvature(1.0)).unwrap_or(0.0);
+        let k_after = after.map(|j| segs[j].curvature(0.0)).unwrap_or(0.0);
+        let scale = before.map(|j| chord_len[j]).unwrap_or(0.0)
+            + after.map(|j| chord_len[j]).unwrap_or(0.0);
+        (k_before - k_after) * scale
+    };
+    for _ in 0..SPLINE_MAX_ITER {
+        let mut segs = (0..n_segs)
+            .map(|i| fit_seg(&th, i))
+            .collect::<Option<Vec<_>>>()?;
+        let r: Vec<f64> = (0..n).map(|i| residual(&segs, i)).collect();
+        if r.iter().all(|r| r.abs() < 1e-9) {
+            return Some(segs);
+        }
+        // The Jacobian is tridiagonal (cyclic for a closed spline), as the
+        // angle at a point only affects the two adjacent segments.
+        let mut sub = vec![0.0; n];
+        let mut diag = vec![0.0; n];
+        let mut sup = vec![0.0; n];
+        let h = 1e-6;
+        for j in 0..n {
+            // The angle and the segments it affects are perturbed in place,
+            // and restored afterwards.
+            let th_j = th[j];
+            let before = if closed || j > 0 {
+                Some((j + n_segs - 1) % n_segs)
+            } else {
+                None
+            };
+            let after = if j < n_segs { Some(j) } else { None };
+            let saved = (before.map(|s| segs[s]), after.map(|s| segs[s]));
+            let mut deltas = [0.0; 3];
+            for &(sign, dt) in &[(1.0, h), (-1.0, -h)] {
+                th[j] = th_j + dt;
+                for s in before.into_iter().chain(after) {
+                    segs[s] = fit_seg(&th, s)?;
+                }
+                for (k, delta) in deltas.iter_mut().enumerate() {
+                    let i = (j + n + k - 1) % n;
+                    *delta += sign * residual(&segs, i) / (2.0 * h);
+                }
+            }
+            th[j] = th_j;
+            if let (Some(s), Some(seg)) = (before, saved.0) {
+                segs[s] = seg;
+            }
+            if let (Some(s), Some(seg)) = (after, saved.1) {
+                segs[s] = seg;
+            }
+            // Row j - 1 gets its superdiagonal, row j + 1 its subdiagonal.
+            sup[(j + n - 1) % n] = deltas[0];
+            diag[j] = deltas[1];
+            sub[(j + 1) % n] = deltas[2];
+        }
+        if !closed {
+            sub[0] = 0.0;
+            sup[n - 1] = 0.0;
+        }
+        let rhs: Vec<f64> = r.iter().map(|r| -r).collect();
+        let step = solve_tridiagonal(&sub, &diag, &sup, &rhs, closed)?;
+        let max_step = step.iter().fold(0.0f64, |m, s| m.max(s.abs()));
+        let damping = if max_step > 0.5 { 0.5 / max_step } else { 1.0 };
+        for (th, s) in th.iter_mut().zip(step) {
+            *th += damping * s;
+        }
+    }
+    None
+}
+
+/// Interpolate points with an Euler spiral spline, approximated by a Bézier
+/// path.
+///
+/// This is [`euler_spline`], with each segment converted using
+/// [`EulerSpiral::to_cubics`]. A closed spline ends with a `ClosePath`.
+///
+/// [`euler_spline`]: fn.euler_spline.html
+/// [`EulerSpiral::to_cubics`]: struct.EulerSpiral.html#method.to_cubics
+pub fn euler_spline_path(points: &[Point], closed: bool, tolerance: f64) -> Option<BezPath> {
+    let segs = euler_spline(points, closed)?;
+    let mut path = BezPath::new();
+    path.move_to(points[0]);
+    // Each segment ends exactly at the next point, rather than where its
+    // fit puts it.
+    for (i, seg) in segs.iter().enumerate() {
+        seg.append_to(&mut path, Some(points[(i + 1) % points.len()]), tolerance);
+    }
+    if closed {
+        path.close_path();
+    }
+    Some(path)
+}
+
+/// Solve a tridiagonal system, where `sub[i]` and `sup[i]` are the entries
+/// left and right of `diag[i]` in row `i`.
+///
+/// If `cyclic` is true, `sub[0]` is the entry in the last column of the
+/// first row and `sup[n - 1]` the entry in the first column of the last
+/// row; the system is then solved with the Sherman-Morrison formula.
+fn solve_tridiagonal(
+    sub: &[f64],
+    diag: &[f64],
+    sup: &[f64],
+    rhs: &[f64],
+    cyclic: bool,
+) -> Option<Vec<f64>> {
+    let n = diag.len();
+    if !cyclic {
+        return solve_thomas(sub, diag, sup, rhs);
+    }
+    let (alpha, beta) = (sup[n - 1], sub[0]);
+    let gamma = -diag[0];
+    let mut diag = diag.to_vec();
+    diag[0] -= gamma;
+    diag[n - 1] -= alpha * beta / gamma;
+    let x = solve_thomas(sub, &diag, sup, rhs)?;
+    let mut u = vec![0.0; n];
+    u[0] = gamma;
+    u[n - 1] = alpha;
+    let z = solve_thomas(sub, &diag, sup, &u)?;
+    let fact = (x[0] + beta * x[n - 1] / gamma) / (1.0 + z[0] + beta * z[n - 1] / gamma);
+    let result: Vec<f64> = x.iter().zip(&z).map(|(x, z)| x - fact * z).collect();
+    if result.iter().all(|x| x.is_finite()) {
+        Some(result)
+    } else {
+        None
+    }
+}
+
+/// The Thomas algorithm for a tridiagonal system; `sub[0]` and `sup[n - 1]`
+/// are ignored.
+fn solve_thomas(sub: &[f64], diag: &[f64], sup: &[f64], rhs: &[f64]) -> Option<Vec<f64>> {
+    let n = diag.len();
+    let mut c = vec![0.0; n];
+    let mut d = vec![0.0; n];
+    for i in 0..n {
+        let (c_prev, d_prev) = if i > 0 {
+            (c[i - 1], d[i - 1])
+        } else {
+            (0.0, 0.0)
+        };
+        let a = if i > 0 { sub[i] } else { 0.0 };
+        let denom = diag[i] - a * c_prev;
+        if denom == 0.0 || !denom.is_finite() {
+            return None;
+        }
+        c[i] = sup[i] / denom;
+        d[i] = (rhs[i] - a * d_prev) / denom;
+    }
+    for i in (0..n - 1).rev() {
+        d[i] -= c[i] * d[i + 1];
+    }
+    Some(d)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        euler_spline, euler_spline_path, EulerSpiral, ParamCurve, ParamCurveArclen, Point, Shape,
+        Vec2,
+    };
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn assert_near(p0: Point, p1: Point, epsilon: f64) {
+        assert!((p1 - p0).hypot() < epsilon, "{:?} != {:?}", p0, p1);
+    }
+
+    #[test]
+    fn euler_spiral_line_and_circle() {
+        let line = EulerSpiral::new((1.0, 2.0), FRAC_PI_2, 0.0, 0.0, 3.0);
+        assert_near(line.eval(1.0), Point::new(1.0, 5.0), 1e-12);
+        assert_eq!(line.arclen(1e-9), 3.0);
+
+        // A half circle of radius 2, turning left.
+        let circle = EulerSpiral::new((0.0, 0.0), 0.0, 0.5, 0.0, 2.0 * PI);
+        assert_near(circle.eval(0.5), Point::new(2.0, 2.0), 1e-12);
+        assert_near(circle.eval(1.0), Point::new(0.0, 4.0), 1e-12);
+        assert!((circle.tangent_angle(1.0) - PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn euler_spiral_subsegment() {
+        let es = EulerSpiral::new((0.0, 0.0), 0.3, -0.2, 0.7, 5.0);
+        let sub = es.subsegment(0.25..0.75);
+        assert_near(sub.start(), es.eval(0.25), 1e-12);
+        assert_near(sub.end(), es.eval(0.75), 1e-12);
+        assert!((sub.curvature(0.5) - es.curvature(0.5)).abs() < 1e-12);
+        let rev = es.subsegment(1.0..0.0);
+        assert_near(rev.end(), es.start(), 1e-12);
+    }
+
+    #[test]
+    fn euler_spiral_fit() {
+        let es = EulerSpiral::new((1.0, -1.0), 0.4, 0.1, 0.3, 4.0);
+        let p1 = es.end();
+        let fit = EulerSpiral::fit(es.p0, es.theta0, p1, es.tangent_angle(1.0)).unwrap();
+        assert!((fit.len - es.len).abs() < 1e-9);
+        assert!((fit.k0 - es.k0).abs() < 1e-9);
+        assert!((fit.k1 - es.k1).abs() < 1e-9);
+        assert_near(fit.end(), p1, 1e-9);
+
+        // A half circle of radius 1, turning right.
+        let arc = EulerSpiral::fit(Point::ORIGIN, FRAC_PI_2, Point::new(2.0, 0.0), -FRAC_PI_2);
+        let arc = arc.unwrap();
+        assert!((arc.len - PI).abs() < 1e-9);
+        assert!((arc.k0 + 1.0).abs() < 1e-9);
+        assert!(arc.k1.abs() < 1e-9);
+
+        assert!(EulerSpiral::fit(p1, 0.0, p1, 1.0).is_none());
+    }
+
+    #[test]
+    fn euler_spiral_to_path() {
+        let es = EulerSpiral::new((0.0, 0.0), 0.0, 0.0, 0.5, 6.0);
+        let cubics = es.to_cubics(1e-6);
+        assert_near(cubics.last().unwrap().p3, es.end(), 1e-12);
+        let n = cubics.len() as f64;
+        for (i, c) in cubics.iter().enumerate() {
+            assert_near(c.eval(0.5), es.eval((i as f64 + 0.5) / n), 1e-6);
+        }
+        assert_eq!(es.to_path(1e-6).elements().len(), cubics.len() + 1);
+
+        // The number of pieces is bounded, and bad input gives none.
+        let tight = EulerSpiral::new((0.0, 0.0), 0.0, 2e5, 0.0, 1.0);
+        assert!(tight.to_cubics(1e-6).len() <= 1 << 16);
+        let bad = EulerSpiral::new((0.0, 0.0), 0.0, f64::NAN, 0.0, 1.0);
+        assert!(bad.to_cubics(1e-6).is_empty());
+        let bad = EulerSpiral::new((0.0, 0.0), 0.0, 0.0, f64::INFINITY, 1.0);
+        assert!(bad.to_cubics(1e-6).is_empty());
+        // A huge but finite curvature is past the limit of the integration,
+        // which still finishes, but gives no cubics.
+        let wound = EulerSpiral::new((0.0, 0.0), 0.0, 1e12, 0.0, 1.0);
+        let end = wound.end();
+        assert!(end.x.is_finite() && end.y.is_finite());
+        assert!(wound.eval(0.5).distance(Point::ORIGIN) <= 0.5);
+        assert!(wound.to_cubics(1e-6).is_empty());
+    }
+
+    #[test]
+    fn euler_spline_open() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(3.0, 0.5),
+            Point::new(4.0, 2.0),
+            Point::new(6.0, 2.5),
+        ];
+        let segs = euler_spline(&points, false).unwrap();
+        assert_eq!(segs.len(), 4);
+        assert!(segs[0].k0.abs() < 1e-9);
+        assert!(segs[3].curvature(1.0).abs() < 1e-9);
+        for (seg, next) in segs.iter().zip(&segs[1..]) {
+            assert_near(seg.end(), next.start(), 1e-9);
+            assert!((seg.curvature(1.0) - next.k0).abs() < 1e-8);
+            let dth = (seg.tangent_angle(1.0) - next.theta0).sin();
+            assert!(dth.abs() < 1e-9);
+        }
+        assert_near(segs[3].end(), points[4], 1e-9);
+        // The path goes through the points exactly.
+        let path = euler_spline_path(&points, false, 1e-6).unwrap();
+        let mut ends = path.segments().map(|seg| seg.end());
+        for &p in &points[1..] {
+            assert!(ends.any(|end| end == p));
+        }
+
+        let two = euler_spline(&points[..2], false).unwrap();
+        assert!(two[0].k0.abs() < 1e-9 && two[0].k1.abs() < 1e-9);
+        assert!(euler_spline(&points[..1], false).is_none());
+    }
+
+    #[test]
+    fn euler_spline_closed() {
+        // Points on a circle give a circle.
+        let points: Vec<Point> = (0..5)
+            .map(|i| Point::ORIGIN + 2.0 * Vec2::from_angle(i as f64 * 0.4 * PI))
+            .collect();
+        let segs = euler_spline(&points, true).unwrap();
+        assert_eq!(segs.len(), 5);
+        for seg in &segs {
+            assert!((seg.k0 - 0.5).abs() < 1e-8);
+            assert!(seg.k1.abs() < 1e-8);
+        }
+        let path = euler_spline_path(&points, true, 1e-6).unwrap();
+        assert!((path.area() - 4.0 * PI).abs() < 1e-4);
+        assert!(euler_spline(&points[..2], true).is_none());
+    }
+}
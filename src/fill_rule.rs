@@ -0,0 +1,55 @@
+//! Fill rules.
+
+/// The rule for deciding whether a point is inside a shape, given its
+/// winding number.
+///
+/// # Examples
+///
+/// ```
+/// use kurbo::FillRule;
+/// assert!(FillRule::NonZero.is_inside(2));
+/// assert!(!FillRule::EvenOdd.is_inside(2));
+/// assert!(FillRule::EvenOdd.is_inside(-1));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    /// A point is inside if its winding number is nonzero.
+    NonZero,
+    /// A point is inside if its winding number is odd.
+    EvenOdd,
+}
+
+impl FillRule {
+    /// Returns `true` if a point with the given winding number is inside.
+    #[inline]
+    pub fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+impl Default for FillRule {
+    /// The default is `NonZero`, which is what [`Shape::contains`] uses.
+    ///
+    /// [`Shape::contains`]: trait.Shape.html#method.contains
+    fn default() -> FillRule {
+        FillRule::NonZero
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::FillRule;
+
+    #[test]
+    fn fill_rule_is_inside() {
+        for w in -3..=3 {
+            assert_eq!(FillRule::NonZero.is_inside(w), w != 0);
+            assert_eq!(FillRule::EvenOdd.is_inside(w), w % 2 != 0);
+        }
+        assert_eq!(FillRule::default(), FillRule::NonZero);
+    }
+}
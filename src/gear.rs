@@ -0,0 +1,244 @@
+//! Involute gear outlines.
+
+use std::f64::consts::PI;
+
+use crate::{Arc, BezPath, Point, Vec2};
+
+/// The fraction of the tooth depth above the pitch circle, from the
+/// standard proportions of an addendum of one module and a dedendum of
+/// 1.25 modules.
+const ADDENDUM_FRACTION: f64 = 1.0 / 2.25;
+
+/// The outline of a spur gear with involute teeth.
+///
+/// The teeth are spaced evenly around the pitch circle of radius
+/// `pitch_radius` centered on `center`, with the first tooth centered on the
+/// positive x axis, and are as thick as the gaps between them along that
+/// circle. `tooth_depth` is the radial distance from the root circle to the
+/// tips, split between the parts above and below the pitch circle in the
+/// standard ratio of 1 to 1.25. The flanks are involutes of the base circle,
+/// of radius `pitch_radius * pressure_angle.cos()`, continued by radial lines
+/// where the root circle is inside the base circle. Tips and roots are arcs.
+///
+/// The flanks are approximated by cubic Béziers, and the arcs converted as
+/// by [`Arc::append_iter`], all to within `tolerance`. The result is a
+/// single closed subpath, going around in the direction of increasing angle.
+///
+/// Returns `None` if there are fewer than 3 teeth, if the radius, depth or
+/// tolerance is not positive and finite, if the pressure angle is not
+/// strictly between 0 and π/2, if the root circle does not have a positive
+/// radius, or if the teeth would come to a point before their tips or meet
+/// at their roots.
+///
+/// # Examples
+///
+/// ```
+/// use kurbo::{gear, Point, Shape};
+/// let outline = gear(Point::ORIGIN, 10.0, 20, 2.25, 20f64.to_radians(), 1e-3).unwrap();
+/// assert!(outline.contains(Point::ORIGIN));
+/// assert!(outline.bounding_box().width() <= 22.0 + 1e-3);
+/// assert!(gear(Point::ORIGIN, 10.0, 2, 2.25, 0.35, 1e-3).is_none());
+/// ```
+///
+/// [`Arc::append_iter`]: struct.Arc.html#method.append_iter
+pub fn gear(
+    center: Point,
+    pitch_radius: f64,
+    teeth: usize,
+    tooth_depth: f64,
+    pressure_angle: f64,
+    tolerance: f64,
+) -> Option<BezPath> {
+    let positive = |x: f64| x > 0.0 && x.is_finite();
+    let valid = teeth >= 3
+        && positive(pitch_radius)
+        && positive(tooth_depth)
+        && positive(tolerance)
+        && pressure_angle > 0.0
+        && pressure_angle < 0.5 * PI;
+    if !valid {
+        return None;
+    }
+    let outer_radius = pitch_radius + ADDENDUM_FRACTION * tooth_depth;
+    let root_radius = outer_radius - tooth_depth;
+    if root_radius <= 0.0 {
+        return None;
+    }
+    let base_radius = pitch_radius * pressure_angle.cos();
+    let pitch_angle = 2.0 * PI / teeth as f64;
+    // The angle from the center of a tooth to where its flanks leave the
+    // base circle.
+    let base_half_angle = 0.25 * pitch_angle + involute_angle(pitch_radius, base_radius);
+    let tip_half_angle = base_half_angle - involute_angle(outer_radius, base_radius);
+    let root_half_angle = base_half_angle - involute_angle(root_radius, base_radius);
+    if tip_half_angle <= 0.0 || 2.0 * root_half_angle >= pitch_angle {
+        return None;
+    }
+    let roll_start = roll_angle(root_radius.max(base_radius), base_radius);
+    let roll_end = roll_angle(outer_radius, base_radius);
+    let flank = involute_cubics(base_radius, roll_start, roll_end, tolerance);
+    let circle_arc = |radius: f64, start_angle: f64, sweep_angle: f64| Arc {
+        center,
+        radii: Vec2::new(radius, radius),
+        start_angle,
+        sweep_angle,
+        x_rotation: 0.0,
+    };
+
+    let mut path = BezPath::new();
+    path.move_to(center + Vec2::from_angle(-root_half_angle) * root_radius);
+    for i in 0..teeth {
+        let th = i as f64 * pitch_angle;
+        // The rising flank is the involute turned to start at the base
+        // circle, and the falling flank its mirror image across the center
+        // line of the tooth.
+        let rising = |p: Vec2| center + rotate(p, th - base_half_angle);
+        let falling = |p: Vec2| center + rotate(Vec2::new(p.x, -p.y), th + base_half_angle);
+        if root_radius < base_radius {
+            path.line_to(rising(Vec2::new(base_radius, 0.0)));
+        }
+        for c in &flank {
+            path.curve_to(rising(c[1]), rising(c[2]), rising(c[3]));
+        }
+        let tip = circle_arc(outer_radius, th - tip_half_angle, 2.0 * tip_half_angle);
+        path.extend(tip.append_iter(tolerance));
+        for c in flank.iter().rev() {
+            path.curve_to(falling(c[2]), falling(c[1]), falling(c[0]));
+        }
+        if root_radius < base_radius {
+            path.line_to(center + Vec2::from_angle(th + root_half_angle) * root_radius);
+        }
+        let gap = pitch_angle - 2.0 * root_half_angle;
+        let root = circle_arc(root_radius, th + root_half_angle, gap);
+        path.extend(root.append_iter(tolerance));
+    }
+    path.close_path();
+    Some(path)
+}
+
+/// The roll angle at which the involute of a circle of radius `base_radius`
+/// reaches `radius`.
+fn roll_angle(radius: f64, base_radius: f64) -> f64 {
+    ((radius / base_radius).powi(2) - 1.0).max(0.0).sqrt()
+}
+
+/// The polar angle of the involute where it reaches `radius`, measured from
+/// where it leaves the base circle, or 0 inside the base circle.
+fn involute_angle(radius: f64, base_radius: f64) -> f64 {
+    let t = roll_angle(radius, base_radius);
+    t - t.atan()
+}
+
+/// The involute at roll angle `t`, leaving the base circle at angle 0 and
+/// unwinding in the direction of increasing angle.
+fn involute_point(base_radius: f64, t: f64) -> Vec2 {
+    let (s, c) = t.sin_cos();
+    base_radius * Vec2::new(c + t * s, s - t * c)
+}
+
+/// Cubic Hermite interpolants of the involute between roll angles `t0` and
+/// `t1`, as control points relative to the center.
+fn involute_cubics(base_radius: f64, t0: f64, t1: f64, tolerance: f64) -> Vec<[Vec2; 4]> {
+    // The error of Hermite interpolation over a step h is at most
+    // h^4 / 384 times the largest fourth derivative, which for the involute
+    // has magnitude base_radius * sqrt(9 + t^2).
+    let d4 = base_radius * (9.0 + t1 * t1).sqrt();
+    let n = ((t1 - t0) * (d4 / (384.0 * tolerance)).powf(0.25))
+        .ceil()
+        .max(1.0) as usize;
+    let h = (t1 - t0) / n as f64;
+    let deriv = |t: f64| base_radius * t * Vec2::from_angle(t);
+    (0..n)
+        .map(|i| {
+            let ta = t0 + i as f64 * h;
+            let tb = if i + 1 == n { t1 } else { ta + h };
+            let pa = involute_point(base_radius, ta);
+            let pb = involute_point(base_radius, tb);
+            let arm = (tb - ta) / 3.0;
+            [pa, pa + arm * deriv(ta), pb - arm * deriv(tb), pb]
+        })
+        .collect()
+}
+
+fn rotate(p: Vec2, th: f64) -> Vec2 {
+    let (s, c) = th.sin_cos();
+    Vec2::new(c * p.x - s * p.y, s * p.x + c * p.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{gear, ParamCurve, PathSeg, Point, Shape};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn gear_involute_flanks() {
+        let center = Point::new(3.0, -2.0);
+        let (pitch_radius, teeth, depth) = (12.0, 17, 2.0);
+        let pressure_angle = 0.35;
+        let tolerance = 1e-4;
+        let path = gear(
+            center,
+            pitch_radius,
+            teeth,
+            depth,
+            pressure_angle,
+            tolerance,
+        )
+        .unwrap();
+        assert_eq!(path.subpaths().count(), 1);
+        assert_eq!(path.elements().last(), Some(&crate::PathEl::ClosePath));
+        let outer_radius = pitch_radius + depth / 2.25;
+        let root_radius = outer_radius - depth;
+        let base_radius = pitch_radius * pressure_angle.cos();
+        assert!(path.area() > PI * root_radius * root_radius);
+        assert!(path.area() < PI * outer_radius * outer_radius);
+        assert_eq!(path.winding(center), 1);
+
+        // Every point of the outline is on the tip or root circle, on a
+        // radial line below the base circle, or on an involute flank, whose
+        // angular distance from the center line of its tooth is fixed by its
+        // radius.
+        let pitch_angle = 2.0 * PI / teeth as f64;
+        let inv = |r: f64| {
+            let t = ((r / base_radius).powi(2) - 1.0).max(0.0).sqrt();
+            t - t.atan()
+        };
+        let base_half_angle = 0.25 * pitch_angle + inv(pitch_radius);
+        let mut n_flank = 0;
+        for seg in path.segments() {
+            for i in 0..=8 {
+                let p = seg.eval(i as f64 / 8.0) - center;
+                let r = p.hypot();
+                assert!(r > root_radius - tolerance && r < outer_radius + tolerance);
+                let on_circle =
+                    (r - root_radius).abs() < tolerance || (r - outer_radius).abs() < tolerance;
+                if on_circle || r < base_radius {
+                    continue;
+                }
+                let th = p.atan2() / pitch_angle;
+                let off = (th - th.round()).abs() * pitch_angle;
+                let expected = base_half_angle - inv(r);
+                assert!(((off - expected) * r).abs() < tolerance, "{:?}", p);
+                if let PathSeg::Cubic(_) = seg {
+                    n_flank += 1;
+                }
+            }
+        }
+        assert!(n_flank > 2 * teeth);
+    }
+
+    #[test]
+    fn gear_degenerate() {
+        let o = Point::ORIGIN;
+        assert!(gear(o, 10.0, 3, 2.0, 0.35, 1e-3).is_some());
+        assert!(gear(o, 10.0, 2, 2.0, 0.35, 1e-3).is_none());
+        assert!(gear(o, 0.0, 20, 2.0, 0.35, 1e-3).is_none());
+        assert!(gear(o, 10.0, 20, 0.0, 0.35, 1e-3).is_none());
+        assert!(gear(o, 10.0, 20, 2.0, 0.0, 1e-3).is_none());
+        assert!(gear(o, 10.0, 20, 2.0, 0.35, 0.0).is_none());
+        // The root circle would have a negative radius.
+        assert!(gear(o, 1.0, 20, 3.0, 0.35, 1e-3).is_none());
+        // The teeth are too deep for their width, and come to a point.
+        assert!(gear(o, 10.0, 60, 4.0, 0.35, 1e-3).is_none());
+    }
+}
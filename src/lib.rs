@@ -28,38 +28,73 @@
 mod affine;
 mod arc;
 mod bezpath;
+mod binary;
+mod boolean;
+mod bvh;
+mod catmull_rom;
+mod centerline;
 mod circle;
 pub mod common;
 mod cubicbez;
+mod delaunay;
 mod ellipse;
+mod euler;
+mod fill_rule;
+mod gear;
 mod insets;
 mod line;
+mod offset;
 mod param_curve;
 mod point;
+mod polygon;
+mod polylabel;
+mod postscript;
 mod quadbez;
+mod quantize;
+mod rational_quadbez;
 mod rect;
+mod rotated_rect;
 mod rounded_rect;
+mod sample;
 mod shape;
 mod size;
+mod stroke;
 mod svg;
+mod transformed_shape;
 mod translate_scale;
+mod triangle;
 mod vec2;
 
 pub use crate::affine::*;
 pub use crate::arc::*;
 pub use crate::bezpath::*;
+pub use crate::binary::*;
+pub use crate::boolean::*;
+pub use crate::bvh::*;
+pub use crate::catmull_rom::*;
 pub use crate::circle::*;
 pub use crate::cubicbez::*;
+pub use crate::delaunay::*;
 pub use crate::ellipse::*;
+pub use crate::euler::*;
+pub use crate::fill_rule::*;
+pub use crate::gear::*;
 pub use crate::insets::*;
 pub use crate::line::*;
 pub use crate::param_curve::*;
 pub use crate::point::*;
+pub use crate::polygon::*;
 pub use crate::quadbez::*;
+pub use crate::rational_quadbez::*;
 pub use crate::rect::*;
+pub use crate::rotated_rect::*;
 pub use crate::rounded_rect::*;
+pub use crate::sample::*;
 pub use crate::shape::*;
 pub use crate::size::*;
+pub use crate::stroke::*;
 pub use crate::svg::*;
+pub use crate::transformed_shape::*;
 pub use crate::translate_scale::*;
+pub use crate::triangle::*;
 pub use crate::vec2::*;
@@ -4,6 +4,7 @@ use std::ops::{Add, Mul, Range, Sub};
 
 use arrayvec::ArrayVec;
 
+use crate::common::orient2d;
 use crate::{
     Affine, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveCurvature, ParamCurveDeriv,
     ParamCurveExtrema, ParamCurveNearest, PathEl, Point, Rect, Shape, Vec2, DEFAULT_ACCURACY,
@@ -35,6 +36,148 @@ impl Line {
     pub fn length(self) -> f64 {
         self.arclen(DEFAULT_ACCURACY)
     }
+
+    /// The square of the length of the line.
+    #[inline]
+    pub fn length_squared(self) -> f64 {
+        (self.p1 - self.p0).hypot2()
+    }
+
+    /// The midpoint of the line.
+    #[inline]
+    pub fn midpoint(self) -> Point {
+        self.p0.midpoint(self.p1)
+    }
+
+    /// The unit vector pointing from `p0` to `p1`.
+    ///
+    /// Returns `Vec2::ZERO` if the two points coincide.
+    #[inline]
+    pub fn direction(self) -> Vec2 {
+        let v = self.p1 - self.p0;
+        let len = v.hypot();
+        if len == 0.0 {
+            Vec2::ZERO
+        } else {
+            v / len
+        }
+    }
+
+    /// The perpendicular bisector of the line.
+    ///
+    /// This is [`perpendicular_at`] the midpoint: a line of length 1 that
+    /// starts at the midpoint and points to the left of the direction of
+    /// the line (in a y-up coordinate system).
+    ///
+    /// [`perpendicular_at`]: #method.perpendicular_at
+    #[inline]
+    pub fn bisector(self) -> Line {
+        self.perpendicular_at(0.5)
+    }
+
+    /// The perpendicular to the line at parameter `t`.
+    ///
+    /// The result starts at the point `eval(t)`, and ends one unit away from
+    /// it, in the direction of the line rotated by a quarter turn, so that
+    /// rotating the positive X direction gives the positive Y direction. If
+    /// the line has zero length, both ends of the result are at `eval(t)`.
+    #[inline]
+    pub fn perpendicular_at(self, t: f64) -> Line {
+        let p = self.eval(t);
+        let d = self.direction();
+        Line::new(p, p + Vec2::new(-d.y, d.x))
+    }
+
+    /// The parameters at which the infinite lines through two lines cross.
+    ///
+    /// Returns `(t, u)` such that `self.eval(t)` and `other.eval(u)` are the
+    /// crossing point, or `None` if the lines are parallel (including
+    /// collinear), or either has zero length.
+    pub fn crossing_params(self, other: Line) -> Option<(f64, f64)> {
+        let r = self.p1 - self.p0;
+        let s = other.p1 - other.p0;
+        let denom = r.cross(s);
+        if denom == 0.0 {
+            return None;
+        }
+        let qp = other.p0 - self.p0;
+        let t = qp.cross(s) / denom;
+        let u = qp.cross(r) / denom;
+        if t.is_finite() && u.is_finite() {
+            Some((t, u))
+        } else {
+            None
+        }
+    }
+
+    /// The point where the infinite lines through two lines cross.
+    ///
+    /// Returns `None` if the lines are parallel (including collinear), or
+    /// either has zero length. Nearly parallel lines cross far away, and the
+    /// result may then be inaccurate, as it is very sensitive to the input;
+    /// it is computed from the parameter on the longer line, which is the
+    /// better conditioned of the two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Line, Point};
+    /// let a = Line::new((0.0, 0.0), (1.0, 1.0));
+    /// let b = Line::new((4.0, 0.0), (3.0, 1.0));
+    /// assert_eq!(a.crossing_point(b), Some(Point::new(2.0, 2.0)));
+    /// assert_eq!(a.crossing_point(a + (1.0, 0.0).into()), None);
+    /// ```
+    pub fn crossing_point(self, other: Line) -> Option<Point> {
+        let (t, u) = self.crossing_params(other)?;
+        Some(if self.length_squared() >= other.length_squared() {
+            self.eval(t)
+        } else {
+            other.eval(u)
+        })
+    }
+
+    /// The point where two line segments intersect.
+    ///
+    /// This is the [`crossing_point`] of the two lines, if it lies within
+    /// both segments, including at their endpoints. Parallel segments don't
+    /// intersect, unless they are collinear and overlap, in which case the
+    /// point of the overlap nearest to `self.p0` is returned; that is
+    /// `self.p0` itself if it lies on `other`, or else the end of `other`
+    /// lying on `self` that comes first along it. Returns `None` if either
+    /// segment has zero length.
+    ///
+    /// [`crossing_point`]: #method.crossing_point
+    pub fn segment_intersection(self, other: Line) -> Option<Point> {
+        let r = self.p1 - self.p0;
+        let s = other.p1 - other.p0;
+        if r.hypot2() == 0.0 || s.hypot2() == 0.0 {
+            return None;
+        }
+        if r.cross(s) == 0.0 {
+            if orient2d(self.p0, self.p1, other.p0) != 0.0 {
+                return None;
+            }
+            let project = |p: Point| (p - self.p0).dot(r) / r.hypot2();
+            let (u0, u1) = (project(other.p0), project(other.p1));
+            let lo = u0.min(u1).max(0.0);
+            let hi = u0.max(u1).min(1.0);
+            return if lo > hi {
+                None
+            } else if lo == 0.0 {
+                Some(self.p0)
+            } else if lo == u0 {
+                Some(other.p0)
+            } else {
+                Some(other.p1)
+            };
+        }
+        let (t, u) = self.crossing_params(other)?;
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            self.crossing_point(other)
+        } else {
+            None
+        }
+    }
 }
 
 impl ParamCurve for Line {
@@ -212,6 +355,11 @@ impl Shape for Line {
         0
     }
 
+    #[inline]
+    fn distance_to_point(&self, pt: Point, accuracy: f64) -> f64 {
+        self.nearest(pt, accuracy).1.sqrt()
+    }
+
     #[inline]
     fn bounding_box(&self) -> Rect {
         Rect::from_points(self.p0, self.p1)
@@ -238,7 +386,7 @@ impl Iterator for LinePathIter {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Line, ParamCurveArclen};
+    use crate::{Line, ParamCurveArclen, Point, Shape, Vec2};
 
     #[test]
     fn line_arclen() {
@@ -250,4 +398,67 @@ mod tests {
         let t = l.inv_arclen(true_len / 3.0, epsilon);
         assert!((t - 1.0 / 3.0).abs() < epsilon);
     }
+
+    #[test]
+    fn line_distance_to_point() {
+        let l = Line::new((0.0, 0.0), (4.0, 0.0));
+        assert!((l.distance_to_point(Point::new(2.0, 3.0), 1e-9) - 3.0).abs() < 1e-12);
+        assert!((l.distance_to_point(Point::new(7.0, 4.0), 1e-9) - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn line_crossing() {
+        let a = Line::new((0.0, 0.0), (2.0, 0.0));
+        let b = Line::new((1.0, -1.0), (1.0, 3.0));
+        assert_eq!(a.crossing_params(b), Some((0.5, 0.25)));
+        assert_eq!(a.segment_intersection(b), Some(Point::new(1.0, 0.0)));
+        // The infinite lines cross outside the segments.
+        let c = Line::new((3.0, 1.0), (3.0, 2.0));
+        assert_eq!(a.crossing_point(c), Some(Point::new(3.0, 0.0)));
+        assert_eq!(a.segment_intersection(c), None);
+        // Touching at an endpoint counts.
+        let d = Line::new((2.0, 0.0), (5.0, 5.0));
+        assert_eq!(a.segment_intersection(d), Some(Point::new(2.0, 0.0)));
+        // Parallel and collinear.
+        let e = a + Vec2::new(0.0, 1.0);
+        assert_eq!(a.crossing_point(e), None);
+        assert_eq!(a.segment_intersection(e), None);
+        let f = Line::new((3.0, 0.0), (1.5, 0.0));
+        assert_eq!(a.crossing_point(f), None);
+        assert_eq!(a.segment_intersection(f), Some(Point::new(1.5, 0.0)));
+        assert_eq!(f.segment_intersection(a), Some(Point::new(2.0, 0.0)));
+        let g = Line::new((-1.0, 0.0), (0.5, 0.0));
+        assert_eq!(a.segment_intersection(g), Some(Point::new(0.0, 0.0)));
+        let h = Line::new((2.5, 0.0), (4.0, 0.0));
+        assert_eq!(a.segment_intersection(h), None);
+        // Nearly parallel lines still cross at a finite point, accurately
+        // enough on the long line.
+        let long = Line::new((0.0, 0.0), (1e6, 1.0));
+        let short = Line::new((0.0, 1.0), (1.0, 1.0));
+        let p = long.crossing_point(short).unwrap();
+        assert!((p - Point::new(1e6, 1.0)).hypot() < 1e-6);
+        assert_eq!(Line::new((1.0, 1.0), (1.0, 1.0)).crossing_point(b), None);
+    }
+
+    #[test]
+    fn line_accessors() {
+        let l = Line::new((1.0, 1.0), (4.0, 5.0));
+        assert_eq!(l.length(), 5.0);
+        assert_eq!(l.length_squared(), 25.0);
+        assert_eq!(l.midpoint(), Point::new(2.5, 3.0));
+        assert!((l.direction() - Vec2::new(0.6, 0.8)).hypot() < 1e-12);
+        assert_eq!(Line::new((2.0, 2.0), (2.0, 2.0)).direction(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn line_perpendiculars() {
+        let l = Line::new((0.0, 0.0), (4.0, 0.0));
+        assert_eq!(l.bisector(), Line::new((2.0, 0.0), (2.0, 1.0)));
+        assert_eq!(l.perpendicular_at(0.25), Line::new((1.0, 0.0), (1.0, 1.0)));
+        // Equidistant from both ends.
+        let l = Line::new((1.0, 2.0), (-3.0, 0.5));
+        let p = l.bisector().p1;
+        assert!((p.distance(l.p0) - p.distance(l.p1)).abs() < 1e-12);
+        assert!((l.bisector().length() - 1.0).abs() < 1e-12);
+    }
 }
@@ -0,0 +1,499 @@
+//! Offset curves of cubic Béziers, and insets of shapes.
+
+use crate::bezpath::{end_tangent, start_tangent};
+use crate::common::solve_quadratic;
+use crate::cubicbez::fit_cubic_handles;
+use crate::stroke::join_round_within;
+use crate::{
+    BezPath, CubicBez, Line, ParamCurve, ParamCurveArea, ParamCurveDeriv, PathEl, PathSeg, Point,
+    Rect, Vec2,
+};
+
+/// The number of samples used to fit and check each cubic piece.
+const N_SAMPLES: usize = 16;
+
+/// The number of intervals scanned for cusps of the offset.
+const N_CUSP_SCAN: usize = 64;
+
+/// The maximum subdivision depth when fitting.
+const MAX_DEPTH: usize = 12;
+
+/// The maximum number of pairs of pieces examined when looking for the
+/// crossing of two offsets at a corner.
+const CROSSING_BUDGET: usize = 4096;
+
+impl CubicBez {
+    /// An approximation of the offset curve at the given distance.
+    ///
+    /// The offset curve consists of the points at distance `distance` along
+    /// the normal of the curve. Positive distances are to the left of the
+    /// direction of travel in a y-up coordinate system (to the right when y
+    /// points down).
+    ///
+    /// The curve is first split at its inflection points, and at the cusps
+    /// of the offset, where the radius of curvature equals the distance.
+    /// Each piece is fitted by a cubic with the same end tangents, using
+    /// least squares over sample points, and subdivided until the samples are
+    /// within `tolerance` of the mathematical offset.
+    ///
+    /// Where the radius of curvature is smaller than `distance` on the
+    /// offset side, the true offset curve has cusps and a loop, and so does
+    /// the result: it is not trimmed to the outline of the swept region. At a
+    /// cusp of the curve itself the normal flips, and the two sides are
+    /// joined by a straight line. A curve whose points all coincide has no
+    /// normal, and is returned unchanged.
+    pub fn offset(&self, distance: f64, tolerance: f64) -> BezPath {
+        let mut path = BezPath::new();
+        let scale =
+            (self.p1 - self.p0).hypot() + (self.p2 - self.p1).hypot() + (self.p3 - self.p2).hypot();
+        if distance == 0.0 || scale == 0.0 {
+            path.move_to(self.p0);
+            path.curve_to(self.p1, self.p2, self.p3);
+            return path;
+        }
+        let offset = Offset {
+            c: *self,
+            q: self.deriv(),
+            d: distance,
+            eps: 1e-12 * scale,
+        };
+        let mut splits = offset.critical_params();
+        splits.insert(0, 0.0);
+        splits.push(1.0);
+        path.move_to(offset.point(0.0, true));
+        for w in splits.windows(2) {
+            let (t0, t1) = (w[0], w[1]);
+            let start = offset.point(t0, true);
+            if path.elements().len() > 1 && (path_end(&path) - start).hypot() > tolerance * 1e-3 {
+                path.line_to(start);
+            }
+            offset.fit(&mut path, t0, t1, tolerance, 0);
+        }
+        path
+    }
+}
+
+/// The general implementation of [`Shape::inset`].
+///
+/// [`Shape::inset`]: trait.Shape.html#method.inset
+pub(crate) fn inset_path(
+    elements: impl IntoIterator<Item = PathEl>,
+    distance: f64,
+    tolerance: f64,
+) -> BezPath {
+    let mut result = BezPath::new();
+    let path: BezPath = elements.into_iter().collect();
+    // The segments of each subpath, closing it with a line.
+    let subpaths = path.subpaths().map(|subpath| {
+        let mut segs: Vec<PathSeg> = subpath.segments().collect();
+        if let (Some(first), Some(last)) = (segs.first(), segs.last()) {
+            if first.start() != last.end() {
+                segs.push(PathSeg::Line(Line::new(last.end(), first.start())));
+            }
+        }
+        segs
+    });
+    for subpath in subpaths {
+        let area: f64 = subpath.iter().map(|seg| seg.signed_area()).sum();
+        if area == 0.0 {
+            continue;
+        }
+        // Outwards is to the right of a counterclockwise subpath, while
+        // positive offsets of a curve are to its left.
+        let d = if area > 0.0 { -distance } else { distance };
+        let segs: Vec<PathSeg> = subpath
+            .iter()
+            .copied()
+            .filter(|seg| {
+                let c = seg.to_cubic();
+                c.p0 != c.p1 || c.p0 != c.p2 || c.p0 != c.p3
+            })
+            .collect();
+        // The offset of each segment, with the parameter ranges kept of its
+        // first and last pieces after trimming at inner corners.
+        let mut pieces: Vec<(Vec<PathSeg>, f64, f64)> = segs
+            .iter()
+            .map(|&seg| {
+                let piece = match seg {
+                    PathSeg::Line(l) => {
+                        let n = l.direction();
+                        let v = d * Vec2::new(-n.y, n.x);
+                        vec![PathSeg::Line(Line::new(l.p0 + v, l.p1 + v))]
+                    }
+                    _ => seg.to_cubic().offset(d, tolerance).segments().collect(),
+                };
+                (piece, 0.0, 1.0)
+            })
+            .collect();
+        let n = pieces.len();
+        if n == 0 {
+            continue;
+        }
+        // How each corner is joined: by an arc, or, if the offsets cross,
+        // by trimming them to the crossing.
+        let mut round = vec![false; n];
+        for i in 0..n {
+            let j = (i + 1) % n;
+            let a = pieces[i].0.last().unwrap().end();
+            let b = pieces[j].0[0].start();
+            if (a - b).hypot() <= tolerance * 1e-3 {
+                continue;
+            }
+            let turn = end_tangent(segs[i])
+                .unwrap_or(Vec2::ZERO)
+                .cross(start_tangent(segs[j]).unwrap_or(Vec2::ZERO));
+            if (turn < 0.0) == (d > 0.0) {
+                round[i] = true;
+            } else if let Some((t0, t1)) =
+                last_crossing(*pieces[i].0.last().unwrap(), pieces[j].0[0], tolerance)
+            {
+                pieces[i].2 = t0;
+                pieces[j].1 = t1;
+            }
+        }
+        let trimmed: Vec<Vec<PathSeg>> = pieces
+            .into_iter()
+            .map(|(mut piece, t0, t1)| {
+                let last = piece.len() - 1;
+                if last == 0 {
+                    piece[0] = piece[0].subsegment(t0..t1.max(t0));
+                } else {
+                    piece[0] = piece[0].subsegment(t0..1.0);
+                    piece[last] = piece[last].subsegment(0.0..t1);
+                }
+                piece
+            })
+            .collect();
+        result.move_to(trimmed[0][0].start());
+        for i in 0..n {
+            for seg in &trimmed[i] {
+                result.push(match *seg {
+                    PathSeg::Line(l) => PathEl::LineTo(l.p1),
+                    PathSeg::Quad(q) => PathEl::QuadTo(q.p1, q.p2),
+                    PathSeg::Cubic(c) => PathEl::CurveTo(c.p1, c.p2, c.p3),
+                });
+            }
+            let a = trimmed[i].last().unwrap().end();
+            let b = trimmed[(i + 1) % n][0].start();
+            if (a - b).hypot() <= tolerance * 1e-3 {
+                continue;
+            }
+            if round[i] {
+                // The offsets move apart at this corner: join them with an
+                // arc around it.
+                result.extend(join_round_within(segs[i].end(), a, b, d, tolerance));
+            } else {
+                result.line_to(b);
+            }
+        }
+        result.close_path();
+    }
+    result
+}
+
+/// The crossing of two segments closest to the end of `a`, as parameters
+/// on each, found by subdividing until the pieces are within `tolerance`.
+fn last_crossing(a: PathSeg, b: PathSeg, tolerance: f64) -> Option<(f64, f64)> {
+    fn hull(seg: PathSeg) -> Rect {
+        let c = seg.to_cubic();
+        Rect::from_points(c.p0, c.p1).union_pt(c.p2).union_pt(c.p3)
+    }
+    let both_lines = matches!((a, b), (PathSeg::Line(_), PathSeg::Line(_)));
+    let mut best: Option<(f64, f64)> = None;
+    let mut stack = vec![(0.0, 1.0, 0.0, 1.0, 0)];
+    let mut budget = CROSSING_BUDGET;
+    while let Some((a0, a1, b0, b1, depth)) = stack.pop() {
+        budget -= 1;
+        if budget == 0 {
+            break;
+        }
+        let (sa, sb) = (a.subsegment(a0..a1), b.subsegment(b0..b1));
+        let (ra, rb) = (hull(sa), hull(sb));
+        if ra.x0 > rb.x1 || rb.x0 > ra.x1 || ra.y0 > rb.y1 || rb.y0 > ra.y1 {
+            continue;
+        }
+        let size = ra.width().max(ra.height()).max(rb.width()).max(rb.height());
+        if both_lines || size <= tolerance || depth >= 4 * MAX_DEPTH {
+            // Intersect the chords to locate the crossing within the pieces.
+            let (p, r) = (sa.start(), sa.end() - sa.start());
+            let (q, s) = (sb.start(), sb.end() - sb.start());
+            let denom = r.cross(s);
+            let (u, v) = if denom == 0.0 {
+                (0.5, 0.5)
+            } else {
+                ((q - p).cross(s) / denom, (q - p).cross(r) / denom)
+            };
+            if (-0.5..=1.5).contains(&u) && (-0.5..=1.5).contains(&v) {
+                let ta = a0 + u.clamp(0.0, 1.0) * (a1 - a0);
+                let tb = b0 + v.clamp(0.0, 1.0) * (b1 - b0);
+                if best.map(|(t, _)| ta > t).unwrap_or(true) {
+                    best = Some((ta, tb));
+                }
+            }
+            continue;
+        }
+        let (am, bm) = (0.5 * (a0 + a1), 0.5 * (b0 + b1));
+        stack.push((a0, am, b0, bm, depth + 1));
+        stack.push((a0, am, bm, b1, depth + 1));
+        stack.push((am, a1, b0, bm, depth + 1));
+        stack.push((am, a1, bm, b1, depth + 1));
+    }
+    best
+}
+
+fn path_end(path: &BezPath) -> Point {
+    *path.elements().last().unwrap().points().last().unwrap()
+}
+
+/// The offset of a cubic, with its derivative.
+struct Offset {
+    c: CubicBez,
+    q: crate::QuadBez,
+    d: f64,
+    /// Derivatives smaller than this are treated as zero.
+    eps: f64,
+}
+
+impl Offset {
+    /// The unit tangent at `t`, taking the limit from the right or left
+    /// where the derivative vanishes.
+    fn tangent(&self, t: f64, from_right: bool) -> Vec2 {
+        let d1 = self.q.eval(t).to_vec2();
+        if d1.hypot() > self.eps {
+            return d1.normalize();
+        }
+        let sign = if from_right { 1.0 } else { -1.0 };
+        let d2 = self.q.deriv().eval(t).to_vec2();
+        if d2.hypot() > self.eps {
+            return sign * d2.normalize();
+        }
+        (self.c.p3 - self.c.p0).normalize()
+    }
+
+    fn point(&self, t: f64, from_right: bool) -> Point {
+        let tan = self.tangent(t, from_right);
+        self.c.eval(t) + self.d * Vec2::new(-tan.y, tan.x)
+    }
+
+    /// `1 - d κ`, scaled by the cube of the speed to avoid division.
+    ///
+    /// This is the ratio of the speed of the offset to that of the curve, as
+    /// the offset to the left of a left turn (positive `κ`) is tighter.
+    ///
+    /// The offset has a cusp where this is zero, and runs backwards where it
+    /// is negative.
+    fn cusp_fn(&self, t: f64) -> f64 {
+        let d1 = self.q.eval(t).to_vec2();
+        let d2 = self.q.deriv().eval(t).to_vec2();
+        d1.hypot().powi(3) - self.d * d1.cross(d2)
+    }
+
+    /// The inflection points and offset cusps in (0, 1), sorted.
+    fn critical_params(&self) -> Vec<f64> {
+        let (p0, p1, p2, p3) = (
+            self.c.p0.to_vec2(),
+            self.c.p1.to_vec2(),
+            self.c.p2.to_vec2(),
+            self.c.p3.to_vec2(),
+        );
+        // The derivative is a + b t + c t²; the cross product with the
+        // second derivative, b + 2 c t, is then quadratic.
+        let a = 3.0 * (p1 - p0);
+        let b = 6.0 * (p2 - 2.0 * p1 + p0);
+        let c = 3.0 * (p3 - 3.0 * p2 + 3.0 * p1 - p0);
+        let mut result: Vec<f64> = solve_quadratic(a.cross(b), 2.0 * a.cross(c), b.cross(c))
+            .into_iter()
+            .collect();
+        let mut t_prev = 0.0;
+        let mut f_prev = self.cusp_fn(0.0);
+        for i in 1..=N_CUSP_SCAN {
+            let t = i as f64 / N_CUSP_SCAN as f64;
+            let f = self.cusp_fn(t);
+            if (f < 0.0) != (f_prev < 0.0) {
+                let (mut lo, mut hi) = (t_prev, t);
+                for _ in 0..50 {
+                    let mid = 0.5 * (lo + hi);
+                    if (self.cusp_fn(mid) < 0.0) == (f_prev < 0.0) {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                result.push(0.5 * (lo + hi));
+            }
+            t_prev = t;
+            f_prev = f;
+        }
+        result.retain(|&t| t > 1e-9 && t < 1.0 - 1e-9);
+        result.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        result.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+        result
+    }
+
+    /// Append a fit of the offset between `t0` and `t1` to the path.
+    fn fit(&self, path: &mut BezPath, t0: f64, t1: f64, tolerance: f64, depth: usize) {
+        let q0 = self.point(t0, true);
+        let q3 = self.point(t1, false);
+        // The offset runs against the curve where `1 - d κ` is negative.
+        let sign = self.cusp_fn(0.5 * (t0 + t1)).signum();
+        let u0 = sign * self.tangent(t0, true);
+        let u1 = sign * self.tangent(t1, false);
+        let samples: Vec<Point> = (1..N_SAMPLES)
+            .map(|i| {
+                let t = t0 + (t1 - t0) * (i as f64 / N_SAMPLES as f64);
+                self.point(t, true)
+            })
+            .collect();
+        // Parametrize the samples by chord length.
+        let mut lengths = Vec::with_capacity(samples.len() + 1);
+        let mut total = 0.0;
+        let mut last = q0;
+        for &p in samples.iter().chain(std::iter::once(&q3)) {
+            total += (p - last).hypot();
+            lengths.push(total);
+            last = p;
+        }
+        let us: Vec<f64> = if total > 0.0 {
+            lengths.iter().map(|l| l / total).collect()
+        } else {
+            (1..=N_SAMPLES)
+                .map(|i| i as f64 / N_SAMPLES as f64)
+                .collect()
+        };
+        let cubic = fit_cubic_handles(&samples, &us, None, (q0, u0), (q3, u1));
+        let err = samples
+            .iter()
+            .zip(&us)
+            .map(|(&p, &u)| (cubic.eval(u) - p).hypot())
+            .fold(0.0, f64::max);
+        if err <= tolerance || depth >= MAX_DEPTH {
+            path.curve_to(cubic.p1, cubic.p2, cubic.p3);
+        } else {
+            let tm = 0.5 * (t0 + t1);
+            self.fit(path, t0, tm, tolerance, depth + 1);
+            self.fit(path, tm, t1, tolerance, depth + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BezPath, Circle, CubicBez, ParamCurve, ParamCurveDeriv, ParamCurveNearest, PathEl, Point,
+        Rect, RoundedRect, Shape, Triangle, Vec2,
+    };
+
+    /// The maximum difference between the distance to the curve and `d`,
+    /// over points of the offset path.
+    fn offset_error(c: CubicBez, d: f64, tolerance: f64) -> f64 {
+        let path = c.offset(d, tolerance);
+        let mut err: f64 = 0.0;
+        for seg in path.segments() {
+            for i in 0..=8 {
+                let p = seg.eval(i as f64 / 8.0);
+                let dist = c.nearest(p, 1e-9).1.sqrt();
+                err = err.max((dist - d.abs()).abs());
+            }
+        }
+        err
+    }
+
+    #[test]
+    fn offset_line() {
+        let c = CubicBez::new((0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 0.0));
+        let path = c.offset(1.0, 1e-6);
+        assert!(matches!(path.elements()[0], PathEl::MoveTo(p) if p == Point::new(0.0, 1.0)));
+        assert!(offset_error(c, 1.0, 1e-6) < 1e-9);
+    }
+
+    #[test]
+    fn offset_accuracy() {
+        let c = CubicBez::new((0.0, 0.0), (0.0, 4.0), (4.0, 4.0), (4.0, 0.0));
+        for &d in &[0.5, -0.5, 1.0, -1.0, 2.0] {
+            let err = offset_error(c, d, 1e-4);
+            assert!(err < 1e-4, "distance {}: error {}", d, err);
+        }
+        // An S curve, which is split at its inflection.
+        let s = CubicBez::new((0.0, 0.0), (3.0, 3.0), (-1.0, 3.0), (2.0, 6.0));
+        assert!(offset_error(s, 0.3, 1e-4) < 1e-4);
+    }
+
+    #[test]
+    fn offset_cusps() {
+        // The radius of curvature at the top of this arch is about 1.2, so
+        // an offset of 2 towards the inside has two cusps and a loop.
+        let c = CubicBez::new((0.0, 0.0), (1.0, 3.0), (2.0, 3.0), (3.0, 0.0));
+        let start = c.offset(-2.0, 1e-4);
+        assert!(start.segments().count() >= 3);
+        // The endpoints are offset along the end normals.
+        let n0 = Point::new(0.0, 0.0) + 2.0 * Vec2::new(3.0, -1.0).normalize();
+        assert!((start.segments().next().unwrap().start() - n0).hypot() < 1e-9);
+        // Every point of the result is an offset point, at distance 2 from the
+        // curve, except inside the loop where another part of the curve is
+        // closer. So check the other direction: true offset points are on the
+        // path.
+        let off = |t: f64| {
+            let d = c.deriv().eval(t).to_vec2().normalize();
+            c.eval(t) - 2.0 * Vec2::new(-d.y, d.x)
+        };
+        for i in 0..=20 {
+            let p = off(i as f64 / 20.0);
+            let dist = start
+                .segments()
+                .map(|seg| seg.nearest(p, 1e-9).1)
+                .fold(f64::INFINITY, f64::min)
+                .sqrt();
+            assert!(dist < 1e-4, "{:?} {}", p, dist);
+        }
+    }
+
+    #[test]
+    fn inset_shapes() {
+        let c = Circle::new((1.0, 2.0), 3.0);
+        let grown = c.inset(1.0, 1e-9);
+        assert!((grown.area() - Circle::new((1.0, 2.0), 4.0).area()).abs() < 1e-6);
+        assert_eq!(c.inset(-5.0, 1e-9).area(), 0.0);
+
+        let rr = RoundedRect::new(0.0, 0.0, 10.0, 6.0, 2.0);
+        let shrunk = rr.inset(-1.0, 1e-9);
+        let expected = RoundedRect::new(1.0, 1.0, 9.0, 5.0, 1.0);
+        assert!((shrunk.area() - expected.area()).abs() < 1e-6);
+
+        let rect = Rect::new(0.0, 0.0, 4.0, 2.0);
+        let grown = Shape::inset(&rect, 1.0, 1e-9);
+        let expected = 8.0 + 12.0 + std::f64::consts::PI;
+        assert!((grown.area() - expected).abs() < 1e-6);
+        assert_eq!(Shape::inset(&rect, -1.0, 1e-9).area(), 0.0);
+    }
+
+    #[test]
+    fn inset_general() {
+        // A convex polygon grows by its perimeter times the distance, plus
+        // the area of a circle, in either orientation.
+        let tri = Triangle::new((0.0, 0.0), (4.0, 0.0), (0.0, 3.0));
+        let expected = 6.0 + 12.0 * 0.5 + std::f64::consts::PI * 0.25;
+        let grown = tri.inset(0.5, 1e-9);
+        assert!((grown.area() - expected).abs() < 1e-6);
+        let rev = Triangle::new(tri.p0, tri.p2, tri.p1);
+        assert!((rev.inset(0.5, 1e-9).area() + expected).abs() < 1e-6);
+
+        // An L shape shrinks with sharp corners, and its reflex corner
+        // becomes rounded.
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((4.0, 0.0));
+        path.line_to((4.0, 2.0));
+        path.line_to((2.0, 2.0));
+        path.line_to((2.0, 4.0));
+        path.line_to((0.0, 4.0));
+        let shrunk = path.inset(-0.5, 1e-9);
+        let expected = 3.0 * 1.0 + 1.0 * 2.0 + (1.0 - std::f64::consts::PI / 4.0) * 0.25;
+        assert!((shrunk.area() - expected).abs() < 1e-6);
+        assert!(shrunk.contains(Point::new(0.75, 0.75)));
+        assert!(!shrunk.contains(Point::new(0.25, 0.75)));
+
+        let path = Circle::new((0.0, 0.0), 3.0).to_path(1e-9);
+        let shrunk = path.inset(-1.0, 1e-6);
+        assert!((shrunk.area() - Circle::new((0.0, 0.0), 2.0).area()).abs() < 1e-4);
+    }
+}
@@ -1,6 +1,7 @@
 //! A 2D point.
 
 use std::fmt;
+use std::iter::Sum;
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 use crate::common::FloatExt;
@@ -29,6 +30,18 @@ impl Point {
         Point { x, y }
     }
 
+    /// The point at distance `radius` from `center`, in the direction `angle`.
+    ///
+    /// The angle is in radians, following the convention of
+    /// [`Vec2::from_angle`]: zero is the positive X direction and π/2 the
+    /// positive Y direction.
+    ///
+    /// [`Vec2::from_angle`]: struct.Vec2.html#method.from_angle
+    #[inline]
+    pub fn from_polar(center: Point, radius: f64, angle: f64) -> Point {
+        center + radius * Vec2::from_angle(angle)
+    }
+
     /// Convert this point into a `Vec2`.
     #[inline]
     pub const fn to_vec2(self) -> Vec2 {
@@ -36,6 +49,11 @@ impl Point {
     }
 
     /// Linearly interpolate between two points.
+    ///
+    /// This is exact at `t` equal to 0 and 1, and extrapolates for `t`
+    /// outside `[0, 1]`, as [`Vec2::lerp`] does.
+    ///
+    /// [`Vec2::lerp`]: struct.Vec2.html#method.lerp
     #[inline]
     pub fn lerp(self, other: Point, t: f64) -> Point {
         self.to_vec2().lerp(other.to_vec2(), t).to_point()
@@ -53,6 +71,23 @@ impl Point {
         (self - other).hypot()
     }
 
+    /// Clamp each coordinate into the range given by `min` and `max`.
+    ///
+    /// If a coordinate of `min` is greater than the corresponding coordinate
+    /// of `max`, the result for that coordinate is the one from `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::Point;
+    /// let p = Point::new(-5.0, 12.0).clamp(Point::ZERO, Point::new(10.0, 10.0));
+    /// assert_eq!(p, Point::new(0.0, 10.0));
+    /// ```
+    #[inline]
+    pub fn clamp(self, min: Point, max: Point) -> Point {
+        Point::new(self.x.max(min.x).min(max.x), self.y.max(min.y).min(max.y))
+    }
+
     /// Returns a new `Point`,
     /// with `x` and `y` rounded to the nearest integer.
     ///
@@ -240,6 +275,21 @@ impl Sub<Point> for Point {
     }
 }
 
+impl Sum for Point {
+    /// Sums the points as vectors from the origin.
+    ///
+    /// This returns `Point::ZERO` if there are no points.
+    fn sum<I: Iterator<Item = Point>>(iter: I) -> Point {
+        iter.map(Point::to_vec2).sum::<Vec2>().to_point()
+    }
+}
+
+impl<'a> Sum<&'a Point> for Point {
+    fn sum<I: Iterator<Item = &'a Point>>(iter: I) -> Point {
+        iter.copied().sum()
+    }
+}
+
 impl fmt::Debug for Point {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "({:?}, {:?})", self.x, self.y)
@@ -256,9 +306,35 @@ impl fmt::Display for Point {
     }
 }
 
+#[cfg(feature = "mint")]
+impl From<Point> for mint::Point2<f64> {
+    #[inline]
+    fn from(p: Point) -> mint::Point2<f64> {
+        mint::Point2 { x: p.x, y: p.y }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::Point2<f64>> for Point {
+    #[inline]
+    fn from(p: mint::Point2<f64>) -> Point {
+        Point { x: p.x, y: p.y }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[test]
+    fn polar_round_trip() {
+        let center = Point::new(1.0, -2.0);
+        let p = Point::from_polar(center, 3.0, 2.5);
+        let (r, th) = (p - center).to_polar();
+        assert!((r - 3.0).abs() < 1e-12);
+        assert!((th - 2.5).abs() < 1e-12);
+        assert_eq!(Vec2::new(-0.0, 0.0).to_polar(), (0.0, 0.0));
+    }
+
     #[test]
     fn point_arithmetic() {
         assert_eq!(
@@ -283,6 +359,20 @@ mod tests {
         assert_eq!(p1.distance(p2), 5.);
     }
 
+    #[test]
+    fn sum() {
+        let pts = [
+            Point::new(1.0, 2.0),
+            Point::new(3.0, 4.0),
+            Point::new(-1.0, 0.0),
+        ];
+        assert_eq!(pts.iter().sum::<Point>(), Point::new(3.0, 6.0));
+        assert_eq!(pts[..0].iter().sum::<Point>(), Point::ZERO);
+        let centroid = Vec2::mean(pts.iter().map(|p| p.to_vec2())).unwrap();
+        assert_eq!(centroid.to_point(), Point::new(1.0, 2.0));
+        assert_eq!(Point::midpoint(pts[0], pts[1]), Point::new(2.0, 3.0));
+    }
+
     #[test]
     fn display() {
         let p = Point::new(0.12345, 9.87654);
@@ -292,19 +382,3 @@ mod tests {
         assert_eq!(format!("{:.2}", p), "(0.12, 9.88)");
     }
 }
-
-#[cfg(feature = "mint")]
-impl From<Point> for mint::Point2<f64> {
-    #[inline]
-    fn from(p: Point) -> mint::Point2<f64> {
-        mint::Point2 { x: p.x, y: p.y }
-    }
-}
-
-#[cfg(feature = "mint")]
-impl From<mint::Point2<f64>> for Point {
-    #[inline]
-    fn from(p: mint::Point2<f64>) -> Point {
-        Point { x: p.x, y: p.y }
-    }
-}
@@ -0,0 +1,445 @@
+//! Polygons and their triangulation.
+
+use std::f64::consts::PI;
+use std::iter::FromIterator;
+
+use crate::common::orient2d;
+use crate::{PathEl, Point, Rect, Shape};
+
+/// A closed polygon, given by its vertices.
+///
+/// The last vertex is implicitly connected back to the first.
+#[derive(Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polygon(Vec<Point>);
+
+impl Polygon {
+    /// Create a new polygon with no vertices.
+    pub fn new() -> Polygon {
+        Default::default()
+    }
+
+    /// Create a polygon from a vector of vertices.
+    pub fn from_vec(points: Vec<Point>) -> Polygon {
+        Polygon(points)
+    }
+
+    /// Add a vertex at the end.
+    pub fn push(&mut self, p: Point) {
+        self.0.push(p);
+    }
+
+    /// The vertices of the polygon.
+    pub fn points(&self) -> &[Point] {
+        &self.0
+    }
+
+    /// The convex hull of a set of points.
+    ///
+    /// This is the smallest convex polygon containing all the points,
+    /// computed by Andrew's monotone chain algorithm. Its vertices are
+    /// counterclockwise (positive area in a y-up coordinate system), starting
+    /// from the leftmost (then lowest) point, and vertices on a straight part
+    /// of the hull are dropped. Points with non-finite coordinates are
+    /// ignored. Fewer than three distinct points give a polygon of those
+    /// points, and collinear ones the two ends of their segment.
+    pub fn convex_hull(points: impl IntoIterator<Item = Point>) -> Polygon {
+        let mut pts: Vec<Point> = points
+            .into_iter()
+            .filter(|p| p.x.is_finite() && p.y.is_finite())
+            .collect();
+        pts.sort_by(|a, b| {
+            a.x.partial_cmp(&b.x)
+                .unwrap()
+                .then(a.y.partial_cmp(&b.y).unwrap())
+        });
+        pts.dedup();
+        if pts.len() < 3 {
+            return Polygon(pts);
+        }
+        let turns_left = |a: Point, b: Point, c: Point| orient2d(a, b, c) > 0.0;
+        let mut hull: Vec<Point> = Vec::with_capacity(pts.len() + 1);
+        // The lower chain from left to right, then the upper chain back.
+        for pass in 0..2 {
+            let floor = hull.len();
+            let mut chain = |p: Point| {
+                while hull.len() >= floor + 2
+                    && !turns_left(hull[hull.len() - 2], hull[hull.len() - 1], p)
+                {
+                    hull.pop();
+                }
+                hull.push(p);
+            };
+            if pass == 0 {
+                pts.iter().for_each(|&p| chain(p));
+            } else {
+                pts.iter().rev().for_each(|&p| chain(p));
+            }
+            // The last point of each chain is the first of the other.
+            hull.pop();
+        }
+        Polygon(hull)
+    }
+
+    /// Triangulate the polygon by ear clipping.
+    ///
+    /// The polygon must be simple, that is, its edges must not cross, but it
+    /// may be concave and in either orientation. The triangles are
+    /// counterclockwise (positive area in a y-up coordinate system) and
+    /// together cover the polygon exactly.
+    ///
+    /// Repeated vertices and vertices where the boundary goes straight on,
+    /// or doubles back on itself, are removed without emitting a triangle, so
+    /// no triangle has zero area. If the polygon is not simple, no ear may be
+    /// found at some point; the triangles found up to then are returned.
+    pub fn triangulate(&self) -> Vec<[Point; 3]> {
+        self.triangulate_with_holes(&[])
+    }
+
+    /// Triangulate the region inside the polygon and outside the holes.
+    ///
+    /// Each hole must be a simple polygon strictly inside this one, and the
+    /// holes must not overlap each other; their orientation does not matter.
+    /// Each hole is joined to the outer boundary by a bridge, a pair of
+    /// coincident edges running to a visible vertex, and the resulting
+    /// single boundary is triangulated as in [`triangulate`]. Holes that are
+    /// not inside the polygon are ignored, as are holes with a non-finite
+    /// vertex; if the polygon itself has one, there are no triangles.
+    ///
+    /// [`triangulate`]: #method.triangulate
+    pub fn triangulate_with_holes(&self, holes: &[Polygon]) -> Vec<[Point; 3]> {
+        if !self.0.iter().all(|p| p.x.is_finite() && p.y.is_finite()) {
+            return Vec::new();
+        }
+        let mut contour = clean_ring(&self.0, true);
+        let mut rings: Vec<Vec<Point>> = holes
+            .iter()
+            .filter(|h| h.0.iter().all(|p| p.x.is_finite() && p.y.is_finite()))
+            .map(|h| clean_ring(&h.0, false))
+            .filter(|h| h.len() >= 3)
+            .collect();
+        // Holes are bridged from right to left, so that a bridge never crosses
+        // a hole that is yet to be added.
+        let max_x = |ring: &Vec<Point>| ring.iter().fold(f64::NEG_INFINITY, |m, p| m.max(p.x));
+        rings.sort_by(|a, b| max_x(b).partial_cmp(&max_x(a)).unwrap());
+        for hole in &rings {
+            bridge_hole(&mut contour, hole);
+        }
+        let mut triangles = Vec::new();
+        ear_clip(&contour, &mut triangles);
+        triangles
+    }
+}
+
+impl FromIterator<Point> for Polygon {
+    fn from_iter<T: IntoIterator<Item = Point>>(iter: T) -> Self {
+        Polygon(iter.into_iter().collect())
+    }
+}
+
+impl Shape for Polygon {
+    type PathElementsIter = std::vec::IntoIter<PathEl>;
+
+    fn path_elements(&self, _tolerance: f64) -> Self::PathElementsIter {
+        let mut elements = Vec::with_capacity(self.0.len() + 1);
+        for (i, &p) in self.0.iter().enumerate() {
+            elements.push(if i == 0 {
+                PathEl::MoveTo(p)
+            } else {
+                PathEl::LineTo(p)
+            });
+        }
+        if !self.0.is_empty() {
+            elements.push(PathEl::ClosePath);
+        }
+        elements.into_iter()
+    }
+
+    fn area(&self) -> f64 {
+        ring_area(&self.0)
+    }
+
+    fn perimeter(&self, _accuracy: f64) -> f64 {
+        let n = self.0.len();
+        (0..n)
+            .map(|i| self.0[i].distance(self.0[(i + 1) % n]))
+            .sum()
+    }
+
+    fn winding(&self, pt: Point) -> i32 {
+        self.path_segments(0.0).winding(pt)
+    }
+
+    fn bounding_box(&self) -> Rect {
+        match self.0.first() {
+            Some(&p0) => self
+                .0
+                .iter()
+                .fold(Rect::from_points(p0, p0), |r, &p| r.union_pt(p)),
+            None => Rect::default(),
+        }
+    }
+}
+
+/// The signed area of a closed ring of points.
+fn ring_area(ring: &[Point]) -> f64 {
+    let n = ring.len();
+    (0..n)
+        .map(|i| ring[i].to_vec2().cross(ring[(i + 1) % n].to_vec2()))
+        .sum::<f64>()
+        * 0.5
+}
+
+/// Remove repeated vertices, and orient the ring counterclockwise if `ccw`
+/// is true, clockwise otherwise.
+fn clean_ring(points: &[Point], ccw: bool) -> Vec<Point> {
+    let mut ring: Vec<Point> = Vec::with_capacity(points.len());
+    for &p in points {
+        if ring.last() != Some(&p) {
+            ring.push(p);
+        }
+    }
+    while ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    if (ring_area(&ring) > 0.0) != ccw {
+        ring.reverse();
+    }
+    ring
+}
+
+/// Splice a clockwise hole into a counterclockwise contour.
+///
+/// This finds a vertex of the contour visible from the rightmost vertex of
+/// the hole, following David Eberly, "Triangulation by Ear Clipping". All
+/// the points must be finite.
+fn bridge_hole(contour: &mut Vec<Point>, hole: &[Point]) {
+    let (hi, &m) = hole
+        .iter()
+        .enumerate()
+        .max_by(|a, b| (a.1.x, a.1.y).partial_cmp(&(b.1.x, b.1.y)).unwrap())
+        .unwrap();
+    let n = contour.len();
+    // The nearest intersection of a ray from m towards +x with the contour.
+    let mut best: Option<(f64, usize)> = None;
+    for i in 0..n {
+        let (a, b) = (contour[i], contour[(i + 1) % n]);
+        if (a.y <= m.y) == (b.y <= m.y) {
+            continue;
+        }
+        let x = a.x + (m.y - a.y) * (b.x - a.x) / (b.y - a.y);
+        if x >= m.x && best.map(|(bx, _)| x < bx).unwrap_or(true) {
+            best = Some((x, i));
+        }
+    }
+    let (x, i) = match best {
+        Some(best) => best,
+        None => return,
+    };
+    let hit = Point::new(x, m.y);
+    let (a, b) = (contour[i], contour[(i + 1) % n]);
+    let mut target = if a.x > b.x { i } else { (i + 1) % n };
+    if contour[target] != hit {
+        // Reflex vertices inside the triangle between m, the hit point and
+        // the edge endpoint may block the view; the one at the smallest angle
+        // from the ray is visible.
+        let p = contour[target];
+        let tri = [m, hit, p];
+        let mut best_angle = f64::INFINITY;
+        for j in 0..n {
+            let v = contour[j];
+            if j == target || v == p || !is_reflex(contour, j) || !in_triangle(v, tri) {
+                continue;
+            }
+            let angle = (v - m).atan2().abs();
+            let better = angle < best_angle
+                || (angle == best_angle && (v - m).hypot2() < (contour[target] - m).hypot2());
+            if better {
+                best_angle = angle;
+                target = j;
+            }
+        }
+    }
+    // A vertex may occur twice after earlier bridges; use the occurrence
+    // whose interior angle contains the bridge.
+    let p = contour[target];
+    if let Some(j) = (0..n).find(|&j| contour[j] == p && wedge_contains(contour, j, m)) {
+        target = j;
+    }
+    let mut spliced = Vec::with_capacity(n + hole.len() + 2);
+    spliced.extend_from_slice(&contour[..=target]);
+    spliced.extend(hole[hi..].iter().chain(&hole[..hi]));
+    spliced.push(m);
+    spliced.extend_from_slice(&contour[target..]);
+    *contour = spliced;
+}
+
+fn is_reflex(ring: &[Point], i: usize) -> bool {
+    let n = ring.len();
+    let (a, b, c) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+    orient2d(a, b, c) < 0.0
+}
+
+/// Whether `p` is inside or on a counterclockwise or clockwise triangle.
+fn in_triangle(p: Point, [a, b, c]: [Point; 3]) -> bool {
+    let d0 = orient2d(a, b, p);
+    let d1 = orient2d(b, c, p);
+    let d2 = orient2d(c, a, p);
+    (d0 >= 0.0 && d1 >= 0.0 && d2 >= 0.0) || (d0 <= 0.0 && d1 <= 0.0 && d2 <= 0.0)
+}
+
+/// Whether the direction towards `p` from vertex `i` of a counterclockwise
+/// ring is within the interior angle at that vertex.
+fn wedge_contains(ring: &[Point], i: usize, p: Point) -> bool {
+    let n = ring.len();
+    let v = ring[i];
+    let to_next = (ring[(i + 1) % n] - v).atan2();
+    let ccw_angle = |d: crate::Vec2| (d.atan2() - to_next).rem_euclid(2.0 * PI);
+    ccw_angle(p - v) < ccw_angle(ring[(i + n - 1) % n] - v)
+}
+
+/// Triangulate a counterclockwise ring by ear clipping.
+///
+/// The ring may touch itself at coincident vertices, as produced by bridges.
+fn ear_clip(ring: &[Point], out: &mut Vec<[Point; 3]>) {
+    let mut idx: Vec<usize> = (0..ring.len()).collect();
+    let mut i = 0;
+    let mut misses = 0;
+    while idx.len() >= 3 {
+        let m = idx.len();
+        i %= m;
+        let (ia, ib, ic) = (idx[(i + m - 1) % m], idx[i], idx[(i + 1) % m]);
+        let (a, b, c) = (ring[ia], ring[ib], ring[ic]);
+        let cross = orient2d(a, b, c);
+        if cross == 0.0 {
+            // A straight run, a spike or a repeated point.
+            idx.remove(i);
+            misses = 0;
+            continue;
+        }
+        let is_ear = cross > 0.0
+            && idx.iter().all(|&j| {
+                let p = ring[j];
+                j == ia
+                    || j == ib
+                    || j == ic
+                    || p == a
+                    || p == b
+                    || p == c
+                    || !in_triangle(p, [a, b, c])
+            });
+        if is_ear {
+            out.push([a, b, c]);
+            idx.remove(i);
+            misses = 0;
+        } else {
+            i += 1;
+            misses += 1;
+            if misses > m {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Polygon, Shape};
+
+    #[test]
+    fn convex_hull() {
+        let p = |x, y| Point::new(x, y);
+        let pts = [
+            p(0.0, 0.0),
+            p(1.0, 1.0),
+            p(4.0, 0.0),
+            p(2.0, 0.0),
+            p(4.0, 3.0),
+            p(2.0, 1.0),
+            p(0.0, 3.0),
+            p(0.0, 3.0),
+            p(f64::NAN, 2.0),
+        ];
+        let hull = Polygon::convex_hull(pts.iter().copied());
+        assert_eq!(
+            hull.points(),
+            &[p(0.0, 0.0), p(4.0, 0.0), p(4.0, 3.0), p(0.0, 3.0)]
+        );
+        assert_eq!(hull.area(), 12.0);
+
+        let line = Polygon::convex_hull(vec![p(0.0, 0.0), p(2.0, 2.0), p(1.0, 1.0)]);
+        assert_eq!(line.points(), &[p(0.0, 0.0), p(2.0, 2.0)]);
+        assert!(Polygon::convex_hull(Vec::new()).points().is_empty());
+    }
+
+    fn total_area(tris: &[[Point; 3]]) -> f64 {
+        tris.iter()
+            .map(|t| {
+                let a = 0.5 * (t[1] - t[0]).cross(t[2] - t[0]);
+                assert!(a > 0.0);
+                a
+            })
+            .sum()
+    }
+
+    #[test]
+    fn triangulate_concave() {
+        // An arrow, clockwise, with a reflex vertex and collinear vertices.
+        let poly: Polygon = [
+            (0.0, 0.0),
+            (2.0, 2.0),
+            (4.0, 0.0),
+            (4.0, 2.0),
+            (4.0, 4.0),
+            (2.0, 4.0),
+            (0.0, 4.0),
+            (0.0, 4.0),
+        ]
+        .iter()
+        .rev()
+        .map(|&p| Point::from(p))
+        .collect();
+        let tris = poly.triangulate();
+        assert!(tris.len() <= 5);
+        assert!((total_area(&tris) + poly.area()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn triangulate_holes() {
+        let square = |x0: f64, y0: f64, s: f64| {
+            Polygon::from_vec(vec![
+                Point::new(x0, y0),
+                Point::new(x0 + s, y0),
+                Point::new(x0 + s, y0 + s),
+                Point::new(x0, y0 + s),
+            ])
+        };
+        let outer = square(0.0, 0.0, 10.0);
+        let holes = [
+            square(1.0, 1.0, 3.0),
+            square(6.0, 2.0, 2.0),
+            square(2.0, 6.0, 3.0),
+        ];
+        let tris = outer.triangulate_with_holes(&holes);
+        assert!((total_area(&tris) - (100.0 - 9.0 - 4.0 - 9.0)).abs() < 1e-9);
+        // No triangle covers a point inside a hole.
+        for h in &holes {
+            let c = h.bounding_box().center();
+            for t in &tris {
+                let tri = crate::Triangle::new(t[0], t[1], t[2]);
+                assert_eq!(tri.winding(c), 0);
+            }
+        }
+
+        // Non-finite holes are skipped rather than panicking the sort, and a
+        // non-finite outline gives nothing.
+        let nan_hole = Polygon::from_vec(vec![
+            Point::new(f64::NAN, 5.0),
+            Point::new(6.0, 5.0),
+            Point::new(6.0, 6.0),
+        ]);
+        let tris = outer.triangulate_with_holes(&[square(1.0, 1.0, 3.0), nan_hole.clone()]);
+        assert!((total_area(&tris) - (100.0 - 9.0)).abs() < 1e-9);
+        assert!(nan_hole.triangulate().is_empty());
+    }
+}
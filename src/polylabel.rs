@@ -0,0 +1,121 @@
+//! The largest circle inside a shape.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::{BezPath, Circle, Point, Shape, Vec2};
+
+/// The largest circle inside a path, found by quadtree subdivision.
+///
+/// This is the "polylabel" algorithm: the bounding box is covered by square
+/// cells, and the cell that could contain the farthest point from the outside
+/// is repeatedly split into four, until no cell could improve on the best
+/// center found by more than `precision`.
+pub(crate) fn pole_of_inaccessibility(path: &BezPath, precision: f64) -> Circle {
+    let bbox = path.bounding_box().abs();
+    if path.is_empty() || !(bbox.width().is_finite() && bbox.height().is_finite()) {
+        return Circle::new(bbox.center(), 0.0);
+    }
+    let size = bbox.width().min(bbox.height());
+    if size <= 0.0 {
+        return Circle::new(bbox.center(), 0.0);
+    }
+    // Subdividing below this would not terminate for a zero precision.
+    let precision = precision.max(size * 1e-9);
+    let accuracy = precision * 0.1;
+    let cell = |center: Point, half: f64| {
+        let dist = -path.signed_distance(center, accuracy);
+        PoleCell {
+            center,
+            half,
+            dist,
+            max: dist + half * std::f64::consts::SQRT_2,
+        }
+    };
+    let mut queue = BinaryHeap::new();
+    let half = 0.5 * size;
+    let mut y = bbox.y0;
+    while y < bbox.y1 {
+        let mut x = bbox.x0;
+        while x < bbox.x1 {
+            queue.push(cell(Point::new(x + half, y + half), half));
+            x += size;
+        }
+        y += size;
+    }
+    let mut best = cell(bbox.center(), 0.0);
+    while let Some(c) = queue.pop() {
+        if c.dist > best.dist {
+            best = c;
+        }
+        if c.max - best.dist <= precision {
+            // No remaining cell can do better.
+            break;
+        }
+        let h = 0.5 * c.half;
+        for &(dx, dy) in &[(-h, -h), (h, -h), (-h, h), (h, h)] {
+            queue.push(cell(c.center + Vec2::new(dx, dy), h));
+        }
+    }
+    Circle::new(best.center, best.dist.max(0.0))
+}
+
+/// A square cell in the search for the pole of inaccessibility.
+#[derive(Clone, Copy)]
+struct PoleCell {
+    center: Point,
+    half: f64,
+    /// The distance from the center to the outside, negative if outside.
+    dist: f64,
+    /// The greatest such distance of any point in the cell.
+    max: f64,
+}
+
+impl PartialEq for PoleCell {
+    fn eq(&self, other: &PoleCell) -> bool {
+        self.max == other.max
+    }
+}
+
+impl Eq for PoleCell {}
+
+impl PartialOrd for PoleCell {
+    fn partial_cmp(&self, other: &PoleCell) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PoleCell {
+    fn cmp(&self, other: &PoleCell) -> Ordering {
+        self.max.partial_cmp(&other.max).unwrap_or(Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, Circle, Point, Rect, Shape};
+
+    #[test]
+    fn pole_with_hole() {
+        let rect = Rect::new(0.0, 0.0, 20.0, 10.0);
+        let pole = rect.pole_of_inaccessibility(1e-3);
+        assert!((pole.radius - 5.0).abs() < 1e-3);
+        assert!((pole.center.y - 5.0).abs() < 0.1);
+
+        // A hole on the left pushes the circle to the right.
+        let mut path = rect.to_path(1e-9);
+        path.move_to((2.0, 2.0));
+        path.line_to((2.0, 8.0));
+        path.line_to((8.0, 8.0));
+        path.line_to((8.0, 2.0));
+        path.close_path();
+        let pole = path.pole_of_inaccessibility(1e-3);
+        assert!((pole.radius - 5.0).abs() < 1e-3);
+        assert!((pole.center - Point::new(15.0, 5.0)).hypot() < 1e-2);
+
+        let circle = Circle::new((1.0, 2.0), 3.0).pole_of_inaccessibility(1e-4);
+        assert!((circle.radius - 3.0).abs() < 1e-3);
+        assert!((circle.center - Point::new(1.0, 2.0)).hypot() < 1e-3);
+        assert_eq!(BezPath::new().pole_of_inaccessibility(1e-3).radius, 0.0);
+    }
+}
@@ -0,0 +1,106 @@
+//! PostScript path representation.
+
+use std::fmt::Write;
+
+use crate::{BezPath, PathEl, Point, QuadBez};
+
+impl BezPath {
+    /// Convert the path to PostScript path construction operators.
+    ///
+    /// Each element becomes one line: `x y moveto`, `x y lineto`,
+    /// `x1 y1 x2 y2 x3 y3 curveto` or `closepath`. PostScript has no
+    /// quadratic operator, so quadratic segments are raised to the
+    /// equivalent cubics. Coordinates are written with at most `precision`
+    /// digits after the decimal point, dropping trailing zeros.
+    ///
+    /// Coordinates are written unchanged. PostScript and PDF user space has
+    /// the y axis pointing up, unlike SVG, so a path in y-down coordinates
+    /// will appear flipped unless it is first transformed, for instance by
+    /// [`with_flipped_y`] followed by a translation by the page height.
+    ///
+    /// ```
+    /// use kurbo::BezPath;
+    ///
+    /// let mut path = BezPath::new();
+    /// path.move_to((0.0, 0.0));
+    /// path.quad_to((3.0, 3.0), (6.0, 0.0));
+    /// path.close_path();
+    /// assert_eq!(
+    ///     path.to_postscript(2),
+    ///     "0 0 moveto\n2 2 4 2 6 0 curveto\nclosepath\n"
+    /// );
+    /// ```
+    ///
+    /// [`with_flipped_y`]: #method.with_flipped_y
+    pub fn to_postscript(&self, precision: usize) -> String {
+        let mut out = String::new();
+        let mut start = Point::ORIGIN;
+        let mut last = Point::ORIGIN;
+        for el in self.elements() {
+            let points: Vec<Point> = match *el {
+                PathEl::MoveTo(p) => {
+                    start = p;
+                    vec![p]
+                }
+                PathEl::LineTo(p) => vec![p],
+                PathEl::QuadTo(p1, p2) => {
+                    let c = QuadBez::new(last, p1, p2).raise();
+                    vec![c.p1, c.p2, c.p3]
+                }
+                PathEl::CurveTo(p1, p2, p3) => vec![p1, p2, p3],
+                PathEl::ClosePath => vec![],
+            };
+            for p in &points {
+                write_number(&mut out, p.x, precision);
+                out.push(' ');
+                write_number(&mut out, p.y, precision);
+                out.push(' ');
+            }
+            out.push_str(match el {
+                PathEl::MoveTo(_) => "moveto",
+                PathEl::LineTo(_) => "lineto",
+                PathEl::QuadTo(..) | PathEl::CurveTo(..) => "curveto",
+                PathEl::ClosePath => "closepath",
+            });
+            out.push('\n');
+            last = points.last().copied().unwrap_or(start);
+        }
+        out
+    }
+}
+
+/// Write a number with at most `precision` fractional digits.
+fn write_number(out: &mut String, x: f64, precision: usize) {
+    let mut s = String::new();
+    write!(s, "{:.*}", precision, x).unwrap();
+    if s.contains('.') {
+        let trimmed = s.trim_end_matches('0').trim_end_matches('.').len();
+        s.truncate(trimmed);
+    }
+    if s == "-0" {
+        s = "0".to_string();
+    }
+    out.push_str(&s);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BezPath;
+
+    #[test]
+    fn postscript_operators() {
+        let mut path = BezPath::new();
+        path.move_to((1.0, 2.0));
+        path.line_to((1.0 / 3.0, -0.0001));
+        path.curve_to((1.0, 1.0), (2.5, 1.0), (3.0, 0.0));
+        path.close_path();
+        // A quadratic after a close starts at the subpath start.
+        path.quad_to((4.0, 2.0), (7.0, 2.0));
+        assert_eq!(
+            path.to_postscript(3),
+            "1 2 moveto\n0.333 0 lineto\n1 1 2.5 1 3 0 curveto\nclosepath\n\
+             3 2 5 2 7 2 curveto\n"
+        );
+        assert_eq!(path.to_postscript(0).lines().nth(1), Some("0 0 lineto"));
+    }
+}
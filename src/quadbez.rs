@@ -45,6 +45,71 @@ impl QuadBez {
         )
     }
 
+    /// Evaluate the curve at many parameters.
+    ///
+    /// The power basis coefficients are computed once and each point is then
+    /// evaluated with Horner's method, which is faster than repeated calls to
+    /// [`eval`]. Results match `eval` up to floating point rounding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ts` and `out` have different lengths.
+    ///
+    /// [`eval`]: trait.ParamCurve.html#tymethod.eval
+    pub fn eval_many(&self, ts: &[f64], out: &mut [Point]) {
+        assert_eq!(ts.len(), out.len(), "eval_many: length mismatch");
+        let p0 = self.p0.to_vec2();
+        let b = 2.0 * (self.p1 - self.p0);
+        let a = self.p2.to_vec2() - 2.0 * self.p1.to_vec2() + p0;
+        for (&t, p) in ts.iter().zip(out.iter_mut()) {
+            *p = ((a * t + b) * t + p0).to_point();
+        }
+    }
+
+    /// The number of line segments produced when flattening this curve.
+    ///
+    /// This uses the same estimate as [`flatten`], so the result matches the
+    /// number of `LineTo` elements it emits for this segment. It can be used to
+    /// pre-size buffers.
+    ///
+    /// [`flatten`]: fn.flatten.html
+    pub fn flatten_segment_count(&self, tolerance: f64) -> usize {
+        let sqrt_tol = tolerance.sqrt();
+        let params = self.estimate_subdiv(sqrt_tol);
+        params.segment_count(sqrt_tol)
+    }
+
+    /// Find the point on the curve farthest from the given point.
+    ///
+    /// Returns the parameter and the square of the distance. The candidates
+    /// are the endpoints and the stationary points of the distance, which
+    /// are the roots of the same cubic solved by [`nearest`].
+    ///
+    /// [`nearest`]: trait.ParamCurveNearest.html#tymethod.nearest
+    pub fn farthest(&self, p: Point) -> (f64, f64) {
+        let d0 = self.p1 - self.p0;
+        let d1 = self.p0.to_vec2() + self.p2.to_vec2() - 2.0 * self.p1.to_vec2();
+        let d = self.p0 - p;
+        let c0 = d.dot(d0);
+        let c1 = 2.0 * d0.hypot2() + d.dot(d1);
+        let c2 = 3.0 * d1.dot(d0);
+        let c3 = d1.hypot2();
+        let mut best = (0.0, (self.p0 - p).hypot2());
+        let mut consider = |t: f64, q: Point| {
+            let r = (q - p).hypot2();
+            if r > best.1 {
+                best = (t, r);
+            }
+        };
+        consider(1.0, self.p2);
+        for &t in &solve_cubic(c0, c1, c2, c3) {
+            if (0.0..=1.0).contains(&t) {
+                consider(t, self.eval(t));
+            }
+        }
+        best
+    }
+
     /// Estimate the number of subdivisions for flattening.
     pub(crate) fn estimate_subdiv(&self, sqrt_tol: f64) -> FlattenParams {
         // Determine transformation to $y = x^2$ parabola.
@@ -101,6 +166,13 @@ pub(crate) struct FlattenParams {
     pub(crate) val: f64,
 }
 
+impl FlattenParams {
+    /// The number of line segments, given the `sqrt_tol` used for the estimate.
+    pub(crate) fn segment_count(&self, sqrt_tol: f64) -> usize {
+        ((0.5 * self.val / sqrt_tol).ceil() as usize).max(1)
+    }
+}
+
 /// An approximation to $\int (1 + 4x^2) ^ -0.25 dx$
 ///
 /// This is used for flattening curves.
@@ -168,9 +240,12 @@ impl ParamCurveDeriv for QuadBez {
 impl ParamCurveArclen for QuadBez {
     /// Arclength of a quadratic Bézier segment.
     ///
-    /// This computation is based on an analytical formula. Since that formula suffers
-    /// from numerical instability when the curve is very close to a straight line, we
-    /// detect that case and fall back to Legendre-Gauss quadrature.
+    /// This computation is based on an analytical formula. That formula has a
+    /// removable singularity when the curve degenerates to a straight line (the
+    /// second derivative vanishes) and suffers from numerical instability close to
+    /// it, so we detect that case and fall back to Legendre-Gauss quadrature, which
+    /// is exact for straight lines. This also covers the fully degenerate case
+    /// where all control points coincide.
     ///
     /// Accuracy should be better than 1e-13 over the entire range.
     ///
@@ -181,7 +256,7 @@ impl ParamCurveArclen for QuadBez {
         let a = d2.hypot2();
         let d1 = self.p1 - self.p0;
         let c = d1.hypot2();
-        if a < 5e-4 * c {
+        if a <= 5e-4 * c {
             // This case happens for nearly straight Béziers.
             //
             // Calculate arclength using Legendre-Gauss quadrature using formula from Behdad
@@ -230,27 +305,21 @@ impl ParamCurveArea for QuadBez {
 
 impl ParamCurveNearest for QuadBez {
     /// Find nearest point, using analytical algorithm based on cubic root finding.
+    ///
+    /// The derivative of the squared distance is a cubic in `t`, whose real
+    /// roots in `[0, 1]` are found in closed form and polished by a Newton
+    /// step; the nearest of those and the two endpoints is returned. The
+    /// endpoints are always candidates, so a root lost to rounding, as can
+    /// happen for a point on the axis near the apex, where two roots nearly
+    /// coincide, cannot make the result worse than the nearer end.
     fn nearest(&self, p: Point, _accuracy: f64) -> (f64, f64) {
-        fn eval_t(p: Point, t_best: &mut f64, r_best: &mut Option<f64>, t: f64, p0: Point) {
+        fn eval_t(p: Point, t_best: &mut f64, r_best: &mut f64, t: f64, p0: Point) {
             let r = (p0 - p).hypot2();
-            if r_best.map(|r_best| r < r_best).unwrap_or(true) {
-                *r_best = Some(r);
+            if r < *r_best {
+                *r_best = r;
                 *t_best = t;
             }
         }
-        fn try_t(
-            q: &QuadBez,
-            p: Point,
-            t_best: &mut f64,
-            r_best: &mut Option<f64>,
-            t: f64,
-        ) -> bool {
-            if t < 0.0 || t > 1.0 {
-                return true;
-            }
-            eval_t(p, t_best, r_best, t, q.eval(t));
-            false
-        }
         let d0 = self.p1 - self.p0;
         let d1 = self.p0.to_vec2() + self.p2.to_vec2() - 2.0 * self.p1.to_vec2();
         let d = self.p0 - p;
@@ -258,18 +327,19 @@ impl ParamCurveNearest for QuadBez {
         let c1 = 2.0 * d0.hypot2() + d.dot(d1);
         let c2 = 3.0 * d1.dot(d0);
         let c3 = d1.hypot2();
-        let roots = solve_cubic(c0, c1, c2, c3);
-        let mut r_best = None;
         let mut t_best = 0.0;
-        let mut need_ends = false;
-        for &t in &roots {
-            need_ends |= try_t(self, p, &mut t_best, &mut r_best, t);
-        }
-        if need_ends {
-            eval_t(p, &mut t_best, &mut r_best, 0.0, self.p0);
-            eval_t(p, &mut t_best, &mut r_best, 1.0, self.p2);
+        let mut r_best = (self.p0 - p).hypot2();
+        eval_t(p, &mut t_best, &mut r_best, 1.0, self.p2);
+        for &t in &solve_cubic(c0, c1, c2, c3) {
+            let f = c0 + t * (c1 + t * (c2 + t * c3));
+            let df = c1 + t * (2.0 * c2 + t * 3.0 * c3);
+            let polished = t - f / df;
+            let t = if polished.is_finite() { polished } else { t };
+            if (0.0..=1.0).contains(&t) {
+                eval_t(p, &mut t_best, &mut r_best, t, self.eval(t));
+            }
         }
-        (t_best, r_best.unwrap())
+        (t_best, r_best)
     }
 }
 
@@ -315,9 +385,10 @@ impl Mul<QuadBez> for Affine {
 
 #[cfg(test)]
 mod tests {
+    use crate::common::GAUSS_LEGENDRE_COEFFS_24;
     use crate::{
         Affine, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveDeriv, ParamCurveExtrema,
-        ParamCurveNearest, Point, QuadBez,
+        ParamCurveNearest, Point, QuadBez, Vec2,
     };
 
     fn assert_near(p0: Point, p1: Point, epsilon: f64) {
@@ -341,6 +412,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn quadbez_eval_many() {
+        let q = QuadBez::new((0.0, 0.0), (2.0, 3.0), (4.0, -1.0));
+        let ts: Vec<f64> = (0..=20).map(|i| i as f64 / 20.0).collect();
+        let mut out = vec![Point::ORIGIN; ts.len()];
+        q.eval_many(&ts, &mut out);
+        for (&t, &p) in ts.iter().zip(&out) {
+            assert_near(p, q.eval(t), 1e-12);
+        }
+    }
+
     #[test]
     fn quadbez_arclen() {
         let q = QuadBez::new((0.0, 0.0), (0.0, 0.5), (1.0, 1.0));
@@ -353,6 +435,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn quadbez_arclen_vs_quadrature() {
+        // Compare the closed form against high order quadrature on subsegments,
+        // including curves approaching the straight line singularity.
+        for i in 0..12 {
+            let h = 0.5f64.powi(i * 4);
+            let q = QuadBez::new((0.0, 0.0), (0.3, h), (1.0, 0.0));
+            let n = 8;
+            let quadrature: f64 = (0..n)
+                .map(|j| {
+                    let t0 = j as f64 / n as f64;
+                    let t1 = (j + 1) as f64 / n as f64;
+                    q.subsegment(t0..t1).gauss_arclen(GAUSS_LEGENDRE_COEFFS_24)
+                })
+                .sum();
+            let est = q.arclen(1e-12);
+            assert!(
+                (est - quadrature).abs() < 1e-12,
+                "{} != {}",
+                est,
+                quadrature
+            );
+        }
+    }
+
+    #[test]
+    fn quadbez_arclen_degenerate() {
+        let q = QuadBez::new((1.0, 2.0), (1.0, 2.0), (1.0, 2.0));
+        assert!(q.arclen(1e-9).abs() < 1e-13);
+        let q = QuadBez::new((0.0, 0.0), (1.0, 1.0), (2.0, 2.0));
+        assert!((q.arclen(1e-9) - 8.0f64.sqrt()).abs() < 1e-13);
+    }
+
     #[test]
     fn quadbez_arclen_pathological() {
         let q = QuadBez::new((-1.0, 0.0), (1.03, 0.0), (1.0, 0.0));
@@ -471,4 +586,38 @@ mod tests {
         assert!((extrema[0] - 1.0 / 3.0).abs() < 1e-6);
         assert!((extrema[1] - 2.0 / 3.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn quadbez_nearest_vs_iterative() {
+        // Compare against Newton iteration on the raised cubic, on a range of
+        // quads and points, including points on the axis near the apex.
+        let quads = [
+            QuadBez::new((-1.0, 1.0), (0.0, -1.0), (1.0, 1.0)),
+            QuadBez::new((0.0, 0.0), (5.0, 0.1), (1.0, 2.0)),
+            QuadBez::new((0.0, 0.0), (1.0, 1.0), (2.0, 2.0)),
+            QuadBez::new((3.0, 1.0), (3.0, 1.0), (3.0, 1.0)),
+        ];
+        for q in &quads {
+            let c = q.raise();
+            let mut points = vec![q.eval(0.5), q.eval(0.5) + Vec2::new(0.0, 1e-7)];
+            for i in -4..=4 {
+                for j in -4..=4 {
+                    points.push(Point::new(0.5 * i as f64, 0.5 * j as f64 + 0.02));
+                }
+            }
+            for &pt in &points {
+                let (t, r) = q.nearest(pt, 1e-9);
+                assert!((0.0..=1.0).contains(&t));
+                assert!((r - (q.eval(t) - pt).hypot2()).abs() < 1e-12);
+                let (_, r_iter) = c.nearest_robust(pt, 1e-12);
+                assert!(r <= r_iter + 1e-12, "{:?} {:?}: {} {}", q, pt, r, r_iter);
+            }
+        }
+        // Below the focus of y = x^2, the nearest point of the curve to a
+        // point on its axis is the apex.
+        let q = quads[0];
+        let (t, r) = q.nearest(Point::new(0.0, 0.1), 1e-9);
+        assert_eq!(t, 0.5);
+        assert!((r - 0.01).abs() < 1e-15);
+    }
 }
@@ -0,0 +1,162 @@
+//! Snapping paths to a grid.
+
+use crate::{BezPath, PathEl, Point};
+
+impl BezPath {
+    /// Snap every point of the path, control points included, to the
+    /// nearest multiple of `grid` in x and in y.
+    ///
+    /// The elements keep their kinds and order. Each point moves by at most
+    /// `grid / 2` in x and in y, and since every point of a segment is a
+    /// weighted average of its control points, so does every point of the
+    /// curves. Their shape can still change noticeably where control points
+    /// are close to the end points, at the scale of the grid: a short handle
+    /// can be snapped onto its end point, or turned, changing the tangent
+    /// direction there. [`quantize_anchors`] keeps the handles instead.
+    ///
+    /// The path is returned unchanged if `grid` is not positive and finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{BezPath, PathEl, Point};
+    /// let mut path = BezPath::new();
+    /// path.move_to((0.3, 1.9));
+    /// path.quad_to((2.26, 0.74), (4.0, 4.1));
+    /// let snapped = path.quantize(0.5);
+    /// assert_eq!(
+    ///     snapped.elements(),
+    ///     &[
+    ///         PathEl::MoveTo(Point::new(0.5, 2.0)),
+    ///         PathEl::QuadTo(Point::new(2.5, 0.5), Point::new(4.0, 4.0)),
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// [`quantize_anchors`]: #method.quantize_anchors
+    pub fn quantize(&self, grid: f64) -> BezPath {
+        if !(grid > 0.0 && grid.is_finite()) {
+            return self.clone();
+        }
+        self.iter()
+            .map(|el| el.map_points(|p| snap_to_grid(p, grid)))
+            .collect()
+    }
+
+    /// Snap the on-curve points of the path to the nearest multiple of
+    /// `grid` in x and in y, moving the control points with them.
+    ///
+    /// The end points of the segments are snapped as by [`quantize`]. Each
+    /// control point of a cubic moves by the same amount as the end point it
+    /// is attached to, so the handles keep their lengths and directions, and
+    /// the control point of a quadratic moves by the average of the moves of
+    /// its two end points. The control points are not on the grid, but the
+    /// tangents at the ends of cubics are preserved, and every point of the
+    /// curves again moves by at most `grid / 2` in x and in y.
+    ///
+    /// The path is returned unchanged if `grid` is not positive and finite.
+    ///
+    /// [`quantize`]: #method.quantize
+    pub fn quantize_anchors(&self, grid: f64) -> BezPath {
+        if !(grid > 0.0 && grid.is_finite()) {
+            return self.clone();
+        }
+        let mut result = BezPath::new();
+        // The original and snapped positions of the current point and of
+        // the start of the subpath.
+        let mut last = (Point::ORIGIN, Point::ORIGIN);
+        let mut start = last;
+        for el in self.iter() {
+            let snap = |p: Point| (p, snap_to_grid(p, grid));
+            let el = match el {
+                PathEl::MoveTo(p) => {
+                    start = snap(p);
+                    last = start;
+                    PathEl::MoveTo(start.1)
+                }
+                PathEl::LineTo(p) => {
+                    last = snap(p);
+                    PathEl::LineTo(last.1)
+                }
+                PathEl::QuadTo(p1, p2) => {
+                    let (d0, end) = (last.1 - last.0, snap(p2));
+                    let d1 = end.1 - end.0;
+                    last = end;
+                    PathEl::QuadTo(p1 + 0.5 * (d0 + d1), end.1)
+                }
+                PathEl::CurveTo(p1, p2, p3) => {
+                    let (d0, end) = (last.1 - last.0, snap(p3));
+                    last = end;
+                    PathEl::CurveTo(p1 + d0, p2 + (end.1 - end.0), end.1)
+                }
+                PathEl::ClosePath => {
+                    last = start;
+                    PathEl::ClosePath
+                }
+            };
+            result.push(el);
+        }
+        result
+    }
+}
+
+/// The nearest point to `p` with coordinates that are multiples of `grid`.
+fn snap_to_grid(p: Point, grid: f64) -> Point {
+    Point::new((p.x / grid).round() * grid, (p.y / grid).round() * grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, ParamCurve, PathEl, Point};
+
+    #[test]
+    fn test_quantize() {
+        let mut path = BezPath::new();
+        path.move_to((0.26, 0.74));
+        path.curve_to((1.1, 3.05), (2.9, 2.95), (4.13, -0.2));
+        path.quad_to((5.0, 1.4), (6.02, 0.0));
+        path.close_path();
+        path.line_to((1.3, 1.3));
+        let grid = 0.25;
+        let on_grid = |x: f64| ((x / grid).round() * grid - x).abs() < 1e-12;
+        for snapped in &[path.quantize(grid), path.quantize_anchors(grid)] {
+            assert_eq!(snapped.elements().len(), path.elements().len());
+            for (a, b) in snapped.iter().zip(path.iter()) {
+                assert_eq!(std::mem::discriminant(&a), std::mem::discriminant(&b));
+                let (pa, pb) = (a.points(), b.points());
+                let (end_a, end_b) = (pa.last(), pb.last());
+                if let (Some(end_a), Some(end_b)) = (end_a, end_b) {
+                    assert!(on_grid(end_a.x) && on_grid(end_a.y));
+                    assert!((end_a.x - end_b.x).abs() <= 0.5 * grid);
+                    assert!((end_a.y - end_b.y).abs() <= 0.5 * grid);
+                }
+            }
+            // Every point of the curves moves by at most half the grid in
+            // each direction.
+            for (a, b) in snapped.segments().zip(path.segments()) {
+                for i in 0..=10 {
+                    let t = i as f64 / 10.0;
+                    let d = a.eval(t) - b.eval(t);
+                    assert!(d.x.abs() <= 0.5 * grid + 1e-12 && d.y.abs() <= 0.5 * grid + 1e-12);
+                }
+            }
+        }
+        let all = path.quantize(grid);
+        assert!(all
+            .iter()
+            .flat_map(|el| el.points())
+            .all(|p| on_grid(p.x) && on_grid(p.y)));
+        // Snapping only the anchors keeps the cubic handles.
+        let anchors = path.quantize_anchors(grid);
+        if let (PathEl::CurveTo(a1, a2, a3), PathEl::CurveTo(b1, b2, b3)) =
+            (anchors.elements()[1], path.elements()[1])
+        {
+            assert_eq!(a1 - Point::new(0.25, 0.75), b1 - Point::new(0.26, 0.74));
+            assert!(((a2 - a3) - (b2 - b3)).hypot() < 1e-12);
+        } else {
+            panic!();
+        }
+        assert_eq!(path.quantize(0.0), path);
+        assert_eq!(path.quantize_anchors(f64::NAN), path);
+    }
+}
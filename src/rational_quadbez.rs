@@ -0,0 +1,358 @@
+//! Rational quadratic Bézier segments, representing conic sections exactly.
+
+use std::ops::{Mul, Range};
+
+use crate::{Affine, Arc, CubicBez, ParamCurve, Point, QuadBez, Vec2};
+
+/// A rational quadratic Bézier segment.
+///
+/// The middle control point has a weight, and the end points a weight of
+/// one. Every such segment is an arc of a conic section: an ellipse for a
+/// weight below one, a parabola (an ordinary [`QuadBez`]) for a weight of
+/// exactly one, and a hyperbola above one. In particular, arcs of circles
+/// and ellipses are represented exactly, see [`from_arc`]. The weight is
+/// expected to be positive.
+///
+/// [`QuadBez`]: struct.QuadBez.html
+/// [`from_arc`]: #method.from_arc
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(missing_docs)]
+pub struct RationalQuadBez {
+    pub p0: Point,
+    pub p1: Point,
+    pub p2: Point,
+    pub weight: f64,
+}
+
+/// The relative tolerance used to recognize a circular arc.
+const CIRCLE_EPSILON: f64 = 1e-9;
+
+/// The maximum depth of subdivision when converting to cubics.
+const TO_CUBICS_MAX_DEPTH: usize = 16;
+
+impl RationalQuadBez {
+    /// Create a new rational quadratic Bézier segment.
+    #[inline]
+    pub fn new<V: Into<Point>>(p0: V, p1: V, p2: V, weight: f64) -> RationalQuadBez {
+        RationalQuadBez {
+            p0: p0.into(),
+            p1: p1.into(),
+            p2: p2.into(),
+            weight,
+        }
+    }
+
+    /// The segment as an ordinary quadratic Bézier, if its weight is one.
+    #[inline]
+    pub fn as_quad(&self) -> Option<QuadBez> {
+        if self.weight == 1.0 {
+            Some(QuadBez::new(self.p0, self.p1, self.p2))
+        } else {
+            None
+        }
+    }
+
+    /// The segments exactly representing an elliptical arc.
+    ///
+    /// The arc is split into the fewest pieces of equal sweep of at most a
+    /// quarter turn each. A piece of a circle sweeping by `2θ` has its middle
+    /// control point where the tangents at its ends meet, with a weight of
+    /// `cos θ`, so a quarter circle has a weight of `cos 45°`. Those of an
+    /// ellipse are the images of the circular pieces under the affine map
+    /// taking the circle to the ellipse, with the same weights.
+    pub fn from_arc(arc: &Arc) -> Vec<RationalQuadBez> {
+        let n = (arc.sweep_angle.abs() / std::f64::consts::FRAC_PI_2)
+            .ceil()
+            .max(1.0);
+        let half = 0.5 * arc.sweep_angle / n;
+        let weight = half.cos();
+        let affine = Affine::translate(arc.center.to_vec2())
+            * Affine::rotate(arc.x_rotation)
+            * Affine::scale_non_uniform(arc.radii.x, arc.radii.y);
+        let unit = |angle: f64| Point::new(angle.cos(), angle.sin());
+        (0..n as usize)
+            .map(|i| {
+                let a0 = arc.start_angle + 2.0 * half * i as f64;
+                let p1 = unit(a0 + half).to_vec2() / weight;
+                affine
+                    * RationalQuadBez::new(unit(a0), p1.to_point(), unit(a0 + 2.0 * half), weight)
+            })
+            .collect()
+    }
+
+    /// The circular arc this segment represents, if it is one.
+    ///
+    /// This is the case when the control polygon is isosceles, with legs of
+    /// length `l` and a chord of length `2c`, and the weight is `c / l`.
+    /// Both conditions are checked with a small relative tolerance, so that
+    /// the segments from [`from_arc`] for a circle convert back. Returns
+    /// `None` for any other segment, including one with all its control
+    /// points in a line.
+    ///
+    /// [`from_arc`]: #method.from_arc
+    pub fn to_arc(&self) -> Option<Arc> {
+        let (l0, l1) = (self.p1 - self.p0, self.p2 - self.p1);
+        let (len0, len1) = (l0.hypot(), l1.hypot());
+        let chord = self.p2 - self.p0;
+        let c = 0.5 * chord.hypot();
+        let cross = l0.cross(l1);
+        let close = |a: f64, b: f64| (a - b).abs() <= CIRCLE_EPSILON * a.abs().max(b.abs());
+        if len0 == 0.0 || cross == 0.0 || !close(len0, len1) || !close(self.weight, c / len0) {
+            return None;
+        }
+        // The tangents at the ends make an angle `half` with the chord, the
+        // half sweep of the arc.
+        let half = self.weight.min(1.0).acos();
+        let radius = len0 / half.tan();
+        let mid = self.p0.midpoint(self.p2);
+        let to_center = mid - self.p1;
+        let center = self.p1 + to_center * (len0 / half.sin() / to_center.hypot());
+        Some(Arc {
+            center,
+            radii: Vec2::new(radius, radius),
+            start_angle: (self.p0 - center).atan2(),
+            sweep_angle: 2.0 * half * cross.signum(),
+            x_rotation: 0.0,
+        })
+    }
+
+    /// Approximate the segment with cubic Béziers, within `tolerance`.
+    ///
+    /// Each cubic has the same ends and end tangents as the part of the conic
+    /// it replaces, and passes through its midpoint; its handles are
+    /// `4w / (3 (1 + w))` of the way to the middle control point, for a
+    /// weight `w`, which for a circular arc gives the usual handle length.
+    /// The segment is halved until the cubics are within `tolerance` (as
+    /// measured at several parameters), up to a fixed depth. A segment with
+    /// a weight of one is converted to a cubic exactly.
+    pub fn to_cubics(&self, tolerance: f64) -> Vec<CubicBez> {
+        let mut result = Vec::new();
+        if let Some(quad) = self.as_quad() {
+            result.push(quad.raise());
+        } else {
+            self.push_cubics(tolerance, 0, &mut result);
+        }
+        result
+    }
+
+    fn push_cubics(&self, tolerance: f64, depth: usize, result: &mut Vec<CubicBez>) {
+        let alpha = 4.0 * self.weight / (3.0 * (1.0 + self.weight));
+        let cubic = CubicBez::new(
+            self.p0,
+            self.p0.lerp(self.p1, alpha),
+            self.p2.lerp(self.p1, alpha),
+            self.p2,
+        );
+        let err = (1..8)
+            .map(|i| {
+                let t = i as f64 / 8.0;
+                (cubic.eval(t) - self.eval(t)).hypot()
+            })
+            .fold(0.0, f64::max);
+        // The parametrizations differ slightly, so this overestimates the
+        // distance between the curves.
+        if err <= tolerance || depth >= TO_CUBICS_MAX_DEPTH {
+            result.push(cubic);
+        } else {
+            let (a, b) = self.subdivide();
+            a.push_cubics(tolerance, depth + 1, result);
+            b.push_cubics(tolerance, depth + 1, result);
+        }
+    }
+
+    /// The point at parameter `t`, by the rational formula.
+    #[inline]
+    pub fn eval(&self, t: f64) -> Point {
+        let (pt, w) = self.blossom(t, t);
+        (pt / w).to_point()
+    }
+
+    /// The start point.
+    #[inline]
+    pub fn start(&self) -> Point {
+        self.p0
+    }
+
+    /// The end point.
+    #[inline]
+    pub fn end(&self) -> Point {
+        self.p2
+    }
+
+    /// The part of the curve between two parameters.
+    ///
+    /// The subsegment is also a rational quadratic Bézier, rescaled so that
+    /// its end points have a weight of one. It covers the same part of the
+    /// curve, from `eval(range.start)` to `eval(range.end)`, but the
+    /// rescaling changes the parametrization: unless the weight is one, the
+    /// parameter of the subsegment is not an affine function of that of the
+    /// original. This is why the type does not implement [`ParamCurve`],
+    /// whose subsegments keep the parametrization.
+    ///
+    /// [`ParamCurve`]: trait.ParamCurve.html
+    pub fn subsegment(&self, range: Range<f64>) -> RationalQuadBez {
+        let (t0, t1) = (range.start, range.end);
+        let (h0, w0) = self.blossom(t0, t0);
+        let (h1, w1) = self.blossom(t0, t1);
+        let (h2, w2) = self.blossom(t1, t1);
+        RationalQuadBez {
+            p0: (h0 / w0).to_point(),
+            p1: (h1 / w1).to_point(),
+            p2: (h2 / w2).to_point(),
+            weight: w1 / (w0 * w2).sqrt(),
+        }
+    }
+
+    /// Split the curve at parameter 0.5, as by [`subsegment`].
+    ///
+    /// [`subsegment`]: #method.subsegment
+    #[inline]
+    pub fn subdivide(&self) -> (RationalQuadBez, RationalQuadBez) {
+        (self.subsegment(0.0..0.5), self.subsegment(0.5..1.0))
+    }
+
+    /// The point and weight of the curve in homogeneous coordinates, as a
+    /// blossom of the parameters `t0` and `t1`.
+    fn blossom(&self, t0: f64, t1: f64) -> (Vec2, f64) {
+        let (a, b, c) = (
+            (1.0 - t0) * (1.0 - t1),
+            (1.0 - t0) * t1 + t0 * (1.0 - t1),
+            t0 * t1,
+        );
+        let w = self.weight;
+        let pt = a * self.p0.to_vec2() + (b * w) * self.p1.to_vec2() + c * self.p2.to_vec2();
+        (pt, a + b * w + c)
+    }
+}
+
+impl From<QuadBez> for RationalQuadBez {
+    #[inline]
+    fn from(quad: QuadBez) -> RationalQuadBez {
+        RationalQuadBez::new(quad.p0, quad.p1, quad.p2, 1.0)
+    }
+}
+
+impl Mul<RationalQuadBez> for Affine {
+    type Output = RationalQuadBez;
+
+    /// The weight is unchanged, as affine maps preserve it.
+    #[inline]
+    fn mul(self, other: RationalQuadBez) -> RationalQuadBez {
+        RationalQuadBez {
+            p0: self * other.p0,
+            p1: self * other.p1,
+            p2: self * other.p2,
+            weight: other.weight,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use crate::{Arc, ParamCurve, Point, QuadBez, RationalQuadBez, Vec2};
+
+    fn assert_near(p0: Point, p1: Point, epsilon: f64) {
+        assert!((p1 - p0).hypot() < epsilon, "{:?} != {:?}", p0, p1);
+    }
+
+    #[test]
+    fn rational_quad_circle() {
+        // A quarter of the unit circle.
+        let w = (0.25 * PI).cos();
+        let q = RationalQuadBez::new((1.0, 0.0), (1.0, 1.0), (0.0, 1.0), w);
+        for i in 0..=10 {
+            let p = q.eval(i as f64 / 10.0);
+            assert!((p.to_vec2().hypot() - 1.0).abs() < 1e-12);
+        }
+        let sub = q.subsegment(0.2..0.7);
+        assert_near(sub.p0, q.eval(0.2), 1e-12);
+        assert_near(sub.p2, q.eval(0.7), 1e-12);
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert!((sub.eval(t).to_vec2().hypot() - 1.0).abs() < 1e-12);
+        }
+
+        let arc = q.to_arc().unwrap();
+        assert_near(arc.center, Point::ORIGIN, 1e-12);
+        assert!((arc.radii.x - 1.0).abs() < 1e-12);
+        assert!((arc.start_angle).abs() < 1e-12);
+        assert!((arc.sweep_angle - FRAC_PI_2).abs() < 1e-12);
+        // Changing the weight gives an ellipse or hyperbola instead.
+        assert!(RationalQuadBez { weight: 0.5, ..q }.to_arc().is_none());
+        assert!(
+            RationalQuadBez::new((0.0, 0.0), (1.0, 0.0), (2.0, 0.0), 1.0)
+                .to_arc()
+                .is_none()
+        );
+
+        let tolerance = 1e-6;
+        let cubics = q.to_cubics(tolerance);
+        for c in &cubics {
+            for i in 0..=10 {
+                let p = c.eval(i as f64 / 10.0);
+                assert!((p.to_vec2().hypot() - 1.0).abs() < tolerance);
+            }
+        }
+        assert_near(cubics[0].p0, q.p0, 1e-12);
+        assert_near(cubics.last().unwrap().p3, q.p2, 1e-12);
+    }
+
+    #[test]
+    fn rational_quad_from_arc() {
+        let arc = Arc {
+            center: Point::new(2.0, 1.0),
+            radii: Vec2::new(3.0, 1.0),
+            start_angle: 0.5,
+            sweep_angle: -4.0,
+            x_rotation: 0.3,
+        };
+        let pieces = RationalQuadBez::from_arc(&arc);
+        assert_eq!(pieces.len(), 3);
+        let (s, c) = arc.x_rotation.sin_cos();
+        let on_ellipse = |p: Point| {
+            let d = p - arc.center;
+            let (u, v) = (d.x * c + d.y * s, -d.x * s + d.y * c);
+            (u / arc.radii.x).powi(2) + (v / arc.radii.y).powi(2)
+        };
+        for piece in &pieces {
+            for i in 0..=10 {
+                assert!((on_ellipse(piece.eval(i as f64 / 10.0)) - 1.0).abs() < 1e-12);
+            }
+        }
+        let at = |angle: f64| {
+            let (u, v) = (arc.radii.x * angle.cos(), arc.radii.y * angle.sin());
+            arc.center + Vec2::new(u * c - v * s, u * s + v * c)
+        };
+        assert_near(pieces[0].p0, at(arc.start_angle), 1e-12);
+        assert_near(pieces[2].p2, at(arc.start_angle + arc.sweep_angle), 1e-12);
+
+        // A circular arc converts back.
+        let circle_arc = Arc {
+            radii: Vec2::new(2.0, 2.0),
+            ..arc
+        };
+        let back = RationalQuadBez::from_arc(&circle_arc)[1].to_arc().unwrap();
+        assert_near(back.center, arc.center, 1e-9);
+        assert!((back.radii.x - 2.0).abs() < 1e-9);
+        assert!((back.sweep_angle + 4.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rational_quad_weight_one() {
+        let quad = QuadBez::new((0.0, 0.0), (1.0, 2.0), (3.0, 0.0));
+        let q = RationalQuadBez::from(quad);
+        assert_eq!(q.as_quad(), Some(quad));
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert_near(q.eval(t), quad.eval(t), 1e-12);
+        }
+        assert_eq!(q.to_cubics(1e-9), vec![quad.raise()]);
+        let sub = q.subsegment(0.25..0.5);
+        assert!((sub.weight - 1.0).abs() < 1e-12);
+        assert_near(sub.p1, quad.subsegment(0.25..0.5).p1, 1e-12);
+        assert!(RationalQuadBez { weight: 0.9, ..q }.as_quad().is_none());
+    }
+}
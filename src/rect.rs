@@ -3,7 +3,9 @@
 use std::fmt;
 use std::ops::{Add, Sub};
 
-use crate::{Ellipse, Insets, PathEl, Point, RoundedRect, Shape, Size, Vec2};
+use crate::{
+    Affine, BezPath, Ellipse, Insets, PathEl, Point, RotatedRect, RoundedRect, Shape, Size, Vec2,
+};
 
 /// A rectangle.
 #[derive(Clone, Copy, Default, PartialEq)]
@@ -23,6 +25,9 @@ impl Rect {
     /// The empty rectangle at the origin.
     pub const ZERO: Rect = Rect::new(0., 0., 0., 0.);
 
+    /// The unit square, from `(0, 0)` to `(1, 1)`.
+    pub const UNIT: Rect = Rect::new(0., 0., 1., 1.);
+
     /// A new rectangle from minimum and maximum coordinates.
     #[inline]
     pub const fn new(x0: f64, y0: f64, x1: f64, y1: f64) -> Rect {
@@ -41,7 +46,11 @@ impl Rect {
 
     /// A new rectangle from origin and size.
     ///
-    /// The result will have non-negative width and height.
+    /// The result will have non-negative width and height. A negative
+    /// component of `size` extends the rectangle from the origin towards
+    /// negative x or y, so the origin becomes the maximum x or y of the
+    /// result, and `origin()` of the result is not `origin`; the result is
+    /// the same as for the corner `origin + size` and size `-size`.
     #[inline]
     pub fn from_origin_size(origin: impl Into<Point>, size: impl Into<Size>) -> Rect {
         let origin = origin.into();
@@ -49,6 +58,12 @@ impl Rect {
     }
 
     /// A new rectangle from center and size.
+    ///
+    /// As for [`from_origin_size`], the result will have non-negative width
+    /// and height: a size with negative components gives the same rectangle
+    /// as its absolute value.
+    ///
+    /// [`from_origin_size`]: #method.from_origin_size
     #[inline]
     pub fn from_center_size(center: impl Into<Point>, size: impl Into<Size>) -> Rect {
         let center = center.into();
@@ -59,6 +74,7 @@ impl Rect {
             center.x + size.width,
             center.y + size.height,
         )
+        .abs()
     }
 
     /// Create a new `Rect` with the same size as `self` and a new origin.
@@ -174,6 +190,66 @@ impl Rect {
         point.x >= self.x0 && point.x < self.x1 && point.y >= self.y0 && point.y < self.y1
     }
 
+    /// The point inside (or on the boundary of) the rectangle nearest to `pt`.
+    ///
+    /// Points inside the rectangle are returned unchanged. Inverted bounds are
+    /// treated as for [`abs`]; a rectangle with zero width or height clamps to
+    /// the degenerate line or point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Point, Rect};
+    /// let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+    /// assert_eq!(rect.clamp(Point::new(15.0, 5.0)), Point::new(10.0, 5.0));
+    /// assert_eq!(rect.clamp(Point::new(2.0, 3.0)), Point::new(2.0, 3.0));
+    /// ```
+    ///
+    /// [`abs`]: #method.abs
+    #[inline]
+    pub fn clamp(&self, pt: Point) -> Point {
+        let r = self.abs();
+        pt.clamp(r.origin(), Point::new(r.x1, r.y1))
+    }
+
+    /// The point on the boundary of the rectangle nearest to `pt`.
+    ///
+    /// Unlike [`clamp`], this also moves points inside the rectangle, onto the
+    /// nearest edge. Ties are broken in the order left, right, top, bottom
+    /// edge (in y-down spaces). Inverted bounds are treated as for [`abs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Point, Rect};
+    /// let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+    /// assert_eq!(rect.nearest_edge_point(Point::new(2.0, 3.0)), Point::new(0.0, 3.0));
+    /// assert_eq!(rect.nearest_edge_point(Point::new(5.0, 12.0)), Point::new(5.0, 10.0));
+    /// ```
+    ///
+    /// [`clamp`]: #method.clamp
+    /// [`abs`]: #method.abs
+    pub fn nearest_edge_point(&self, pt: Point) -> Point {
+        let r = self.abs();
+        let clamped = r.clamp(pt);
+        if clamped != pt {
+            return clamped;
+        }
+        let candidates = [
+            (pt.x - r.x0, Point::new(r.x0, pt.y)),
+            (r.x1 - pt.x, Point::new(r.x1, pt.y)),
+            (pt.y - r.y0, Point::new(pt.x, r.y0)),
+            (r.y1 - pt.y, Point::new(pt.x, r.y1)),
+        ];
+        let mut best = candidates[0];
+        for &c in &candidates[1..] {
+            if c.0 < best.0 {
+                best = c;
+            }
+        }
+        best.1
+    }
+
     /// Take absolute value of width and height.
     ///
     /// The resulting rect has the same extents as the original, but is
@@ -184,6 +260,20 @@ impl Rect {
         Rect::new(x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1))
     }
 
+    /// Linearly interpolate between two rectangles.
+    ///
+    /// Each coordinate is interpolated separately, as by [`Vec2::lerp`], so
+    /// the result is exact at `t` equal to 0 and 1, and extrapolates for `t`
+    /// outside `[0, 1]`.
+    ///
+    /// [`Vec2::lerp`]: struct.Vec2.html#method.lerp
+    #[inline]
+    pub fn lerp(&self, other: Rect, t: f64) -> Rect {
+        let p0 = self.origin().lerp(other.origin(), t);
+        let p1 = Point::new(self.x1, self.y1).lerp(Point::new(other.x1, other.y1), t);
+        Rect::new(p0.x, p0.y, p1.x, p1.y)
+    }
+
     /// The smallest rectangle enclosing two rectangles.
     ///
     /// Results are valid only if width and height are non-negative.
@@ -239,6 +329,15 @@ impl Rect {
         )
     }
 
+    /// Grow or shrink the rectangle by the same amount on each side, keeping
+    /// the width and height non-negative.
+    pub(crate) fn inflate_clamped(&self, distance: f64) -> Rect {
+        let size = self.abs().size();
+        let width = (size.width + 2.0 * distance).max(0.0);
+        let height = (size.height + 2.0 * distance).max(0.0);
+        Rect::from_center_size(self.center(), (width, height))
+    }
+
     /// Returns a new `Rect`,
     /// with each coordinate value rounded to the nearest integer.
     ///
@@ -437,6 +536,14 @@ impl Rect {
         Ellipse::from_rect(self)
     }
 
+    /// Creates a new [`RotatedRect`] by rotating this `Rect` about its center.
+    ///
+    /// [`RotatedRect`]: struct.RotatedRect.html
+    #[inline]
+    pub fn to_rotated_rect(self, rotation: f64) -> RotatedRect {
+        RotatedRect::new(self, rotation)
+    }
+
     /// The aspect ratio of the `Rect`.
     ///
     /// This is defined as the height divided by the width. It measures the "squareness" of the
@@ -490,6 +597,102 @@ impl Rect {
             Rect::new(self.x0, y0, self.x1, y1)
         }
     }
+
+    /// Scale and center the rectangle within a container.
+    ///
+    /// This follows the semantics of the CSS `object-fit` property; see
+    /// [`FitMode`] for the options. The result is centered on the center of
+    /// `container`, and both rectangles are treated as normalized, so the
+    /// result has non-negative width and height.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{FitMode, Rect};
+    /// let image = Rect::new(0.0, 0.0, 40.0, 20.0);
+    /// let frame = Rect::new(0.0, 0.0, 10.0, 10.0);
+    /// assert_eq!(image.fit_into(frame, FitMode::Contain), Rect::new(0.0, 2.5, 10.0, 7.5));
+    /// assert_eq!(image.fit_into(frame, FitMode::Cover), Rect::new(-5.0, 0.0, 15.0, 10.0));
+    /// ```
+    ///
+    /// [`FitMode`]: enum.FitMode.html
+    pub fn fit_into(&self, container: Rect, mode: FitMode) -> Rect {
+        let (sx, sy) = self.fit_scale(container, mode);
+        let size = self.abs().size();
+        Rect::from_center_size(container.center(), (size.width * sx, size.height * sy))
+    }
+
+    /// The transform that maps the rectangle onto the result of [`fit_into`].
+    ///
+    /// The transform scales about the center of the rectangle and then moves
+    /// that to the center of `container`, without any rotation or
+    /// reflection, so it can be applied to content drawn within the
+    /// rectangle.
+    ///
+    /// [`fit_into`]: #method.fit_into
+    pub fn fit_transform(&self, container: Rect, mode: FitMode) -> Affine {
+        let (sx, sy) = self.fit_scale(container, mode);
+        Affine::translate(container.center().to_vec2())
+            * Affine::scale_non_uniform(sx, sy)
+            * Affine::translate(-self.center().to_vec2())
+    }
+
+    /// The scale factors in x and y used by [`fit_into`].
+    ///
+    /// A factor that would divide by a zero width or height is ignored in
+    /// favor of the other, and is 1 if both would.
+    ///
+    /// [`fit_into`]: #method.fit_into
+    fn fit_scale(&self, container: Rect, mode: FitMode) -> (f64, f64) {
+        let (size, target) = (self.abs().size(), container.abs().size());
+        let ratio = |target: f64, size: f64| {
+            if size > 0.0 {
+                target / size
+            } else {
+                f64::NAN
+            }
+        };
+        // `min` and `max` ignore NaN arguments.
+        let (sx, sy) = (
+            ratio(target.width, size.width),
+            ratio(target.height, size.height),
+        );
+        let or_one = |s: f64| if s.is_nan() { 1.0 } else { s };
+        let uniform = |s: f64| (or_one(s), or_one(s));
+        match mode {
+            FitMode::Fill => {
+                let fill_x = if sx.is_nan() { sy } else { sx };
+                let fill_y = if sy.is_nan() { sx } else { sy };
+                (or_one(fill_x), or_one(fill_y))
+            }
+            FitMode::Contain => uniform(sx.min(sy)),
+            FitMode::Cover => uniform(sx.max(sy)),
+            FitMode::ScaleDown => uniform(sx.min(sy).min(1.0)),
+        }
+    }
+}
+
+/// How [`Rect::fit_into`] fits a rectangle into a container.
+///
+/// These follow the values of the CSS `object-fit` property.
+///
+/// [`Rect::fit_into`]: struct.Rect.html#method.fit_into
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FitMode {
+    /// Scale uniformly to the largest size that fits within the container,
+    /// preserving the aspect ratio without overflowing.
+    Contain,
+    /// Scale uniformly to the smallest size that covers the container,
+    /// preserving the aspect ratio; the parts outside the container would be
+    /// clipped.
+    Cover,
+    /// Stretch to exactly the size of the container, changing the aspect
+    /// ratio if needed.
+    Fill,
+    /// Like `Contain`, but never scaling up, so a rectangle already smaller
+    /// than the container keeps its size.
+    ScaleDown,
 }
 
 impl From<(Point, Point)> for Rect {
@@ -580,11 +783,43 @@ impl Shape for Rect {
         }
     }
 
+    /// Unlike the inherent [`Rect::contains`], this treats inverted bounds
+    /// like [`winding`] does.
+    ///
+    /// [`Rect::contains`]: struct.Rect.html#method.contains
+    /// [`winding`]: #method.winding
+    #[inline]
+    fn contains(&self, pt: Point) -> bool {
+        self.abs().contains(pt)
+    }
+
+    #[inline]
+    fn distance_to_point(&self, pt: Point, _accuracy: f64) -> f64 {
+        pt.distance(self.nearest_edge_point(pt))
+    }
+
+    /// The farthest point is always a corner.
+    fn farthest_point(&self, pt: Point, _accuracy: f64) -> (Point, f64) {
+        let far = |a: f64, b: f64, v: f64| if (a - v).abs() >= (b - v).abs() { a } else { b };
+        let corner = Point::new(far(self.x0, self.x1, pt.x), far(self.y0, self.y1, pt.y));
+        (corner, pt.distance(corner))
+    }
+
     #[inline]
     fn bounding_box(&self) -> Rect {
         self.abs()
     }
 
+    /// Growing gives a rounded rectangle with radius `distance`, and
+    /// shrinking a smaller rectangle, clamped at zero width and height.
+    fn inset(&self, distance: f64, tolerance: f64) -> BezPath {
+        if distance > 0.0 {
+            RoundedRect::from_rect(self.inflate_clamped(distance), distance).to_path(tolerance)
+        } else {
+            self.inflate_clamped(distance).to_path(tolerance)
+        }
+    }
+
     #[inline]
     fn as_rect(&self) -> Option<Rect> {
         Some(*self)
@@ -639,7 +874,7 @@ impl fmt::Display for Rect {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Point, Rect, Shape};
+    use crate::{FitMode, Point, Rect, Shape, Size};
 
     fn assert_approx_eq(x: f64, y: f64) {
         assert!((x - y).abs() < 1e-7);
@@ -666,6 +901,82 @@ mod tests {
         assert_eq!(r_flip.winding(center), p_flip.winding(center));
     }
 
+    #[test]
+    fn clamp_degenerate() {
+        let inverted = Rect::new(10.0, 10.0, 0.0, 0.0);
+        assert_eq!(inverted.clamp(Point::new(-1.0, 5.0)), Point::new(0.0, 5.0));
+        let line = Rect::new(0.0, 2.0, 10.0, 2.0);
+        assert_eq!(line.clamp(Point::new(5.0, 7.0)), Point::new(5.0, 2.0));
+        let point = Rect::new(3.0, 4.0, 3.0, 4.0);
+        assert_eq!(point.clamp(Point::new(-1.0, 9.0)), Point::new(3.0, 4.0));
+        assert_eq!(
+            point.nearest_edge_point(Point::new(3.0, 4.0)),
+            Point::new(3.0, 4.0)
+        );
+        let r = Rect::new(0.0, 0.0, 10.0, 4.0);
+        assert_eq!(
+            r.nearest_edge_point(Point::new(7.0, 3.0)),
+            Point::new(7.0, 4.0)
+        );
+        assert_eq!(
+            r.nearest_edge_point(Point::new(9.5, 2.0)),
+            Point::new(10.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn shape_contains() {
+        fn shape_contains(shape: impl Shape, pt: Point) -> bool {
+            shape.contains(pt)
+        }
+        let r = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let r_flip = Rect::new(10.0, 10.0, 0.0, 0.0);
+        for &pt in &[
+            Point::new(5.0, 5.0),
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 5.0),
+        ] {
+            assert_eq!(shape_contains(r, pt), r.winding(pt) != 0);
+            assert_eq!(shape_contains(r_flip, pt), r_flip.winding(pt) != 0);
+        }
+        assert!(shape_contains(r_flip, Point::new(5.0, 5.0)));
+        assert!(!r_flip.contains(Point::new(5.0, 5.0)));
+    }
+
+    #[test]
+    fn distance_to_point() {
+        let r = Rect::new(0.0, 0.0, 10.0, 4.0);
+        assert_approx_eq(r.distance_to_point(Point::new(5.0, 1.0), 1e-9), 1.0);
+        assert_approx_eq(r.distance_to_point(Point::new(13.0, 8.0), 1e-9), 5.0);
+        let p = r.to_path(1e-9);
+        for &pt in &[
+            Point::new(5.0, 1.0),
+            Point::new(13.0, 8.0),
+            Point::new(-1.0, 2.0),
+        ] {
+            assert_approx_eq(p.distance_to_point(pt, 1e-9), r.distance_to_point(pt, 1e-9));
+        }
+    }
+
+    #[test]
+    fn farthest_point() {
+        let r = Rect::new(0.0, 0.0, 10.0, 4.0);
+        let (far, d) = r.farthest_point(Point::new(1.0, 1.0), 1e-9);
+        assert_eq!(far, Point::new(10.0, 4.0));
+        assert_approx_eq(d, 90f64.sqrt());
+        let p = r.to_path(1e-9);
+        for &pt in &[
+            Point::new(5.0, 1.0),
+            Point::new(13.0, 8.0),
+            Point::new(-1.0, 2.0),
+        ] {
+            let (far, d) = r.farthest_point(pt, 1e-9);
+            let (path_far, path_d) = p.farthest_point(pt, 1e-9);
+            assert_approx_eq(d, path_d);
+            assert_approx_eq(far.distance(pt), path_far.distance(pt));
+        }
+    }
+
     #[test]
     fn display() {
         let r = Rect::from_origin_size((10., 12.23214), (22.222222222, 23.1));
@@ -726,4 +1037,60 @@ mod tests {
         let test = Rect::new(0.0, 0.0, 1.0, 1.0);
         assert!((test.aspect_ratio() - 1.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn fit_into() {
+        let image = Rect::new(10.0, 10.0, 30.0, 20.0);
+        let frame = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let contain = image.fit_into(frame, FitMode::Contain);
+        assert_eq!(contain, Rect::new(0.0, 2.5, 10.0, 7.5));
+        assert_eq!(contain.aspect_ratio(), image.aspect_ratio());
+        let cover = image.fit_into(frame, FitMode::Cover);
+        assert_eq!(cover, Rect::new(-5.0, 0.0, 15.0, 10.0));
+        assert_eq!(image.fit_into(frame, FitMode::Fill), frame);
+        assert_eq!(image.fit_into(frame, FitMode::ScaleDown), contain);
+        let small = Rect::new(0.0, 0.0, 2.0, 4.0);
+        assert_eq!(
+            small.fit_into(frame, FitMode::ScaleDown),
+            Rect::new(4.0, 3.0, 6.0, 7.0)
+        );
+        // The transform maps the rectangle onto the fitted one.
+        for &mode in &[FitMode::Contain, FitMode::Cover, FitMode::Fill] {
+            let affine = image.fit_transform(frame, mode);
+            let fitted = affine.transform_rect_bbox(image);
+            assert_eq!(fitted, image.fit_into(frame, mode));
+        }
+        // A zero height doesn't produce NaN.
+        let line = Rect::new(0.0, 0.0, 5.0, 0.0);
+        assert_eq!(
+            line.fit_into(frame, FitMode::Cover),
+            Rect::new(0.0, 5.0, 10.0, 5.0)
+        );
+        let point = Rect::new(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(
+            point.fit_into(frame, FitMode::Fill),
+            Rect::new(5.0, 5.0, 5.0, 5.0)
+        );
+    }
+
+    #[test]
+    fn lerp() {
+        let a = Rect::new(0.1, 0.2, 3.3, 4.4);
+        let b = Rect::new(-1.0, 2.0, 7.0, 5.0);
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        let mid = a.lerp(b, 0.5);
+        assert!((mid.width() - 0.5 * (a.width() + b.width())).abs() < 1e-12);
+        assert_eq!(a.size().lerp(b.size(), 1.0), b.size());
+    }
+
+    #[test]
+    fn negative_size() {
+        let r = Rect::from_origin_size((4.0, 5.0), (-2.0, 3.0));
+        assert_eq!(r, Rect::new(2.0, 5.0, 4.0, 8.0));
+        assert_eq!(r.size(), Size::new(2.0, 3.0));
+        assert_eq!(r, Rect::from_origin_size((2.0, 8.0), (2.0, -3.0)));
+        let c = Rect::from_center_size((0.0, 0.0), (-2.0, -4.0));
+        assert_eq!(c, Rect::new(-1.0, -2.0, 1.0, 2.0));
+    }
 }
@@ -0,0 +1,199 @@
+//! A rectangle rotated about its center.
+
+use crate::{Affine, PathEl, Point, Rect, Shape, Vec2};
+
+/// A rectangle rotated about its center.
+///
+/// This keeps the cheap analytic queries of [`Rect`] (area, winding,
+/// bounding box) while supporting an arbitrary rotation, which would
+/// otherwise require converting to a generic [`BezPath`].
+///
+/// The easiest way to create a `RotatedRect` is often to create a [`Rect`],
+/// and then call [`to_rotated_rect`].
+///
+/// [`Rect`]: struct.Rect.html
+/// [`BezPath`]: struct.BezPath.html
+/// [`to_rotated_rect`]: struct.Rect.html#method.to_rotated_rect
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RotatedRect {
+    /// The rectangle before rotation.
+    rect: Rect,
+    /// Rotation about the center of the rectangle, in radians.
+    rotation: f64,
+}
+
+impl RotatedRect {
+    /// A new rotated rectangle from a rectangle and a rotation (in radians)
+    /// about its center.
+    ///
+    /// The rectangle is normalized so that it has non-negative width and height.
+    #[inline]
+    pub fn new(rect: Rect, rotation: f64) -> RotatedRect {
+        RotatedRect {
+            rect: rect.abs(),
+            rotation,
+        }
+    }
+
+    /// The rectangle before rotation.
+    #[inline]
+    pub fn unrotated_rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// The rotation about the center, in radians.
+    #[inline]
+    pub fn rotation(&self) -> f64 {
+        self.rotation
+    }
+
+    /// The center point of the rectangle.
+    ///
+    /// This is unchanged by the rotation.
+    #[inline]
+    pub fn center(&self) -> Point {
+        self.rect.center()
+    }
+
+    /// The width of the rectangle, before rotation.
+    #[inline]
+    pub fn width(&self) -> f64 {
+        self.rect.width()
+    }
+
+    /// The height of the rectangle, before rotation.
+    #[inline]
+    pub fn height(&self) -> f64 {
+        self.rect.height()
+    }
+
+    /// The affine transformation mapping the unrotated rectangle to this one.
+    pub fn transform(&self) -> Affine {
+        let center = self.center().to_vec2();
+        Affine::translate(center) * Affine::rotate(self.rotation) * Affine::translate(-center)
+    }
+
+    /// The four corners, in the same order as the path of the unrotated
+    /// rectangle.
+    pub fn corners(&self) -> [Point; 4] {
+        let Rect { x0, y0, x1, y1 } = self.rect;
+        let a = self.transform();
+        [
+            a * Point::new(x0, y0),
+            a * Point::new(x1, y0),
+            a * Point::new(x1, y1),
+            a * Point::new(x0, y1),
+        ]
+    }
+}
+
+#[doc(hidden)]
+pub struct RotatedRectPathIter {
+    corners: [Point; 4],
+    ix: usize,
+}
+
+impl Shape for RotatedRect {
+    type PathElementsIter = RotatedRectPathIter;
+
+    fn path_elements(&self, _tolerance: f64) -> RotatedRectPathIter {
+        RotatedRectPathIter {
+            corners: self.corners(),
+            ix: 0,
+        }
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.rect.area()
+    }
+
+    #[inline]
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        self.rect.perimeter(accuracy)
+    }
+
+    /// Note: the point is mapped into the space of the unrotated rectangle,
+    /// so this inherits the half-open boundary rule of [`Rect`].
+    ///
+    /// [`Rect`]: struct.Rect.html
+    fn winding(&self, pt: Point) -> i32 {
+        self.rect.winding(self.transform().inverse() * pt)
+    }
+
+    fn bounding_box(&self) -> Rect {
+        let (sin, cos) = self.rotation.sin_cos();
+        let half_w = 0.5 * self.width();
+        let half_h = 0.5 * self.height();
+        let range = Vec2::new(
+            (half_w * cos).abs() + (half_h * sin).abs(),
+            (half_w * sin).abs() + (half_h * cos).abs(),
+        );
+        let center = self.center();
+        Rect::from_points(center - range, center + range)
+    }
+}
+
+impl Iterator for RotatedRectPathIter {
+    type Item = PathEl;
+
+    fn next(&mut self) -> Option<PathEl> {
+        self.ix += 1;
+        match self.ix {
+            1 => Some(PathEl::MoveTo(self.corners[0])),
+            2..=4 => Some(PathEl::LineTo(self.corners[self.ix - 1])),
+            5 => Some(PathEl::ClosePath),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Rect, Shape};
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    fn assert_approx_eq(x: f64, y: f64) {
+        assert!((x - y).abs() < 1e-9, "{} != {}", x, y);
+    }
+
+    #[test]
+    fn rotated_rect_area_and_path() {
+        let r = Rect::new(0.0, 0.0, 4.0, 2.0).to_rotated_rect(0.3);
+        assert_approx_eq(r.area(), 8.0);
+        assert_approx_eq(r.to_path(1e-9).area(), 8.0);
+        assert_approx_eq(r.perimeter(1e-9), 12.0);
+        assert!(r.as_rect().is_none());
+        assert_eq!(r.unrotated_rect(), Rect::new(0.0, 0.0, 4.0, 2.0));
+    }
+
+    #[test]
+    fn rotated_rect_winding() {
+        let r = Rect::new(-2.0, -1.0, 2.0, 1.0).to_rotated_rect(FRAC_PI_2);
+        assert_eq!(r.winding(Point::new(0.0, 1.5)), 1);
+        assert_eq!(r.winding(Point::new(1.5, 0.0)), 0);
+        let path = r.to_path(1e-9);
+        for &pt in &[
+            Point::new(0.2, 1.7),
+            Point::new(0.9, -0.3),
+            Point::new(-1.5, 0.1),
+        ] {
+            assert_eq!(r.winding(pt), path.winding(pt));
+        }
+    }
+
+    #[test]
+    fn rotated_rect_bounding_box() {
+        let r = Rect::new(0.0, 0.0, 2.0, 2.0).to_rotated_rect(FRAC_PI_4);
+        let bbox = r.bounding_box();
+        let s = 2.0f64.sqrt();
+        assert_approx_eq(bbox.x0, 1.0 - s);
+        assert_approx_eq(bbox.y0, 1.0 - s);
+        assert_approx_eq(bbox.x1, 1.0 + s);
+        assert_approx_eq(bbox.y1, 1.0 + s);
+        let path_bbox = r.to_path(1e-9).bounding_box();
+        assert_approx_eq(bbox.x0, path_bbox.x0);
+        assert_approx_eq(bbox.y1, path_bbox.y1);
+    }
+}
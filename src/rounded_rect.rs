@@ -1,6 +1,6 @@
 //! A rectangle with rounded corners.
 
-use crate::{arc::ArcAppendIter, Arc, PathEl, Point, Rect, Shape, Size, Vec2};
+use crate::{arc::ArcAppendIter, Arc, BezPath, PathEl, Point, Rect, Shape, Size, Vec2};
 use std::f64::consts::{FRAC_PI_2, PI};
 
 /// A rectangle with equally rounded corners.
@@ -218,11 +218,23 @@ impl Shape for RoundedRect {
         }
     }
 
+    #[inline]
+    fn contains(&self, pt: Point) -> bool {
+        self.winding(pt) != 0
+    }
+
     #[inline]
     fn bounding_box(&self) -> Rect {
         self.rect.bounding_box()
     }
 
+    /// The rectangle, with its width and height clamped at zero, and the
+    /// radius are both changed by `distance`.
+    fn inset(&self, distance: f64, tolerance: f64) -> BezPath {
+        let radius = (self.radius + distance).max(0.0);
+        RoundedRect::from_rect(self.rect.inflate_clamped(distance), radius).to_path(tolerance)
+    }
+
     #[inline]
     fn as_rounded_rect(&self) -> Option<RoundedRect> {
         Some(*self)
@@ -326,6 +338,42 @@ mod tests {
         assert_eq!(rect.winding(Point::new(10.0, 20.0)), 1); // bottom-right corner
     }
 
+    #[test]
+    fn contains_corner_arcs() {
+        let rect = RoundedRect::new(1.0, 2.0, 21.0, 32.0, 5.0);
+        // Points exactly on each corner arc, 3-4-5 from its center, and just
+        // outside along the same direction.
+        let corners = [
+            (Point::new(6.0, 7.0), -1.0, -1.0),
+            (Point::new(16.0, 7.0), 1.0, -1.0),
+            (Point::new(16.0, 27.0), 1.0, 1.0),
+            (Point::new(6.0, 27.0), -1.0, 1.0),
+        ];
+        for &(c, sx, sy) in &corners {
+            let on = Point::new(c.x + 3.0 * sx, c.y + 4.0 * sy);
+            assert!(rect.contains(on), "{:?}", on);
+            let off = Point::new(c.x + 3.001 * sx, c.y + 4.001 * sy);
+            assert!(!rect.contains(off), "{:?}", off);
+            assert_eq!(rect.winding(off), 0);
+            // The corner of the bounding rectangle is outside.
+            let corner = Point::new(c.x + 5.0 * sx, c.y + 5.0 * sy);
+            assert!(!rect.contains(corner));
+        }
+        // The straight edges, and the agreement with the path.
+        assert!(rect.contains(Point::new(1.0, 17.0)));
+        assert!(!rect.contains(Point::new(0.999, 17.0)));
+        let path = rect.to_path(1e-9);
+        for i in 0..30 {
+            for j in 0..40 {
+                let pt = Point::new(0.5 + 0.75 * i as f64, 1.5 + 0.8 * j as f64);
+                let near_boundary = path.distance_to_point(pt, 1e-9) < 1e-6;
+                if !near_boundary {
+                    assert_eq!(rect.winding(pt), path.winding(pt), "{:?}", pt);
+                }
+            }
+        }
+    }
+
     #[test]
     fn bez_conversion() {
         let rect = RoundedRect::new(-5.0, -5.0, 10.0, 20.0, 5.0);
@@ -0,0 +1,266 @@
+//! Sampling points inside shapes.
+
+use crate::{Point, Shape, Vec2};
+
+/// Points on a square grid that fall inside a shape.
+///
+/// The grid consists of the integer multiples of `spacing` in both
+/// directions, restricted to the bounding box of the shape. Each point is
+/// then moved by a random offset of up to `jitter` in x and in y, and kept
+/// if the moved point is inside the shape (by [`Shape::contains`]). The
+/// offsets are a function of `seed` and the grid cell only, so the result is
+/// reproducible, and a cell's point does not change when the shape does.
+///
+/// Points are returned row by row, in increasing y and then x. Returns an
+/// empty vector if `spacing` is not positive and finite, or if the grid
+/// over the bounding box would have more than 2²² points, as for a shape
+/// that is unbounded or very large compared to `spacing`.
+///
+/// [`Shape::contains`]: trait.Shape.html#method.contains
+pub fn sample_grid(shape: &impl Shape, spacing: f64, jitter: f64, seed: u64) -> Vec<Point> {
+    let mut result = Vec::new();
+    if !(spacing > 0.0 && spacing.is_finite()) {
+        return result;
+    }
+    let jitter = jitter.abs();
+    let bbox = shape.bounding_box().abs().inflate(jitter, jitter);
+    let (i0, i1) = ((bbox.x0 / spacing).ceil(), (bbox.x1 / spacing).floor());
+    let (j0, j1) = ((bbox.y0 / spacing).ceil(), (bbox.y1 / spacing).floor());
+    let cells = (i1 - i0 + 1.0) * (j1 - j0 + 1.0);
+    if cells.is_nan() || cells > MAX_SAMPLE_CELLS as f64 {
+        return result;
+    }
+    let (i0, i1, j0, j1) = (i0 as i64, i1 as i64, j0 as i64, j1 as i64);
+    for j in j0..=j1 {
+        for i in i0..=i1 {
+            let mut pt = Point::new(i as f64 * spacing, j as f64 * spacing);
+            if jitter > 0.0 {
+                let mut rng = Rng::new(seed ^ hash_cell(i, j));
+                pt.x += jitter * (2.0 * rng.next_f64() - 1.0);
+                pt.y += jitter * (2.0 * rng.next_f64() - 1.0);
+            }
+            if shape.contains(pt) {
+                result.push(pt);
+            }
+        }
+    }
+    result
+}
+
+/// The largest number of grid cells a sampling routine covers the bounding
+/// box with.
+const MAX_SAMPLE_CELLS: usize = 1 << 22;
+
+/// Points inside a shape, no two of which are closer than `radius`.
+///
+/// This is Bridson's algorithm: starting from a random point inside the
+/// shape, new points are repeatedly tried in the annulus between `radius` and
+/// `2 * radius` around an existing point, and kept if they are inside the
+/// shape (by [`Shape::contains`]) and far enough from all others. A point
+/// stops being extended after 30 failures. When no point can be extended,
+/// new starting points are tried at random in the bounding box, so separate
+/// parts of the shape are filled too, although a part small enough to be
+/// missed by those attempts may remain empty.
+///
+/// Every attempt either adds a point or counts towards retiring one, and
+/// the number of points is bounded, so this terminates after a number of
+/// steps proportional to the area of the bounding box divided by the square
+/// of `radius`. The result depends only on the shape, `radius` and `seed`.
+/// Returns an empty vector if `radius` is not positive and finite, or if
+/// the bounding box is not finite or would need a background grid of more
+/// than 2²² cells, each of size `radius / √2`.
+///
+/// [`Shape::contains`]: trait.Shape.html#method.contains
+pub fn poisson_disk(shape: &impl Shape, radius: f64, seed: u64) -> Vec<Point> {
+    let mut points = Vec::new();
+    if !(radius > 0.0 && radius.is_finite()) {
+        return points;
+    }
+    let bbox = shape.bounding_box().abs();
+    if !(bbox.width().is_finite() && bbox.height().is_finite()) {
+        return points;
+    }
+    let cell = radius * std::f64::consts::FRAC_1_SQRT_2;
+    let count = |extent: f64| ((extent / cell).floor() as usize).checked_add(1);
+    let (cols, rows) = match (count(bbox.width()), count(bbox.height())) {
+        (Some(cols), Some(rows)) => (cols, rows),
+        _ => return points,
+    };
+    let cells = match cols.checked_mul(rows) {
+        Some(cells) if cells <= MAX_SAMPLE_CELLS => cells,
+        _ => return points,
+    };
+    let mut grid: Vec<Option<usize>> = vec![None; cells];
+    let cell_of = |p: Point| {
+        let i = (((p.x - bbox.x0) / cell) as usize).min(cols - 1);
+        let j = (((p.y - bbox.y0) / cell) as usize).min(rows - 1);
+        (i, j)
+    };
+    let mut rng = Rng::new(seed);
+    let mut active = Vec::new();
+    let radius2 = radius * radius;
+    // Adds the point if it is valid, returning whether it was.
+    let mut try_add = |p: Point, points: &mut Vec<Point>, active: &mut Vec<usize>| {
+        if !(p.x >= bbox.x0 && p.x <= bbox.x1 && p.y >= bbox.y0 && p.y <= bbox.y1) {
+            return false;
+        }
+        let (i, j) = cell_of(p);
+        for nj in j.saturating_sub(2)..(j + 3).min(rows) {
+            for ni in i.saturating_sub(2)..(i + 3).min(cols) {
+                if let Some(ix) = grid[nj * cols + ni] {
+                    if (points[ix] - p).hypot2() < radius2 {
+                        return false;
+                    }
+                }
+            }
+        }
+        if !shape.contains(p) {
+            return false;
+        }
+        grid[j * cols + i] = Some(points.len());
+        active.push(points.len());
+        points.push(p);
+        true
+    };
+    loop {
+        // Look for a new starting point.
+        let mut found = false;
+        for _ in 0..POISSON_DISK_ATTEMPTS {
+            let p = Point::new(
+                bbox.x0 + rng.next_f64() * bbox.width(),
+                bbox.y0 + rng.next_f64() * bbox.height(),
+            );
+            if try_add(p, &mut points, &mut active) {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            return points;
+        }
+        while !active.is_empty() {
+            let k = (rng.next_f64() * active.len() as f64) as usize;
+            let center = points[active[k]];
+            let mut extended = false;
+            for _ in 0..POISSON_DISK_ATTEMPTS {
+                // Uniform by area in the annulus.
+                let r = radius * (1.0 + 3.0 * rng.next_f64()).sqrt();
+                let th = 2.0 * std::f64::consts::PI * rng.next_f64();
+                let p = center + r * Vec2::from_angle(th);
+                if try_add(p, &mut points, &mut active) {
+                    extended = true;
+                    break;
+                }
+            }
+            if !extended {
+                active.swap_remove(k);
+            }
+        }
+    }
+}
+
+/// The number of candidates tried around each point by `poisson_disk`.
+const POISSON_DISK_ATTEMPTS: usize = 30;
+
+/// Combine grid cell indices into a seed.
+fn hash_cell(i: i64, j: i64) -> u64 {
+    let mut rng = Rng::new((i as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ (j as u64));
+    rng.next_u64()
+}
+
+/// A small deterministic pseudorandom generator (SplitMix64).
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed value in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{poisson_disk, sample_grid, Affine, BezPath, Circle, Rect, Shape};
+
+    #[test]
+    fn grid_in_rect() {
+        let rect = Rect::new(0.5, 0.5, 10.5, 5.5);
+        let points = sample_grid(&rect, 1.0, 0.0, 0);
+        assert_eq!(points.len(), 50);
+        assert!(points.iter().all(|&p| rect.contains(p)));
+        assert!(sample_grid(&rect, 0.0, 0.0, 0).is_empty());
+        // Too many grid points, or unbounded ones, give nothing.
+        assert!(sample_grid(&rect, 1e-6, 0.0, 0).is_empty());
+        let unbounded = Rect::new(0.0, 0.0, f64::INFINITY, 1.0);
+        assert!(sample_grid(&unbounded, 1.0, 0.0, 0).is_empty());
+        let nan = Rect::new(0.0, 0.0, f64::NAN, 1.0);
+        assert!(sample_grid(&nan, 1.0, 0.0, 0).is_empty());
+    }
+
+    #[test]
+    fn grid_jitter() {
+        let circle = Circle::new((0.0, 0.0), 10.0);
+        let a = sample_grid(&circle, 1.0, 0.4, 7);
+        let b = sample_grid(&circle, 1.0, 0.4, 7);
+        let c = sample_grid(&circle, 1.0, 0.4, 8);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.iter().all(|&p| Shape::contains(&circle, p)));
+        // Roughly one point per unit of area.
+        let n = a.len() as f64;
+        assert!((n - circle.area()).abs() < 0.1 * circle.area());
+    }
+
+    #[test]
+    fn poisson_disk_spacing() {
+        let circle = Circle::new((1.0, 2.0), 5.0);
+        let points = poisson_disk(&circle, 0.5, 42);
+        assert_eq!(points, poisson_disk(&circle, 0.5, 42));
+        assert!(points.iter().all(|&p| Shape::contains(&circle, p)));
+        for (i, &p) in points.iter().enumerate() {
+            for &q in &points[..i] {
+                assert!((p - q).hypot() >= 0.5);
+            }
+        }
+        // Maximal packing density is bounded, and Bridson's is reasonably dense.
+        let n = points.len() as f64;
+        assert!(n > 0.5 * circle.area() / 0.25 && n < 1.2 * circle.area() / 0.25);
+    }
+
+    #[test]
+    fn poisson_disk_components() {
+        // Two squares far apart are both filled.
+        let mut path = BezPath::new();
+        path.extend_path(
+            &Rect::new(0.0, 0.0, 2.0, 2.0).to_path(1e-9),
+            Affine::IDENTITY,
+        );
+        path.extend_path(
+            &Rect::new(8.0, 0.0, 10.0, 2.0).to_path(1e-9),
+            Affine::IDENTITY,
+        );
+        let points = poisson_disk(&path, 0.2, 1);
+        assert!(points.iter().any(|p| p.x < 2.0));
+        assert!(points.iter().any(|p| p.x > 8.0));
+        assert!(poisson_disk(&BezPath::new(), 0.2, 1).is_empty());
+        assert!(poisson_disk(&path, 0.0, 1).is_empty());
+        // A grid too large to allocate gives nothing.
+        assert!(poisson_disk(&path, 1e-9, 1).is_empty());
+        let huge = Rect::new(-1e300, -1e300, 1e300, 1e300);
+        assert!(poisson_disk(&huge, 1.0, 1).is_empty());
+        let unbounded = Rect::new(0.0, 0.0, f64::INFINITY, 1.0);
+        assert!(poisson_disk(&unbounded, 1.0, 1).is_empty());
+    }
+}
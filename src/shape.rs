@@ -1,6 +1,9 @@
 //! A generic trait for shapes.
 
-use crate::{segments, BezPath, Circle, Line, PathEl, Point, Rect, RoundedRect, Segments};
+use crate::{
+    flatten, segments, BezPath, Circle, FillRule, Line, ParamCurve, ParamCurveNearest, PathEl,
+    Point, Rect, RoundedRect, Segments, SpatialRelation,
+};
 
 /// A generic trait for open and closed shapes.
 pub trait Shape: Sized {
@@ -61,14 +64,71 @@ pub trait Shape: Sized {
         segments(self.path_elements(tolerance))
     }
 
+    /// Flatten the shape into a list of points for each subpath.
+    ///
+    /// Curves are approximated by lines within `tolerance`, as in
+    /// [`flatten`]. The points of a closed subpath end with its first point
+    /// repeated, so that the closing edge is explicit; an open subpath ends
+    /// at its last point. A subpath consisting of a single `MoveTo` gives a
+    /// list of one point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{Rect, Shape};
+    /// let rings = Rect::new(0.0, 0.0, 2.0, 1.0).to_polyline(0.1);
+    /// assert_eq!(rings.len(), 1);
+    /// assert_eq!(rings[0].len(), 5);
+    /// assert_eq!(rings[0][0], rings[0][4]);
+    /// ```
+    ///
+    /// [`flatten`]: fn.flatten.html
+    fn to_polyline(&self, tolerance: f64) -> Vec<Vec<Point>> {
+        let mut result: Vec<Vec<Point>> = Vec::new();
+        // Whether the last list is a closed subpath, which a following
+        // segment does not extend.
+        let mut closed = true;
+        let mut start = Point::ORIGIN;
+        flatten(self.path_elements(tolerance), tolerance, |el| match el {
+            PathEl::MoveTo(p) => {
+                result.push(vec![p]);
+                start = p;
+                closed = false;
+            }
+            PathEl::LineTo(p) => {
+                if closed {
+                    result.push(vec![start]);
+                    closed = false;
+                }
+                result.last_mut().unwrap().push(p);
+            }
+            PathEl::ClosePath => {
+                if let Some(points) = result.last_mut().filter(|_| !closed) {
+                    if points.last() != Some(&start) || points.len() == 1 {
+                        points.push(start);
+                    }
+                }
+                closed = true;
+            }
+            _ => (),
+        });
+        result
+    }
+
     /// Signed area.
     ///
-    /// This method only produces meaningful results with closed shapes.
+    /// This method only produces meaningful results with closed shapes. For
+    /// Bézier paths, each open subpath is treated as closed by a straight
+    /// line back to its start.
     ///
     /// The convention for positive area is that y increases when x is
     /// positive. Thus, it is clockwise when down is increasing y (the
     /// usual convention for graphics), and anticlockwise when
-    /// up is increasing y (the usual convention for math).
+    /// up is increasing y (the usual convention for math). Reflecting a
+    /// shape in one axis, as [`BezPath::with_flipped_y`] does, negates the
+    /// area.
+    ///
+    /// [`BezPath::with_flipped_y`]: struct.BezPath.html#method.with_flipped_y
     fn area(&self) -> f64;
 
     /// Total length of perimeter.
@@ -76,7 +136,8 @@ pub trait Shape: Sized {
 
     /// Winding number of point.
     ///
-    /// This method only produces meaningful results with closed shapes.
+    /// This method only produces meaningful results with closed shapes. Open
+    /// subpaths of Bézier paths are treated as closed, as for [`area`].
     ///
     /// The sign of the winding number is consistent with that of [`area`],
     /// meaning it is +1 when the point is inside a positive area shape
@@ -86,9 +147,216 @@ pub trait Shape: Sized {
     /// [`area`]: #tymethod.area
     fn winding(&self, pt: Point) -> i32;
 
+    /// Returns `true` if the [`Point`] is inside this shape.
+    ///
+    /// This is the [`FillRule::NonZero`] rule applied to [`winding`]. Shapes
+    /// with a cheaper test may override it.
+    ///
+    /// [`Point`]: struct.Point.html
+    /// [`FillRule::NonZero`]: enum.FillRule.html#variant.NonZero
+    /// [`winding`]: #tymethod.winding
+    fn contains(&self, pt: Point) -> bool {
+        self.winding(pt) != 0
+    }
+
+    /// The distance from a point to the nearest point on the boundary of
+    /// the shape.
+    ///
+    /// This is unsigned; it doesn't matter whether the point is inside or
+    /// outside. The default implementation takes the minimum over all
+    /// [`path_segments`], using `accuracy` both for the conversion to Bézier
+    /// segments and for finding the nearest point on each. Returns infinity
+    /// if the shape has no segments.
+    ///
+    /// [`path_segments`]: #method.path_segments
+    fn distance_to_point(&self, pt: Point, accuracy: f64) -> f64 {
+        self.path_segments(accuracy)
+            .map(|seg| seg.nearest(pt, accuracy).1)
+            .fold(f64::INFINITY, f64::min)
+            .sqrt()
+    }
+
+    /// The point on the boundary of the shape farthest from a point, and
+    /// its distance.
+    ///
+    /// The default implementation takes the maximum over all
+    /// [`path_segments`], using `accuracy` both for the conversion to Bézier
+    /// segments and for finding the farthest point on each. The endpoints
+    /// of the segments are always candidates. Returns `pt` itself and a
+    /// distance of zero if the shape has no segments.
+    ///
+    /// [`path_segments`]: #method.path_segments
+    fn farthest_point(&self, pt: Point, accuracy: f64) -> (Point, f64) {
+        let mut best = (pt, 0.0);
+        for seg in self.path_segments(accuracy) {
+            let (t, r) = seg.farthest(pt, accuracy);
+            if r > best.1 {
+                best = (seg.eval(t), r);
+            }
+        }
+        (best.0, best.1.sqrt())
+    }
+
+    /// The spatial relationship between this shape and another.
+    ///
+    /// Both shapes are flattened with the given tolerance and overlaid, using
+    /// the nonzero fill rule. Differences in area thinner than `tolerance`
+    /// along the boundaries are ignored, so a shape is `Equal` to a close
+    /// enough approximation of itself. Shapes without overlapping interiors
+    /// are `Touching` if their boundaries come within `tolerance` of each
+    /// other; determining this compares every pair of boundary edges.
+    fn relate(&self, other: &impl Shape, tolerance: f64) -> SpatialRelation {
+        crate::boolean::relate(self, other, tolerance)
+    }
+
+    /// Triangulate the filled interior of the shape.
+    ///
+    /// The shape is flattened with the given tolerance and split at its
+    /// self-intersections, and the region inside it according to `fill_rule`
+    /// is divided into faces, each with an outer boundary and any number of
+    /// holes, which are triangulated by ear clipping. The result is a list
+    /// of vertices and, for each triangle, the indices of its vertices, in
+    /// counterclockwise order (positive area in a y-up coordinate system).
+    ///
+    /// Vertices are shared: a point used by several triangles appears once.
+    /// Holes are left uncovered, so an annulus produces a ring of triangles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::{FillRule, Rect, Shape};
+    /// let (vertices, indices) = Rect::new(0.0, 0.0, 2.0, 1.0).tessellate(0.1, FillRule::NonZero);
+    /// assert_eq!(vertices.len(), 4);
+    /// assert_eq!(indices.len(), 2);
+    /// ```
+    fn tessellate(&self, tolerance: f64, fill_rule: FillRule) -> (Vec<Point>, Vec<[u32; 3]>) {
+        crate::boolean::tessellate(self, tolerance, fill_rule)
+    }
+
+    /// The part of the shape's fill inside the fill of `clip`.
+    ///
+    /// This is the [`intersection`] of the two shapes, using the nonzero fill
+    /// rule for both, so holes in either shape are preserved. The result is
+    /// flattened with the given tolerance, except in the common cases of a
+    /// clip that misses the shape, giving an empty path, and of a rectangular
+    /// clip containing the shape, giving the shape's own path.
+    ///
+    /// [`intersection`]: fn.intersection.html
+    fn clip_to(&self, clip: &impl Shape, tolerance: f64) -> BezPath {
+        crate::boolean::clip(self, clip, tolerance)
+    }
+
+    /// The outline of the shape grown or shrunk by a distance.
+    ///
+    /// Positive distances grow the shape, and negative ones shrink it: each
+    /// closed subpath (an open one is closed implicitly) is replaced by its
+    /// offset curve on the outside or inside, relative to its orientation.
+    /// Where the offsets of adjacent segments move apart, they are joined
+    /// by a circular arc around the corner; where they cross, both are
+    /// trimmed at the crossing. Loops of the offset curves themselves, and
+    /// the inverted parts of a subpath shrunk by more than its width, are
+    /// not removed. Subpaths with zero area are dropped.
+    ///
+    /// Circles, rectangles and rounded rectangles have exact results, with
+    /// sizes and radii clamped at zero when shrinking. Note that [`Rect`]
+    /// has an inherent `inset` method taking [`Insets`], so this is called
+    /// as `Shape::inset(&rect, distance, tolerance)`.
+    ///
+    /// [`Rect`]: struct.Rect.html
+    /// [`Insets`]: struct.Insets.html
+    fn inset(&self, distance: f64, tolerance: f64) -> BezPath {
+        crate::offset::inset_path(self.path_elements(tolerance), distance, tolerance)
+    }
+
+    /// The filled area of the shape within a rectangle.
+    ///
+    /// This is the area of the intersection of the shape and the rectangle,
+    /// as computed by [`intersection_area`], so it is exact for a rectangle
+    /// and otherwise uses the shape flattened with the given tolerance and
+    /// the nonzero fill rule; holes within the rectangle are not counted.
+    /// The result is never negative, whatever the orientation of the shape.
+    /// A shape whose bounding box does not overlap the rectangle gives zero
+    /// without further work.
+    ///
+    /// [`intersection_area`]: fn.intersection_area.html
+    fn area_in_rect(&self, rect: Rect, tolerance: f64) -> f64 {
+        let (bbox, rect) = (self.bounding_box().abs(), rect.abs());
+        if bbox.x1 <= rect.x0 || rect.x1 <= bbox.x0 || bbox.y1 <= rect.y0 || rect.y1 <= bbox.y0 {
+            return 0.0;
+        }
+        crate::intersection_area(self, &rect, tolerance)
+    }
+
+    /// The area of the convex hull of the shape not covered by the shape.
+    ///
+    /// This is zero for a convex shape and grows with its concavities and
+    /// holes, for example, a C shape has a larger defect than a blob of the
+    /// same area. The shape is flattened with the given tolerance; the hull is
+    /// that of the flattened points, and the covered area is the filled area
+    /// by the nonzero rule, not the signed area: holes, where the winding
+    /// number is zero, count as uncovered, and regions covered by several
+    /// subpaths count once. The result is an area, scaling with the square of the
+    /// size of the shape; see [`solidity`] for a measure independent of
+    /// scale. Differences in area due to flattening are clamped at zero.
+    ///
+    /// [`solidity`]: #method.solidity
+    fn convexity_defect(&self, tolerance: f64) -> f64 {
+        let (hull, filled) = crate::boolean::hull_and_filled_area(self, tolerance);
+        (hull - filled).max(0.0)
+    }
+
+    /// The ratio of the filled area of the shape to that of its convex hull.
+    ///
+    /// This is one for a convex shape, and smaller for a shape with
+    /// concavities or holes, independently of scale; one minus the ratio is
+    /// the [`convexity_defect`] relative to the area of the hull. The areas
+    /// are computed as for [`convexity_defect`]. A shape whose hull has no
+    /// area, such as a line, has a solidity of one.
+    ///
+    /// [`convexity_defect`]: #method.convexity_defect
+    fn solidity(&self, tolerance: f64) -> f64 {
+        let (hull, filled) = crate::boolean::hull_and_filled_area(self, tolerance);
+        if hull > 0.0 {
+            (filled / hull).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// The largest circle inside the shape.
+    ///
+    /// This is found by the "polylabel" algorithm, a quadtree subdivision of
+    /// the bounding box that maximizes the [`signed_distance`] to the
+    /// boundary, which includes the boundaries of any holes; insideness is
+    /// by the nonzero rule. The radius is within `precision` of the largest
+    /// possible, which controls how deep the subdivision goes. The center is
+    /// a good place for a label, well inside irregular shapes for which the
+    /// centroid may be near an edge or outside.
+    ///
+    /// If the shape has no interior, the radius is zero.
+    ///
+    /// [`signed_distance`]: struct.BezPath.html#method.signed_distance
+    fn pole_of_inaccessibility(&self, precision: f64) -> Circle {
+        crate::polylabel::pole_of_inaccessibility(&self.to_path(precision * 0.1), precision)
+    }
+
     /// The smallest rectangle that encloses the shape.
     fn bounding_box(&self) -> Rect;
 
+    /// A rectangle that encloses the shape, possibly larger than the
+    /// [`bounding_box`].
+    ///
+    /// This is intended for coarse culling, where a cheap but conservative
+    /// estimate is preferable: the result always contains the exact bounding
+    /// box. For Bézier paths, this is the bounding box of all control points,
+    /// which avoids solving for extrema. By default, it is the same as the
+    /// exact bounding box.
+    ///
+    /// [`bounding_box`]: #tymethod.bounding_box
+    fn bounding_box_fast(&self) -> Rect {
+        self.bounding_box()
+    }
+
     /// If the shape is a line, make it available.
     fn as_line(&self) -> Option<Line> {
         None
@@ -121,7 +389,7 @@ pub trait Shape: Sized {
 }
 
 /// Blanket implementation so `impl Shape` will accept owned or reference.
-impl<'a, T: Shape> Shape for &'a T {
+impl<T: Shape> Shape for &T {
     type PathElementsIter = T::PathElementsIter;
 
     fn path_elements(&self, tolerance: f64) -> Self::PathElementsIter {
@@ -148,10 +416,62 @@ impl<'a, T: Shape> Shape for &'a T {
         (*self).winding(pt)
     }
 
+    fn contains(&self, pt: Point) -> bool {
+        (*self).contains(pt)
+    }
+
+    fn distance_to_point(&self, pt: Point, accuracy: f64) -> f64 {
+        (*self).distance_to_point(pt, accuracy)
+    }
+
+    fn farthest_point(&self, pt: Point, accuracy: f64) -> (Point, f64) {
+        (*self).farthest_point(pt, accuracy)
+    }
+
+    fn relate(&self, other: &impl Shape, tolerance: f64) -> SpatialRelation {
+        (*self).relate(other, tolerance)
+    }
+
+    fn tessellate(&self, tolerance: f64, fill_rule: FillRule) -> (Vec<Point>, Vec<[u32; 3]>) {
+        (*self).tessellate(tolerance, fill_rule)
+    }
+
+    fn to_polyline(&self, tolerance: f64) -> Vec<Vec<Point>> {
+        (*self).to_polyline(tolerance)
+    }
+
+    fn clip_to(&self, clip: &impl Shape, tolerance: f64) -> BezPath {
+        (*self).clip_to(clip, tolerance)
+    }
+
+    fn inset(&self, distance: f64, tolerance: f64) -> BezPath {
+        (*self).inset(distance, tolerance)
+    }
+
+    fn area_in_rect(&self, rect: Rect, tolerance: f64) -> f64 {
+        (*self).area_in_rect(rect, tolerance)
+    }
+
+    fn convexity_defect(&self, tolerance: f64) -> f64 {
+        (*self).convexity_defect(tolerance)
+    }
+
+    fn solidity(&self, tolerance: f64) -> f64 {
+        (*self).solidity(tolerance)
+    }
+
+    fn pole_of_inaccessibility(&self, precision: f64) -> Circle {
+        (*self).pole_of_inaccessibility(precision)
+    }
+
     fn bounding_box(&self) -> Rect {
         (*self).bounding_box()
     }
 
+    fn bounding_box_fast(&self) -> Rect {
+        (*self).bounding_box_fast()
+    }
+
     fn as_circle(&self) -> Option<Circle> {
         (*self).as_circle()
     }
@@ -172,3 +492,31 @@ impl<'a, T: Shape> Shape for &'a T {
         (*self).as_path_slice()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Shape};
+
+    #[test]
+    fn test_to_polyline() {
+        let mut path = crate::Circle::new((0.0, 0.0), 10.0).to_path(1e-9);
+        path.move_to((20.0, 0.0))
+            .quad_to((25.0, 10.0), (30.0, 0.0))
+            .close_path()
+            .line_to((20.0, -5.0));
+        let rings = path.to_polyline(0.01);
+        assert_eq!(rings.len(), 3);
+        // The circle is closed and within tolerance.
+        let circle = &rings[0];
+        assert_eq!(circle.first(), circle.last());
+        assert!(circle.len() > 20);
+        for w in circle.windows(2) {
+            let mid = w[0].midpoint(w[1]);
+            assert!((mid.distance(Point::ZERO) - 10.0).abs() <= 0.01 + 1e-9);
+        }
+        assert_eq!(rings[1].first(), Some(&Point::new(20.0, 0.0)));
+        assert_eq!(rings[1].last(), Some(&Point::new(20.0, 0.0)));
+        // After the `ClosePath`, an open subpath from the same start.
+        assert_eq!(rings[2], [Point::new(20.0, 0.0), Point::new(20.0, -5.0)]);
+    }
+}
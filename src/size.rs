@@ -200,6 +200,18 @@ impl Size {
         self.height / self.width
     }
 
+    /// Linearly interpolate between two sizes.
+    ///
+    /// The width and height are interpolated separately, as by
+    /// [`Vec2::lerp`], so the result is exact at `t` equal to 0 and 1, and
+    /// extrapolates for `t` outside `[0, 1]`.
+    ///
+    /// [`Vec2::lerp`]: struct.Vec2.html#method.lerp
+    #[inline]
+    pub fn lerp(self, other: Size, t: f64) -> Size {
+        self.to_vec2().lerp(other.to_vec2(), t).to_size()
+    }
+
     /// Convert this `Size` into a [`Rect`] with origin `(0.0, 0.0)`.
     ///
     /// [`Rect`]: struct.Rect.html
@@ -341,10 +353,10 @@ impl From<Size> for (f64, f64) {
 }
 
 //FIXME: remove for 0.6.0 https://github.com/linebender/kurbo/issues/95
-impl Into<Size> for Vec2 {
+impl From<Vec2> for Size {
     #[inline]
-    fn into(self) -> Size {
-        self.to_size()
+    fn from(v: Vec2) -> Size {
+        v.to_size()
     }
 }
 
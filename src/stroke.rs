@@ -0,0 +1,642 @@
+//! Stroke styles, queries on stroked paths, and ribbon outlines.
+
+use std::f64::consts::PI;
+
+use crate::bezpath::{end_tangent, start_tangent};
+use crate::{
+    Arc, BezPath, ParamCurve, ParamCurveDeriv, ParamCurveExtrema, PathEl, PathSeg, Point, Rect,
+    Vec2,
+};
+
+/// The shape drawn where two segments of a stroke meet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Join {
+    /// The outer corners of the two segments are connected by a line.
+    Bevel,
+    /// The outer edges are extended until they meet, unless the miter limit
+    /// is exceeded, in which case the join is beveled.
+    Miter,
+    /// The outer corners are connected by a circular arc.
+    Round,
+}
+
+/// The shape drawn at the ends of an open subpath.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cap {
+    /// The stroke ends exactly at the endpoint.
+    Butt,
+    /// The stroke is extended past the endpoint by half its width.
+    Square,
+    /// The stroke ends in a half circle around the endpoint.
+    Round,
+}
+
+/// The parameters of a stroke.
+///
+/// The default is a stroke of width 1 with miter joins, a miter limit of 4,
+/// and butt caps, as in SVG.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrokeStyle {
+    /// The width of the stroke.
+    pub width: f64,
+    /// The shape of the joins between segments.
+    pub join: Join,
+    /// The maximum ratio of the length of a miter join to the width.
+    pub miter_limit: f64,
+    /// The shape of the ends of open subpaths.
+    pub cap: Cap,
+}
+
+impl StrokeStyle {
+    /// A stroke style with the given width, and default joins and caps.
+    #[inline]
+    pub fn new(width: f64) -> StrokeStyle {
+        StrokeStyle {
+            width,
+            ..Default::default()
+        }
+    }
+
+    /// Builder method for setting the join style.
+    #[inline]
+    pub fn with_join(mut self, join: Join) -> StrokeStyle {
+        self.join = join;
+        self
+    }
+
+    /// Builder method for setting the miter limit.
+    #[inline]
+    pub fn with_miter_limit(mut self, miter_limit: f64) -> StrokeStyle {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Builder method for setting the cap style.
+    #[inline]
+    pub fn with_cap(mut self, cap: Cap) -> StrokeStyle {
+        self.cap = cap;
+        self
+    }
+}
+
+impl Default for StrokeStyle {
+    fn default() -> StrokeStyle {
+        StrokeStyle {
+            width: 1.0,
+            join: Join::Miter,
+            miter_limit: 4.0,
+            cap: Cap::Butt,
+        }
+    }
+}
+
+impl BezPath {
+    /// The smallest rectangle that encloses the stroke of the path.
+    ///
+    /// This is computed from the geometry of the stroke without building its
+    /// outline. Each segment contributes the points at half the width along
+    /// its normal at its ends and at its extrema, where the normal is
+    /// parallel to an axis. Miter joins add their tip, unless the miter
+    /// limit is exceeded, and round joins and caps add the extreme points of
+    /// their arcs. Square caps add their outer corners. Subpaths without any
+    /// segments contribute nothing, and neither does an empty path, which
+    /// returns the empty rectangle at the origin.
+    pub fn stroked_bounding_box(&self, style: &StrokeStyle) -> Rect {
+        let hw = 0.5 * style.width.abs();
+        let mut bbox: Option<Rect> = None;
+        let mut add = |p: Point| {
+            bbox = Some(match bbox {
+                Some(r) => r.union_pt(p),
+                None => Rect::from_points(p, p),
+            });
+        };
+        for subpath in self.subpaths() {
+            let closed = subpath.elements().last() == Some(&PathEl::ClosePath);
+            let segs: Vec<PathSeg> = subpath.segments().collect();
+            for &seg in &segs {
+                let ts = std::iter::once(0.0)
+                    .chain(seg.extrema())
+                    .chain(std::iter::once(1.0));
+                for t in ts {
+                    let p = seg.eval(t);
+                    let tangent = if t == 0.0 {
+                        start_tangent(seg)
+                    } else if t == 1.0 {
+                        end_tangent(seg)
+                    } else {
+                        Some(seg_deriv(seg, t))
+                            .filter(|d| d.hypot2() > 0.0)
+                            .map(Vec2::normalize)
+                    };
+                    if let Some(tangent) = tangent {
+                        let n = normal(tangent);
+                        add(p + hw * n);
+                        add(p - hw * n);
+                    } else {
+                        add_arc_extrema(&mut add, p, hw, Vec2::new(1.0, 0.0), 2.0 * PI);
+                    }
+                }
+            }
+            let n = segs.len();
+            let joins = if closed { n } else { n.saturating_sub(1) };
+            for i in 0..joins {
+                let (a, b) = (segs[i], segs[(i + 1) % n]);
+                if let (Some(d_in), Some(d_out)) = (end_tangent(a), start_tangent(b)) {
+                    add_join(&mut add, a.end(), d_in, d_out, hw, style);
+                }
+            }
+            if !closed && n > 0 {
+                if let Some(d) = start_tangent(segs[0]) {
+                    add_cap(&mut add, segs[0].start(), -d, hw, style.cap);
+                }
+                if let Some(d) = end_tangent(segs[n - 1]) {
+                    add_cap(&mut add, segs[n - 1].end(), d, hw, style.cap);
+                }
+            }
+        }
+        bbox.unwrap_or_default()
+    }
+}
+
+/// The outer apex of a miter join.
+///
+/// The corner is at `p_corner`, reached from `p_prev` and left towards
+/// `p_next`, and the outer edges of the stroke are offset by `half_width`
+/// from the centerline. Returns the point where the outer edges meet, or
+/// `None` if the ratio of the length of the miter to the width of the
+/// stroke, `1 / sin(θ / 2)` for an angle `θ` between the segments, exceeds
+/// `miter_limit`, in which case the join should be beveled.
+///
+/// If the segments continue in a straight line, the edges meet at the offset
+/// of the corner itself, to the left of the direction of travel, and the
+/// ratio is 1. A reversal, where the path doubles back on itself, has an
+/// infinitely long miter and always returns `None`, as does a corner with a
+/// zero length segment on either side, whose direction is not known.
+pub fn miter_point(
+    p_prev: Point,
+    p_corner: Point,
+    p_next: Point,
+    half_width: f64,
+    miter_limit: f64,
+) -> Option<Point> {
+    let (d_in, d_out) = (p_corner - p_prev, p_next - p_corner);
+    if d_in.hypot2() == 0.0 || d_out.hypot2() == 0.0 {
+        return None;
+    }
+    let (d_in, d_out) = (d_in.normalize(), d_out.normalize());
+    // The outer side is to the right of a left turn.
+    let side = if d_in.cross(d_out) > 0.0 { -1.0 } else { 1.0 };
+    let (n_in, n_out) = (side * normal(d_in), side * normal(d_out));
+    let cos = n_in.dot(n_out);
+    if 1.0 + cos <= 0.0 {
+        return None;
+    }
+    let miter = (n_in + n_out) / (1.0 + cos);
+    let ratio = miter.hypot();
+    if ratio.is_finite() && ratio <= miter_limit {
+        Some(p_corner + half_width.abs() * miter)
+    } else {
+        None
+    }
+}
+
+/// The tolerance of the arcs of [`join_round`], relative to their radius.
+///
+/// [`join_round`]: fn.join_round.html
+const ARC_RELATIVE_TOLERANCE: f64 = 1e-6;
+
+/// A round join: the arc around `center` from `from` to `to`.
+///
+/// The arc has the given `radius`, and runs the short way round, sweeping by
+/// at most half a turn, which is the arc on the outside of a corner at
+/// `center` whose offsets end at `from` and start at `to`. It is emitted as
+/// cubic Bézier `CurveTo` elements, each within a millionth of the radius
+/// of the true circle, to be appended after a path element ending at
+/// `from`. If `from` and `to` are in the same direction from the center, as
+/// at a corner where the path goes straight on, or the radius is zero,
+/// nothing is emitted. When they are in exactly opposite directions there
+/// are two half circles to choose from, and the counterclockwise one (in a
+/// y-up coordinate system) is used.
+pub fn join_round(
+    center: Point,
+    from: Point,
+    to: Point,
+    radius: f64,
+) -> impl Iterator<Item = PathEl> {
+    join_round_within(
+        center,
+        from,
+        to,
+        radius,
+        radius.abs() * ARC_RELATIVE_TOLERANCE,
+    )
+}
+
+/// The arc of [`join_round`], converted to cubics within `tolerance`.
+///
+/// [`join_round`]: fn.join_round.html
+pub(crate) fn join_round_within(
+    center: Point,
+    from: Point,
+    to: Point,
+    radius: f64,
+    tolerance: f64,
+) -> impl Iterator<Item = PathEl> {
+    let (v0, v1) = (from - center, to - center);
+    let sweep = v0.angle_between(v1);
+    let radius = radius.abs();
+    let arc = if sweep != 0.0 && radius > 0.0 && v0.hypot2() > 0.0 && v1.hypot2() > 0.0 {
+        Some(Arc {
+            center,
+            radii: Vec2::new(radius, radius),
+            start_angle: v0.atan2(),
+            sweep_angle: sweep,
+            x_rotation: 0.0,
+        })
+    } else {
+        None
+    };
+    arc.into_iter()
+        .flat_map(move |arc| arc.append_iter(tolerance))
+}
+
+/// The outline of a ribbon along a polyline, with a varying width.
+///
+/// `widths` gives the half-width at each point of `centerline`, and the
+/// half-width varies linearly along each segment in between; a point without
+/// a width uses the last one given, and negative widths are treated as
+/// positive. Each side of the ribbon is made of the offsets of the segments.
+/// At each interior point, the offsets on the outside of the turn are joined
+/// by a circular arc, and those on the inside meet at the point along the
+/// bisector, limited to four times the width for sharp turns. The ends have
+/// round caps, and the result is a single closed subpath, running forwards
+/// along the right side of the centerline and back along the left, so that
+/// it is counterclockwise in a y-up coordinate system.
+///
+/// Repeated points are skipped. Returns an empty path if there are fewer
+/// than two distinct points, or no widths.
+pub fn variable_width_outline(centerline: &[Point], widths: &[f64]) -> BezPath {
+    let mut path = BezPath::new();
+    let last_width = match widths.last() {
+        Some(&w) => w,
+        None => return path,
+    };
+    let mut points: Vec<(Point, f64)> = Vec::with_capacity(centerline.len());
+    for (i, &p) in centerline.iter().enumerate() {
+        let w = widths.get(i).copied().unwrap_or(last_width).abs();
+        match points.last_mut() {
+            Some(last) if last.0 == p => last.1 = w,
+            _ => points.push((p, w)),
+        }
+    }
+    if points.len() < 2 {
+        return path;
+    }
+    offset_side(&mut path, &points);
+    add_cap_arc(&mut path, &points[points.len() - 2..]);
+    points.reverse();
+    offset_side(&mut path, &points);
+    add_cap_arc(&mut path, &points[points.len() - 2..]);
+    path.close_path();
+    path
+}
+
+/// The limit on the length of the inner joins in `variable_width_outline`,
+/// relative to the width.
+const VARIABLE_WIDTH_INNER_LIMIT: f64 = 4.0;
+
+/// Append the offset to the right of a polyline.
+fn offset_side(path: &mut BezPath, points: &[(Point, f64)]) {
+    let n = points.len();
+    let dir = |i: usize| (points[i + 1].0 - points[i].0).normalize();
+    let right = |d: Vec2| -normal(d);
+    let start = points[0].0 + points[0].1 * right(dir(0));
+    if path.is_empty() {
+        path.move_to(start);
+    } else {
+        path.line_to(start);
+    }
+    for (i, &(p, w)) in points.iter().enumerate().take(n - 1).skip(1) {
+        let (d_in, d_out) = (dir(i - 1), dir(i));
+        let (n_in, n_out) = (right(d_in), right(d_out));
+        if d_in.cross(d_out) < 0.0 {
+            // The right side is on the inside of the turn.
+            let cos = n_in.dot(n_out);
+            let mut m = (n_in + n_out) / (1.0 + cos).max(1e-12);
+            if m.hypot() > VARIABLE_WIDTH_INNER_LIMIT {
+                m = m.normalize() * VARIABLE_WIDTH_INNER_LIMIT;
+            }
+            path.line_to(p + w * m);
+        } else {
+            path.line_to(p + w * n_in);
+            path.extend(join_round(p, p + w * n_in, p + w * n_out, w));
+        }
+    }
+    let (p, w) = points[n - 1];
+    path.line_to(p + w * right(dir(n - 2)));
+}
+
+/// Append a half circle around the second point, from the right to the left
+/// of the direction from the first.
+fn add_cap_arc(path: &mut BezPath, ends: &[(Point, f64)]) {
+    let (p, w) = ends[1];
+    if w > 0.0 {
+        let n = -normal((p - ends[0].0).normalize());
+        // The ends are opposite, so this is the counterclockwise half circle.
+        path.extend(join_round(p, p + w * n, p - w * n, w));
+    }
+}
+
+fn seg_deriv(seg: PathSeg, t: f64) -> Vec2 {
+    match seg {
+        PathSeg::Line(l) => l.p1 - l.p0,
+        PathSeg::Quad(q) => q.deriv().eval(t).to_vec2(),
+        PathSeg::Cubic(c) => c.deriv().eval(t).to_vec2(),
+    }
+}
+
+/// The unit normal to the left of a unit tangent.
+fn normal(tangent: Vec2) -> Vec2 {
+    Vec2::new(-tangent.y, tangent.x)
+}
+
+/// Add the points of the circle of radius `r` around `center` in the axis
+/// directions that lie within the arc starting in direction `from` and
+/// sweeping by `sweep` radians.
+fn add_arc_extrema(add: &mut impl FnMut(Point), center: Point, r: f64, from: Vec2, sweep: f64) {
+    let from_angle = from.atan2();
+    for k in 0..4 {
+        let angle = k as f64 * 0.5 * PI;
+        let mut delta = (angle - from_angle).rem_euclid(2.0 * PI);
+        if sweep < 0.0 {
+            delta = (from_angle - angle).rem_euclid(2.0 * PI);
+        }
+        if delta <= sweep.abs() {
+            add(center + r * Vec2::from_angle(angle));
+        }
+    }
+}
+
+/// Add the extent of the join at `v` between segments with the given unit
+/// end and start tangents.
+fn add_join(
+    add: &mut impl FnMut(Point),
+    v: Point,
+    d_in: Vec2,
+    d_out: Vec2,
+    hw: f64,
+    style: &StrokeStyle,
+) {
+    let cross = d_in.cross(d_out);
+    if cross == 0.0 && d_in.dot(d_out) > 0.0 {
+        return;
+    }
+    // The outer side is to the right of a left turn.
+    let side = if cross > 0.0 { -1.0 } else { 1.0 };
+    let (n_in, n_out) = (side * normal(d_in), side * normal(d_out));
+    match style.join {
+        Join::Bevel => (),
+        Join::Miter => {
+            if let Some(p) = miter_point(v - d_in, v, v + d_out, hw, style.miter_limit) {
+                add(p);
+            }
+        }
+        Join::Round => {
+            let sweep = if cross == 0.0 {
+                // A reversal: the arc goes around the front.
+                side * PI
+            } else {
+                n_in.angle_between(n_out)
+            };
+            add_arc_extrema(add, v, hw, n_in, sweep);
+        }
+    }
+}
+
+/// Add the extent of the cap at `p`, where the stroke ends while heading in
+/// the unit direction `d`.
+fn add_cap(add: &mut impl FnMut(Point), p: Point, d: Vec2, hw: f64, cap: Cap) {
+    let n = normal(d);
+    match cap {
+        Cap::Butt => (),
+        Cap::Square => {
+            add(p + hw * (d + n));
+            add(p + hw * (d - n));
+        }
+        Cap::Round => add_arc_extrema(add, p, hw, n, -PI),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        join_round, miter_point, variable_width_outline, BezPath, Cap, Circle, Join, ParamCurve,
+        ParamCurveDeriv, ParamCurveExtrema, PathEl, Point, Rect, Shape, StrokeStyle, Vec2,
+    };
+
+    fn assert_rect_eq(a: Rect, b: Rect) {
+        let d = (a.x0 - b.x0)
+            .abs()
+            .max((a.y0 - b.y0).abs())
+            .max((a.x1 - b.x1).abs())
+            .max((a.y1 - b.y1).abs());
+        assert!(d < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn stroked_bbox_caps() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((10.0, 0.0));
+        let style = StrokeStyle::new(2.0);
+        assert_rect_eq(
+            path.stroked_bounding_box(&style),
+            Rect::new(0.0, -1.0, 10.0, 1.0),
+        );
+        let square = style.with_cap(Cap::Square);
+        assert_rect_eq(
+            path.stroked_bounding_box(&square),
+            Rect::new(-1.0, -1.0, 11.0, 1.0),
+        );
+        // A diagonal line: round caps reach a full radius past the ends.
+        let mut diag = BezPath::new();
+        diag.move_to((0.0, 0.0));
+        diag.line_to((3.0, 4.0));
+        let round = style.with_cap(Cap::Round);
+        assert_rect_eq(
+            diag.stroked_bounding_box(&round),
+            Rect::new(-1.0, -1.0, 4.0, 5.0),
+        );
+        // Butt caps only extend along the normal.
+        assert_rect_eq(
+            diag.stroked_bounding_box(&style),
+            Rect::new(-0.8, -0.6, 3.8, 4.6),
+        );
+        // Curves are bounded at their extrema.
+        let circle = Circle::new((0.0, 0.0), 5.0).to_path(1e-9);
+        assert_rect_eq(
+            circle.stroked_bounding_box(&style),
+            Rect::new(-6.0, -6.0, 6.0, 6.0),
+        );
+    }
+
+    #[test]
+    fn stroked_bbox_s_curve() {
+        // An S-shaped cubic with more extrema than fit alongside its ends.
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.curve_to((1.0, 2.0), (2.0, -2.0), (0.0, 1.0));
+        let seg = path.segments().next().unwrap();
+        assert!(seg.extrema().len() >= 3);
+        let style = StrokeStyle::new(0.5);
+        let bbox = path.stroked_bounding_box(&style);
+        // Every point of the stroke's sides is inside, and the box is tight.
+        let mut sampled: Option<Rect> = None;
+        for i in 0..=1000 {
+            let t = i as f64 / 1000.0;
+            let d = seg.deriv().eval(t).to_vec2();
+            let n = Vec2::new(-d.y, d.x).normalize() * 0.25;
+            for &p in &[seg.eval(t) + n, seg.eval(t) - n] {
+                assert!(bbox.inflate(1e-9, 1e-9).contains(p));
+                sampled = Some(sampled.map_or(Rect::from_points(p, p), |r| r.union_pt(p)));
+            }
+        }
+        let sampled = sampled.unwrap();
+        assert!(bbox.width() - sampled.width() < 1e-3);
+        assert!(bbox.height() - sampled.height() < 1e-3);
+    }
+
+    #[test]
+    fn stroked_bbox_joins() {
+        // A sharp wedge pointing up: its miter is about 6.1 half-widths long.
+        let mut path = BezPath::new();
+        path.move_to((-1.0, 0.0));
+        path.line_to((0.0, 6.0));
+        path.line_to((1.0, 0.0));
+        let miter = StrokeStyle::new(2.0).with_miter_limit(10.0);
+        let tip = 37.0f64.sqrt();
+        let bbox = path.stroked_bounding_box(&miter);
+        assert!((bbox.y1 - (6.0 + tip)).abs() < 1e-9);
+        // Beyond the limit, the join is beveled, and the outer corners of
+        // the segments are the extremes.
+        let beveled = path.stroked_bounding_box(&miter.with_miter_limit(4.0));
+        assert!(beveled.y1 < 6.2);
+        assert_eq!(
+            beveled,
+            path.stroked_bounding_box(&miter.with_join(Join::Bevel))
+        );
+        let round = path.stroked_bounding_box(&miter.with_join(Join::Round));
+        assert!((round.y1 - 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn miter_points() {
+        let p = |x, y| Point::new(x, y);
+        // A right angle turning left: the apex is at the outer corner.
+        let apex = miter_point(p(0.0, 0.0), p(2.0, 0.0), p(2.0, 2.0), 1.0, 4.0).unwrap();
+        assert!((apex - p(3.0, -1.0)).hypot() < 1e-12);
+        // Turning right, the outer side is on the left.
+        let apex = miter_point(p(0.0, 0.0), p(2.0, 0.0), p(2.0, -2.0), 1.0, 4.0).unwrap();
+        assert!((apex - p(3.0, 1.0)).hypot() < 1e-12);
+        // The right angle has a ratio of sqrt(2).
+        assert!(miter_point(p(0.0, 0.0), p(2.0, 0.0), p(2.0, 2.0), 1.0, 1.4).is_none());
+
+        // Nearly straight corners stay close to the offset of the corner.
+        let apex = miter_point(p(0.0, 0.0), p(1.0, 0.0), p(2.0, 1e-9), 0.5, 1.0).unwrap();
+        assert!((apex - p(1.0, -0.5)).hypot() < 1e-9);
+        let apex = miter_point(p(0.0, 0.0), p(1.0, 0.0), p(2.0, 0.0), 0.5, 1.0).unwrap();
+        assert_eq!(apex, p(1.0, 0.5));
+
+        // Reversals and nearly reversals exceed any limit.
+        assert!(miter_point(p(0.0, 0.0), p(1.0, 0.0), p(0.0, 0.0), 0.5, 1e9).is_none());
+        assert!(miter_point(p(0.0, 0.0), p(1.0, 0.0), p(0.0, 1e-9), 0.5, 1e3).is_none());
+        let sharp = miter_point(p(0.0, 0.0), p(1.0, 0.0), p(0.0, 1e-2), 0.5, 1e3).unwrap();
+        assert!((sharp - p(1.0, 0.0)).hypot() > 0.5 * 100.0);
+        assert!(miter_point(p(0.0, 0.0), p(0.0, 0.0), p(1.0, 0.0), 0.5, 4.0).is_none());
+    }
+
+    #[test]
+    fn round_joins() {
+        let p = |x, y| Point::new(x, y);
+        let ends = |els: &[PathEl]| -> Vec<Point> {
+            els.iter()
+                .map(|el| match *el {
+                    PathEl::CurveTo(_, _, p3) => p3,
+                    _ => panic!("expected only curves, got {:?}", el),
+                })
+                .collect()
+        };
+        // A left turn at the origin, the offsets on the outside (right).
+        let center = p(0.0, 0.0);
+        let (from, to) = (p(0.0, -2.0), p(2.0, 0.0));
+        let join: Vec<_> = join_round(center, from, to, 2.0).collect();
+        assert!(!join.is_empty());
+        let pts = ends(&join);
+        assert!((*pts.last().unwrap() - to).hypot() < 1e-9);
+        let mut path = BezPath::new();
+        path.move_to(from);
+        path.extend(join.iter().cloned());
+        for seg in path.segments() {
+            for i in 0..=8 {
+                let q = seg.eval(i as f64 / 8.0);
+                assert!(((q - center).hypot() - 2.0).abs() < 2e-6);
+                // The minor arc, through the fourth quadrant.
+                assert!(q.x >= -1e-9 && q.y <= 1e-9);
+            }
+        }
+        // The opposite direction retraces the same arc.
+        let back = ends(&join_round(center, to, from, 2.0).collect::<Vec<_>>());
+        assert!((*back.last().unwrap() - from).hypot() < 1e-9);
+        assert!(back.iter().all(|q| q.x >= -1e-9 && q.y <= 1e-9));
+
+        // Collinear corners and zero widths emit nothing.
+        assert_eq!(join_round(center, p(0.0, 1.0), p(0.0, 1.0), 1.0).count(), 0);
+        assert_eq!(join_round(center, p(0.0, 1.0), p(0.0, 3.0), 1.0).count(), 0);
+        assert_eq!(join_round(center, center, center, 0.0).count(), 0);
+        // A reversal emits a half circle.
+        let half = ends(&join_round(center, p(0.0, -1.0), p(0.0, 1.0), 1.0).collect::<Vec<_>>());
+        assert!((*half.last().unwrap() - p(0.0, 1.0)).hypot() < 1e-9);
+        assert!(half.iter().any(|q| q.x > 0.9));
+    }
+
+    #[test]
+    fn variable_width_ribbon() {
+        // A straight tapered ribbon: a trapezoid with round ends.
+        let line = [Point::new(0.0, 0.0), Point::new(10.0, 0.0)];
+        let outline = variable_width_outline(&line, &[1.0, 2.0]);
+        assert!(matches!(
+            outline.elements().last(),
+            Some(crate::PathEl::ClosePath)
+        ));
+        assert!(outline.area() > 0.0);
+        let expected = 30.0 + 0.5 * std::f64::consts::PI * (1.0 + 4.0);
+        assert!((outline.area() - expected).abs() < 1e-5);
+        assert!(outline.contains(Point::new(11.9, 0.0)));
+        assert!(!outline.contains(Point::new(5.0, 1.6)));
+        assert!(outline.contains(Point::new(5.0, 1.4)));
+
+        // A bent ribbon covers the points near each corner, without gaps at
+        // the outer join.
+        let bent = [
+            Point::new(0.0, 0.0),
+            Point::new(5.0, 0.0),
+            Point::new(5.0, 5.0),
+            Point::new(0.0, 5.0),
+        ];
+        let outline = variable_width_outline(&bent, &[0.5]);
+        assert_eq!(outline.winding(Point::new(5.4, -0.2)), 1);
+        assert_eq!(outline.winding(Point::new(4.8, 0.2)), 1);
+        assert_eq!(outline.winding(Point::new(2.5, 2.5)), 0);
+        // Each corner adds a quarter of the outer circle and loses a square
+        // of overlap on the inside, and the caps make up a full circle.
+        let quarter = 0.25 * std::f64::consts::PI * 0.25;
+        let expected = 15.0 + 2.0 * (quarter - 0.25) + 4.0 * quarter;
+        assert!((outline.area() - expected).abs() < 1e-5);
+
+        assert!(variable_width_outline(&bent[..1], &[1.0]).is_empty());
+        assert!(variable_width_outline(&bent, &[]).is_empty());
+    }
+}
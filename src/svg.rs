@@ -5,7 +5,7 @@ use std::f64::consts::PI;
 use std::fmt::{self, Display, Formatter};
 use std::io::{self, Write};
 
-use crate::{Arc, BezPath, ParamCurve, PathEl, PathSeg, Point, Vec2};
+use crate::{Arc, ArcAppendIter, BezPath, ParamCurve, PathEl, PathSeg, Point, Vec2};
 
 // Note: the SVG arc logic is heavily adapted from https://github.com/nical/lyon
 
@@ -87,34 +87,96 @@ impl BezPath {
     /// user controls the source of paths, and is not intended as a replacement
     /// for a general, robust SVG parser.
     pub fn from_svg(data: &str) -> Result<BezPath, SvgParseError> {
-        let mut lexer = SvgLexer::new(data);
-        let mut path = BezPath::new();
-        let mut last_cmd = 0;
-        let mut last_ctrl = None;
-        let mut first_pt = Point::ORIGIN;
-        let mut implicit_moveto = None;
-        while let Some(c) = lexer.get_cmd(last_cmd) {
-            if c != b'm' && c != b'M' {
-                if let Some(pt) = implicit_moveto.take() {
-                    path.move_to(pt);
-                }
-            }
-            match c {
+        SvgPathParser::new(data).collect()
+    }
+}
+
+/// An iterator parsing the elements of an SVG path one at a time.
+///
+/// This is the streaming form of [`BezPath::from_svg`], yielding the same
+/// elements in the same order, without building the whole path. The state
+/// needed between commands, such as the current point for relative
+/// coordinates, the start of the subpath and the control point reflected by
+/// smooth curves, is kept by the parser, so a command repeated implicitly by
+/// further numbers continues as it would in a single pass. Arcs yield one
+/// `CurveTo` at a time as well.
+///
+/// After an error, which is yielded as an `Err` item, the iterator ends.
+///
+/// # Examples
+///
+/// ```
+/// use kurbo::{PathEl, Point, SvgPathParser};
+/// let mut parser = SvgPathParser::new("m1 2 3 4 h5 z l 1 1");
+/// assert_eq!(parser.next().unwrap().unwrap(), PathEl::MoveTo(Point::new(1.0, 2.0)));
+/// // The implicit line after the move is relative to it.
+/// assert_eq!(parser.next().unwrap().unwrap(), PathEl::LineTo(Point::new(4.0, 6.0)));
+/// assert_eq!(parser.next().unwrap().unwrap(), PathEl::LineTo(Point::new(9.0, 6.0)));
+/// assert_eq!(parser.next().unwrap().unwrap(), PathEl::ClosePath);
+/// // A command after a close starts a new subpath at the old start.
+/// assert_eq!(parser.next().unwrap().unwrap(), PathEl::MoveTo(Point::new(1.0, 2.0)));
+/// assert_eq!(parser.next().unwrap().unwrap(), PathEl::LineTo(Point::new(2.0, 3.0)));
+/// assert!(parser.next().is_none());
+/// ```
+///
+/// [`BezPath::from_svg`]: struct.BezPath.html#method.from_svg
+pub struct SvgPathParser<'a> {
+    lexer: SvgLexer<'a>,
+    last_cmd: u8,
+    last_ctrl: Option<Point>,
+    first_pt: Point,
+    implicit_moveto: Option<Point>,
+    /// An element parsed along with the implicit `MoveTo` before it.
+    pending: Option<PathEl>,
+    /// The rest of the current arc.
+    arc: Option<ArcAppendIter>,
+    failed: bool,
+}
+
+impl<'a> SvgPathParser<'a> {
+    /// Create a parser for the SVG path data `data`.
+    pub fn new(data: &'a str) -> SvgPathParser<'a> {
+        SvgPathParser {
+            lexer: SvgLexer::new(data),
+            last_cmd: 0,
+            last_ctrl: None,
+            first_pt: Point::ORIGIN,
+            implicit_moveto: None,
+            pending: None,
+            arc: None,
+            failed: false,
+        }
+    }
+
+    /// Parse the next command, returning its first element.
+    fn parse_command(&mut self) -> Result<Option<PathEl>, SvgParseError> {
+        loop {
+            let c = match self.lexer.get_cmd(self.last_cmd) {
+                Some(c) => c,
+                None => return Ok(None),
+            };
+            let moveto = if c != b'm' && c != b'M' {
+                self.implicit_moveto.take()
+            } else {
+                None
+            };
+            let lexer = &mut self.lexer;
+            let el = match c {
                 b'm' | b'M' => {
-                    implicit_moveto = None;
+                    self.implicit_moveto = None;
                     let pt = lexer.get_maybe_relative(c)?;
-                    path.move_to(pt);
                     lexer.last_pt = pt;
-                    first_pt = pt;
-                    last_ctrl = Some(pt);
-                    last_cmd = c - (b'M' - b'L');
+                    self.first_pt = pt;
+                    self.last_ctrl = Some(pt);
+                    self.last_cmd = c - (b'M' - b'L');
+                    PathEl::MoveTo(pt)
                 }
                 b'l' | b'L' => {
                     let pt = lexer.get_maybe_relative(c)?;
-                    path.line_to(pt);
                     lexer.last_pt = pt;
-                    last_ctrl = Some(pt);
-                    last_cmd = c;
+                    self.last_ctrl = Some(pt);
+                    self.last_cmd = c;
+                    PathEl::LineTo(pt)
                 }
                 b'h' | b'H' => {
                     let mut x = lexer.get_number()?;
@@ -123,10 +185,10 @@ impl BezPath {
                         x += lexer.last_pt.x;
                     }
                     let pt = Point::new(x, lexer.last_pt.y);
-                    path.line_to(pt);
                     lexer.last_pt = pt;
-                    last_ctrl = Some(pt);
-                    last_cmd = c;
+                    self.last_ctrl = Some(pt);
+                    self.last_cmd = c;
+                    PathEl::LineTo(pt)
                 }
                 b'v' | b'V' => {
                     let mut y = lexer.get_number()?;
@@ -135,50 +197,50 @@ impl BezPath {
                         y += lexer.last_pt.y;
                     }
                     let pt = Point::new(lexer.last_pt.x, y);
-                    path.line_to(pt);
                     lexer.last_pt = pt;
-                    last_ctrl = Some(pt);
-                    last_cmd = c;
+                    self.last_ctrl = Some(pt);
+                    self.last_cmd = c;
+                    PathEl::LineTo(pt)
                 }
                 b'q' | b'Q' => {
                     let p1 = lexer.get_maybe_relative(c)?;
                     let p2 = lexer.get_maybe_relative(c)?;
-                    path.quad_to(p1, p2);
-                    last_ctrl = Some(p1);
+                    self.last_ctrl = Some(p1);
                     lexer.last_pt = p2;
-                    last_cmd = c;
+                    self.last_cmd = c;
+                    PathEl::QuadTo(p1, p2)
                 }
                 b't' | b'T' => {
-                    let p1 = match last_ctrl {
+                    let p1 = match self.last_ctrl {
                         Some(ctrl) => (2.0 * lexer.last_pt.to_vec2() - ctrl.to_vec2()).to_point(),
                         None => lexer.last_pt,
                     };
                     let p2 = lexer.get_maybe_relative(c)?;
-                    path.quad_to(p1, p2);
-                    last_ctrl = Some(p1);
+                    self.last_ctrl = Some(p1);
                     lexer.last_pt = p2;
-                    last_cmd = c;
+                    self.last_cmd = c;
+                    PathEl::QuadTo(p1, p2)
                 }
                 b'c' | b'C' => {
                     let p1 = lexer.get_maybe_relative(c)?;
                     let p2 = lexer.get_maybe_relative(c)?;
                     let p3 = lexer.get_maybe_relative(c)?;
-                    path.curve_to(p1, p2, p3);
-                    last_ctrl = Some(p2);
+                    self.last_ctrl = Some(p2);
                     lexer.last_pt = p3;
-                    last_cmd = c;
+                    self.last_cmd = c;
+                    PathEl::CurveTo(p1, p2, p3)
                 }
                 b's' | b'S' => {
-                    let p1 = match last_ctrl {
+                    let p1 = match self.last_ctrl {
                         Some(ctrl) => (2.0 * lexer.last_pt.to_vec2() - ctrl.to_vec2()).to_point(),
                         None => lexer.last_pt,
                     };
                     let p2 = lexer.get_maybe_relative(c)?;
                     let p3 = lexer.get_maybe_relative(c)?;
-                    path.curve_to(p1, p2, p3);
-                    last_ctrl = Some(p2);
+                    self.last_ctrl = Some(p2);
                     lexer.last_pt = p3;
-                    last_cmd = c;
+                    self.last_cmd = c;
+                    PathEl::CurveTo(p1, p2, p3)
                 }
                 b'a' | b'A' => {
                     let radii = lexer.get_number_pair()?;
@@ -197,32 +259,71 @@ impl BezPath {
                         large_arc,
                         sweep,
                     };
+                    self.last_ctrl = Some(p);
+                    lexer.last_pt = p;
+                    self.last_cmd = c;
 
                     match Arc::from_svg_arc(&svg_arc) {
                         Some(arc) => {
                             // TODO: consider making tolerance configurable
-                            arc.to_cubic_beziers(0.1, |p1, p2, p3| {
-                                path.curve_to(p1, p2, p3);
-                            });
-                        }
-                        None => {
-                            path.line_to(p);
+                            let mut iter = arc.append_iter(0.1);
+                            match iter.next() {
+                                Some(el) => {
+                                    self.arc = Some(iter);
+                                    el
+                                }
+                                None => {
+                                    // An empty arc still ends an implicit
+                                    // `MoveTo`.
+                                    if let Some(pt) = moveto {
+                                        return Ok(Some(PathEl::MoveTo(pt)));
+                                    }
+                                    continue;
+                                }
+                            }
                         }
+                        None => PathEl::LineTo(p),
                     }
-
-                    last_ctrl = Some(p);
-                    lexer.last_pt = p;
-                    last_cmd = c;
                 }
                 b'z' | b'Z' => {
-                    path.close_path();
-                    lexer.last_pt = first_pt;
-                    implicit_moveto = Some(first_pt);
+                    lexer.last_pt = self.first_pt;
+                    self.implicit_moveto = Some(self.first_pt);
+                    PathEl::ClosePath
                 }
                 _ => return Err(SvgParseError::UnknownCommand(c as char)),
+            };
+            return Ok(Some(match moveto {
+                Some(pt) => {
+                    self.pending = Some(el);
+                    PathEl::MoveTo(pt)
+                }
+                None => el,
+            }));
+        }
+    }
+}
+
+impl<'a> Iterator for SvgPathParser<'a> {
+    type Item = Result<PathEl, SvgParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(el) = self.pending.take() {
+            return Some(Ok(el));
+        }
+        if let Some(el) = self.arc.as_mut().and_then(Iterator::next) {
+            return Some(Ok(el));
+        }
+        self.arc = None;
+        if self.failed {
+            return None;
+        }
+        match self.parse_command() {
+            Ok(el) => el.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
             }
         }
-        Ok(path)
     }
 }
 
@@ -256,7 +357,7 @@ struct SvgLexer<'a> {
 }
 
 impl<'a> SvgLexer<'a> {
-    fn new(data: &str) -> SvgLexer {
+    fn new(data: &'a str) -> SvgLexer<'a> {
         SvgLexer {
             data,
             ix: 0,
@@ -276,9 +377,9 @@ impl<'a> SvgLexer<'a> {
     fn get_cmd(&mut self, last_cmd: u8) -> Option<u8> {
         self.skip_ws();
         if let Some(c) = self.get_byte() {
-            if (c >= b'a' && c <= b'z') || (c >= b'A' && c <= b'Z') {
+            if c.is_ascii_alphabetic() {
                 return Some(c);
-            } else if last_cmd != 0 && (c == b'-' || c == b'.' || (c >= b'0' && c <= b'9')) {
+            } else if last_cmd != 0 && (c == b'-' || c == b'.' || c.is_ascii_digit()) {
                 // Plausible number start
                 self.unget();
                 return Some(last_cmd);
@@ -310,7 +411,7 @@ impl<'a> SvgLexer<'a> {
         let mut digit_count = 0;
         let mut seen_period = false;
         while let Some(c) = self.get_byte() {
-            if c >= b'0' && c <= b'9' {
+            if c.is_ascii_digit() {
                 digit_count += 1;
             } else if c == b'.' && !seen_period {
                 seen_period = true;
@@ -325,11 +426,11 @@ impl<'a> SvgLexer<'a> {
                 if c == b'-' || c == b'+' {
                     c = self.get_byte().ok_or(SvgParseError::Wrong)?
                 }
-                if !(c >= b'0' && c <= b'9') {
+                if !c.is_ascii_digit() {
                     return Err(SvgParseError::Wrong);
                 }
                 while let Some(c) = self.get_byte() {
-                    if !(c >= b'0' && c <= b'9') {
+                    if !c.is_ascii_digit() {
                         self.unget();
                         break;
                     }
@@ -366,7 +467,7 @@ impl<'a> SvgLexer<'a> {
 
     fn get_maybe_relative(&mut self, cmd: u8) -> Result<Point, SvgParseError> {
         let pt = self.get_number_pair()?;
-        if cmd >= b'a' && cmd <= b'z' {
+        if cmd.is_ascii_lowercase() {
             Ok(self.last_pt + pt.to_vec2())
         } else {
             Ok(pt)
@@ -480,7 +581,10 @@ impl Arc {
 
 #[cfg(test)]
 mod tests {
-    use crate::{BezPath, CubicBez, Line, ParamCurve, PathSeg, Point, QuadBez, Shape};
+    use crate::{
+        BezPath, CubicBez, Line, ParamCurve, PathEl, PathSeg, Point, QuadBez, Shape, SvgParseError,
+        SvgPathParser,
+    };
 
     #[test]
     fn test_parse_svg() {
@@ -495,6 +599,40 @@ mod tests {
         assert_eq!(path.segments().count(), 6);
     }
 
+    #[test]
+    fn test_svg_path_parser() {
+        let data = "M1 2c1 1 2 2 3 0 1-1 2 0 3 1s4 0 5 5q1 1 2 0t3 3zh4v-2a3 2 30 1 0 5 5 \
+                    3 3 0 0 1 -2 2zZm1 1 2 2z";
+        let path = BezPath::from_svg(data).unwrap();
+        let streamed: Vec<PathEl> = SvgPathParser::new(data).map(Result::unwrap).collect();
+        assert_eq!(streamed, path.elements());
+        // A close followed by a command, and by another close, implies a
+        // move to the start of the subpath.
+        let moves = streamed
+            .iter()
+            .filter(|el| matches!(el, PathEl::MoveTo(_)))
+            .count();
+        assert_eq!(moves, 4);
+        assert_eq!(
+            streamed[2],
+            PathEl::CurveTo((5.0, 1.0).into(), (6.0, 2.0).into(), (7.0, 3.0).into())
+        );
+
+        // The elements before an error are yielded, and nothing after it.
+        let mut parser = SvgPathParser::new("M0 0 L1 1 2 x 3 3");
+        assert_eq!(
+            parser.next().unwrap().unwrap(),
+            PathEl::MoveTo(Point::new(0.0, 0.0))
+        );
+        assert_eq!(
+            parser.next().unwrap().unwrap(),
+            PathEl::LineTo(Point::new(1.0, 1.0))
+        );
+        assert!(matches!(parser.next(), Some(Err(SvgParseError::Wrong))));
+        assert!(parser.next().is_none());
+        assert!(SvgPathParser::new("").next().is_none());
+    }
+
     #[test]
     fn test_parse_svg_arc() {
         let path = BezPath::from_svg("M 100 100 A 25 25 0 1 0 -25 25 z").unwrap();
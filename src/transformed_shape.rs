@@ -0,0 +1,182 @@
+//! A shape under an affine transformation.
+
+use crate::{Affine, Point, Rect, Shape, Transformed};
+
+/// A shape with an affine transformation applied to it.
+///
+/// The path elements of the wrapped shape are transformed lazily, so this
+/// can be passed anywhere a [`Shape`] is expected without converting to a
+/// [`BezPath`] first. Area and winding number are computed from those of
+/// the wrapped shape: the area is scaled by the determinant of the
+/// transform, and the winding number is that of the inverse transformed
+/// point, negated when the transform is a reflection (has a negative
+/// determinant), so that its sign stays consistent with the area.
+///
+/// [`Shape`]: trait.Shape.html
+/// [`BezPath`]: struct.BezPath.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransformedShape<S: Shape> {
+    shape: S,
+    affine: Affine,
+}
+
+impl<S: Shape> TransformedShape<S> {
+    /// Wrap a shape with a transform.
+    #[inline]
+    pub fn new(shape: S, affine: Affine) -> TransformedShape<S> {
+        TransformedShape { shape, affine }
+    }
+
+    /// The shape before transformation.
+    #[inline]
+    pub fn shape(&self) -> &S {
+        &self.shape
+    }
+
+    /// The transform applied to the shape.
+    #[inline]
+    pub fn affine(&self) -> Affine {
+        self.affine
+    }
+
+    /// The shape before transformation, discarding the transform.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.shape
+    }
+
+    /// The largest factor by which the transform stretches a distance.
+    fn max_scale(&self) -> f64 {
+        let [a, b, c, d, _, _] = self.affine.as_coeffs();
+        let sum = a * a + b * b + c * c + d * d;
+        let det = self.affine.determinant();
+        (0.5 * (sum + (sum * sum - 4.0 * det * det).max(0.0).sqrt())).sqrt()
+    }
+
+    /// Whether the transform maps axis-aligned rectangles to axis-aligned
+    /// rectangles, that is, it only scales and translates.
+    fn is_axis_aligned(&self) -> bool {
+        let [_, b, c, _, _, _] = self.affine.as_coeffs();
+        b == 0.0 && c == 0.0
+    }
+}
+
+/// The tolerance used to compute the bounding box of a transformed curved
+/// shape.
+const BBOX_TOLERANCE: f64 = 1e-9;
+
+impl<S: Shape> Shape for TransformedShape<S> {
+    type PathElementsIter = Transformed<S::PathElementsIter>;
+
+    /// The tolerance is divided by the largest scale factor of the transform
+    /// before it is passed to the wrapped shape, so that the transformed
+    /// approximation stays within `tolerance`.
+    fn path_elements(&self, tolerance: f64) -> Self::PathElementsIter {
+        let scale = self.max_scale();
+        let tolerance = if scale > 0.0 {
+            tolerance / scale
+        } else {
+            tolerance
+        };
+        self.affine
+            .transform_elements(self.shape.path_elements(tolerance))
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.shape.area() * self.affine.determinant()
+    }
+
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        self.path_segments(accuracy).perimeter(accuracy)
+    }
+
+    /// Returns 0 everywhere if the transform is singular.
+    fn winding(&self, pt: Point) -> i32 {
+        let det = self.affine.determinant();
+        if det == 0.0 {
+            return 0;
+        }
+        let winding = self.shape.winding(self.affine.inverse() * pt);
+        if det < 0.0 {
+            -winding
+        } else {
+            winding
+        }
+    }
+
+    /// For transforms that only scale and translate, this is the transformed
+    /// bounding box of the wrapped shape. Otherwise it is computed from the
+    /// transformed path elements.
+    fn bounding_box(&self) -> Rect {
+        if self.is_axis_aligned() {
+            self.affine.transform_rect_bbox(self.shape.bounding_box())
+        } else {
+            self.path_segments(BBOX_TOLERANCE).bounding_box()
+        }
+    }
+
+    #[inline]
+    fn bounding_box_fast(&self) -> Rect {
+        self.affine
+            .transform_rect_bbox(self.shape.bounding_box_fast())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Affine, Circle, Point, Rect, Shape, TransformedShape, Vec2};
+
+    fn assert_approx_eq(x: f64, y: f64, eps: f64) {
+        assert!((x - y).abs() < eps, "{} != {}", x, y);
+    }
+
+    #[test]
+    fn transformed_shape_area_winding() {
+        let rect = Rect::new(0.0, 0.0, 2.0, 1.0);
+        let affine = Affine::translate(Vec2::new(1.0, 1.0)) * Affine::scale_non_uniform(3.0, 2.0);
+        let t = TransformedShape::new(rect, affine);
+        assert_eq!(t.area(), 12.0);
+        assert_eq!(t.winding(Point::new(4.0, 2.0)), 1);
+        assert_eq!(t.winding(Point::new(0.5, 2.0)), 0);
+        assert_eq!(t.bounding_box(), Rect::new(1.0, 1.0, 7.0, 3.0));
+        assert_eq!(t.perimeter(1e-9), 16.0);
+
+        // A reflection negates both the area and the winding number.
+        let flipped = TransformedShape::new(rect, Affine::FLIP_Y);
+        assert_eq!(flipped.area(), -2.0);
+        assert_eq!(flipped.winding(Point::new(1.0, -0.5)), -1);
+        let path = flipped.to_path(1e-9);
+        assert_eq!(path.area(), flipped.area());
+        assert_eq!(path.winding(Point::new(1.0, -0.5)), -1);
+
+        let singular = TransformedShape::new(rect, Affine::scale_non_uniform(1.0, 0.0));
+        assert_eq!(singular.area(), 0.0);
+        assert_eq!(singular.winding(Point::new(1.0, 0.0)), 0);
+    }
+
+    #[test]
+    fn transformed_shape_curved() {
+        let circle = Circle::new((0.0, 0.0), 1.0);
+        let affine = Affine::rotate(0.5) * Affine::scale_non_uniform(4.0, 1.0);
+        let ellipse = TransformedShape::new(circle, affine);
+        let tolerance = 1e-3;
+        let path = ellipse.to_path(tolerance);
+        assert_approx_eq(ellipse.area(), 4.0 * std::f64::consts::PI, 1e-12);
+        assert_approx_eq(path.area(), ellipse.area(), 4.0 * 8.0 * tolerance);
+        // The exact extent of the rotated ellipse in x.
+        let (s, c) = 0.5f64.sin_cos();
+        let half_width = (16.0 * c * c + s * s).sqrt();
+        let bbox = ellipse.bounding_box();
+        assert_approx_eq(bbox.x1, half_width, 1e-6);
+        assert_approx_eq(bbox.x0, -half_width, 1e-6);
+        assert!(ellipse
+            .bounding_box_fast()
+            .contains(Point::new(half_width - 1e-6, 0.0)));
+        for i in 0..20 {
+            let pt = Point::new(-4.0 + 0.4 * i as f64, 0.3);
+            assert_eq!(ellipse.winding(pt), path.winding(pt));
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 
-use crate::{Affine, Circle, CubicBez, Line, Point, QuadBez, Rect, RoundedRect, Vec2};
+use crate::{Affine, Circle, CubicBez, Line, Point, QuadBez, Rect, RoundedRect, Size, Vec2};
 
 /// A transformation including scaling and translation.
 ///
@@ -199,6 +199,13 @@ impl Mul<Line> for TranslateScale {
     }
 }
 
+/// The transformed rectangle is computed directly from two corners, without
+/// going through [`Affine`]. Its width and height are non-negative, even if
+/// the scale is negative, and it is the same as the result of
+/// [`Affine::transform_rect_bbox`].
+///
+/// [`Affine`]: struct.Affine.html
+/// [`Affine::transform_rect_bbox`]: struct.Affine.html#method.transform_rect_bbox
 impl Mul<Rect> for TranslateScale {
     type Output = Rect;
 
@@ -210,12 +217,24 @@ impl Mul<Rect> for TranslateScale {
     }
 }
 
+/// Sizes are scaled, ignoring the translation. The scale factor is taken as
+/// positive, so that the size of a transformed rectangle is the transformed
+/// size of the rectangle.
+impl Mul<Size> for TranslateScale {
+    type Output = Size;
+
+    #[inline]
+    fn mul(self, other: Size) -> Size {
+        self.scale.abs() * other
+    }
+}
+
 impl Mul<RoundedRect> for TranslateScale {
     type Output = RoundedRect;
 
     #[inline]
     fn mul(self, other: RoundedRect) -> RoundedRect {
-        RoundedRect::from_rect(self * other.rect(), self.scale * other.radius())
+        RoundedRect::from_rect(self * other.rect(), self.scale.abs() * other.radius())
     }
 }
 
@@ -244,7 +263,7 @@ impl Mul<CubicBez> for TranslateScale {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Affine, Point, TranslateScale, Vec2};
+    use crate::{Affine, Point, Rect, RoundedRect, TranslateScale, Vec2};
 
     fn assert_near(p0: Point, p1: Point) {
         assert!((p1 - p0).hypot() < 1e-9, "{:?} != {:?}", p0, p1);
@@ -281,4 +300,22 @@ mod tests {
         assert_near(p, (ts * ts.inverse()) * p);
         assert_near(p, (ts.inverse() * ts) * p);
     }
+
+    #[test]
+    fn transform_rect() {
+        let rect = Rect::new(1.0, -2.0, 4.0, 3.0);
+        for &s in &[2.0, -0.5, 0.0] {
+            let ts = TranslateScale::new(Vec2::new(5.0, 6.0), s);
+            let r = ts * rect;
+            assert!(r.width() >= 0.0 && r.height() >= 0.0);
+            let a = Affine::from(ts).transform_rect_bbox(rect);
+            assert_near(r.origin(), a.origin());
+            assert_near((r.x1, r.y1).into(), (a.x1, a.y1).into());
+        }
+        let rr = TranslateScale::scale(-2.0) * RoundedRect::from_rect(rect, 0.5);
+        assert_eq!(rr.radius(), 1.0);
+        assert_eq!(rr.rect(), Rect::new(-8.0, -6.0, -2.0, 4.0));
+        let ts = TranslateScale::new(Vec2::new(1.0, 1.0), -3.0);
+        assert_eq!((ts * rect).size(), ts * rect.size());
+    }
 }
@@ -0,0 +1,206 @@
+//! Triangles.
+
+use std::ops::{Add, Mul, Sub};
+
+use crate::{Affine, Circle, Line, ParamCurveNearest, PathEl, Point, Rect, Shape, Vec2};
+
+/// A triangle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangle {
+    /// The first vertex.
+    pub p0: Point,
+    /// The second vertex.
+    pub p1: Point,
+    /// The third vertex.
+    pub p2: Point,
+}
+
+impl Triangle {
+    /// Create a new triangle.
+    #[inline]
+    pub fn new(p0: impl Into<Point>, p1: impl Into<Point>, p2: impl Into<Point>) -> Triangle {
+        Triangle {
+            p0: p0.into(),
+            p1: p1.into(),
+            p2: p2.into(),
+        }
+    }
+
+    /// The centroid, the average of the three vertices.
+    #[inline]
+    pub fn centroid(self) -> Point {
+        ((self.p0.to_vec2() + self.p1.to_vec2() + self.p2.to_vec2()) / 3.0).to_point()
+    }
+
+    /// The three edges, from `p0` to `p1`, `p1` to `p2`, and `p2` to `p0`.
+    pub fn edges(self) -> [Line; 3] {
+        [
+            Line::new(self.p0, self.p1),
+            Line::new(self.p1, self.p2),
+            Line::new(self.p2, self.p0),
+        ]
+    }
+
+    /// The center of the circle through the three vertices.
+    ///
+    /// Returns `None` if the vertices are collinear, in which case there is
+    /// no such circle.
+    pub fn circumcenter(self) -> Option<Point> {
+        let b = self.p1 - self.p0;
+        let c = self.p2 - self.p0;
+        let d = 2.0 * b.cross(c);
+        if d == 0.0 {
+            return None;
+        }
+        let (b2, c2) = (b.hypot2(), c.hypot2());
+        let center = self.p0 + Vec2::new(c.y * b2 - b.y * c2, b.x * c2 - c.x * b2) / d;
+        if center.x.is_finite() && center.y.is_finite() {
+            Some(center)
+        } else {
+            None
+        }
+    }
+
+    /// The circle through the three vertices.
+    ///
+    /// Returns `None` if the vertices are collinear.
+    pub fn circumcircle(self) -> Option<Circle> {
+        let center = self.circumcenter()?;
+        Some(Circle::new(center, center.distance(self.p0)))
+    }
+}
+
+impl Mul<Triangle> for Affine {
+    type Output = Triangle;
+
+    #[inline]
+    fn mul(self, other: Triangle) -> Triangle {
+        Triangle::new(self * other.p0, self * other.p1, self * other.p2)
+    }
+}
+
+impl Add<Vec2> for Triangle {
+    type Output = Triangle;
+
+    #[inline]
+    fn add(self, v: Vec2) -> Triangle {
+        Triangle::new(self.p0 + v, self.p1 + v, self.p2 + v)
+    }
+}
+
+impl Sub<Vec2> for Triangle {
+    type Output = Triangle;
+
+    #[inline]
+    fn sub(self, v: Vec2) -> Triangle {
+        Triangle::new(self.p0 - v, self.p1 - v, self.p2 - v)
+    }
+}
+
+/// An iterator yielding the path for a triangle.
+#[doc(hidden)]
+pub struct TrianglePathIter {
+    triangle: Triangle,
+    ix: usize,
+}
+
+impl Shape for Triangle {
+    type PathElementsIter = TrianglePathIter;
+
+    #[inline]
+    fn path_elements(&self, _tolerance: f64) -> TrianglePathIter {
+        TrianglePathIter {
+            triangle: *self,
+            ix: 0,
+        }
+    }
+
+    /// The signed area, positive when the vertices are in counterclockwise
+    /// order (in a y-up coordinate system).
+    #[inline]
+    fn area(&self) -> f64 {
+        0.5 * (self.p1 - self.p0).cross(self.p2 - self.p0)
+    }
+
+    #[inline]
+    fn perimeter(&self, _accuracy: f64) -> f64 {
+        self.p0.distance(self.p1) + self.p1.distance(self.p2) + self.p2.distance(self.p0)
+    }
+
+    /// The winding number is 1 strictly inside a counterclockwise triangle,
+    /// -1 strictly inside a clockwise one, and 0 elsewhere, including on the
+    /// edges.
+    fn winding(&self, pt: Point) -> i32 {
+        let d0 = (self.p1 - self.p0).cross(pt - self.p0);
+        let d1 = (self.p2 - self.p1).cross(pt - self.p1);
+        let d2 = (self.p0 - self.p2).cross(pt - self.p2);
+        if d0 > 0.0 && d1 > 0.0 && d2 > 0.0 {
+            1
+        } else if d0 < 0.0 && d1 < 0.0 && d2 < 0.0 {
+            -1
+        } else {
+            0
+        }
+    }
+
+    fn distance_to_point(&self, pt: Point, _accuracy: f64) -> f64 {
+        if self.winding(pt) != 0 {
+            return 0.0;
+        }
+        self.edges()
+            .iter()
+            .map(|e| e.nearest(pt, 0.0).1)
+            .fold(f64::INFINITY, f64::min)
+            .sqrt()
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Rect {
+        Rect::from_points(self.p0, self.p1).union_pt(self.p2)
+    }
+}
+
+impl Iterator for TrianglePathIter {
+    type Item = PathEl;
+
+    fn next(&mut self) -> Option<PathEl> {
+        self.ix += 1;
+        match self.ix {
+            1 => Some(PathEl::MoveTo(self.triangle.p0)),
+            2 => Some(PathEl::LineTo(self.triangle.p1)),
+            3 => Some(PathEl::LineTo(self.triangle.p2)),
+            4 => Some(PathEl::ClosePath),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Shape, Triangle};
+
+    #[test]
+    fn triangle_shape() {
+        let t = Triangle::new((0.0, 0.0), (4.0, 0.0), (0.0, 3.0));
+        assert_eq!(t.area(), 6.0);
+        assert_eq!(t.perimeter(1e-9), 12.0);
+        assert_eq!(t.area(), t.to_path(1e-9).area());
+        assert_eq!(t.winding(Point::new(1.0, 1.0)), 1);
+        assert_eq!(t.winding(Point::new(3.0, 3.0)), 0);
+        let rev = Triangle::new(t.p0, t.p2, t.p1);
+        assert_eq!(rev.winding(Point::new(1.0, 1.0)), -1);
+        assert_eq!(t.distance_to_point(Point::new(2.0, -1.0), 1e-9), 1.0);
+    }
+
+    #[test]
+    fn triangle_circumcircle() {
+        let t = Triangle::new((1.0, 1.0), (5.0, 1.0), (1.0, 4.0));
+        let c = t.circumcircle().unwrap();
+        assert_eq!(c.center, Point::new(3.0, 2.5));
+        assert_eq!(c.radius, 2.5);
+        assert!(Triangle::new((0.0, 0.0), (1.0, 1.0), (2.0, 2.0))
+            .circumcircle()
+            .is_none());
+    }
+}
@@ -1,6 +1,7 @@
 //! A simple 2D vector.
 
 use std::fmt;
+use std::iter::Sum;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use crate::common::FloatExt;
@@ -55,7 +56,7 @@ impl Vec2 {
 
     /// Cross product of two vectors.
     ///
-    /// This is signed so that (0, 1) × (1, 0) = 1.
+    /// This is signed so that (1, 0) × (0, 1) = 1.
     #[inline]
     pub fn cross(self, other: Vec2) -> f64 {
         self.x * other.y - self.y * other.x
@@ -100,10 +101,79 @@ impl Vec2 {
         }
     }
 
+    /// The magnitude and angle of the vector, in that order.
+    ///
+    /// The angle is in radians, as given by [`atan2`], so it is in the range
+    /// [-π, π]. For the zero vector, including negative zeros, the result is
+    /// `(0.0, 0.0)`. The vector is recovered as `r * Vec2::from_angle(th)`,
+    /// using [`from_angle`].
+    ///
+    /// [`atan2`]: #method.atan2
+    /// [`from_angle`]: #method.from_angle
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::Vec2;
+    /// let (r, th) = Vec2::new(0.0, -2.0).to_polar();
+    /// assert_eq!(r, 2.0);
+    /// assert_eq!(th, -std::f64::consts::FRAC_PI_2);
+    /// ```
+    #[inline]
+    pub fn to_polar(self) -> (f64, f64) {
+        if self.x == 0.0 && self.y == 0.0 {
+            return (0.0, 0.0);
+        }
+        (self.hypot(), self.atan2())
+    }
+
+    /// The signed angle from `self` to `other`, in radians.
+    ///
+    /// The result is in the range (-π, π], and is positive when rotating
+    /// `self` by it in the sense of [`rotate`] turns it towards `other`. It
+    /// is zero if either vector is zero.
+    ///
+    /// [`rotate`]: #method.rotate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::Vec2;
+    /// use std::f64::consts::PI;
+    /// let a = Vec2::new(2.0, 0.0);
+    /// assert!((a.angle_between(Vec2::new(0.0, 3.0)) - PI / 2.0).abs() < 1e-12);
+    /// assert!((a.angle_between(Vec2::new(1.0, -1.0)) + PI / 4.0).abs() < 1e-12);
+    /// assert_eq!(a.angle_between(Vec2::new(-1.0, 0.0)), PI);
+    /// ```
+    #[inline]
+    pub fn angle_between(self, other: Vec2) -> f64 {
+        let th = self.cross(other).atan2(self.dot(other));
+        if th == -std::f64::consts::PI {
+            std::f64::consts::PI
+        } else {
+            th
+        }
+    }
+
+    /// Rotate the vector by the given angle, in radians.
+    ///
+    /// A positive angle rotates the positive X direction into positive Y,
+    /// consistent with [`Affine::rotate`](struct.Affine.html#method.rotate)
+    /// and [`from_angle`](#method.from_angle).
+    #[inline]
+    pub fn rotate(self, th: f64) -> Vec2 {
+        let (s, c) = th.sin_cos();
+        Vec2::new(c * self.x - s * self.y, s * self.x + c * self.y)
+    }
+
     /// Linearly interpolate between two vectors.
+    ///
+    /// The result is exactly `self` when `t` is 0 and exactly `other` when
+    /// `t` is 1. Values of `t` outside `[0, 1]` extrapolate along the line
+    /// through the two vectors.
     #[inline]
     pub fn lerp(self, other: Vec2, t: f64) -> Vec2 {
-        self + t * (other - self)
+        (1.0 - t) * self + t * other
     }
 
     /// Returns a vector of magnitude 1.0 with the same angle as `self`; i.e.
@@ -194,6 +264,28 @@ impl Vec2 {
         Vec2::new(self.x.expand(), self.y.expand())
     }
 
+    /// The mean of a sequence of vectors.
+    ///
+    /// Returns `None` if the sequence is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kurbo::Vec2;
+    /// let vecs = [Vec2::new(1.0, 2.0), Vec2::new(3.0, -2.0)];
+    /// assert_eq!(Vec2::mean(vecs.iter().copied()), Some(Vec2::new(2.0, 0.0)));
+    /// assert_eq!(Vec2::mean(Vec::new()), None);
+    /// ```
+    pub fn mean(vecs: impl IntoIterator<Item = Vec2>) -> Option<Vec2> {
+        let mut count = 0usize;
+        let sum: Vec2 = vecs.into_iter().inspect(|_| count += 1).sum();
+        if count == 0 {
+            None
+        } else {
+            Some(sum / count as f64)
+        }
+    }
+
     /// Returns a new `Vec2`,
     /// with `x` and `y` rounded towards zero to the nearest integer,
     /// unless they are already an integer.
@@ -324,6 +416,19 @@ impl DivAssign<f64> for Vec2 {
     }
 }
 
+impl Sum for Vec2 {
+    /// The sum of the vectors, or `Vec2::ZERO` if there are none.
+    fn sum<I: Iterator<Item = Vec2>>(iter: I) -> Vec2 {
+        iter.fold(Vec2::ZERO, |acc, v| acc + v)
+    }
+}
+
+impl<'a> Sum<&'a Vec2> for Vec2 {
+    fn sum<I: Iterator<Item = &'a Vec2>>(iter: I) -> Vec2 {
+        iter.copied().sum()
+    }
+}
+
 impl Neg for Vec2 {
     type Output = Vec2;
 
@@ -372,4 +477,44 @@ mod tests {
         let s = format!("{:.2}", v);
         assert_eq!(s.as_str(), "𝐯=(1.23, 532.11)");
     }
+
+    #[test]
+    fn sum() {
+        let vecs = vec![
+            Vec2::new(1.0, 2.0),
+            Vec2::new(-3.0, 0.5),
+            Vec2::new(0.5, 1.0),
+        ];
+        assert_eq!(vecs.iter().sum::<Vec2>(), Vec2::new(-1.5, 3.5));
+        assert_eq!(vecs.into_iter().sum::<Vec2>(), Vec2::new(-1.5, 3.5));
+        assert_eq!(Vec::<Vec2>::new().into_iter().sum::<Vec2>(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn rotate_matches_affine() {
+        let v = Vec2::new(3.0, -1.5);
+        for &th in &[0.3, 2.0, -2.9] {
+            let r = v.rotate(th);
+            let a = (crate::Affine::rotate(th) * v.to_point()).to_vec2();
+            assert!((r - a).hypot() < 1e-12);
+            assert!((v.angle_between(r) - th).abs() < 1e-12);
+        }
+        assert_eq!(
+            Vec2::new(1.0, 0.0).angle_between(Vec2::new(-1.0, -0.0)),
+            std::f64::consts::PI
+        );
+    }
+
+    #[test]
+    fn lerp() {
+        let a = Vec2::new(0.1, -3.7);
+        let b = Vec2::new(1e-3, 12.3);
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert!((a.lerp(b, 0.5) - Vec2::new(0.0505, 4.3)).hypot() < 1e-12);
+        assert!((a.lerp(b, 2.0) - (2.0 * b - a)).hypot() < 1e-12);
+        let (p, q) = (a.to_point(), b.to_point());
+        assert_eq!(p.lerp(q, 1.0), q);
+        assert_eq!(p.lerp(q, -1.0), a.lerp(b, -1.0).to_point());
+    }
 }